@@ -364,8 +364,11 @@ mod tests {
             }))
         }
 
-        fn multi_get(&self, _access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
-            todo!()
+        fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+            access_paths
+                .iter()
+                .map(|access_path| self.get(access_path))
+                .collect()
         }
 
         fn is_genesis(&self) -> bool {
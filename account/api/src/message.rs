@@ -7,6 +7,7 @@ use starcoin_service_registry::ServiceRequest;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::token_code::TokenCode;
 use starcoin_types::sign_message::{SignedMessage, SigningMessage};
+use starcoin_types::transaction::authenticator::TransactionAuthenticator;
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
 use std::time::Duration;
 
@@ -22,6 +23,11 @@ pub enum AccountRequest {
         txn: Box<RawUserTransaction>,
         signer: AccountAddress,
     },
+    SignTxnWithFeePayer {
+        txn: Box<RawUserTransaction>,
+        fee_payer: AccountAddress,
+        signer: AccountAddress,
+    },
     SignMessage {
         signer: AccountAddress,
         message: SigningMessage,
@@ -60,6 +66,7 @@ pub enum AccountResponse {
     AccountInfoOption(Box<Option<AccountInfo>>),
     AccountList(Vec<AccountInfo>),
     SignedTxn(Box<SignedUserTransaction>),
+    Authenticator(Box<TransactionAuthenticator>),
     UnlockAccountResponse,
     ExportAccountResponse(Vec<u8>),
     AcceptedTokens(Vec<TokenCode>),
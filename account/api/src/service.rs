@@ -8,6 +8,7 @@ use starcoin_service_registry::{ActorService, ServiceHandler, ServiceRef};
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::token_code::TokenCode;
 use starcoin_types::sign_message::{SignedMessage, SigningMessage};
+use starcoin_types::transaction::authenticator::TransactionAuthenticator;
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
 
 #[async_trait::async_trait]
@@ -34,6 +35,14 @@ pub trait AccountAsyncService:
         raw_txn: RawUserTransaction,
         signer_address: AccountAddress,
     ) -> Result<SignedUserTransaction>;
+
+    /// Signs a sponsored transaction as the sender or the fee payer, returns the signer's authenticator.
+    async fn sign_txn_with_fee_payer(
+        &self,
+        raw_txn: RawUserTransaction,
+        fee_payer: AccountAddress,
+        signer_address: AccountAddress,
+    ) -> Result<TransactionAuthenticator>;
     async fn unlock_account(
         &self,
         address: AccountAddress,
@@ -161,6 +170,26 @@ where
         }
     }
 
+    async fn sign_txn_with_fee_payer(
+        &self,
+        raw_txn: RawUserTransaction,
+        fee_payer: AccountAddress,
+        signer_address: AccountAddress,
+    ) -> Result<TransactionAuthenticator> {
+        let response = self
+            .send(AccountRequest::SignTxnWithFeePayer {
+                txn: Box::new(raw_txn),
+                fee_payer,
+                signer: signer_address,
+            })
+            .await??;
+        if let AccountResponse::Authenticator(authenticator) = response {
+            Ok(*authenticator)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
     async fn unlock_account(
         &self,
         address: AccountAddress,
@@ -141,6 +141,14 @@ impl ServiceHandler<AccountService, AccountRequest> for AccountService {
                 txn: raw_txn,
                 signer,
             } => AccountResponse::SignedTxn(Box::new(self.manager.sign_txn(signer, *raw_txn)?)),
+            AccountRequest::SignTxnWithFeePayer {
+                txn: raw_txn,
+                fee_payer,
+                signer,
+            } => AccountResponse::Authenticator(Box::new(
+                self.manager
+                    .sign_txn_with_fee_payer(signer, *raw_txn, fee_payer)?,
+            )),
             AccountRequest::SignMessage { message, signer } => AccountResponse::SignedMessage(
                 Box::new(self.manager.sign_message(signer, message)?),
             ),
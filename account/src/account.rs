@@ -15,8 +15,10 @@ use starcoin_types::account_address;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::genesis_config::ChainId;
 use starcoin_types::sign_message::{SignedMessage, SigningMessage};
-use starcoin_types::transaction::authenticator::AuthenticationKey;
-use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
+use starcoin_types::transaction::authenticator::{AuthenticationKey, TransactionAuthenticator};
+use starcoin_types::transaction::{
+    RawUserTransaction, RawUserTransactionWithFeePayer, SignedUserTransaction,
+};
 
 pub struct Account {
     addr: AccountAddress,
@@ -146,6 +148,19 @@ impl Account {
         Ok(SignedUserTransaction::new(raw_txn, signature))
     }
 
+    /// Sign a sponsored transaction as the sender or the fee payer, return the authenticator of the signer.
+    pub fn sign_txn_with_fee_payer(
+        &self,
+        raw_txn: RawUserTransaction,
+        fee_payer: AccountAddress,
+    ) -> Result<TransactionAuthenticator> {
+        let message = RawUserTransactionWithFeePayer::new(raw_txn, fee_payer);
+        self.private_key
+            .as_ref()
+            .map(|private_key| private_key.sign(&message))
+            .ok_or_else(|| format_err!("Readonly account can not sign txn"))
+    }
+
     pub fn destroy(self) -> Result<()> {
         self.store.destroy_account(self.addr)
     }
@@ -16,7 +16,9 @@ use starcoin_types::sign_message::{SignedMessage, SigningMessage};
 use starcoin_types::{
     account_address::AccountAddress,
     account_config::token_code::TokenCode,
-    transaction::{RawUserTransaction, SignedUserTransaction},
+    transaction::{
+        authenticator::TransactionAuthenticator, RawUserTransaction, SignedUserTransaction,
+    },
 };
 use std::collections::HashMap;
 use std::convert::TryFrom;
@@ -252,6 +254,25 @@ impl AccountManager {
         }
     }
 
+    pub fn sign_txn_with_fee_payer(
+        &self,
+        signer_address: AccountAddress,
+        raw_txn: RawUserTransaction,
+        fee_payer: AccountAddress,
+    ) -> AccountResult<TransactionAuthenticator> {
+        let pass = self.key_cache.write().get_pass(&signer_address);
+        match pass {
+            None => Err(AccountError::AccountLocked(signer_address)),
+            Some(p) => {
+                let account = Account::load(signer_address, Some(p), self.store.clone())?
+                    .ok_or(AccountError::AccountNotExist(signer_address))?;
+                account
+                    .sign_txn_with_fee_payer(raw_txn, fee_payer)
+                    .map_err(AccountError::TransactionSignError)
+            }
+        }
+    }
+
     pub fn set_default_account(&self, address: AccountAddress) -> AccountResult<AccountInfo> {
         let mut account_info = self
             .account_info(address)?
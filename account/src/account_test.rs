@@ -6,7 +6,7 @@ use crate::Account;
 use crate::AccountManager;
 use anyhow::Result;
 use starcoin_account_api::error::AccountError;
-use starcoin_account_api::AccountPublicKey;
+use starcoin_account_api::{AccountPrivateKey, AccountPublicKey};
 use starcoin_config::RocksdbConfig;
 use starcoin_crypto::keygen::KeyGen;
 use starcoin_crypto::{SigningKey, ValidCryptoMaterial};
@@ -144,6 +144,44 @@ pub fn test_wallet_unlock() -> Result<()> {
     Ok(())
 }
 
+#[test]
+pub fn test_secp256k1_account() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage.clone(), ChainId::test())?;
+
+    let (private_key, public_key) = KeyGen::from_os_rng().generate_secp256k1_keypair();
+    let private_key = AccountPrivateKey::Secp256k1(private_key);
+    let address = AccountPublicKey::Secp256k1(public_key).derived_address();
+    let account = manager.import_account(address, private_key.to_bytes(), "hello")?;
+
+    // test reload
+    let loaded_account = Account::load(address, Some("hello".to_string()), storage)?.unwrap();
+    assert_eq!(account.info(), loaded_account.info());
+    assert_eq!(Some(&private_key), loaded_account.private_key());
+
+    manager.unlock_account(address, "hello", Duration::from_secs(1))?;
+    let fake_txn = RawUserTransaction::new_with_default_gas_token(
+        address,
+        1,
+        TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+        1000,
+        1,
+        100000,
+        ChainId::new(1),
+    );
+    let signed = manager.sign_txn(address, fake_txn)?;
+    assert_eq!(
+        address,
+        signed
+            .authenticator()
+            .authentication_key()
+            .derived_address()
+    );
+    signed.check_signature()?;
+    Ok(())
+}
+
 #[test]
 pub fn test_sign_message() -> Result<()> {
     let tempdir = tempfile::tempdir()?;
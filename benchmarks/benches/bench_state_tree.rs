@@ -98,7 +98,83 @@ fn bench_put_and_commit(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_get_with_proof, bench_put_and_commit);
+fn bench_multi_get(c: &mut Criterion) {
+    ::logger::init();
+    let tmp_dir = starcoin_config::temp_path();
+    let db_store = new_empty_store(tmp_dir.as_ref()) as Arc<dyn StateNodeStore>;
+    let mem_store = Arc::new(MockStateNodeStore::new()) as Arc<dyn StateNodeStore>;
+
+    let mut group = c.benchmark_group("multi_get");
+    for (id, s) in [("mem_store", mem_store), ("db_store", db_store)].iter() {
+        let tree = StateTree::new(s.clone(), None);
+        let (kvs, root) = prepare_tree(&tree, &[1, 2, 3, 4], 100_000);
+        // read from the store instead of the tree cache.
+        let tree = StateTree::new(s.clone(), Some(root));
+        let ks = kvs.keys().copied().collect::<Vec<_>>();
+        for batch_size in [10usize, 100, 1000] {
+            let batches = ks.chunks(batch_size).collect::<Vec<_>>();
+            let batch_len = batches.len();
+            group.bench_with_input(
+                BenchmarkId::new(format!("{}_get", id), batch_size),
+                &(&tree, &batches),
+                |b, (tree, batches)| {
+                    let mut i = 0usize;
+                    b.iter(|| {
+                        let batch = batches[i % batch_len];
+                        i += 1;
+                        for k in batch {
+                            assert!(tree.get(k).unwrap().is_some());
+                        }
+                    });
+                },
+            );
+            group.bench_with_input(
+                BenchmarkId::new(format!("{}_multi_get", id), batch_size),
+                &(&tree, &batches),
+                |b, (tree, batches)| {
+                    let mut i = 0usize;
+                    b.iter(|| {
+                        let batch = batches[i % batch_len];
+                        i += 1;
+                        assert_eq!(batch.len(), tree.multi_get(batch).unwrap().len());
+                    });
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_dump(c: &mut Criterion) {
+    ::logger::init();
+    let tmp_dir = starcoin_config::temp_path();
+    let db_store = new_empty_store(tmp_dir.as_ref()) as Arc<dyn StateNodeStore>;
+
+    let mut group = c.benchmark_group("dump");
+    group.sample_size(10);
+    for num_keys in [100usize, 1000, 10_000] {
+        let tree = StateTree::new(db_store.clone(), None);
+        let (_, root) = prepare_tree(&tree, num_keys.to_le_bytes().as_ref(), num_keys);
+        // read from the store instead of the tree cache.
+        let tree = StateTree::<HashValueKey>::new(db_store.clone(), Some(root));
+        group.bench_with_input(
+            BenchmarkId::new("db_store_dump", num_keys),
+            &tree,
+            |b, tree| {
+                b.iter(|| assert_eq!(num_keys, tree.dump().unwrap().len()));
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_get_with_proof,
+    bench_put_and_commit,
+    bench_multi_get,
+    bench_dump
+);
 criterion_main!(benches);
 
 fn gen_kv_from_seed(seed: &[u8], num_keys: usize) -> HashMap<HashValueKey, Blob> {
@@ -1,7 +1,8 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
-use executor_benchmark::run_benchmark;
+use executor_benchmark::{run_benchmark, ParallelTransfers};
+use starcoin_executor::ParallelExecutor;
 
 pub fn transaction_execution(c: &mut Criterion) {
     ::logger::init();
@@ -15,5 +16,23 @@ pub fn transaction_execution(c: &mut Criterion) {
     }
 }
 
-criterion_group!(starcoin_vm_benches, transaction_execution);
+pub fn parallel_transaction_execution(c: &mut Criterion) {
+    ::logger::init();
+    let mut group = c.benchmark_group("vm");
+    group.sample_size(10);
+    let bench_id = "parallel_transaction_execution";
+    let transfers = ParallelTransfers::new(100);
+    for concurrency_level in vec![1usize, 2, 4, 8].into_iter() {
+        let parallel_executor = ParallelExecutor::new(concurrency_level).unwrap();
+        group.bench_function(BenchmarkId::new(bench_id, concurrency_level), |b| {
+            b.iter(|| transfers.execute(parallel_executor.clone()))
+        });
+    }
+}
+
+criterion_group!(
+    starcoin_vm_benches,
+    transaction_execution,
+    parallel_transaction_execution
+);
 criterion_main!(starcoin_vm_benches);
@@ -3,18 +3,23 @@
 
 pub mod message;
 
-use crate::message::{ContractEventNotification, Event, Notification, ThinBlock};
-use anyhow::{format_err, Result};
+use crate::message::{
+    ContractEventNotification, Event, Notification, ThinBlock, TxnWriteSet, WriteSetNotification,
+};
+use anyhow::{ensure, format_err, Result};
+use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
 use starcoin_storage::{Storage, Store};
-use starcoin_types::block::Block;
+use starcoin_types::block::{Block, BlockHeader, BlockNumber};
 use starcoin_types::system_events::NewHeadBlock;
+use starcoin_types::write_set::WriteSet;
 use std::sync::Arc;
 
 /// ChainNotify watch `NewHeadBlock` message from bus,
-/// and then reproduce `Notification<ThinBlock>` and `Notification<Arc<[Event]>>` message to bus.
-/// User can subscribe the two notification to watch onchain events.
+/// and then reproduce `Notification<ThinBlock>`, `Notification<Arc<[Event]>>` and
+/// `WriteSetNotification`(if the write sets come with the `NewHeadBlock`) message to bus.
+/// User can subscribe the notifications to watch onchain events and state changes.
 pub struct ChainNotifyHandlerService {
     store: Arc<dyn Store>,
 }
@@ -52,7 +57,7 @@ impl EventHandler<Self, NewHeadBlock> for ChainNotifyHandlerService {
         item: NewHeadBlock,
         ctx: &mut ServiceContext<ChainNotifyHandlerService>,
     ) {
-        let NewHeadBlock(block_detail) = item;
+        let NewHeadBlock(block_detail, write_sets) = item;
         let block = block_detail.block();
         // notify header.
         self.notify_new_block(block, ctx);
@@ -61,6 +66,18 @@ impl EventHandler<Self, NewHeadBlock> for ChainNotifyHandlerService {
         if let Err(e) = self.notify_events(block, self.store.clone(), ctx) {
             error!(target: "pubsub", "fail to notify events to client, err: {}", &e);
         }
+
+        // notify write sets of every enacted block.
+        for block_write_sets in write_sets.iter().flat_map(|write_sets| write_sets.iter()) {
+            if let Err(e) = self.notify_write_sets(
+                &block_write_sets.header,
+                &block_write_sets.write_sets,
+                self.store.clone(),
+                ctx,
+            ) {
+                error!(target: "pubsub", "fail to notify write sets to client, err: {}", &e);
+            }
+        }
     }
 }
 
@@ -79,26 +96,72 @@ impl ChainNotifyHandlerService {
         store: Arc<dyn Store>,
         ctx: &mut ServiceContext<Self>,
     ) -> Result<()> {
-        let block_number = block.header().number();
-        let block_id = block.id();
+        let all_events = block_events(store.as_ref(), block.id(), block.header().number())?;
+        let events_notification: ContractEventNotification =
+            Notification((block.header.state_root(), all_events.into()));
+        ctx.broadcast(events_notification);
+        Ok(())
+    }
+
+    pub fn notify_write_sets(
+        &self,
+        header: &BlockHeader,
+        block_write_sets: &[WriteSet],
+        store: Arc<dyn Store>,
+        ctx: &mut ServiceContext<Self>,
+    ) -> Result<()> {
+        let block_number = header.number();
+        let block_id = header.id();
         let txn_info_ids = store.get_block_txn_info_ids(block_id)?;
-        let mut all_events: Vec<Event> = vec![];
-        for (i, txn_info_id) in txn_info_ids.into_iter().enumerate().rev() {
+        ensure!(
+            txn_info_ids.len() == block_write_sets.len(),
+            "write sets' length should be equal to txn infos' length"
+        );
+        let mut write_sets: Vec<TxnWriteSet> = vec![];
+        for (i, (txn_info_id, write_set)) in txn_info_ids
+            .into_iter()
+            .zip(block_write_sets.iter())
+            .enumerate()
+        {
             let txn_hash = store
                 .get_transaction_info(txn_info_id)?
                 .map(|info| info.transaction_hash())
                 .ok_or_else(|| format_err!("cannot find txn info by it's id {}", &txn_info_id))?;
-            // get events directly by txn_info_id
-            let events = store.get_contract_events(txn_info_id)?.unwrap_or_default();
-            all_events.extend(
-                events
-                    .into_iter()
-                    .map(|evt| Event::new(block_id, block_number, txn_hash, Some(i as u32), evt)),
-            );
+            write_sets.push(TxnWriteSet {
+                block_hash: block_id,
+                block_number,
+                transaction_hash: txn_hash,
+                transaction_index: i as u32,
+                write_set: write_set.clone(),
+            });
         }
-        let events_notification: ContractEventNotification =
-            Notification((block.header.state_root(), all_events.into()));
-        ctx.broadcast(events_notification);
+        let notification: WriteSetNotification =
+            Notification((header.state_root(), write_sets.into()));
+        ctx.broadcast(notification);
         Ok(())
     }
 }
+
+/// Load the contract events of the block from the store.
+pub fn block_events(
+    store: &dyn Store,
+    block_id: HashValue,
+    block_number: BlockNumber,
+) -> Result<Vec<Event>> {
+    let txn_info_ids = store.get_block_txn_info_ids(block_id)?;
+    let mut all_events: Vec<Event> = vec![];
+    for (i, txn_info_id) in txn_info_ids.into_iter().enumerate().rev() {
+        let txn_hash = store
+            .get_transaction_info(txn_info_id)?
+            .map(|info| info.transaction_hash())
+            .ok_or_else(|| format_err!("cannot find txn info by it's id {}", &txn_info_id))?;
+        // get events directly by txn_info_id
+        let events = store.get_contract_events(txn_info_id)?.unwrap_or_default();
+        all_events.extend(
+            events
+                .into_iter()
+                .map(|evt| Event::new(block_id, block_number, txn_hash, Some(i as u32), evt)),
+        );
+    }
+    Ok(all_events)
+}
@@ -3,6 +3,7 @@
 
 use starcoin_crypto::HashValue;
 use starcoin_types::block::BlockHeader;
+use starcoin_types::write_set::WriteSet;
 use starcoin_types::{block::BlockNumber, contract_event::ContractEvent};
 use std::sync::Arc;
 
@@ -11,6 +12,7 @@ pub struct Notification<T>(pub T);
 
 pub type ContractEventNotification = Notification<(HashValue, Arc<[Event]>)>;
 pub type NewHeadEventNotification = Notification<ThinBlock>;
+pub type WriteSetNotification = Notification<(HashValue, Arc<[TxnWriteSet]>)>;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Event {
@@ -40,6 +42,17 @@ impl Event {
     }
 }
 
+/// Write set of an executed txn in the new head block.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TxnWriteSet {
+    pub block_hash: HashValue,
+    pub block_number: BlockNumber,
+    pub transaction_hash: HashValue,
+    // txn index in block
+    pub transaction_index: u32,
+    pub write_set: WriteSet,
+}
+
 /// Block with only txn hashes.
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct ThinBlock {
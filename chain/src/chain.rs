@@ -14,6 +14,7 @@ use starcoin_chain_api::{
     verify_block, ChainReader, ChainWriter, ConnectBlockError, ExcludedTxns, ExecutedBlock,
     MintedUncleNumber, VerifiedBlock, VerifyBlockField,
 };
+use starcoin_executor::ParallelExecutor;
 use starcoin_open_block::OpenedBlock;
 use starcoin_state_api::{AccountStateReader, ChainState, ChainStateReader, ChainStateWriter};
 use starcoin_statedb::ChainStateDB;
@@ -28,6 +29,7 @@ use starcoin_types::{
     contract_event::ContractEvent,
     error::BlockExecutorError,
     transaction::{SignedUserTransaction, Transaction, TransactionInfo},
+    write_set::WriteSet,
     U256,
 };
 use starcoin_vm_types::account_config::genesis_address;
@@ -55,6 +57,7 @@ pub struct BlockChain {
     time_service: Arc<dyn TimeService>,
     uncles: HashMap<HashValue, MintedUncleNumber>,
     epoch: Epoch,
+    parallel_executor: ParallelExecutor,
 }
 
 impl BlockChain {
@@ -109,6 +112,7 @@ impl BlockChain {
             storage,
             uncles: HashMap::new(),
             epoch,
+            parallel_executor: ParallelExecutor::default(),
         };
         watch(CHAIN_WATCH_NAME, "n1251");
         match uncles {
@@ -133,7 +137,7 @@ impl BlockChain {
             storage.get_accumulator_store(AccumulatorStoreType::Block),
         );
         let statedb = ChainStateDB::new(storage.clone().into_super_arc(), None);
-        let executed_block = Self::execute_block_and_save(
+        let (executed_block, _) = Self::execute_block_and_save(
             storage.as_ref(),
             statedb,
             txn_accumulator,
@@ -141,10 +145,16 @@ impl BlockChain {
             &genesis_epoch,
             None,
             genesis_block,
+            &ParallelExecutor::default(),
         )?;
         Self::new(time_service, executed_block.block.id(), storage)
     }
 
+    /// Execute the user txns of blocks in parallel with the threads of `parallel_executor`.
+    pub fn set_parallel_executor(&mut self, parallel_executor: ParallelExecutor) {
+        self.parallel_executor = parallel_executor;
+    }
+
     pub fn current_epoch_uncles_size(&self) -> u64 {
         self.uncles.len() as u64
     }
@@ -327,6 +337,33 @@ impl BlockChain {
         self.connect(executed_block)
     }
 
+    /// Apply the block like `apply`, and return the write sets of the block's txns too.
+    /// The write sets are not persisted, so they can only be got at execution time.
+    pub fn apply_with_write_sets(
+        &mut self,
+        block: Block,
+    ) -> Result<(ExecutedBlock, Vec<WriteSet>)> {
+        let verified_block = self.verify_with_verifier::<FullVerifier>(block)?;
+        let (executed_block, write_sets) = self.execute_with_write_sets(verified_block)?;
+        Ok((self.connect(executed_block)?, write_sets))
+    }
+
+    fn execute_with_write_sets(
+        &self,
+        verified_block: VerifiedBlock,
+    ) -> Result<(ExecutedBlock, Vec<WriteSet>)> {
+        Self::execute_block_and_save(
+            self.storage.as_ref(),
+            self.statedb.fork(),
+            self.txn_accumulator.fork(),
+            self.block_accumulator.fork(),
+            &self.epoch,
+            Some(self.status.status.clone()),
+            verified_block.0,
+            &self.parallel_executor,
+        )
+    }
+
     //TODO remove this function.
     pub fn update_chain_head(&mut self, block: Block) -> Result<ExecutedBlock> {
         let block_info = self
@@ -345,7 +382,8 @@ impl BlockChain {
         epoch: &Epoch,
         parent_status: Option<ChainStatus>,
         block: Block,
-    ) -> Result<ExecutedBlock> {
+        parallel_executor: &ParallelExecutor,
+    ) -> Result<(ExecutedBlock, Vec<WriteSet>)> {
         let header = block.header();
         debug_assert!(header.is_genesis() || parent_status.is_some());
         debug_assert!(!header.is_genesis() || parent_status.is_none());
@@ -370,8 +408,12 @@ impl BlockChain {
         };
 
         watch(CHAIN_WATCH_NAME, "n21");
-        let executed_data =
-            starcoin_executor::block_execute(&statedb, txns.clone(), epoch.block_gas_limit())?;
+        let executed_data = starcoin_executor::block_execute_in_parallel(
+            &statedb,
+            txns.clone(),
+            epoch.block_gas_limit(),
+            parallel_executor.clone(),
+        )?;
         watch(CHAIN_WATCH_NAME, "n22");
         let state_root = executed_data.state_root;
         let vec_transaction_info = &executed_data.txn_infos;
@@ -448,7 +490,10 @@ impl BlockChain {
             (executed_data.txn_infos, executed_data.txn_events),
         )?;
         watch(CHAIN_WATCH_NAME, "n26");
-        Ok(ExecutedBlock { block, block_info })
+        Ok((
+            ExecutedBlock { block, block_info },
+            executed_data.txn_write_sets,
+        ))
     }
 
     fn save(
@@ -671,12 +716,14 @@ impl ChainReader for BlockChain {
         } else {
             None
         };
-        BlockChain::new_with_uncles(
+        let mut chain = BlockChain::new_with_uncles(
             self.time_service.clone(),
             head,
             uncles,
             self.storage.clone(),
-        )
+        )?;
+        chain.set_parallel_executor(self.parallel_executor.clone());
+        Ok(chain)
     }
 
     fn epoch_uncles(&self) -> &HashMap<HashValue, MintedUncleNumber> {
@@ -711,15 +758,8 @@ impl ChainReader for BlockChain {
     }
 
     fn execute(&self, verified_block: VerifiedBlock) -> Result<ExecutedBlock> {
-        Self::execute_block_and_save(
-            self.storage.as_ref(),
-            self.statedb.fork(),
-            self.txn_accumulator.fork(),
-            self.block_accumulator.fork(),
-            &self.epoch,
-            Some(self.status.status.clone()),
-            verified_block.0,
-        )
+        self.execute_with_write_sets(verified_block)
+            .map(|(executed_block, _)| executed_block)
     }
 }
 
@@ -475,3 +475,45 @@ fn test_get_blocks_by_number() -> Result<()> {
     assert_eq!(blocks.len(), 11);
     Ok(())
 }
+
+#[stest::test]
+fn test_apply_with_write_sets() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let mut block_chain = test_helper::gen_blockchain_for_test(config.net())?;
+    let miner_account = AccountInfo::random();
+    let receiver = AccountInfo::random();
+    let txn = build_transfer_from_association(
+        receiver.address,
+        0,
+        10000,
+        config.net().time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+        config.net(),
+    );
+    let (template, excluded) = block_chain.create_block_template(
+        *miner_account.address(),
+        None,
+        vec![txn.as_signed_user_txn()?.clone()],
+        vec![],
+        None,
+    )?;
+    assert!(excluded.discarded_txns.is_empty(), "txn is discarded.");
+    let block = block_chain
+        .consensus()
+        .create_block(template, config.net().time_service().as_ref())?;
+    let (executed_block, write_sets) = block_chain.apply_with_write_sets(block)?;
+    assert_eq!(
+        block_chain.current_header().id(),
+        executed_block.header().id()
+    );
+
+    // one write set for the block metadata txn and one for the user txn.
+    let txn_infos = block_chain
+        .get_storage()
+        .get_block_transaction_infos(executed_block.header().id())?;
+    assert_eq!(write_sets.len(), 2);
+    assert_eq!(write_sets.len(), txn_infos.len());
+    assert!(write_sets[1]
+        .iter()
+        .any(|(access_path, _)| access_path.address == receiver.address));
+    Ok(())
+}
@@ -1,13 +1,10 @@
 use anyhow::Result;
 use bcs_ext::BCSCodec;
 use clap::Clap;
-use jsonrpc_core_client::{RpcChannel, RpcError};
 use serde::Deserialize;
 use starcoin_crypto::{HashValue, ValidCryptoMaterialStringExt};
 use starcoin_rpc_api::types::{TransactionInfoView, TransactionStatusView};
-use starcoin_rpc_api::{
-    chain::ChainClient, node::NodeClient, state::StateClient, txpool::TxPoolClient,
-};
+use starcoin_rpc_client::AsyncRpcClient;
 use starcoin_types::access_path::{AccessPath, DataPath};
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::{
@@ -45,24 +42,14 @@ pub struct AirdropInfo {
     amount: u128,
 }
 
-fn map_rpc_error(err: RpcError) -> anyhow::Error {
-    anyhow::anyhow!(format!("{}", err))
-}
-
 #[tokio::main]
 async fn main() -> Result<()> {
     let options: Options = Options::parse();
     let node_url = options.node_url.clone();
     let airdrop_file = options.airdrop_file.clone();
     let batch_size = options.batch_size;
-    let channel: RpcChannel = jsonrpc_core_client::transports::http::connect(node_url.as_str())
-        .await
-        .map_err(map_rpc_error)?;
-    let chain_client = ChainClient::from(channel.clone());
-    let txpool_client = TxPoolClient::from(channel.clone());
-    let state_client = StateClient::from(channel.clone());
-    let node_client = NodeClient::from(channel.clone());
-    let chain_id: u8 = chain_client.id().await.map_err(map_rpc_error)?.id;
+    let client = AsyncRpcClient::connect_http(node_url.as_str()).await?;
+    let chain_id: u8 = client.chain_id().await?.id;
 
     let airdrop_infos: Vec<AirdropInfo> = {
         let mut csv_reader = csv::ReaderBuilder::default()
@@ -97,7 +84,7 @@ async fn main() -> Result<()> {
     // read from onchain
     let account_sequence_number = {
         let ap = AccessPath::new(sender, DataPath::Resource(account_struct_tag()));
-        let account_data: Option<Vec<u8>> = state_client.get(ap).await.map_err(map_rpc_error)?;
+        let account_data: Option<Vec<u8>> = client.state_get(ap).await?;
         account_data
             .map(|account_data| AccountResource::decode(&account_data))
             .transpose()?
@@ -141,7 +128,7 @@ async fn main() -> Result<()> {
             ],
         );
 
-        let now = node_client.info().await.map_err(map_rpc_error)?.now_seconds;
+        let now = client.node_info().await?.now_seconds;
         let txn = RawUserTransaction::new_script_function(
             sender,
             account_sequence_number + i as u64,
@@ -154,16 +141,9 @@ async fn main() -> Result<()> {
         let signature = private_key.sign(&txn);
         let signed_txn = SignedUserTransaction::new(txn, signature);
 
-        let signed_txn_hex = hex::encode(signed_txn.encode()?);
-        let txn_hash: HashValue = txpool_client
-            .submit_hex_transaction(signed_txn_hex)
-            .await
-            .map_err(map_rpc_error)?;
+        let txn_hash: HashValue = client.submit_transaction(signed_txn).await?;
         let txn_info: TransactionInfoView = loop {
-            let txn_info = chain_client
-                .get_transaction_info(txn_hash)
-                .await
-                .map_err(map_rpc_error)?;
+            let txn_info = client.chain_get_transaction_info(txn_hash).await?;
             match txn_info {
                 None => {
                     println!("wait txn to be mined, {}", txn_hash);
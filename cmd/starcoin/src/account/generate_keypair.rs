@@ -25,6 +25,9 @@ pub struct GenerateKeypairOpt {
     /// How many keypair to generate
     #[structopt(short = "c", name = "count")]
     count: Option<u32>,
+    /// Generate secp256k1 keypair, default is ed25519.
+    #[structopt(long = "secp256k1")]
+    secp256k1: bool,
 }
 
 pub struct GenerateKeypairCommand;
@@ -61,9 +64,19 @@ impl CommandAction for GenerateKeypairCommand {
         let keypairs = (0..opt.count.unwrap_or(1))
             .into_iter()
             .map(|_| {
-                let (private_key, public_key) = key_gen.generate_keypair();
-                let account_public_key = AccountPublicKey::single(public_key);
-                let account_private_key = AccountPrivateKey::Single(private_key);
+                let (account_private_key, account_public_key) = if opt.secp256k1 {
+                    let (private_key, public_key) = key_gen.generate_secp256k1_keypair();
+                    (
+                        AccountPrivateKey::Secp256k1(private_key),
+                        AccountPublicKey::secp256k1(public_key),
+                    )
+                } else {
+                    let (private_key, public_key) = key_gen.generate_keypair();
+                    (
+                        AccountPrivateKey::Single(private_key),
+                        AccountPublicKey::single(public_key),
+                    )
+                };
 
                 let address = account_public_key.derived_address();
                 let receipt_identifier = address.to_bech32();
@@ -37,6 +37,7 @@ pub mod receipt_identifier_cmd;
 pub mod remove_cmd;
 mod show_cmd;
 mod sign_cmd;
+pub mod sign_fee_payer_txn_cmd;
 pub mod sign_multisig_txn_cmd;
 pub mod submit_txn_cmd;
 mod transfer_cmd;
@@ -0,0 +1,239 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{bail, ensure, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use short_hex_str::AsShortHexStr;
+use starcoin_crypto::hash::PlainCryptoHash;
+use starcoin_rpc_api::types::{FunctionIdView, RawUserTransactionView};
+use starcoin_rpc_client::StateRootOption;
+use starcoin_state_api::StateReaderExt;
+use starcoin_types::transaction::authenticator::TransactionAuthenticator;
+use starcoin_types::transaction::{
+    parse_transaction_argument, RawUserTransaction, SignedUserTransaction, TransactionArgument,
+};
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::token::stc::STC_TOKEN_CODE_STR;
+use starcoin_vm_types::transaction::{ScriptFunction, TransactionPayload};
+use starcoin_vm_types::transaction_argument::convert_txn_args;
+use starcoin_vm_types::{language_storage::TypeTag, parser::parse_type_tag};
+use std::convert::TryInto;
+use std::env::current_dir;
+use std::fs::File;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "sign-fee-payer-txn")]
+/// Generate a sponsored txn whose gas is paid by the fee payer, sign it as the sender,
+/// and output the txn to file, waiting for the fee payer to co-sign the txn.
+/// Or co-sign the txn file generated by the sender as the fee payer, and output the signed txn to file.
+pub struct SignFeePayerTxnOpt {
+    #[structopt(name = "fee-payer-txn-file")]
+    /// sponsored txn data signed by the sender.
+    fee_payer_txn_file: Option<PathBuf>,
+
+    #[structopt(short = "s", required_unless = "fee-payer-txn-file")]
+    /// sender address of this sponsored txn.
+    sender: Option<AccountAddress>,
+
+    #[structopt(long = "fee-payer", required_unless = "fee-payer-txn-file")]
+    /// the address pays the gas of this sponsored txn.
+    fee_payer: Option<AccountAddress>,
+
+    #[structopt(
+        long = "function",
+        name = "script-function",
+        required_unless = "fee-payer-txn-file"
+    )]
+    /// script function to execute, example: 0x1::TransferScripts::peer_to_peer
+    script_function: Option<FunctionIdView>,
+
+    #[structopt(
+    short = "t",
+    long = "type_tag",
+    name = "type-tag",
+    help = "can specify multi type_tag",
+    parse(try_from_str = parse_type_tag)
+    )]
+    type_tags: Option<Vec<TypeTag>>,
+
+    #[structopt(long = "arg", name = "transaction-arg",  parse(try_from_str = parse_transaction_argument))]
+    /// transaction arguments
+    args: Option<Vec<TransactionArgument>>,
+
+    #[structopt(
+        name = "expiration_time",
+        long = "timeout",
+        default_value = "3000",
+        help = "how long(in seconds) the txn stay alive"
+    )]
+    expiration_time: u64,
+
+    #[structopt(
+        short = "g",
+        long = "max-gas",
+        name = "max-gas-amount",
+        default_value = "10000000",
+        help = "max gas used to execute the script"
+    )]
+    max_gas_amount: u64,
+    #[structopt(
+        long = "gas-price",
+        name = "price of gas",
+        default_value = "1",
+        help = "gas price used to execute the script"
+    )]
+    gas_price: u64,
+
+    #[structopt(name = "output-dir", long = "output-dir")]
+    /// dir used to save txn data file. Default to current dir.
+    output_dir: Option<PathBuf>,
+}
+
+/// The sponsored txn data signed by the sender, waiting for the fee payer to co-sign.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FeePayerTxnData {
+    raw_txn: RawUserTransaction,
+    fee_payer: AccountAddress,
+    sender_authenticator: TransactionAuthenticator,
+}
+
+pub struct SignFeePayerTxnCommand;
+
+impl CommandAction for SignFeePayerTxnCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SignFeePayerTxnOpt;
+    type ReturnItem = PathBuf;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let client = ctx.state().client();
+
+        // gen sponsored txn or read from file signed by the sender.
+        let (raw_txn, fee_payer, sender_authenticator) = if let Some(function_id) =
+            opt.script_function.clone().map(|t| t.0)
+        {
+            let sender = opt.sender.expect("sender adress should be provided");
+            let fee_payer = opt.fee_payer.expect("fee payer should be provided");
+            ensure!(
+                sender != fee_payer,
+                "the fee payer should not be the sender of the txn"
+            );
+            let script_function = ScriptFunction::new(
+                function_id.module,
+                function_id.function,
+                opt.type_tags.clone().unwrap_or_default(),
+                convert_txn_args(&opt.args.clone().unwrap_or_default()),
+            );
+            let payload = TransactionPayload::ScriptFunction(script_function);
+
+            let node_info = client.node_info()?;
+            let chain_state_reader = client.state_reader(StateRootOption::Latest)?;
+            let account_resource = match chain_state_reader.get_account_resource(sender)? {
+                Some(account_resource) => account_resource,
+                None => bail!("address {} not exists on chain", &sender),
+            };
+            let expiration_time = opt.expiration_time + node_info.now_seconds;
+            let raw_txn = RawUserTransaction::new(
+                sender,
+                account_resource.sequence_number(),
+                payload,
+                opt.max_gas_amount,
+                opt.gas_price,
+                expiration_time,
+                ctx.state().net().chain_id(),
+                STC_TOKEN_CODE_STR.to_string(),
+            );
+            (raw_txn, fee_payer, None)
+        } else if let Some(file_input) = opt.fee_payer_txn_file.as_ref() {
+            let data: FeePayerTxnData = bcs_ext::from_bytes(&std::fs::read(file_input.as_path())?)?;
+            (
+                data.raw_txn,
+                data.fee_payer,
+                Some(data.sender_authenticator),
+            )
+        } else {
+            unreachable!()
+        };
+        let mut raw_txn_view: RawUserTransactionView = raw_txn.clone().try_into()?;
+        raw_txn_view.decoded_payload = Some(
+            ctx.state()
+                .decode_txn_payload(raw_txn.payload())?
+                .try_into()?,
+        );
+        // Use `eprintln` instead of `println`, for keep the cli stdout's format(such as json) is not broken by print.
+        eprintln!(
+            "Prepare to sign the transaction sponsored by {}: \n {}",
+            fee_payer,
+            serde_json::to_string_pretty(&raw_txn_view)?
+        );
+
+        let output_file = match sender_authenticator {
+            // sign as the sender, and output the txn data for the fee payer.
+            None => {
+                let sender_authenticator = client.account_sign_txn_with_fee_payer(
+                    raw_txn.clone(),
+                    fee_payer,
+                    raw_txn.sender(),
+                )?;
+                let data = FeePayerTxnData {
+                    raw_txn,
+                    fee_payer,
+                    sender_authenticator,
+                };
+                let output_file = output_file(
+                    opt.output_dir.clone(),
+                    data.raw_txn.crypto_hash().short_str().as_str(),
+                    "fee-payer-txn",
+                )?;
+                let mut file = File::create(output_file.clone())?;
+                bcs_ext::serialize_into(&mut file, &data)?;
+                eprintln!(
+                    "txn signed by the sender, send the file to the fee payer {} to co-sign",
+                    fee_payer
+                );
+                output_file
+            }
+            // co-sign as the fee payer, and output the signed txn which can be submitted.
+            Some(sender_authenticator) => {
+                let fee_payer_authenticator = client.account_sign_txn_with_fee_payer(
+                    raw_txn.clone(),
+                    fee_payer,
+                    fee_payer,
+                )?;
+                let signed_txn = SignedUserTransaction::with_fee_payer(
+                    raw_txn,
+                    sender_authenticator,
+                    fee_payer,
+                    fee_payer_authenticator,
+                );
+                let signed_txn = signed_txn.check_signature()?.into_inner();
+                let output_file = output_file(
+                    opt.output_dir.clone(),
+                    signed_txn.id().short_str().as_str(),
+                    "signed-txn",
+                )?;
+                let mut file = File::create(output_file.clone())?;
+                bcs_ext::serialize_into(&mut file, &signed_txn)?;
+                eprintln!("txn co-signed by the fee payer, txn can be submitted now");
+                output_file
+            }
+        };
+        Ok(output_file)
+    }
+}
+
+fn output_file(output_dir: Option<PathBuf>, file_name: &str, extension: &str) -> Result<PathBuf> {
+    let mut output_dir = output_dir.unwrap_or(current_dir()?);
+    output_dir.push(file_name);
+    output_dir.set_extension(extension);
+    Ok(output_dir)
+}
@@ -145,7 +145,9 @@ impl CommandAction for GenerateMultisigTxnCommand {
                     bcs_ext::from_bytes(&std::fs::read(file_input.as_path())?)?;
 
                 let existing_signatures = match txn.authenticator() {
-                    TransactionAuthenticator::Ed25519 { .. } => {
+                    TransactionAuthenticator::Ed25519 { .. }
+                    | TransactionAuthenticator::Secp256k1Ecdsa { .. }
+                    | TransactionAuthenticator::FeePayer { .. } => {
                         bail!(
                             "expect a multisig txn in file {}",
                             file_input.as_path().display()
@@ -179,7 +181,7 @@ impl CommandAction for GenerateMultisigTxnCommand {
             .account_get(sender)?
             .ok_or_else(|| anyhow::anyhow!("cannot find multisig address {}", sender))?;
         let account_public_key = match &account.public_key {
-            AccountPublicKey::Single(_) => {
+            AccountPublicKey::Single(_) | AccountPublicKey::Secp256k1(_) => {
                 bail!("sender {} is not a multisig address", sender);
             }
             AccountPublicKey::Multi(m) => m.clone(),
@@ -1,9 +1,12 @@
 use crate::cli_state::CliState;
 use crate::StarcoinOpt;
-use anyhow::Result;
+use anyhow::{format_err, Result};
 use futures::{StreamExt, TryStream, TryStreamExt};
 use scmd::{CommandAction, ExecContext};
-use starcoin_rpc_api::types::pubsub::EventFilter;
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::pubsub::{
+    EventFilter, ResourceChangeFilter, ResourceKey, TxnStatusFilter,
+};
 use starcoin_rpc_api::types::TypeTagView;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::event::EventKey;
@@ -18,7 +21,7 @@ pub struct SubscribeEventOpt {
         short = "f",
         long = "from",
         name = "from_block",
-        help = "from block number"
+        help = "from block number, events of the history blocks since it are replayed first"
     )]
     from_block: Option<u64>,
     #[structopt(short = "t", long = "to", name = "to_block", help = "to block number")]
@@ -98,6 +101,28 @@ impl CommandAction for SubscribeBlockCommand {
         Ok(())
     }
 }
+/// Subscribe the re-organizations of main chain.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "chain_reorg")]
+pub struct SubscribeChainReorgOpt {}
+pub struct SubscribeChainReorgCommand;
+impl CommandAction for SubscribeChainReorgCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SubscribeChainReorgOpt;
+    type ReturnItem = ();
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let event_stream = ctx.state().client().subscribe_chain_reorg()?;
+        println!("Subscribe successful, Press `q` and Enter to quit");
+        blocking_display_notification(event_stream, |evt| {
+            serde_json::to_string(&evt).expect("should never fail")
+        });
+        Ok(())
+    }
+}
 #[derive(Debug, StructOpt)]
 #[structopt(name = "new_pending_txn")]
 pub struct SubscribeNewTxnOpt {}
@@ -120,6 +145,94 @@ impl CommandAction for SubscribeNewTxnCommand {
     }
 }
 
+/// Subscribe the status transitions of txns.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "txn_status")]
+pub struct SubscribeTxnStatusOpt {
+    #[structopt(long = "txn-hash", name = "txn-hash", multiple = true)]
+    /// status of which txns to subscribe
+    txn_hashes: Option<Vec<HashValue>>,
+    #[structopt(long = "sender", name = "sender", multiple = true)]
+    /// status of which senders' txns to subscribe
+    senders: Option<Vec<AccountAddress>>,
+}
+pub struct SubscribeTxnStatusCommand;
+impl CommandAction for SubscribeTxnStatusCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SubscribeTxnStatusOpt;
+    type ReturnItem = ();
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let filter = TxnStatusFilter {
+            txn_hashes: ctx.opt().txn_hashes.clone(),
+            senders: ctx.opt().senders.clone(),
+        };
+        let event_stream = ctx.state().client().subscribe_transaction_status(filter)?;
+        println!("Subscribe successful, Press `q` and Enter to quit");
+        blocking_display_notification(event_stream, |evt| {
+            serde_json::to_string(&evt).expect("should never fail")
+        });
+        Ok(())
+    }
+}
+
+/// Subscribe the changes of account resources.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "resource_changes")]
+pub struct SubscribeResourceChangesOpt {
+    #[structopt(
+        long = "resource",
+        name = "resource",
+        multiple = true,
+        parse(try_from_str = parse_resource_key)
+    )]
+    /// resources to subscribe, in format of `address/struct_tag`, eg: 0x1/0x1::Account::Account
+    resources: Option<Vec<ResourceKey>>,
+    #[structopt(long = "address", name = "address", multiple = true)]
+    /// accounts whose resource changes are all subscribed
+    addrs: Option<Vec<AccountAddress>>,
+}
+
+fn parse_resource_key(s: &str) -> Result<ResourceKey> {
+    let mut parts = s.splitn(2, '/');
+    match (parts.next(), parts.next()) {
+        (Some(address), Some(resource_type)) => Ok(ResourceKey {
+            address: address.parse()?,
+            resource_type: resource_type.parse()?,
+        }),
+        _ => Err(format_err!(
+            "Invalid resource {}, expect format: address/struct_tag",
+            s
+        )),
+    }
+}
+
+pub struct SubscribeResourceChangesCommand;
+impl CommandAction for SubscribeResourceChangesCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SubscribeResourceChangesOpt;
+    type ReturnItem = ();
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let filter = ResourceChangeFilter {
+            resources: ctx.opt().resources.clone(),
+            addrs: ctx.opt().addrs.clone(),
+        };
+        let event_stream = ctx.state().client().subscribe_resource_changes(filter)?;
+        println!("Subscribe successful, Press `q` and Enter to quit");
+        blocking_display_notification(event_stream, |evt| {
+            serde_json::to_string(&evt).expect("should never fail")
+        });
+        Ok(())
+    }
+}
+
 fn blocking_display_notification<T, F>(
     mut event_stream: impl TryStream<Ok = T, Error = anyhow::Error> + Unpin,
     display: F,
@@ -41,6 +41,7 @@ pub fn add_command(
                 .subcommand(account::ExecuteScriptFunctionCmd)
                 .subcommand(account::ExecuteScriptCommand)
                 .subcommand(account::sign_multisig_txn_cmd::GenerateMultisigTxnCommand)
+                .subcommand(account::sign_fee_payer_txn_cmd::SignFeePayerTxnCommand)
                 .subcommand(account::submit_txn_cmd::SubmitSignedTxnCommand)
                 .subcommand(account::SignMessageCmd)
                 .subcommand(account::VerifySignMessageCmd)
@@ -71,6 +72,12 @@ pub fn add_command(
                     //TODO support shutdown by command    
                     //.subcommand(node::service::ShutdownSystemCommand),
                 )
+                .subcommand(
+                    Command::with_name("auth")
+                        .subcommand(node::auth::ListCommand)
+                        .subcommand(node::auth::AddCommand)
+                        .subcommand(node::auth::RemoveCommand)
+                )
                 .subcommand(
                     Command::with_name("sync")
                         .subcommand(node::sync::StartCommand)
@@ -103,7 +110,12 @@ pub fn add_command(
             Command::with_name("txpool")
                 .subcommand(txpool::PendingTxnCommand)
                 .subcommand(txpool::PendingTxnsCommand)
-                .subcommand(txpool::TxPoolStatusCommand),
+                .subcommand(txpool::TxPoolStatusCommand)
+                .subcommand(txpool::GasPriceCommand)
+                .subcommand(txpool::RemoveTxnCommand)
+                .subcommand(txpool::InspectCommand)
+                .subcommand(txpool::ClearSenderCommand)
+                .subcommand(txpool::PendingAllCommand),
         )
         .command(
             Command::with_name("dev")
@@ -125,7 +137,10 @@ pub fn add_command(
                         .with_about("Subscribe the chain events")
                         .subcommand(dev::SubscribeBlockCommand)
                         .subcommand(dev::SubscribeEventCommand)
-                        .subcommand(dev::SubscribeNewTxnCommand),
+                        .subcommand(dev::SubscribeNewTxnCommand)
+                        .subcommand(dev::SubscribeTxnStatusCommand)
+                        .subcommand(dev::SubscribeResourceChangesCommand)
+                        .subcommand(dev::SubscribeChainReorgCommand),
                 )
                 .subcommand(
                     Command::with_name("log")
@@ -64,10 +64,19 @@ fn run() -> Result<()> {
                 }
                 Connect::WebSocket(address) => {
                     info!("Try to connect node by websocket: {:?}", address);
-                    let client = RpcClient::connect_websocket(address)?;
+                    let client = match opt.tls_ca_cert.as_ref() {
+                        Some(ca_cert) => {
+                            RpcClient::connect_websocket_with_ca_cert(address, ca_cert)?
+                        }
+                        None => RpcClient::connect_websocket(address)?,
+                    };
                     (client, None)
                 }
             };
+            if let Some(auth_token) = opt.auth_token.as_ref() {
+                let identity = client.authenticate(auth_token.clone())?;
+                info!("Rpc authenticated as {}", identity);
+            }
 
             let node_info = client.node_info()?;
             let state = CliState::new(
@@ -0,0 +1,54 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_config::{ApiSet, RpcAuthKind};
+use structopt::StructOpt;
+
+/// Add a rpc auth key, and print the generated secret.
+/// For api_key, the secret is the token, for jwt, the secret is used to sign HS256 token whose `sub` is the key name.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "add")]
+pub struct AddOpt {
+    #[structopt(name = "name")]
+    /// the key name, used as the identity of rpc user.
+    name: String,
+
+    #[structopt(long = "kind", default_value = "api_key")]
+    /// api_key or jwt
+    kind: RpcAuthKind,
+
+    #[structopt(long = "apis")]
+    /// api sets the key is allowed to call, eg: safe,account
+    apis: Option<ApiSet>,
+
+    #[structopt(long = "method", number_of_values = 1)]
+    /// individual methods the key is allowed to call, eg: --method account.list --method account.get
+    methods: Vec<String>,
+}
+
+pub struct AddCommand;
+
+impl CommandAction for AddCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = AddOpt;
+    type ReturnItem = String;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let client = ctx.state().client();
+        client.node_add_rpc_auth_key(
+            opt.name.clone(),
+            opt.kind.to_string(),
+            opt.apis.as_ref().map(ToString::to_string),
+            Some(opt.methods.clone()),
+        )
+    }
+}
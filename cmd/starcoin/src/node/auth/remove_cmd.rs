@@ -0,0 +1,33 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use structopt::StructOpt;
+
+/// Remove the rpc auth key, return false if the key does not exist.
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "remove")]
+pub struct RemoveOpt {
+    #[structopt(name = "name")]
+    name: String,
+}
+
+pub struct RemoveCommand;
+
+impl CommandAction for RemoveCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = RemoveOpt;
+    type ReturnItem = bool;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.node_remove_rpc_auth_key(ctx.opt().name.clone())
+    }
+}
@@ -7,6 +7,7 @@ mod peers_cmd;
 
 pub mod network;
 
+pub mod auth;
 pub mod manager;
 pub mod service;
 pub mod sync;
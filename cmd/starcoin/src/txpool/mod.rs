@@ -7,7 +7,7 @@ use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
 use starcoin_crypto::HashValue;
 use starcoin_rpc_api::types::SignedUserTransactionView;
-use starcoin_txpool_api::TxPoolStatus;
+use starcoin_txpool_api::{GasPriceStats, SenderTxnsInspect, TxPoolStatus};
 use starcoin_vm_types::account_address::AccountAddress;
 use structopt::StructOpt;
 
@@ -88,3 +88,130 @@ impl CommandAction for TxPoolStatusCommand {
         client.txpool_status()
     }
 }
+
+///Get suggested gas prices and the gas price distributions of recent blocks and pending txns
+#[derive(Debug, StructOpt)]
+#[structopt(name = "gas-price")]
+pub struct GasPriceOpt {}
+
+pub struct GasPriceCommand;
+
+impl CommandAction for GasPriceCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = GasPriceOpt;
+    type ReturnItem = GasPriceStats;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.txpool_gas_price_stats()
+    }
+}
+
+/// Remove the txn from txpool
+#[derive(Debug, StructOpt)]
+#[structopt(name = "remove-txn")]
+pub struct RemoveTxnOpt {
+    #[structopt(name = "hash", help = "hash of the txn")]
+    hash: HashValue,
+}
+
+pub struct RemoveTxnCommand;
+
+impl CommandAction for RemoveTxnCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = RemoveTxnOpt;
+    type ReturnItem = Option<SignedUserTransactionView>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.txpool_remove_txn(ctx.opt().hash)
+    }
+}
+
+/// Inspect the ready and future txns of the sender, or of all senders
+#[derive(Debug, StructOpt)]
+#[structopt(name = "inspect")]
+pub struct InspectOpt {
+    #[structopt(
+        name = "sender",
+        help = "sender of txns, inspect all senders if absent"
+    )]
+    sender: Option<AccountAddress>,
+}
+
+pub struct InspectCommand;
+
+impl CommandAction for InspectCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = InspectOpt;
+    type ReturnItem = Vec<SenderTxnsInspect>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.txpool_inspect(ctx.opt().sender)
+    }
+}
+
+/// Remove all txns of the sender from txpool
+#[derive(Debug, StructOpt)]
+#[structopt(name = "clear-sender")]
+pub struct ClearSenderOpt {
+    #[structopt(name = "sender", help = "sender of txns")]
+    sender: AccountAddress,
+}
+
+pub struct ClearSenderCommand;
+
+impl CommandAction for ClearSenderCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ClearSenderOpt;
+    type ReturnItem = Vec<HashValue>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.txpool_clear_sender(ctx.opt().sender)
+    }
+}
+
+/// List all txns in txpool, no matter ready or in future
+#[derive(Debug, StructOpt)]
+#[structopt(name = "pending-all")]
+pub struct PendingAllOpt {
+    #[structopt(long, help = "skip the first offset txns")]
+    offset: Option<u64>,
+    #[structopt(long, help = "max num to return, default to 100")]
+    limit: Option<u64>,
+}
+
+pub struct PendingAllCommand;
+
+impl CommandAction for PendingAllCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = PendingAllOpt;
+    type ReturnItem = Vec<SignedUserTransactionView>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.txpool_pending_all(ctx.opt().offset, ctx.opt().limit)
+    }
+}
@@ -3,6 +3,7 @@
 
 use crate::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
+    secp256k1::{Secp256k1PrivateKey, Secp256k1PublicKey},
     PrivateKey, Uniform,
 };
 use rand::{
@@ -33,4 +34,11 @@ impl KeyGen {
         let public_key = private_key.public_key();
         (private_key, public_key)
     }
+
+    /// Generate a Secp256k1 key pair.
+    pub fn generate_secp256k1_keypair(&mut self) -> (Secp256k1PrivateKey, Secp256k1PublicKey) {
+        let private_key = Secp256k1PrivateKey::generate(&mut self.0);
+        let public_key = private_key.public_key();
+        (private_key, public_key)
+    }
 }
@@ -26,6 +26,7 @@ pub mod ed25519 {
 pub mod hash;
 pub mod keygen;
 pub mod multi_ed25519;
+pub mod secp256k1;
 
 pub mod test_utils {
     pub use diem_crypto::test_utils::*;
@@ -0,0 +1,232 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! ECDSA signatures on the secp256k1 curve, the signed message is the `CryptoHash` of the message.
+//! The diem-crypto traits are sealed, so the keys provide the sign and verify methods by themselves.
+
+use crate::derive::{DeserializeKey, SerializeKey, SilentDebug, SilentDisplay};
+use crate::hash::{CryptoHash, HashValue};
+use crate::{CryptoMaterialError, Length, ValidCryptoMaterial, ValidCryptoMaterialStringExt};
+use anyhow::{bail, Result};
+use libsecp256k1::{Message, PublicKey, PublicKeyFormat, SecretKey, Signature};
+use serde::Serialize;
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// The length of the Secp256k1PrivateKey
+pub const SECP256K1_PRIVATE_KEY_LENGTH: usize = 32;
+/// The length of the Secp256k1PublicKey, it is the uncompressed key without the 0x04 prefix.
+pub const SECP256K1_PUBLIC_KEY_LENGTH: usize = 64;
+/// The length of the Secp256k1Signature, it is `r || s`.
+pub const SECP256K1_SIGNATURE_LENGTH: usize = 64;
+
+/// A secp256k1 private key
+#[derive(DeserializeKey, SerializeKey, SilentDebug, SilentDisplay)]
+pub struct Secp256k1PrivateKey(SecretKey);
+
+/// A secp256k1 public key
+#[derive(Clone, DeserializeKey, SerializeKey)]
+pub struct Secp256k1PublicKey(PublicKey);
+
+/// A secp256k1 signature in the lower-S form
+#[derive(Clone, DeserializeKey, SerializeKey)]
+pub struct Secp256k1Signature(Signature);
+
+fn signing_message<T: CryptoHash + Serialize>(message: &T) -> Message {
+    Message::parse_slice(message.hash().as_ref()).expect("HashValue is 32 bytes.")
+}
+
+impl Secp256k1PrivateKey {
+    /// Generate a random private key.
+    pub fn generate<R>(rng: &mut R) -> Self
+    where
+        R: ::rand::RngCore + ::rand::CryptoRng,
+    {
+        loop {
+            let mut bytes = [0u8; SECP256K1_PRIVATE_KEY_LENGTH];
+            rng.fill_bytes(&mut bytes);
+            // retry if the bytes is zero or not less than the curve order.
+            if let Ok(key) = SecretKey::parse(&bytes) {
+                return Self(key);
+            }
+        }
+    }
+
+    pub fn public_key(&self) -> Secp256k1PublicKey {
+        Secp256k1PublicKey(PublicKey::from_secret_key(&self.0))
+    }
+
+    /// Sign the `CryptoHash` of the message.
+    pub fn sign<T: CryptoHash + Serialize>(&self, message: &T) -> Secp256k1Signature {
+        let (signature, _recovery_id) = libsecp256k1::sign(&signing_message(message), &self.0);
+        Secp256k1Signature(signature)
+    }
+}
+
+impl Secp256k1Signature {
+    /// Verify the signature of the `CryptoHash` of the message.
+    pub fn verify<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+        public_key: &Secp256k1PublicKey,
+    ) -> Result<()> {
+        self.verify_hash(&message.hash(), public_key)
+    }
+
+    /// Verify the signature of a 32 bytes hash.
+    pub fn verify_hash(&self, hash: &HashValue, public_key: &Secp256k1PublicKey) -> Result<()> {
+        // reject the malleable signature.
+        if self.0.s.is_high() {
+            bail!("Secp256k1 signature is not in the lower-S form.");
+        }
+        let message = Message::parse_slice(hash.as_ref())
+            .map_err(|e| anyhow::anyhow!("Invalid message hash: {:?}", e))?;
+        if libsecp256k1::verify(&message, &self.0, &public_key.0) {
+            Ok(())
+        } else {
+            bail!("Secp256k1 signature verification failed.")
+        }
+    }
+}
+
+impl ValidCryptoMaterial for Secp256k1PrivateKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.serialize().to_vec()
+    }
+}
+
+impl ValidCryptoMaterial for Secp256k1PublicKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.serialize()[1..].to_vec()
+    }
+}
+
+impl ValidCryptoMaterial for Secp256k1Signature {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.serialize().to_vec()
+    }
+}
+
+impl Length for Secp256k1PrivateKey {
+    fn length(&self) -> usize {
+        SECP256K1_PRIVATE_KEY_LENGTH
+    }
+}
+
+impl Length for Secp256k1PublicKey {
+    fn length(&self) -> usize {
+        SECP256K1_PUBLIC_KEY_LENGTH
+    }
+}
+
+impl Length for Secp256k1Signature {
+    fn length(&self) -> usize {
+        SECP256K1_SIGNATURE_LENGTH
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256k1PrivateKey {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> std::result::Result<Self, CryptoMaterialError> {
+        if bytes.len() != SECP256K1_PRIVATE_KEY_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        SecretKey::parse_slice(bytes)
+            .map(Self)
+            .map_err(|_| CryptoMaterialError::DeserializationError)
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256k1PublicKey {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> std::result::Result<Self, CryptoMaterialError> {
+        if bytes.len() != SECP256K1_PUBLIC_KEY_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        PublicKey::parse_slice(bytes, Some(PublicKeyFormat::Raw))
+            .map(Self)
+            .map_err(|_| CryptoMaterialError::PointNotOnCurveError)
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256k1Signature {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> std::result::Result<Self, CryptoMaterialError> {
+        if bytes.len() != SECP256K1_SIGNATURE_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        let signature = Signature::parse_standard_slice(bytes)
+            .map_err(|_| CryptoMaterialError::DeserializationError)?;
+        if signature.s.is_high() {
+            return Err(CryptoMaterialError::CanonicalRepresentationError);
+        }
+        Ok(Self(signature))
+    }
+}
+
+impl PartialEq for Secp256k1PrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Eq for Secp256k1PrivateKey {}
+
+impl PartialEq for Secp256k1PublicKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Eq for Secp256k1PublicKey {}
+
+impl Hash for Secp256k1PublicKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write(&self.to_bytes())
+    }
+}
+
+impl PartialEq for Secp256k1Signature {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Eq for Secp256k1Signature {}
+
+impl Hash for Secp256k1Signature {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write(&self.to_bytes())
+    }
+}
+
+impl fmt::Debug for Secp256k1PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secp256k1PublicKey({})", hex::encode(&self.to_bytes()))
+    }
+}
+
+impl fmt::Display for Secp256k1PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.to_bytes()))
+    }
+}
+
+impl fmt::Debug for Secp256k1Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secp256k1Signature({})", hex::encode(&self.to_bytes()))
+    }
+}
+
+impl fmt::Display for Secp256k1Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.to_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests;
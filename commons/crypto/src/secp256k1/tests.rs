@@ -0,0 +1,48 @@
+use super::*;
+use crate::test_utils::{TestDiemCrypto, TEST_SEED};
+use rand::prelude::*;
+
+fn message() -> TestDiemCrypto {
+    TestDiemCrypto("Test Message".to_string())
+}
+
+#[test]
+pub fn test_sign_and_verify() {
+    let mut rng = StdRng::from_seed(TEST_SEED);
+    let private_key = Secp256k1PrivateKey::generate(&mut rng);
+    let public_key = private_key.public_key();
+    let msg = message();
+    let signature = private_key.sign(&msg);
+    signature.verify(&msg, &public_key).unwrap();
+
+    let other_msg = TestDiemCrypto("Other Message".to_string());
+    assert!(signature.verify(&other_msg, &public_key).is_err());
+    let other_public_key = Secp256k1PrivateKey::generate(&mut rng).public_key();
+    assert!(signature.verify(&msg, &other_public_key).is_err());
+}
+
+#[test]
+pub fn test_serialize() {
+    let mut rng = StdRng::from_seed(TEST_SEED);
+    let private_key = Secp256k1PrivateKey::generate(&mut rng);
+    let public_key = private_key.public_key();
+    let signature = private_key.sign(&message());
+
+    let private_key2 = Secp256k1PrivateKey::try_from(private_key.to_bytes().as_slice()).unwrap();
+    assert_eq!(private_key, private_key2);
+    let public_key2 = Secp256k1PublicKey::try_from(public_key.to_bytes().as_slice()).unwrap();
+    assert_eq!(public_key, public_key2);
+    let signature2 = Secp256k1Signature::try_from(signature.to_bytes().as_slice()).unwrap();
+    assert_eq!(signature, signature2);
+
+    let public_key3 =
+        Secp256k1PublicKey::from_encoded_string(&public_key.to_encoded_string().unwrap()).unwrap();
+    assert_eq!(public_key, public_key3);
+    let bytes = bcs_ext::to_bytes(&signature).unwrap();
+    assert_eq!(
+        signature,
+        bcs_ext::from_bytes::<Secp256k1Signature>(&bytes).unwrap()
+    );
+
+    assert!(Secp256k1PublicKey::try_from(&public_key.to_bytes()[1..]).is_err());
+}
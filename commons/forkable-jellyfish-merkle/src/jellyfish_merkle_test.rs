@@ -565,6 +565,118 @@ fn test_1000_keys() {
     many_keys_get_proof_and_verify_tree_root(seed, 1000);
 }
 
+#[test]
+fn test_multi_get() {
+    let mut rng: StdRng = StdRng::from_seed([1u8; 32]);
+    let db = MockTreeStore::default();
+    let tree = JellyfishMerkleTree::new(&db);
+
+    let mut kvs = vec![];
+    for _i in 0..1000 {
+        let key = HashValue::random_with_rng(&mut rng);
+        let value = Blob::from(HashValue::random_with_rng(&mut rng).to_vec());
+        kvs.push((HashValueKey(key), value));
+    }
+    let (root, batch) = tree.put_blob_set(None, kvs.clone()).unwrap();
+    db.write_tree_update_batch(batch).unwrap();
+
+    let mut keys = kvs.iter().map(|(k, _)| k.key_hash()).collect::<Vec<_>>();
+    // non-existent keys, one of them only differs from an existent key in the last nibble,
+    // and duplicated keys.
+    let last_nibble = keys[0].to_vec()[HashValue::LENGTH - 1] & 0x0f;
+    keys.push(HashValue::random_with_rng(&mut rng));
+    keys.push(update_nibble(&keys[0], 63, last_nibble ^ 0x01));
+    keys.push(keys[1]);
+
+    let values = tree.multi_get(root, &keys).unwrap();
+    assert_eq!(values.len(), keys.len());
+    for (key, value) in keys.iter().zip(values) {
+        assert_eq!(value, tree.get(root, *key).unwrap());
+    }
+}
+
+#[test]
+fn test_dump() {
+    let mut rng: StdRng = StdRng::from_seed([1u8; 32]);
+    let db = MockTreeStore::default();
+    let tree = JellyfishMerkleTree::new(&db);
+    assert!(tree
+        .dump(*SPARSE_MERKLE_PLACEHOLDER_HASH)
+        .unwrap()
+        .is_empty());
+
+    let mut kvs = vec![];
+    for _i in 0..1000 {
+        let key = HashValue::random_with_rng(&mut rng);
+        let value = Blob::from(HashValue::random_with_rng(&mut rng).to_vec());
+        kvs.push((HashValueKey(key), value));
+    }
+    let (root, batch) = tree.put_blob_set(None, kvs).unwrap();
+    db.write_tree_update_batch(batch).unwrap();
+
+    let expected = JellyfishMerkleIterator::new(&db, root, HashValue::zero())
+        .unwrap()
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(expected.len(), 1000);
+    assert_eq!(tree.dump(root).unwrap(), expected);
+}
+
+#[test]
+fn test_diff() {
+    let mut rng: StdRng = StdRng::from_seed([2u8; 32]);
+    let db = MockTreeStore::default();
+    let tree = JellyfishMerkleTree::new(&db);
+
+    let mut kvs = vec![];
+    for _i in 0..1000 {
+        let key = HashValue::random_with_rng(&mut rng);
+        let value = Blob::from(HashValue::random_with_rng(&mut rng).to_vec());
+        kvs.push((HashValueKey(key), value));
+    }
+    let (old_root, batch) = tree.put_blob_set(None, kvs.clone()).unwrap();
+    db.write_tree_update_batch(batch).unwrap();
+    assert!(tree.diff(old_root, old_root).unwrap().is_empty());
+
+    // update 10 keys, delete 10 keys and insert 10 keys.
+    let mut updates = vec![];
+    let mut expected = vec![];
+    for (key, value) in kvs.iter().take(10) {
+        let new_value = Blob::from(HashValue::random_with_rng(&mut rng).to_vec());
+        updates.push((*key, Some(new_value.clone())));
+        expected.push((*key, Some(value.clone()), Some(new_value)));
+    }
+    for (key, value) in kvs.iter().skip(10).take(10) {
+        updates.push((*key, None));
+        expected.push((*key, Some(value.clone()), None));
+    }
+    for _i in 0..10 {
+        let key = HashValueKey(HashValue::random_with_rng(&mut rng));
+        let value = Blob::from(HashValue::random_with_rng(&mut rng).to_vec());
+        updates.push((key, Some(value.clone())));
+        expected.push((key, None, Some(value)));
+    }
+    let (new_root, batch) = tree.updates(Some(old_root), updates).unwrap();
+    db.write_tree_update_batch(batch).unwrap();
+
+    expected.sort_by_key(|(key, _, _)| key.key_hash());
+    assert_eq!(tree.diff(old_root, new_root).unwrap(), expected);
+
+    let reversed = expected
+        .into_iter()
+        .map(|(key, old_value, new_value)| (key, new_value, old_value))
+        .collect::<Vec<_>>();
+    assert_eq!(tree.diff(new_root, old_root).unwrap(), reversed);
+
+    let all = tree
+        .diff(*SPARSE_MERKLE_PLACEHOLDER_HASH, old_root)
+        .unwrap();
+    assert_eq!(all.len(), 1000);
+    assert!(all
+        .iter()
+        .all(|(_, old_value, new_value)| old_value.is_none() && new_value.is_some()));
+}
+
 fn many_versions_get_proof_and_verify_tree_root(seed: &[u8], num_versions: usize) {
     assert!(seed.len() < 32);
     let mut actual_seed = [0u8; 32];
@@ -95,8 +95,11 @@ use proof::{SparseMerkleProof, SparseMerkleRangeProof};
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
 use serde::{de::DeserializeOwned, Serialize};
-use starcoin_crypto::{hash::PlainCryptoHash, HashValue};
-use std::collections::{BTreeMap, BTreeSet};
+use starcoin_crypto::{
+    hash::{PlainCryptoHash, SPARSE_MERKLE_PLACEHOLDER_HASH},
+    HashValue,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::marker::PhantomData;
 use tree_cache::TreeCache;
 
@@ -116,6 +119,14 @@ pub trait TreeReader<K: RawKey> {
     /// Gets node given a node key. Returns `None` if the node does not exist.
     fn get_node_option(&self, node_key: &NodeKey) -> Result<Option<Node<K>>>;
 
+    /// Gets nodes given a list of node keys, the storage can override it to read in one batch.
+    fn multi_get_node_option(&self, node_keys: &[NodeKey]) -> Result<Vec<Option<Node<K>>>> {
+        node_keys
+            .iter()
+            .map(|node_key| self.get_node_option(node_key))
+            .collect()
+    }
+
     /// Gets the rightmost leaf. Note that this assumes we are in the process of restoring the tree
     /// and all nodes are at the same version.
     fn get_rightmost_leaf(&self) -> Result<Option<(NodeKey, LeafNode<K>)>> {
@@ -692,6 +703,196 @@ where
         bail!("Jellyfish Merkle tree has cyclic graph inside.");
     }
 
+    /// Returns the values of the `keys`, the nodes at the same depth are read in one batch.
+    /// Unlike `get_with_proof`, no proof is generated.
+    pub fn multi_get(
+        &self,
+        state_root_hash: HashValue,
+        keys: &[HashValue],
+    ) -> Result<Vec<Option<Blob>>> {
+        let mut values = vec![None; keys.len()];
+        // The index of the key, and the node to visit for it.
+        let mut pending: Vec<(usize, NodeKey)> =
+            (0..keys.len()).map(|i| (i, state_root_hash)).collect();
+
+        // We limit the number of loops here deliberately to avoid potential cyclic graph bugs
+        // in the tree structure.
+        for nibble_depth in 0..=ROOT_NIBBLE_HEIGHT {
+            if pending.is_empty() {
+                return Ok(values);
+            }
+            let node_keys = pending
+                .iter()
+                .map(|(_, node_key)| *node_key)
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect::<Vec<_>>();
+            let nodes = node_keys
+                .iter()
+                .zip(self.reader.multi_get_node_option(&node_keys)?)
+                .map(|(node_key, node)| {
+                    node.map(|node| (*node_key, node))
+                        .ok_or_else(|| format_err!("Missing node at {:?}.", node_key))
+                })
+                .collect::<Result<HashMap<_, _>>>()?;
+
+            let mut next_pending = vec![];
+            for (index, node_key) in pending {
+                match &nodes[&node_key] {
+                    Node::Internal(internal_node) => {
+                        let queried_child_index = NibblePath::new(keys[index].to_vec())
+                            .nibbles()
+                            .nth(nibble_depth)
+                            .ok_or_else(|| format_err!("ran out of nibbles"))?;
+                        if let Some(child) = internal_node.child(queried_child_index) {
+                            next_pending.push((index, child.hash));
+                        }
+                    }
+                    Node::Leaf(leaf_node) => {
+                        if leaf_node.raw_key().key_hash() == keys[index] {
+                            values[index] = Some(leaf_node.blob().clone());
+                        }
+                    }
+                    Node::Null => {
+                        if nibble_depth != 0 {
+                            bail!("Non-root null node exists with node key {:?}", node_key);
+                        }
+                    }
+                }
+            }
+            pending = next_pending;
+        }
+        if pending.is_empty() {
+            Ok(values)
+        } else {
+            bail!("Jellyfish Merkle tree has cyclic graph inside.")
+        }
+    }
+
+    /// Returns all the key-value pairs of the tree in the order of key hash,
+    /// the nodes at the same depth are read in one batch.
+    pub fn dump(&self, state_root_hash: HashValue) -> Result<Vec<(K, Blob)>> {
+        if state_root_hash == *SPARSE_MERKLE_PLACEHOLDER_HASH {
+            return Ok(vec![]);
+        }
+        let mut leaves = vec![];
+        let mut pending = vec![state_root_hash];
+
+        // We limit the number of loops here deliberately to avoid potential cyclic graph bugs
+        // in the tree structure.
+        for nibble_depth in 0..=ROOT_NIBBLE_HEIGHT {
+            if pending.is_empty() {
+                break;
+            }
+            let nodes = self.reader.multi_get_node_option(&pending)?;
+            let mut next_pending = vec![];
+            for (node_key, node) in pending.iter().zip(nodes) {
+                match node.ok_or_else(|| format_err!("Missing node at {:?}.", node_key))? {
+                    Node::Internal(internal_node) => {
+                        next_pending.extend(internal_node.all_child());
+                    }
+                    Node::Leaf(leaf_node) => {
+                        leaves.push((leaf_node.raw_key().clone(), leaf_node.blob().clone()));
+                    }
+                    Node::Null => {
+                        if nibble_depth != 0 {
+                            bail!("Non-root null node exists with node key {:?}", node_key);
+                        }
+                    }
+                }
+            }
+            pending = next_pending;
+        }
+        if !pending.is_empty() {
+            bail!("Jellyfish Merkle tree has cyclic graph inside.");
+        }
+        leaves.sort_by_key(|(key, _)| key.key_hash());
+        Ok(leaves)
+    }
+
+    /// Returns the keys whose values differ between the tree of `old_root_hash` and the tree of
+    /// `new_root_hash` in the order of key hash, with the old and the new values, a value is `None`
+    /// if the key does not exist in the tree. Only the subtrees whose hashes differ are visited.
+    #[allow(clippy::type_complexity)]
+    pub fn diff(
+        &self,
+        old_root_hash: HashValue,
+        new_root_hash: HashValue,
+    ) -> Result<Vec<(K, Option<Blob>, Option<Blob>)>> {
+        let mut old_leaves = BTreeMap::new();
+        let mut new_leaves = BTreeMap::new();
+        let mut pending = vec![(old_root_hash, new_root_hash)];
+
+        // We limit the number of loops here deliberately to avoid potential cyclic graph bugs
+        // in the tree structure.
+        for _nibble_depth in 0..=ROOT_NIBBLE_HEIGHT {
+            pending.retain(|(old_node_key, new_node_key)| old_node_key != new_node_key);
+            if pending.is_empty() {
+                break;
+            }
+            let mut next_pending = vec![];
+            for (old_node_key, new_node_key) in pending {
+                match (
+                    self.get_node_or_null(&old_node_key)?,
+                    self.get_node_or_null(&new_node_key)?,
+                ) {
+                    (Node::Internal(old_node), Node::Internal(new_node)) => {
+                        for n in 0..16u8 {
+                            let child_hash = |node: &InternalNode| {
+                                node.child(nibble::Nibble::from(n))
+                                    .map(|child| child.hash)
+                                    .unwrap_or(*SPARSE_MERKLE_PLACEHOLDER_HASH)
+                            };
+                            next_pending.push((child_hash(&old_node), child_hash(&new_node)));
+                        }
+                    }
+                    // the subtrees have different shapes, compare all their leaves.
+                    _ => {
+                        for (key, blob) in self.dump(old_node_key)? {
+                            old_leaves.insert(key.key_hash(), (key, blob));
+                        }
+                        for (key, blob) in self.dump(new_node_key)? {
+                            new_leaves.insert(key.key_hash(), (key, blob));
+                        }
+                    }
+                }
+            }
+            pending = next_pending;
+        }
+        if !pending.is_empty() {
+            bail!("Jellyfish Merkle tree has cyclic graph inside.");
+        }
+
+        let key_hashes = old_leaves
+            .keys()
+            .chain(new_leaves.keys())
+            .copied()
+            .collect::<BTreeSet<_>>();
+        let mut diff = vec![];
+        for key_hash in key_hashes {
+            let old = old_leaves.remove(&key_hash);
+            let new = new_leaves.remove(&key_hash);
+            let key = match (&old, &new) {
+                (Some((key, _)), _) | (None, Some((key, _))) => key.clone(),
+                (None, None) => continue,
+            };
+            let old_blob = old.map(|(_, blob)| blob);
+            let new_blob = new.map(|(_, blob)| blob);
+            if old_blob != new_blob {
+                diff.push((key, old_blob, new_blob));
+            }
+        }
+        Ok(diff)
+    }
+
+    fn get_node_or_null(&self, node_key: &NodeKey) -> Result<Node<K>> {
+        if node_key == &*SPARSE_MERKLE_PLACEHOLDER_HASH {
+            Ok(Node::new_null())
+        } else {
+            self.reader.get_node(node_key)
+        }
+    }
+
     /// Gets the proof that shows a list of keys up to `rightmost_key_to_prove` exist at `version`.
     pub fn get_range_proof(
         &self,
@@ -97,7 +97,7 @@ impl TaskEventCounter {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct TaskProgressReport {
     pub task_name: String,
     pub sub_task: u64,
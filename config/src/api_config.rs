@@ -16,6 +16,7 @@ pub enum Api {
     State,
     SyncManager,
     TxPool,
+    TxPoolManager,
     Contract,
 }
 impl Serialize for Api {
@@ -50,6 +51,7 @@ impl std::fmt::Display for Api {
             Self::State => "state",
             Self::SyncManager => "sync_manager",
             Self::TxPool => "txpool",
+            Self::TxPoolManager => "txpool_manager",
             Self::Contract => "contract",
         };
         write!(f, "{}", display)
@@ -74,6 +76,7 @@ impl FromStr for Api {
             "state" => Ok(State),
             "sync_manager" => Ok(SyncManager),
             "txpool" => Ok(TxPool),
+            "txpool_manager" => Ok(TxPoolManager),
             "contract" => Ok(Contract),
             api => Err(format!("Unknown api: {}", api)),
         }
@@ -204,6 +207,7 @@ impl ApiSet {
                 public_list.insert(Api::NetworkManager);
                 public_list.insert(Api::SyncManager);
                 public_list.insert(Api::NodeManager);
+                public_list.insert(Api::TxPoolManager);
                 public_list
             }
 
@@ -0,0 +1,40 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BaseConfig, ConfigModule, StarcoinOpt};
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use structopt::StructOpt;
+
+#[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, StructOpt)]
+#[serde(deny_unknown_fields)]
+pub struct ExecutorConfig {
+    /// threads used to execute the user txns of a block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "executor-concurrency-level",
+        long,
+        help = "threads used to execute the user txns of a block, 1 means sequential execution, default 1."
+    )]
+    concurrency_level: Option<usize>,
+}
+
+impl ExecutorConfig {
+    pub fn concurrency_level(&self) -> usize {
+        self.concurrency_level.unwrap_or(1)
+    }
+}
+
+impl ConfigModule for ExecutorConfig {
+    fn merge_with_opt(&mut self, opt: &StarcoinOpt, _base: Arc<BaseConfig>) -> Result<()> {
+        if opt.executor.concurrency_level.is_some() {
+            self.concurrency_level = opt.executor.concurrency_level;
+        }
+        ensure!(
+            self.concurrency_level() > 0,
+            "executor concurrency level must be greater than 0"
+        );
+        Ok(())
+    }
+}
@@ -14,6 +14,17 @@ where
     Ok(())
 }
 
+/// Save a config which contains secrets, the file is only readable and writable by the owner.
+pub(crate) fn save_secret_config<T, P>(c: &T, output_file: P) -> Result<()>
+where
+    T: Serialize + DeserializeOwned,
+    P: AsRef<Path>,
+{
+    let mut file = open_secret_config_file(output_file)?;
+    file.write_all(to_toml(c)?.as_bytes())?;
+    Ok(())
+}
+
 pub(crate) fn to_toml<T>(c: &T) -> Result<String>
 where
     T: Serialize + DeserializeOwned,
@@ -94,6 +105,37 @@ where
         .open(path)
 }
 
+/// Opens a config file containing secrets in write mode, the existing content is truncated.
+#[cfg(unix)]
+fn open_secret_config_file<P>(path: P) -> io::Result<fs::File>
+where
+    P: AsRef<Path>,
+{
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // the mode only applies to a new file, so fix the permissions of an existing file.
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    Ok(file)
+}
+
+/// Opens a config file containing secrets in write mode, the existing content is truncated.
+#[cfg(not(unix))]
+fn open_secret_config_file<P>(path: P) -> io::Result<fs::File>
+where
+    P: AsRef<Path>,
+{
+    fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
+}
+
 const NODE_NAME_MAX_LENGTH: usize = 64;
 /// Generate a valid random name for the node
 pub(crate) fn generate_node_name() -> String {
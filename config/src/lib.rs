@@ -28,12 +28,14 @@ mod account_vault_config;
 mod api_config;
 mod api_quota;
 mod available_port;
+mod executor_config;
 pub mod genesis_config;
 mod helper;
 mod logger_config;
 mod metrics_config;
 mod miner_config;
 mod network_config;
+mod rpc_auth_config;
 mod rpc_config;
 mod storage_config;
 mod stratum_config;
@@ -49,6 +51,7 @@ pub use available_port::{
     get_available_port_from, get_random_available_port, get_random_available_ports,
 };
 pub use diem_temppath::TempPath;
+pub use executor_config::ExecutorConfig;
 pub use genesis_config::{
     BuiltinNetworkID, ChainNetwork, ChainNetworkID, FutureBlockParameter,
     FutureBlockParameterResolver, GenesisBlockParameter, GenesisBlockParameterConfig,
@@ -59,14 +62,15 @@ pub use logger_config::LoggerConfig;
 pub use metrics_config::MetricsConfig;
 pub use miner_config::{MinerClientConfig, MinerConfig};
 pub use network_config::{NetworkConfig, NetworkRpcQuotaConfiguration};
+pub use rpc_auth_config::{RpcAuthKey, RpcAuthKeys, RpcAuthKind};
 pub use rpc_config::{
-    ApiQuotaConfiguration, HttpConfiguration, IpcConfiguration, RpcConfig, TcpConfiguration,
-    WsConfiguration,
+    ApiQuotaConfiguration, HttpConfiguration, IpcConfiguration, RpcConfig, RpcTlsConfig,
+    TcpConfiguration, WsConfiguration,
 };
 pub use starcoin_crypto::ed25519::genesis_key_pair;
 pub use starcoin_vm_types::time::{MockTimeService, RealTimeService, TimeService};
 pub use storage_config::{RocksdbConfig, StorageConfig, DEFAULT_CACHE_SIZE};
-pub use txpool_config::TxPoolConfig;
+pub use txpool_config::{TxPoolConfig, TxPoolJournalScope};
 
 pub static CRATE_VERSION: &str = crate_version!();
 pub static GIT_VERSION: &str = git_version!(
@@ -174,6 +178,16 @@ pub struct StarcoinOpt {
     /// Connect and attach to a node
     pub connect: Option<Connect>,
 
+    #[serde(skip)]
+    #[structopt(long = "auth-token")]
+    /// Api key or jwt token to authenticate the connection, if the rpc endpoint require auth.
+    pub auth_token: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "tls-ca-cert", parse(from_os_str))]
+    /// PEM CA certificate to verify the wss rpc server, such as a self-signed certificate.
+    pub tls_ca_cert: Option<PathBuf>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     #[structopt(long = "data-dir", short = "d", parse(from_os_str))]
     /// Path to data dir, this dir is base dir, the final data_dir is base_dir/chain_network_name
@@ -215,6 +229,9 @@ pub struct StarcoinOpt {
     #[serde(default)]
     #[structopt(flatten)]
     pub stratum: StratumConfig,
+    #[serde(default)]
+    #[structopt(flatten)]
+    pub executor: ExecutorConfig,
 }
 
 impl std::fmt::Display for StarcoinOpt {
@@ -430,6 +447,8 @@ pub struct NodeConfig {
     pub logger: LoggerConfig,
     #[serde(default)]
     pub stratum: StratumConfig,
+    #[serde(default)]
+    pub executor: ExecutorConfig,
 }
 
 impl std::fmt::Display for NodeConfig {
@@ -497,7 +516,8 @@ impl NodeConfig {
         self.vault.merge_with_opt(opt, base.clone())?;
         self.metrics.merge_with_opt(opt, base.clone())?;
         self.logger.merge_with_opt(opt, base.clone())?;
-        self.stratum.merge_with_opt(opt, base)?;
+        self.stratum.merge_with_opt(opt, base.clone())?;
+        self.executor.merge_with_opt(opt, base)?;
         Ok(())
     }
 }
@@ -0,0 +1,98 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::helper::{load_config, save_secret_config};
+use crate::{Api, ApiSet};
+use anyhow::{format_err, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::str::FromStr;
+
+/// How a rpc auth key is presented by the client.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RpcAuthKind {
+    /// The secret itself is used as a bearer token.
+    ApiKey,
+    /// The secret is used to verify HS256 JWT tokens whose `sub` is the key name.
+    Jwt,
+}
+
+impl Default for RpcAuthKind {
+    fn default() -> Self {
+        RpcAuthKind::ApiKey
+    }
+}
+
+impl std::fmt::Display for RpcAuthKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RpcAuthKind::ApiKey => write!(f, "api_key"),
+            RpcAuthKind::Jwt => write!(f, "jwt"),
+        }
+    }
+}
+
+impl FromStr for RpcAuthKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "api_key" => Ok(RpcAuthKind::ApiKey),
+            "jwt" => Ok(RpcAuthKind::Jwt),
+            _ => Err(format_err!("Unknown rpc auth kind: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RpcAuthKey {
+    /// Unique name of the key, it is used as the identity of the rpc user.
+    pub name: String,
+    #[serde(default)]
+    pub kind: RpcAuthKind,
+    /// The api key, or the HS256 secret of jwt.
+    pub secret: String,
+    /// Api sets the key is allowed to call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apis: Option<ApiSet>,
+    /// Individual methods the key is allowed to call, in addition to `apis`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub methods: Vec<String>,
+}
+
+impl RpcAuthKey {
+    pub fn is_allowed(&self, api: Option<Api>, method: &str) -> bool {
+        if self.methods.iter().any(|m| m == method) {
+            return true;
+        }
+        match (api, self.apis.as_ref()) {
+            (Some(api), Some(apis)) => apis.list_apis().contains(&api),
+            _ => false,
+        }
+    }
+}
+
+/// The rpc auth keys file, keys are managed by the admin cli and persisted in toml.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RpcAuthKeys {
+    #[serde(default)]
+    pub keys: Vec<RpcAuthKey>,
+}
+
+impl RpcAuthKeys {
+    /// Load keys from `path`, return empty keys if the file does not exist.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        if !path.as_ref().exists() {
+            return Ok(Self::default());
+        }
+        load_config(path)
+    }
+
+    /// Save keys to `path`, the file holds plaintext secrets, so it is created with mode 0600.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        save_secret_config(self, path)
+    }
+}
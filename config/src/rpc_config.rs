@@ -5,7 +5,7 @@ use crate::{
     get_available_port_from, get_random_available_ports, parse_key_val, ApiQuotaConfig, ApiSet,
     BaseConfig, ConfigModule, QuotaDuration, StarcoinOpt,
 };
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use serde::{Deserialize, Serialize};
 use starcoin_logger::prelude::*;
 use std::collections::HashSet;
@@ -25,6 +25,7 @@ const DEFAULT_WEB_SOCKET_PORT: u16 = 9870;
 // UNSPECIFIED is 0.0.0.0
 const DEFAULT_RPC_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
 const DEFAULT_BLOCK_QUERY_MAX_RANGE: u64 = 32;
+const DEFAULT_AUTH_KEYS_FILE: &str = "rpc_auth_keys.toml";
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, StructOpt)]
 pub struct HttpConfiguration {
@@ -62,6 +63,26 @@ pub struct HttpConfiguration {
     /// list of http header which identify a ip, Default: X-Real-IP,X-Forwarded-For
     pub ip_headers: Option<Vec<String>>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "http-auth", long)]
+    /// Require api key or jwt authentication for http endpoint, default is false
+    pub auth: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "http-tls-cert", long, parse(from_os_str))]
+    /// PEM certificate chain file, enable tls for http endpoint if set with the key.
+    pub tls_cert: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "http-tls-key", long, parse(from_os_str))]
+    /// PEM private key(pkcs8 or rsa) file of the http tls certificate.
+    pub tls_key: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "http-tls-client-ca", long, parse(from_os_str))]
+    /// PEM CA certificates file, if set, http clients must present a certificate signed by the CA.
+    pub tls_client_ca: Option<PathBuf>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     #[structopt(name = "unsupported-rpc-protocols", long, use_delimiter = true)]
     unsupported_rpc_protocols: Option<Vec<String>>,
@@ -78,6 +99,12 @@ impl HttpConfiguration {
     pub fn apis(&self) -> &ApiSet {
         self.apis.as_ref().unwrap_or(&ApiSet::UnsafeContext)
     }
+    pub fn auth(&self) -> bool {
+        self.auth.unwrap_or(false)
+    }
+    pub fn tls(&self) -> Option<RpcTlsConfig> {
+        RpcTlsConfig::new(&self.tls_cert, &self.tls_key, &self.tls_client_ca)
+    }
     pub fn ip_headers(&self) -> Vec<String> {
         self.ip_headers
             .clone()
@@ -100,6 +127,18 @@ impl HttpConfiguration {
         if o.threads.is_some() {
             self.threads = o.threads;
         }
+        if o.auth.is_some() {
+            self.auth = o.auth;
+        }
+        if o.tls_cert.is_some() {
+            self.tls_cert = o.tls_cert.clone();
+        }
+        if o.tls_key.is_some() {
+            self.tls_key = o.tls_key.clone();
+        }
+        if o.tls_client_ca.is_some() {
+            self.tls_client_ca = o.tls_client_ca.clone();
+        }
         if o.ip_headers.is_some() {
             let mut ip_headers: HashSet<String> = self
                 .ip_headers
@@ -147,12 +186,38 @@ pub struct TcpConfiguration {
     #[structopt(name = "tcp-port", long)]
     /// Default tcp port is 9860
     pub port: Option<u16>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "tcp-auth", long)]
+    /// Require api key or jwt authentication for tcp endpoint, default is false
+    pub auth: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "tcp-tls-cert", long, parse(from_os_str))]
+    /// PEM certificate chain file, enable tls for tcp endpoint if set with the key.
+    pub tls_cert: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "tcp-tls-key", long, parse(from_os_str))]
+    /// PEM private key(pkcs8 or rsa) file of the tcp tls certificate.
+    pub tls_key: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "tcp-tls-client-ca", long, parse(from_os_str))]
+    /// PEM CA certificates file, if set, tcp clients must present a certificate signed by the CA.
+    pub tls_client_ca: Option<PathBuf>,
 }
 
 impl TcpConfiguration {
     pub fn apis(&self) -> &ApiSet {
         self.apis.as_ref().unwrap_or(&ApiSet::UnsafeContext)
     }
+    pub fn auth(&self) -> bool {
+        self.auth.unwrap_or(false)
+    }
+    pub fn tls(&self) -> Option<RpcTlsConfig> {
+        RpcTlsConfig::new(&self.tls_cert, &self.tls_key, &self.tls_client_ca)
+    }
 
     pub fn merge(&mut self, o: &Self) -> Result<()> {
         if o.disable {
@@ -164,6 +229,18 @@ impl TcpConfiguration {
         if o.port.is_some() {
             self.port = o.port;
         }
+        if o.auth.is_some() {
+            self.auth = o.auth;
+        }
+        if o.tls_cert.is_some() {
+            self.tls_cert = o.tls_cert.clone();
+        }
+        if o.tls_key.is_some() {
+            self.tls_key = o.tls_key.clone();
+        }
+        if o.tls_client_ca.is_some() {
+            self.tls_client_ca = o.tls_client_ca.clone();
+        }
         Ok(())
     }
 }
@@ -191,6 +268,26 @@ pub struct WsConfiguration {
     #[structopt(name = "websocket-max-request-body", long)]
     /// Max request body in bytes, Default is 10M
     pub max_request_body_size: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "websocket-auth", long)]
+    /// Require api key or jwt authentication for websocket endpoint, default is false
+    pub auth: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "websocket-tls-cert", long, parse(from_os_str))]
+    /// PEM certificate chain file, enable tls for websocket endpoint if set with the key.
+    pub tls_cert: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "websocket-tls-key", long, parse(from_os_str))]
+    /// PEM private key(pkcs8 or rsa) file of the websocket tls certificate.
+    pub tls_key: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "websocket-tls-client-ca", long, parse(from_os_str))]
+    /// PEM CA certificates file, if set, websocket clients must present a certificate signed by the CA.
+    pub tls_client_ca: Option<PathBuf>,
 }
 
 impl WsConfiguration {
@@ -201,6 +298,12 @@ impl WsConfiguration {
     pub fn apis(&self) -> &ApiSet {
         self.apis.as_ref().unwrap_or(&ApiSet::PubSub)
     }
+    pub fn auth(&self) -> bool {
+        self.auth.unwrap_or(false)
+    }
+    pub fn tls(&self) -> Option<RpcTlsConfig> {
+        RpcTlsConfig::new(&self.tls_cert, &self.tls_key, &self.tls_client_ca)
+    }
     pub fn merge(&mut self, o: &Self) -> Result<()> {
         if o.disable {
             self.disable = true;
@@ -214,6 +317,18 @@ impl WsConfiguration {
         if o.max_request_body_size.is_some() {
             self.max_request_body_size = o.max_request_body_size;
         }
+        if o.auth.is_some() {
+            self.auth = o.auth;
+        }
+        if o.tls_cert.is_some() {
+            self.tls_cert = o.tls_cert.clone();
+        }
+        if o.tls_key.is_some() {
+            self.tls_key = o.tls_key.clone();
+        }
+        if o.tls_client_ca.is_some() {
+            self.tls_client_ca = o.tls_client_ca.clone();
+        }
         Ok(())
     }
 }
@@ -227,6 +342,11 @@ pub struct IpcConfiguration {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[structopt(name = "ipc-apis", long, help = "rpc apiset to serve")]
     pub apis: Option<ApiSet>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "ipc-auth", long)]
+    /// Require api key or jwt authentication for ipc endpoint, default is false
+    pub auth: Option<bool>,
 }
 
 impl Default for IpcConfiguration {
@@ -234,6 +354,7 @@ impl Default for IpcConfiguration {
         Self {
             disable: false,
             apis: None,
+            auth: None,
         }
     }
 }
@@ -242,6 +363,9 @@ impl IpcConfiguration {
     pub fn apis(&self) -> &ApiSet {
         self.apis.as_ref().unwrap_or(&ApiSet::IpcContext)
     }
+    pub fn auth(&self) -> bool {
+        self.auth.unwrap_or(false)
+    }
     pub fn merge(&mut self, o: &Self) -> Result<()> {
         if o.disable {
             self.disable = true;
@@ -249,6 +373,54 @@ impl IpcConfiguration {
         if o.apis.is_some() {
             self.apis = o.apis.clone();
         }
+        if o.auth.is_some() {
+            self.auth = o.auth;
+        }
+        Ok(())
+    }
+}
+
+/// Tls config of a rpc listener.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcTlsConfig {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+    /// Verify client certificate by the CA if set.
+    pub client_ca: Option<PathBuf>,
+}
+
+impl RpcTlsConfig {
+    fn new(
+        cert: &Option<PathBuf>,
+        key: &Option<PathBuf>,
+        client_ca: &Option<PathBuf>,
+    ) -> Option<Self> {
+        match (cert, key) {
+            (Some(cert), Some(key)) => Some(Self {
+                cert: cert.clone(),
+                key: key.clone(),
+                client_ca: client_ca.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    fn check(
+        listener: &str,
+        cert: &Option<PathBuf>,
+        key: &Option<PathBuf>,
+        client_ca: &Option<PathBuf>,
+    ) -> Result<()> {
+        ensure!(
+            cert.is_some() == key.is_some(),
+            "Both tls cert and key of {} rpc should be set",
+            listener
+        );
+        ensure!(
+            client_ca.is_none() || cert.is_some(),
+            "Tls client ca of {} rpc requires tls cert and key",
+            listener
+        );
         Ok(())
     }
 }
@@ -371,6 +543,11 @@ pub struct RpcConfig {
     #[structopt(long = "event-query-max-block-range")]
     pub block_query_max_range: Option<u64>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "rpc-auth-keys-file", parse(from_os_str))]
+    /// Rpc auth keys file path, relative path is in data_dir. default to rpc_auth_keys.toml
+    pub auth_keys_file: Option<PathBuf>,
+
     #[serde(skip)]
     #[structopt(skip)]
     http_address: Option<ListenAddress>,
@@ -451,6 +628,18 @@ impl RpcConfig {
             .unwrap_or(DEFAULT_BLOCK_QUERY_MAX_RANGE)
     }
 
+    pub fn auth_keys_file(&self) -> PathBuf {
+        let path = self
+            .auth_keys_file
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_AUTH_KEYS_FILE));
+        if path.is_absolute() {
+            path
+        } else {
+            self.base().data_dir().join(path)
+        }
+    }
+
     fn base(&self) -> &BaseConfig {
         self.base.as_ref().expect("Config should init.")
     }
@@ -486,17 +675,28 @@ impl RpcConfig {
         self.http_address = if self.http.disable {
             None
         } else {
-            Some(ListenAddress::new("http", self.rpc_address(), http_port))
+            let protocol = if self.http.tls().is_some() {
+                "https"
+            } else {
+                "http"
+            };
+            Some(ListenAddress::new(protocol, self.rpc_address(), http_port))
         };
         self.tcp_address = if self.tcp.disable {
             None
         } else {
-            Some(ListenAddress::new("tcp", self.rpc_address(), tcp_port))
+            let protocol = if self.tcp.tls().is_some() {
+                "tls"
+            } else {
+                "tcp"
+            };
+            Some(ListenAddress::new(protocol, self.rpc_address(), tcp_port))
         };
         self.ws_address = if self.ws.disable {
             None
         } else {
-            Some(ListenAddress::new("ws", self.rpc_address(), ws_port))
+            let protocol = if self.ws.tls().is_some() { "wss" } else { "ws" };
+            Some(ListenAddress::new(protocol, self.rpc_address(), ws_port))
         };
     }
 
@@ -523,11 +723,32 @@ impl ConfigModule for RpcConfig {
         if opt.rpc.block_query_max_range.is_some() {
             self.block_query_max_range = opt.rpc.block_query_max_range;
         }
+        if opt.rpc.auth_keys_file.is_some() {
+            self.auth_keys_file = opt.rpc.auth_keys_file.clone();
+        }
         self.http.merge(&opt.rpc.http)?;
         self.tcp.merge(&opt.rpc.tcp)?;
         self.ws.merge(&opt.rpc.ws)?;
         self.ipc.merge(&opt.rpc.ipc)?;
         self.api_quotas.merge(&opt.rpc.api_quotas)?;
+        RpcTlsConfig::check(
+            "http",
+            &self.http.tls_cert,
+            &self.http.tls_key,
+            &self.http.tls_client_ca,
+        )?;
+        RpcTlsConfig::check(
+            "tcp",
+            &self.tcp.tls_cert,
+            &self.tcp.tls_key,
+            &self.tcp.tls_client_ca,
+        )?;
+        RpcTlsConfig::check(
+            "websocket",
+            &self.ws.tls_cert,
+            &self.ws.tls_key,
+            &self.ws.tls_client_ca,
+        )?;
 
         self.generate_address();
 
@@ -28,6 +28,42 @@ pub struct SyncConfig {
         help = "max retry times once sync block failed, default 15."
     )]
     max_retry_times: Option<u64>,
+
+    /// block count of every block fetch request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "block-fetch-batch-size",
+        long,
+        help = "block count of every block fetch request, default 10."
+    )]
+    block_fetch_batch_size: Option<u64>,
+
+    /// max concurrent block fetch requests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "max-parallel-block-fetches",
+        long,
+        help = "max concurrent block fetch requests, default 8."
+    )]
+    max_parallel_block_fetches: Option<u64>,
+
+    /// block fetch request timeout in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "block-fetch-timeout",
+        long,
+        help = "block fetch request timeout in milliseconds, then re-request from another peer, default 10000."
+    )]
+    block_fetch_timeout: Option<u64>,
+
+    /// block archive source, a local directory or a http(s) mirror url
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "block-archive-source",
+        long,
+        help = "sync from a block archive before sync from peers, the source is a local directory or a http(s) mirror url."
+    )]
+    block_archive_source: Option<String>,
 }
 
 impl SyncConfig {
@@ -38,6 +74,22 @@ impl SyncConfig {
     pub fn max_retry_times(&self) -> u64 {
         self.max_retry_times.unwrap_or(15)
     }
+
+    pub fn block_fetch_batch_size(&self) -> u64 {
+        self.block_fetch_batch_size.unwrap_or(10)
+    }
+
+    pub fn max_parallel_block_fetches(&self) -> u64 {
+        self.max_parallel_block_fetches.unwrap_or(8)
+    }
+
+    pub fn block_fetch_timeout(&self) -> u64 {
+        self.block_fetch_timeout.unwrap_or(10_000)
+    }
+
+    pub fn block_archive_source(&self) -> Option<&str> {
+        self.block_archive_source.as_deref()
+    }
 }
 
 impl ConfigModule for SyncConfig {
@@ -50,6 +102,22 @@ impl ConfigModule for SyncConfig {
             self.max_retry_times = opt.sync.max_retry_times;
         }
 
+        if opt.sync.block_fetch_batch_size.is_some() {
+            self.block_fetch_batch_size = opt.sync.block_fetch_batch_size;
+        }
+
+        if opt.sync.max_parallel_block_fetches.is_some() {
+            self.max_parallel_block_fetches = opt.sync.max_parallel_block_fetches;
+        }
+
+        if opt.sync.block_fetch_timeout.is_some() {
+            self.block_fetch_timeout = opt.sync.block_fetch_timeout;
+        }
+
+        if opt.sync.block_archive_source.is_some() {
+            self.block_archive_source = opt.sync.block_archive_source.clone();
+        }
+
         Ok(())
     }
 }
@@ -183,3 +183,27 @@ fn test_genesis_config_security() {
         );
     }
 }
+
+#[test]
+fn test_rpc_auth_keys_save() -> Result<()> {
+    let temp_path = temp_path();
+    let file_path = temp_path.path().join("rpc_auth_keys.toml");
+    let keys = RpcAuthKeys {
+        keys: vec![RpcAuthKey {
+            name: "admin".to_string(),
+            kind: RpcAuthKind::Jwt,
+            secret: "secret".to_string(),
+            apis: Some(ApiSet::All),
+            methods: vec![],
+        }],
+    };
+    keys.save(&file_path)?;
+    assert_eq!(RpcAuthKeys::load(&file_path)?, keys);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(&file_path)?.permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+    Ok(())
+}
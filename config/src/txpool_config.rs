@@ -2,15 +2,63 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{BaseConfig, ConfigModule, StarcoinOpt};
-use anyhow::Result;
+use anyhow::{format_err, Result};
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use starcoin_system::get_free_mem_size;
+use starcoin_types::account_address::AccountAddress;
+use std::cmp;
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 use structopt::StructOpt;
 
 pub const DEFAULT_MEM_SIZE: u64 = 128 * 1024 * 1024; // 128M
 
-#[derive(Default, Clone, Debug, Eq, PartialEq, Deserialize, Serialize, StructOpt)]
+static DEFAULT_JOURNAL_PATH: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("txpool/journal.bcs"));
+
+/// Which transactions are written to the txpool journal.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxPoolJournalScope {
+    /// Do not journal transactions.
+    Disable,
+    /// Only journal local transactions, which are submitted through this node.
+    Local,
+    /// Journal all pending transactions.
+    All,
+}
+
+impl Default for TxPoolJournalScope {
+    fn default() -> Self {
+        TxPoolJournalScope::Disable
+    }
+}
+
+impl std::fmt::Display for TxPoolJournalScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TxPoolJournalScope::Disable => write!(f, "disable"),
+            TxPoolJournalScope::Local => write!(f, "local"),
+            TxPoolJournalScope::All => write!(f, "all"),
+        }
+    }
+}
+
+impl FromStr for TxPoolJournalScope {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "disable" => Ok(TxPoolJournalScope::Disable),
+            "local" => Ok(TxPoolJournalScope::Local),
+            "all" => Ok(TxPoolJournalScope::All),
+            _ => Err(format_err!("Unknown txpool journal scope: {}", s)),
+        }
+    }
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Deserialize, Serialize, StructOpt)]
 #[serde(deny_unknown_fields)]
 pub struct TxPoolConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -37,6 +85,58 @@ pub struct TxPoolConfig {
     #[structopt(name = "txpool-min-gas-price", long)]
     /// reject transaction whose gas_price is less than the min_gas_price. default to 1.
     min_gas_price: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-replace-gas-price-bump-percent", long)]
+    /// the min gas price bump percent to replace a transaction with the same sender and sequence number. default to 0.
+    replace_gas_price_bump_percent: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-replace-local-by-remote", long)]
+    /// allow a local transaction to be replaced by a transaction received from peers. default to false.
+    replace_local_by_remote: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-max-replacements-per-sender", long)]
+    /// max replacements of a sender's transactions in the pool. default to 16.
+    max_replacements_per_sender: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-gas-price-sample-blocks", long)]
+    /// count of the recent blocks sampled by the gas price oracle. default to 20.
+    gas_price_sample_blocks: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-journal-scope", long)]
+    /// which transactions are journaled and replayed after restart, disable|local|all. default to disable.
+    journal_scope: Option<TxPoolJournalScope>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-journal-path", long, parse(from_os_str))]
+    /// txpool journal file path, relative path is in data_dir. default to txpool/journal.bcs
+    journal_path: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-journal-rotate-interval", long)]
+    /// interval(s) of regenerating the journal from the pending transactions. default to 3600.
+    journal_rotate_interval: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-priority-senders", long, number_of_values = 1)]
+    /// the transactions of the priority senders are prioritized as local transactions,
+    /// and exempt from the max per sender limit.
+    priority_senders: Option<Vec<AccountAddress>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-local-reserved-percent", long)]
+    /// percent of the pool capacity reserved for the local and priority senders' transactions,
+    /// the other transactions are rejected when the rest capacity is used up.
+    /// default to 0, no capacity is reserved.
+    local_reserved_percent: Option<u64>,
+
+    #[serde(skip)]
+    #[structopt(skip)]
+    base: Option<Arc<BaseConfig>>,
 }
 
 impl TxPoolConfig {
@@ -46,6 +146,9 @@ impl TxPoolConfig {
     pub fn max_count(&self) -> u64 {
         self.max_count.unwrap_or(4096)
     }
+    pub fn set_max_per_sender(&mut self, max_per_sender: u64) {
+        self.max_per_sender = Some(max_per_sender);
+    }
     pub fn max_per_sender(&self) -> u64 {
         self.max_per_sender.unwrap_or(128)
     }
@@ -68,10 +171,59 @@ impl TxPoolConfig {
     pub fn min_gas_price(&self) -> u64 {
         self.min_gas_price.unwrap_or(1)
     }
+    pub fn set_replace_gas_price_bump_percent(&mut self, replace_gas_price_bump_percent: u64) {
+        self.replace_gas_price_bump_percent = Some(replace_gas_price_bump_percent);
+    }
+    pub fn replace_gas_price_bump_percent(&self) -> u64 {
+        self.replace_gas_price_bump_percent.unwrap_or(0)
+    }
+    pub fn replace_local_by_remote(&self) -> bool {
+        self.replace_local_by_remote.unwrap_or(false)
+    }
+    pub fn max_replacements_per_sender(&self) -> u64 {
+        self.max_replacements_per_sender.unwrap_or(16)
+    }
+    pub fn gas_price_sample_blocks(&self) -> u64 {
+        self.gas_price_sample_blocks.unwrap_or(20)
+    }
+    pub fn set_journal_scope(&mut self, journal_scope: TxPoolJournalScope) {
+        self.journal_scope = Some(journal_scope);
+    }
+    pub fn journal_scope(&self) -> TxPoolJournalScope {
+        self.journal_scope.unwrap_or_default()
+    }
+    /// The journal file path, None if the journal is disabled or the config is not init.
+    pub fn journal_path(&self) -> Option<PathBuf> {
+        if self.journal_scope() == TxPoolJournalScope::Disable {
+            return None;
+        }
+        let path = self.journal_path.as_ref().unwrap_or(&DEFAULT_JOURNAL_PATH);
+        if path.is_absolute() {
+            Some(path.clone())
+        } else {
+            self.base.as_ref().map(|base| base.data_dir().join(path))
+        }
+    }
+    pub fn journal_rotate_interval(&self) -> u64 {
+        self.journal_rotate_interval.unwrap_or(3600)
+    }
+    pub fn set_priority_senders(&mut self, priority_senders: Vec<AccountAddress>) {
+        self.priority_senders = Some(priority_senders);
+    }
+    pub fn priority_senders(&self) -> Vec<AccountAddress> {
+        self.priority_senders.clone().unwrap_or_default()
+    }
+    pub fn set_local_reserved_percent(&mut self, local_reserved_percent: u64) {
+        self.local_reserved_percent = Some(local_reserved_percent);
+    }
+    pub fn local_reserved_percent(&self) -> u64 {
+        cmp::min(self.local_reserved_percent.unwrap_or(0), 100)
+    }
 }
 
 impl ConfigModule for TxPoolConfig {
-    fn merge_with_opt(&mut self, opt: &StarcoinOpt, _base: Arc<BaseConfig>) -> Result<()> {
+    fn merge_with_opt(&mut self, opt: &StarcoinOpt, base: Arc<BaseConfig>) -> Result<()> {
+        self.base = Some(base);
         let txpool_opt = &opt.txpool;
         if let Some(m) = txpool_opt.max_mem_usage.as_ref() {
             self.max_mem_usage = Some(*m);
@@ -88,6 +240,33 @@ impl ConfigModule for TxPoolConfig {
         if let Some(m) = txpool_opt.min_gas_price.as_ref() {
             self.min_gas_price = Some(*m);
         }
+        if let Some(m) = txpool_opt.replace_gas_price_bump_percent.as_ref() {
+            self.replace_gas_price_bump_percent = Some(*m);
+        }
+        if let Some(m) = txpool_opt.replace_local_by_remote.as_ref() {
+            self.replace_local_by_remote = Some(*m);
+        }
+        if let Some(m) = txpool_opt.max_replacements_per_sender.as_ref() {
+            self.max_replacements_per_sender = Some(*m);
+        }
+        if let Some(m) = txpool_opt.gas_price_sample_blocks.as_ref() {
+            self.gas_price_sample_blocks = Some(*m);
+        }
+        if let Some(m) = txpool_opt.journal_scope.as_ref() {
+            self.journal_scope = Some(*m);
+        }
+        if let Some(m) = txpool_opt.journal_path.as_ref() {
+            self.journal_path = Some(m.clone());
+        }
+        if let Some(m) = txpool_opt.journal_rotate_interval.as_ref() {
+            self.journal_rotate_interval = Some(*m);
+        }
+        if let Some(m) = txpool_opt.priority_senders.as_ref() {
+            self.priority_senders = Some(m.clone());
+        }
+        if let Some(m) = txpool_opt.local_reserved_percent.as_ref() {
+            self.local_reserved_percent = Some(*m);
+        }
         Ok(())
     }
 }
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use serde_reflection::{Error, Samples, Tracer, TracerConfig};
 use starcoin_crypto::ed25519::Ed25519PrivateKey;
+use starcoin_crypto::keygen::KeyGen;
 use starcoin_crypto::multi_ed25519::MultiEd25519PrivateKey;
 use starcoin_crypto::{
     hash::{CryptoHash, CryptoHasher},
@@ -63,6 +64,12 @@ fn generate() -> Result<(), Error> {
         tracer.trace_value(&mut samples, &pri_key.public_key())?;
         tracer.trace_value(&mut samples, &pri_key.sign(&DummyObj::default()))?;
     }
+    {
+        let (pri_key, pub_key) = KeyGen::from_os_rng().generate_secp256k1_keypair();
+        tracer.trace_value(&mut samples, &pri_key)?;
+        tracer.trace_value(&mut samples, &pub_key)?;
+        tracer.trace_value(&mut samples, &pri_key.sign(&DummyObj::default()))?;
+    }
 
     tracer.trace_type::<BlockMetadata>(&samples)?;
 
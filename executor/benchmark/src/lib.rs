@@ -6,7 +6,11 @@ use crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
     HashValue, PrivateKey, Uniform,
 };
-use executor::{encode_create_account_script_function, encode_transfer_script_function};
+use executor::account::{create_account_txn_sent_as_association, peer_to_peer_txn};
+use executor::{
+    encode_create_account_script_function, encode_transfer_script_function, Account,
+    ParallelExecutor, DEFAULT_EXPIRATION_TIME,
+};
 use logger::prelude::*;
 use rand::{rngs::StdRng, SeedableRng};
 use starcoin_config::ChainNetwork;
@@ -284,6 +288,67 @@ pub fn run_benchmark(
     exe_thread.join().unwrap();
 }
 
+/// Independent transfers, every transfer has its own sender and receiver,
+/// so no transfer conflicts with the others when they are executed in parallel.
+pub struct ParallelTransfers {
+    chain_state: ChainStateDB,
+    txns: Vec<Transaction>,
+}
+
+impl ParallelTransfers {
+    pub fn new(num_transfers: usize) -> Self {
+        let storage = Arc::new(Storage::new(StorageInstance::new_cache_instance()).unwrap());
+        let chain_state = ChainStateDB::new(storage, None);
+        let net = ChainNetwork::new_test();
+        let genesis_txn = Genesis::build_genesis_transaction(&net).unwrap();
+        let _txn_info = Genesis::execute_genesis_txn(&chain_state, genesis_txn).unwrap();
+
+        let accounts: Vec<Account> = (0..num_transfers * 2).map(|_| Account::new()).collect();
+        let expiration = net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME;
+        let create_account_txns = accounts
+            .iter()
+            .enumerate()
+            .map(|(i, account)| {
+                Transaction::UserTransaction(create_account_txn_sent_as_association(
+                    account,
+                    i as u64,
+                    1_000_000_000,
+                    expiration,
+                    &net,
+                ))
+            })
+            .collect();
+        executor::block_execute(&chain_state, create_account_txns, u64::MAX)
+            .expect("Execute create account transactions fail.");
+        let txns = accounts
+            .chunks(2)
+            .map(|pair| {
+                Transaction::UserTransaction(peer_to_peer_txn(
+                    &pair[0],
+                    &pair[1],
+                    0,
+                    1,
+                    expiration,
+                    net.chain_id(),
+                ))
+            })
+            .collect();
+        Self { chain_state, txns }
+    }
+
+    /// Execute the transfers by the `parallel_executor`, the state is not changed.
+    pub fn execute(&self, parallel_executor: ParallelExecutor) {
+        let outputs = executor::execute_block_transactions_in_parallel(
+            &self.chain_state,
+            self.txns.clone(),
+            u64::MAX,
+            parallel_executor,
+        )
+        .expect("Execute transactions fail.");
+        assert_eq!(outputs.len(), self.txns.len());
+    }
+}
+
 fn create_transaction(
     sequence_number: u64,
     program: ScriptFunction,
@@ -82,6 +82,19 @@ impl Account {
         }
     }
 
+    /// Creates a new account with a random secp256k1 keypair.
+    ///
+    /// Like with [`Account::new`], the account returned by this constructor is a purely logical
+    /// entity.
+    pub fn new_secp256k1() -> Self {
+        let (privkey, _pubkey) = KeyGen::from_os_rng().generate_secp256k1_keypair();
+        let private_key = AccountPrivateKey::Secp256k1(privkey);
+        Account {
+            addr: private_key.public_key().derived_address(),
+            private_key: Arc::new(private_key),
+        }
+    }
+
     /// Creates a new account in memory representing an account created in the genesis transaction.
     ///
     /// The address will be [`address`], which should be an address for a genesis account and
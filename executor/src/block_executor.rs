@@ -6,15 +6,18 @@ use starcoin_state_api::ChainState;
 use starcoin_types::error::BlockExecutorError;
 use starcoin_types::error::ExecutorResult;
 use starcoin_types::transaction::TransactionStatus;
-use starcoin_types::transaction::{Transaction, TransactionInfo};
+use starcoin_types::transaction::{Transaction, TransactionInfo, TransactionOutput};
 use starcoin_vm_types::contract_event::ContractEvent;
+use starcoin_vm_types::write_set::WriteSet;
 use vm_runtime::metrics::TXN_STATUS_COUNTERS;
+use vm_runtime::parallel_executor::ParallelExecutor;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BlockExecutedData {
     pub state_root: HashValue,
     pub txn_infos: Vec<TransactionInfo>,
     pub txn_events: Vec<Vec<ContractEvent>>,
+    pub txn_write_sets: Vec<WriteSet>,
 }
 
 impl Default for BlockExecutedData {
@@ -23,6 +26,7 @@ impl Default for BlockExecutedData {
             state_root: HashValue::zero(),
             txn_events: vec![],
             txn_infos: vec![],
+            txn_write_sets: vec![],
         }
     }
 }
@@ -35,7 +39,35 @@ pub fn block_execute(
     let txn_outputs =
         crate::execute_block_transactions(chain_state.as_super(), txns.clone(), block_gas_limit)
             .map_err(BlockExecutorError::BlockTransactionExecuteErr)?;
+    apply_block_outputs(chain_state, txns, txn_outputs)
+}
+
+/// Execute the block txns like `block_execute`, but the user txns are executed in parallel
+/// by the `parallel_executor`, or sequentially if it has no thread.
+pub fn block_execute_in_parallel<S>(
+    chain_state: &S,
+    txns: Vec<Transaction>,
+    block_gas_limit: u64,
+    parallel_executor: ParallelExecutor,
+) -> ExecutorResult<BlockExecutedData>
+where
+    S: ChainState + Sync,
+{
+    let txn_outputs = crate::execute_block_transactions_in_parallel(
+        chain_state,
+        txns.clone(),
+        block_gas_limit,
+        parallel_executor,
+    )
+    .map_err(BlockExecutorError::BlockTransactionExecuteErr)?;
+    apply_block_outputs(chain_state, txns, txn_outputs)
+}
 
+fn apply_block_outputs(
+    chain_state: &dyn ChainState,
+    txns: Vec<Transaction>,
+    txn_outputs: Vec<TransactionOutput>,
+) -> ExecutorResult<BlockExecutedData> {
     let mut executed_data = BlockExecutedData::default();
     for (txn, output) in txns
         .iter()
@@ -54,7 +86,7 @@ pub fn block_execute(
             TransactionStatus::Keep(status) => {
                 TXN_STATUS_COUNTERS.with_label_values(&["KEEP"]).inc();
                 chain_state
-                    .apply_write_set(write_set)
+                    .apply_write_set(write_set.clone())
                     .map_err(BlockExecutorError::BlockChainStateErr)?;
 
                 let txn_state_root = chain_state
@@ -69,6 +101,7 @@ pub fn block_execute(
                     status,
                 ));
                 executed_data.txn_events.push(events);
+                executed_data.txn_write_sets.push(write_set);
             }
         };
     }
@@ -76,3 +109,61 @@ pub fn block_execute(
     executed_data.state_root = chain_state.state_root();
     Ok(executed_data)
 }
+
+/// A txn re-executed by `block_replay`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReplayedTransaction {
+    pub txn_hash: HashValue,
+    pub status: TransactionStatus,
+    /// `None` if the txn is discarded.
+    pub txn_info: Option<TransactionInfo>,
+    pub write_set: WriteSet,
+}
+
+/// Re-execute the block txns like `block_execute`, but a discarded txn is returned instead of
+/// failing the whole block, so the result can be compared with the stored one.
+/// The write sets are committed to `chain_state` but not flushed.
+pub fn block_replay(
+    chain_state: &dyn ChainState,
+    txns: Vec<Transaction>,
+    block_gas_limit: u64,
+) -> ExecutorResult<Vec<ReplayedTransaction>> {
+    let txn_outputs =
+        crate::execute_block_transactions(chain_state.as_super(), txns.clone(), block_gas_limit)
+            .map_err(BlockExecutorError::BlockTransactionExecuteErr)?;
+
+    let mut replayed_txns = vec![];
+    for (txn, output) in txns
+        .iter()
+        .take(txn_outputs.len())
+        .zip(txn_outputs.into_iter())
+    {
+        let txn_hash = txn.id();
+        let (write_set, events, gas_used, status) = output.into_inner();
+        let txn_info = match &status {
+            TransactionStatus::Discard(_) => None,
+            TransactionStatus::Keep(kept_status) => {
+                chain_state
+                    .apply_write_set(write_set.clone())
+                    .map_err(BlockExecutorError::BlockChainStateErr)?;
+                let txn_state_root = chain_state
+                    .commit()
+                    .map_err(BlockExecutorError::BlockChainStateErr)?;
+                Some(TransactionInfo::new(
+                    txn_hash,
+                    txn_state_root,
+                    events.as_slice(),
+                    gas_used,
+                    kept_status.clone(),
+                ))
+            }
+        };
+        replayed_txns.push(ReplayedTransaction {
+            txn_hash,
+            status,
+            txn_info,
+            write_set,
+        });
+    }
+    Ok(replayed_txns)
+}
@@ -6,6 +6,7 @@ use starcoin_types::transaction::{SignedUserTransaction, Transaction, Transactio
 use starcoin_vm_types::identifier::Identifier;
 use starcoin_vm_types::language_storage::{ModuleId, TypeTag};
 use starcoin_vm_types::{state_view::StateView, vm_status::VMStatus};
+use vm_runtime::parallel_executor::ParallelExecutor;
 use vm_runtime::{metrics::TXN_EXECUTION_HISTOGRAM, starcoin_vm::StarcoinVM};
 
 pub fn execute_transactions(
@@ -25,6 +26,30 @@ pub fn execute_block_transactions(
     do_execute_block_transactions(chain_state, txns, Some(block_gas_limit))
 }
 
+/// Execute a block transactions like `execute_block_transactions`,
+/// but the user txns are executed in parallel by the `parallel_executor`.
+pub fn execute_block_transactions_in_parallel(
+    chain_state: &(dyn StateView + Sync),
+    txns: Vec<Transaction>,
+    block_gas_limit: u64,
+    parallel_executor: ParallelExecutor,
+) -> Result<Vec<TransactionOutput>> {
+    let timer = TXN_EXECUTION_HISTOGRAM
+        .with_label_values(&["execute_block_transactions"])
+        .start_timer();
+    let mut vm = StarcoinVM::new_with_parallel_executor(parallel_executor);
+    let result = vm
+        .execute_block_transactions_in_parallel(chain_state, txns, Some(block_gas_limit))?
+        .into_iter()
+        .map(|(_, output)| {
+            debug! {"{:?}", output};
+            output
+        })
+        .collect();
+    timer.observe_duration();
+    Ok(result)
+}
+
 fn do_execute_block_transactions(
     chain_state: &dyn StateView,
     txns: Vec<Transaction>,
@@ -4,7 +4,9 @@
 extern crate log;
 
 pub use account::Account;
-pub use block_executor::{block_execute, BlockExecutedData};
+pub use block_executor::{
+    block_execute, block_execute_in_parallel, block_replay, BlockExecutedData, ReplayedTransaction,
+};
 pub use executor::*;
 pub use starcoin_transaction_builder::{
     build_accept_token_txn, build_batch_transfer_txn, build_transfer_from_association,
@@ -14,6 +16,7 @@ pub use starcoin_transaction_builder::{
     encode_transfer_script_function, peer_to_peer_txn_sent_as_association, DEFAULT_EXPIRATION_TIME,
     DEFAULT_MAX_GAS_AMOUNT,
 };
+pub use vm_runtime::parallel_executor::ParallelExecutor;
 
 pub mod account;
 mod block_executor;
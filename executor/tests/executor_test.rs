@@ -4,6 +4,7 @@
 use anyhow::anyhow;
 use anyhow::Result;
 use logger::prelude::*;
+use starcoin_config::ChainNetwork;
 use starcoin_executor::account::{create_account_txn_sent_as_association, peer_to_peer_txn};
 use starcoin_executor::{encode_create_account_script_function, validate_transaction, Account};
 use starcoin_transaction_builder::{
@@ -12,19 +13,23 @@ use starcoin_transaction_builder::{
 };
 use starcoin_types::identifier::Identifier;
 use starcoin_types::language_storage::ModuleId;
-use starcoin_types::transaction::{RawUserTransaction, ScriptFunction, TransactionArgument};
+use starcoin_types::transaction::{
+    DryRunTransaction, RawUserTransaction, ScriptFunction, TransactionArgument,
+};
 use starcoin_types::{
     account_config, block_metadata::BlockMetadata, transaction::Transaction,
     transaction::TransactionPayload, transaction::TransactionStatus,
 };
 use starcoin_vm_types::access_path::AccessPath;
 use starcoin_vm_types::account_config::genesis_address;
-use starcoin_vm_types::account_config::AccountResource;
+use starcoin_vm_types::account_config::{AccountResource, BalanceResource};
+use starcoin_vm_types::file_format::CompiledModule;
 use starcoin_vm_types::genesis_config::ChainId;
+use starcoin_vm_types::language_storage::StructTag;
 use starcoin_vm_types::on_chain_config::{ConsensusConfig, OnChainConfig};
 use starcoin_vm_types::state_view::StateView;
 use starcoin_vm_types::token::stc::{stc_type_tag, STCUnit};
-use starcoin_vm_types::vm_status::KeptVMStatus;
+use starcoin_vm_types::vm_status::{KeptVMStatus, VMStatus};
 use starcoin_vm_types::{transaction::Package, vm_status::StatusCode};
 use test_helper::executor::{
     account_execute, account_execute_should_success, association_execute_should_success,
@@ -37,10 +42,14 @@ use test_helper::executor::{
     prepare_genesis,
 };
 // use test_helper::Account;
-use starcoin_state_api::StateReaderExt;
+use starcoin_dev::state_overlay::OverlayStateView;
+use starcoin_state_api::{ChainStateReader, ChainStateWriter, StateReaderExt};
 use starcoin_types::account_config::STC_TOKEN_CODE;
 use starcoin_vm_types::account_config::core_code_address;
+use statedb::ChainStateDB;
+use std::convert::TryInto;
 use vm_runtime::starcoin_vm::StarcoinVM;
+use vm_runtime::tracer::CallKind;
 
 #[derive(Default)]
 pub struct NullStateView;
@@ -982,3 +991,502 @@ fn test_block_metadata() -> Result<()> {
 
     Ok(())
 }
+
+#[stest::test]
+fn test_dry_run_with_trace() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+
+    let association = Account::new_association();
+    let receiver = Account::new();
+    let raw_txn = raw_peer_to_peer_txn(
+        *association.address(),
+        *receiver.address(),
+        STCUnit::STC.value_of(1).scaling(),
+        get_sequence_number(*association.address(), &chain_state),
+        1,
+        DEFAULT_MAX_GAS_AMOUNT,
+        STC_TOKEN_CODE.clone(),
+        net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+        net.chain_id(),
+    );
+    let (vm_status, output, calls) = starcoin_dev::playground::dry_run_with_trace(
+        &chain_state,
+        DryRunTransaction {
+            raw_txn,
+            public_key: association.public_key(),
+        },
+    )?;
+    assert_eq!(VMStatus::Executed, vm_status);
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+
+    let kinds: Vec<CallKind> = calls.iter().map(|call| call.kind).collect();
+    assert_eq!(
+        vec![
+            CallKind::Prologue,
+            CallKind::ScriptFunction,
+            CallKind::Epilogue
+        ],
+        kinds
+    );
+    assert!(calls[1].gas_used > 0);
+    Ok(())
+}
+
+#[stest::test]
+fn test_dry_run_with_trace_of_aborted_txn() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+
+    let association = Account::new_association();
+    let receiver = Account::new();
+    let amount = u128::max_value();
+    let raw_txn = raw_peer_to_peer_txn(
+        *association.address(),
+        *receiver.address(),
+        amount,
+        get_sequence_number(*association.address(), &chain_state),
+        1,
+        DEFAULT_MAX_GAS_AMOUNT,
+        STC_TOKEN_CODE.clone(),
+        net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+        net.chain_id(),
+    );
+    let (vm_status, _, calls) = starcoin_dev::playground::dry_run_with_trace(
+        &chain_state,
+        DryRunTransaction {
+            raw_txn,
+            public_key: association.public_key(),
+        },
+    )?;
+    assert!(matches!(vm_status, VMStatus::MoveAbort(..)));
+
+    let kinds: Vec<CallKind> = calls.iter().map(|call| call.kind).collect();
+    assert_eq!(
+        vec![
+            CallKind::Prologue,
+            CallKind::ScriptFunction,
+            CallKind::Epilogue
+        ],
+        kinds
+    );
+    let call = &calls[1];
+    assert_eq!(
+        Some("peer_to_peer_v2"),
+        call.function.as_ref().map(|function| function.as_str())
+    );
+    assert_eq!(vec![stc_type_tag()], call.type_args);
+    assert_eq!(
+        vec![
+            bcs_ext::to_bytes(receiver.address())?,
+            bcs_ext::to_bytes(&amount)?
+        ],
+        call.args
+    );
+    assert!(call.gas_used > 0);
+    assert!(matches!(call.status, VMStatus::MoveAbort(..)));
+    // the failure epilogue still charges the gas.
+    assert_eq!(VMStatus::Executed, calls[2].status);
+    Ok(())
+}
+
+#[stest::test]
+fn test_trace_block_transaction() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let association = Account::new_association();
+    let block_meta = Transaction::BlockMetadata(BlockMetadata::new(
+        starcoin_crypto::HashValue::random(),
+        net.time_service().now_millis(),
+        *association.address(),
+        None,
+        0,
+        current_block_number(&chain_state) + 1,
+        net.chain_id(),
+        0,
+    ));
+    let (vm_status, _, calls) =
+        starcoin_dev::playground::trace_block_transaction(&chain_state, vec![block_meta.clone()])?;
+    assert_eq!(VMStatus::Executed, vm_status);
+    assert_eq!(1, calls.len());
+    assert_eq!(CallKind::BlockPrologue, calls[0].kind);
+    assert_eq!(
+        Some(&*account_config::TRANSACTION_MANAGER_MODULE),
+        calls[0].module.as_ref()
+    );
+
+    // only the last txn is traced, on the state changed by the previous txns.
+    let txns = (0..2)
+        .map(|index| {
+            Transaction::UserTransaction(peer_to_peer_txn(
+                &association,
+                &Account::new(),
+                get_sequence_number(*association.address(), &chain_state) + index,
+                10_000,
+                net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+                net.chain_id(),
+            ))
+        })
+        .collect::<Vec<_>>();
+    let (vm_status, output, calls) = starcoin_dev::playground::trace_block_transaction(
+        &chain_state,
+        std::iter::once(block_meta).chain(txns).collect(),
+    )?;
+    assert_eq!(VMStatus::Executed, vm_status);
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    let kinds: Vec<CallKind> = calls.iter().map(|call| call.kind).collect();
+    assert_eq!(
+        vec![
+            CallKind::Prologue,
+            CallKind::ScriptFunction,
+            CallKind::Epilogue
+        ],
+        kinds
+    );
+    Ok(())
+}
+
+#[stest::test]
+fn test_dry_run_with_state_overrides() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let account1 = Account::new();
+    let txn = Transaction::UserTransaction(create_account_txn_sent_as_association(
+        &account1,
+        get_sequence_number(account_config::association_address(), &chain_state),
+        0,
+        net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+        &net,
+    ));
+    execute_and_apply(&chain_state, txn);
+
+    let amount = STCUnit::STC.value_of(1).scaling();
+    let dry_run_txn = DryRunTransaction {
+        raw_txn: raw_peer_to_peer_txn(
+            *account1.address(),
+            account_config::association_address(),
+            amount,
+            0,
+            1,
+            DEFAULT_MAX_GAS_AMOUNT,
+            STC_TOKEN_CODE.clone(),
+            net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+            net.chain_id(),
+        ),
+        public_key: account1.public_key(),
+    };
+    // account1 can not pay the txn without balance.
+    let (vm_status, _) = starcoin_dev::playground::dry_run(&chain_state, dry_run_txn.clone())?;
+    assert_eq!(
+        VMStatus::Error(StatusCode::INSUFFICIENT_BALANCE_FOR_TRANSACTION_FEE),
+        vm_status
+    );
+
+    let mut state_view = OverlayStateView::new(&chain_state);
+    let stc_struct_tag: StructTag = STC_TOKEN_CODE.clone().try_into()?;
+    state_view.set_balance(*account1.address(), stc_struct_tag.clone(), amount * 2)?;
+    let (vm_status, output) = starcoin_dev::playground::dry_run(&state_view, dry_run_txn)?;
+    assert_eq!(VMStatus::Executed, vm_status);
+
+    // the overrides never touch the base state.
+    let balance_path = AccessPath::resource_access_path(
+        *account1.address(),
+        BalanceResource::struct_tag_for_token(stc_struct_tag),
+    );
+    let balance_of = |state_view: &dyn StateView| -> Result<Option<u128>> {
+        Ok(state_view
+            .get(&balance_path)?
+            .map(|value| bcs_ext::from_bytes::<BalanceResource>(value.as_slice()))
+            .transpose()?
+            .map(|balance| balance.token()))
+    };
+    assert_eq!(Some(0), balance_of(&chain_state)?);
+    assert_eq!(Some(amount * 2), balance_of(&state_view)?);
+    state_view.apply_write_set(output.write_set());
+    let balance = balance_of(&state_view)?.unwrap();
+    // the gas unit price is 1.
+    assert_eq!(amount, balance + output.gas_used() as u128);
+    assert_eq!(
+        state_view.multi_get(&[balance_path.clone()])?,
+        vec![state_view.get(&balance_path)?]
+    );
+
+    state_view.set(balance_path.clone(), None);
+    assert_eq!(None, balance_of(&state_view)?);
+
+    let module = compile_modules_with_address(*account1.address(), TEST_MODULE)
+        .pop()
+        .unwrap();
+    let module_path = AccessPath::from(&CompiledModule::deserialize(module.code())?.self_id());
+    assert!(chain_state.get(&module_path)?.is_none());
+    state_view.set_module(module.code().to_vec())?;
+    assert_eq!(Some(module.code().to_vec()), state_view.get(&module_path)?);
+    Ok(())
+}
+
+#[stest::test]
+fn test_simulate_bundle_with_dependent_txns() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let association = Account::new_association();
+    let account1 = Account::new();
+    let seq_num = get_sequence_number(*association.address(), &chain_state);
+    let expiration = net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME;
+    let amount = STCUnit::STC.value_of(1).scaling();
+    let create_account = DryRunTransaction {
+        raw_txn: create_account_txn_sent_as_association(
+            &account1, seq_num, amount, expiration, &net,
+        )
+        .into_raw_transaction(),
+        public_key: association.public_key(),
+    };
+    // account1 only exists after the first txn.
+    let transfer = DryRunTransaction {
+        raw_txn: peer_to_peer_txn(
+            &account1,
+            &Account::new(),
+            0,
+            10_000,
+            expiration,
+            net.chain_id(),
+        )
+        .into_raw_transaction(),
+        public_key: account1.public_key(),
+    };
+    let (vm_status, _) = starcoin_dev::playground::dry_run(&chain_state, transfer.clone())?;
+    assert_ne!(VMStatus::Executed, vm_status);
+
+    let block_meta = BlockMetadata::new(
+        starcoin_crypto::HashValue::random(),
+        net.time_service().now_millis(),
+        *association.address(),
+        None,
+        0,
+        current_block_number(&chain_state) + 1,
+        net.chain_id(),
+        0,
+    );
+    for block_meta in vec![None, Some(block_meta)] {
+        let outputs = starcoin_dev::playground::simulate_bundle(
+            &chain_state,
+            block_meta,
+            vec![create_account.clone(), transfer.clone()],
+        )?;
+        assert_eq!(2, outputs.len());
+        for (vm_status, output) in outputs {
+            assert_eq!(VMStatus::Executed, vm_status);
+            assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+        }
+    }
+    // the simulation never changes the state.
+    assert_eq!(0, get_balance(*account1.address(), &chain_state));
+    assert_eq!(
+        seq_num,
+        get_sequence_number(*association.address(), &chain_state)
+    );
+    Ok(())
+}
+
+#[stest::test]
+fn test_simulate_bundle_with_discarded_txn() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let association = Account::new_association();
+    let seq_num = get_sequence_number(*association.address(), &chain_state);
+    let transfer = |seq_num: u64| DryRunTransaction {
+        raw_txn: raw_peer_to_peer_txn(
+            *association.address(),
+            *Account::new().address(),
+            10_000,
+            seq_num,
+            1,
+            DEFAULT_MAX_GAS_AMOUNT,
+            STC_TOKEN_CODE.clone(),
+            net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+            net.chain_id(),
+        ),
+        public_key: association.public_key(),
+    };
+    let outputs = starcoin_dev::playground::simulate_bundle(
+        &chain_state,
+        None,
+        vec![
+            transfer(seq_num),
+            transfer(seq_num + 2),
+            transfer(seq_num + 1),
+        ],
+    )?;
+    assert_eq!(3, outputs.len());
+    assert_eq!(VMStatus::Executed, outputs[0].0);
+    // the discarded txn changes nothing, so the next txn still uses the next sequence number.
+    assert_eq!(
+        VMStatus::Error(StatusCode::SEQUENCE_NUMBER_TOO_NEW),
+        outputs[1].0
+    );
+    assert!(matches!(
+        outputs[1].1.status(),
+        TransactionStatus::Discard(_)
+    ));
+    assert!(outputs[1].1.write_set().is_empty());
+    assert_eq!(VMStatus::Executed, outputs[2].0);
+    Ok(())
+}
+
+#[stest::test]
+fn test_block_replay() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let account1 = Account::new();
+    let txn = Transaction::UserTransaction(create_account_txn_sent_as_association(
+        &account1,
+        get_sequence_number(account_config::association_address(), &chain_state),
+        50_000_000,
+        net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+        &net,
+    ));
+    execute_and_apply(&chain_state, txn);
+    chain_state.commit()?;
+    chain_state.flush()?;
+    let parent_state = chain_state.fork();
+
+    let block_meta = BlockMetadata::new(
+        starcoin_crypto::HashValue::random(),
+        net.time_service().now_millis(),
+        *account1.address(),
+        Some(account1.auth_key()),
+        0,
+        current_block_number(&chain_state) + 1,
+        net.chain_id(),
+        0,
+    );
+    let mut txns = (0u64..2)
+        .map(|seq_number| {
+            Transaction::UserTransaction(peer_to_peer_txn(
+                &account1,
+                &Account::new(),
+                seq_number,
+                10_000,
+                net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+                net.chain_id(),
+            ))
+        })
+        .collect::<Vec<_>>();
+    txns.insert(0, Transaction::BlockMetadata(block_meta));
+    let block_gas_limit = chain_state.get_epoch()?.block_gas_limit();
+    let executed_data =
+        starcoin_executor::block_execute(&chain_state, txns.clone(), block_gas_limit)?;
+
+    // replay on the parent state gets the same result.
+    let replayed_txns =
+        starcoin_executor::block_replay(&parent_state, txns.clone(), block_gas_limit)?;
+    assert_eq!(replayed_txns.len(), executed_data.txn_infos.len());
+    for ((replayed, txn_info), write_set) in replayed_txns
+        .iter()
+        .zip(executed_data.txn_infos.iter())
+        .zip(executed_data.txn_write_sets.iter())
+    {
+        assert_eq!(replayed.txn_info.as_ref(), Some(txn_info));
+        assert_eq!(&replayed.write_set, write_set);
+    }
+    assert_eq!(parent_state.state_root(), executed_data.state_root);
+
+    // replay on the state after the block, the user txns are discarded.
+    chain_state.flush()?;
+    let replayed_txns =
+        starcoin_executor::block_replay(&chain_state.fork(), txns[1..].to_vec(), block_gas_limit)?;
+    for replayed in replayed_txns {
+        assert_eq!(
+            replayed.status,
+            TransactionStatus::Discard(StatusCode::SEQUENCE_NUMBER_TOO_OLD)
+        );
+        assert!(replayed.txn_info.is_none());
+    }
+    Ok(())
+}
+
+fn association_transfer_txn(
+    chain_state: &ChainStateDB,
+    net: &ChainNetwork,
+    amount: u128,
+    max_gas_amount: u64,
+) -> DryRunTransaction {
+    let association = Account::new_association();
+    DryRunTransaction {
+        raw_txn: raw_peer_to_peer_txn(
+            *association.address(),
+            *Account::new().address(),
+            amount,
+            get_sequence_number(*association.address(), chain_state),
+            1,
+            max_gas_amount,
+            STC_TOKEN_CODE.clone(),
+            net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+            net.chain_id(),
+        ),
+        public_key: association.public_key(),
+    }
+}
+
+#[stest::test]
+fn test_estimate_gas() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let amount = STCUnit::STC.value_of(1).scaling();
+    let txn = |max_gas_amount| association_transfer_txn(&chain_state, &net, amount, max_gas_amount);
+
+    let estimate =
+        starcoin_dev::playground::estimate_gas(&chain_state, txn(DEFAULT_MAX_GAS_AMOUNT))?;
+    assert_eq!(VMStatus::Executed, estimate.vm_status);
+    assert!(estimate.gas_used > 0);
+    let min_max_gas_amount = estimate.min_max_gas_amount.unwrap();
+    assert!(min_max_gas_amount >= estimate.gas_used);
+
+    // the minimal max gas amount is enough, and the one less is not.
+    let (vm_status, _) = starcoin_dev::playground::dry_run(&chain_state, txn(min_max_gas_amount))?;
+    assert_eq!(VMStatus::Executed, vm_status);
+    let (vm_status, _) =
+        starcoin_dev::playground::dry_run(&chain_state, txn(min_max_gas_amount - 1))?;
+    assert_ne!(VMStatus::Executed, vm_status);
+
+    // the max gas amount of the txn is not enough, search up to the max gas amount of the chain.
+    let estimate_with_low_gas =
+        starcoin_dev::playground::estimate_gas(&chain_state, txn(estimate.gas_used / 2))?;
+    assert_eq!(VMStatus::Executed, estimate_with_low_gas.vm_status);
+    assert_eq!(estimate.gas_used, estimate_with_low_gas.gas_used);
+    assert_eq!(
+        Some(min_max_gas_amount),
+        estimate_with_low_gas.min_max_gas_amount
+    );
+    Ok(())
+}
+
+#[stest::test]
+fn test_estimate_gas_margin() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let txn = association_transfer_txn(
+        &chain_state,
+        &net,
+        STCUnit::STC.value_of(1).scaling(),
+        DEFAULT_MAX_GAS_AMOUNT,
+    );
+    let estimate = starcoin_dev::playground::estimate_gas(&chain_state, txn)?;
+    let min_max_gas_amount = estimate.min_max_gas_amount.unwrap();
+    assert_eq!(
+        Some(
+            min_max_gas_amount
+                + min_max_gas_amount * starcoin_dev::playground::GAS_ESTIMATE_MARGIN_PERCENT / 100
+        ),
+        estimate.suggested_max_gas_amount
+    );
+    assert!(estimate.suggested_max_gas_amount.unwrap() > min_max_gas_amount);
+    Ok(())
+}
+
+#[stest::test]
+fn test_estimate_gas_of_aborted_txn() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    // the balance of association is not enough, the txn aborts with any max gas amount.
+    let txn = association_transfer_txn(&chain_state, &net, u128::max_value(), 1_000);
+    let estimate = starcoin_dev::playground::estimate_gas(&chain_state, txn)?;
+    assert!(
+        matches!(estimate.vm_status, VMStatus::MoveAbort(..)),
+        "unexpected vm status: {:?}",
+        estimate.vm_status
+    );
+    assert!(estimate.gas_used > 0);
+    assert!(estimate.min_max_gas_amount.is_none());
+    assert!(estimate.suggested_max_gas_amount.is_none());
+    Ok(())
+}
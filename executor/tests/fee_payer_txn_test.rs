@@ -0,0 +1,125 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_config::ChainNetwork;
+use starcoin_executor::account::create_account_txn_sent_as_association;
+use starcoin_executor::DEFAULT_MAX_GAS_AMOUNT;
+use starcoin_state_api::StateReaderExt;
+use starcoin_transaction_builder::{encode_transfer_script_function, DEFAULT_EXPIRATION_TIME};
+use starcoin_types::transaction::{
+    RawUserTransactionWithFeePayer, SignedUserTransaction, Transaction, TransactionPayload,
+    TransactionStatus,
+};
+use starcoin_vm_types::vm_status::{KeptVMStatus, StatusCode};
+use statedb::ChainStateDB;
+use test_helper::executor::*;
+use test_helper::Account;
+
+fn create_account(
+    chain_state: &ChainStateDB,
+    net: &ChainNetwork,
+    seq_num: u64,
+    initial_amount: u128,
+) -> Account {
+    let account = Account::new();
+    let txn = Transaction::UserTransaction(create_account_txn_sent_as_association(
+        &account,
+        seq_num,
+        initial_amount,
+        net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+        net,
+    ));
+    let output = execute_and_apply(chain_state, txn);
+    assert_eq!(
+        &TransactionStatus::Keep(KeptVMStatus::Executed),
+        output.status()
+    );
+    account
+}
+
+fn sponsored_peer_to_peer_txn(
+    sender: &Account,
+    fee_payer: &Account,
+    receiver: &Account,
+    seq_num: u64,
+    transfer_amount: u128,
+    net: &ChainNetwork,
+) -> SignedUserTransaction {
+    let raw_txn = Account::create_raw_txn_impl(
+        *sender.address(),
+        TransactionPayload::ScriptFunction(encode_transfer_script_function(
+            *receiver.address(),
+            transfer_amount,
+        )),
+        seq_num,
+        DEFAULT_MAX_GAS_AMOUNT,
+        1,
+        net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+        net.chain_id(),
+    );
+    let message = RawUserTransactionWithFeePayer::new(raw_txn.clone(), *fee_payer.address());
+    SignedUserTransaction::with_fee_payer(
+        raw_txn,
+        sender.private_key().sign(&message),
+        *fee_payer.address(),
+        fee_payer.private_key().sign(&message),
+    )
+}
+
+#[stest::test]
+fn test_fee_payer_txn_gated_by_version() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let sender = create_account(&chain_state, &net, 0, 1000);
+    let fee_payer = create_account(&chain_state, &net, 1, 50_000_000);
+
+    let txn = sponsored_peer_to_peer_txn(&sender, &fee_payer, &Account::new(), 0, 1000, &net);
+    let output = execute_and_apply(&chain_state, Transaction::UserTransaction(txn));
+    assert_eq!(
+        &TransactionStatus::Discard(StatusCode::FEATURE_UNDER_GATING),
+        output.status()
+    );
+    Ok(())
+}
+
+#[stest::test]
+fn test_fee_payer_txn() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let sender = create_account(&chain_state, &net, 0, 1000);
+    let fee_payer = create_account(&chain_state, &net, 1, 50_000_000);
+    set_version(&chain_state, 8)?;
+
+    let receiver = Account::new();
+    let txn = sponsored_peer_to_peer_txn(&sender, &fee_payer, &receiver, 0, 1000, &net);
+    let output = execute_and_apply(&chain_state, Transaction::UserTransaction(txn));
+    assert_eq!(
+        &TransactionStatus::Keep(KeptVMStatus::Executed),
+        output.status()
+    );
+    // the sender transfers all its balance, the gas is paid by the fee payer.
+    assert_eq!(Some(0), chain_state.get_balance(*sender.address())?);
+    assert_eq!(Some(1000), chain_state.get_balance(*receiver.address())?);
+    assert_eq!(
+        Some(50_000_000 - output.gas_used() as u128),
+        chain_state.get_balance(*fee_payer.address())?
+    );
+    assert_eq!(1, chain_state.get_sequence_number(*sender.address())?);
+    assert_eq!(0, chain_state.get_sequence_number(*fee_payer.address())?);
+    Ok(())
+}
+
+#[stest::test]
+fn test_fee_payer_txn_insufficient_balance() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let sender = create_account(&chain_state, &net, 0, 50_000_000);
+    let fee_payer = create_account(&chain_state, &net, 1, 1);
+    set_version(&chain_state, 8)?;
+
+    let txn = sponsored_peer_to_peer_txn(&sender, &fee_payer, &Account::new(), 0, 1000, &net);
+    let output = execute_and_apply(&chain_state, Transaction::UserTransaction(txn));
+    assert_eq!(
+        &TransactionStatus::Discard(StatusCode::INSUFFICIENT_BALANCE_FOR_TRANSACTION_FEE),
+        output.status()
+    );
+    Ok(())
+}
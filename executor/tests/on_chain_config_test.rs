@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
+use starcoin_config::{BuiltinNetworkID, ChainNetwork};
 use starcoin_crypto::HashValue;
 use starcoin_executor::{encode_create_account_script_function, execute_readonly_function};
 use starcoin_state_api::{AccountStateReader, StateReaderExt};
@@ -9,15 +10,16 @@ use starcoin_types::account_config::stc_type_tag;
 use starcoin_types::block_metadata::BlockMetadata;
 use starcoin_types::identifier::Identifier;
 use starcoin_types::language_storage::ModuleId;
-use starcoin_types::transaction::{TransactionArgument, TransactionPayload};
+use starcoin_types::transaction::{Script, TransactionArgument, TransactionPayload};
 use starcoin_vm_types::account_config::genesis_address;
 use starcoin_vm_types::gas_schedule::{GasAlgebra, InternalGasUnits};
 use starcoin_vm_types::on_chain_config::{
-    consensus_config_type_tag, vm_config_type_tag, ConsensusConfig, OnChainConfig, VMConfig,
-    CONSENSUS_CONFIG_IDENTIFIER,
+    consensus_config_type_tag, initial_native_table, latest_native_table, vm_config_type_tag,
+    ConsensusConfig, OnChainConfig, VMConfig, CONSENSUS_CONFIG_IDENTIFIER,
 };
 use starcoin_vm_types::transaction::Transaction;
 use starcoin_vm_types::value::{serialize_values, MoveValue};
+use starcoin_vm_types::vm_status::KeptVMStatus;
 use test_helper::dao::{
     dao_vote_test, empty_txn_payload, execute_script_on_chain_config, on_chain_config_type_tag,
     reward_config_type_tag, transasction_timeout_type_tag, txn_publish_config_type_tag,
@@ -25,8 +27,9 @@ use test_helper::dao::{
     vote_txn_timeout_script, vote_vm_config_script,
 };
 use test_helper::executor::{
-    account_execute_with_output, association_execute_should_success, blockmeta_execute,
-    build_raw_txn, current_block_number, execute_and_apply, prepare_genesis,
+    account_execute_with_output, association_execute, association_execute_should_success,
+    blockmeta_execute, build_raw_txn, compile_script, current_block_number, execute_and_apply,
+    prepare_customized_genesis, prepare_genesis,
 };
 use test_helper::Account;
 
@@ -260,3 +263,45 @@ fn test_modify_on_chain_vm_config_option() -> Result<()> {
     assert!(output.gas_used() > old_gas_used);
     Ok(())
 }
+
+#[stest::test]
+fn test_natives_enabled_by_native_table_upgrade() -> Result<()> {
+    let alice = Account::new();
+    let mut genesis_config = BuiltinNetworkID::Test.genesis_config().clone();
+    genesis_config.vm_config.gas_schedule.native_table = initial_native_table();
+    let net = ChainNetwork::new(BuiltinNetworkID::Test.into(), genesis_config);
+    let chain_state = prepare_customized_genesis(&net);
+    let script = compile_script(
+        r#"
+        script {
+            use 0x1::Hash;
+            fun main() {
+                assert(Hash::ripemd160(b"abc") == x"8eb208f7e05d987a9b044a8e98c6b087f15a0bfc", 1000);
+            }
+        }
+        "#,
+    );
+    let payload = TransactionPayload::Script(Script::new(script, vec![], vec![]));
+
+    // The natives without costs in the genesis native table are rejected.
+    let output = association_execute(&net, &chain_state, payload.clone())?;
+    assert_ne!(KeptVMStatus::Executed, output.status().status().unwrap());
+
+    let mut vm_config = AccountStateReader::new(&chain_state)
+        .get_on_chain_config::<VMConfig>()?
+        .unwrap();
+    vm_config.gas_schedule.native_table = latest_native_table();
+    let action_type_tag = vm_config_type_tag();
+    dao_vote_test(
+        &alice,
+        &chain_state,
+        &net,
+        vote_vm_config_script(&net, vm_config),
+        on_chain_config_type_tag(action_type_tag.clone()),
+        execute_script_on_chain_config(&net, action_type_tag, 0u64),
+        0,
+    )?;
+
+    association_execute_should_success(&net, &chain_state, payload)?;
+    Ok(())
+}
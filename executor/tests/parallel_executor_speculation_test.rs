@@ -0,0 +1,68 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The counters of the parallel executor are global, so this test is in its own test binary.
+
+use anyhow::Result;
+use starcoin_executor::account::{create_account_txn_sent_as_association, peer_to_peer_txn};
+use starcoin_executor::{Account, ParallelExecutor, DEFAULT_EXPIRATION_TIME};
+use starcoin_types::account_config::association_address;
+use starcoin_types::transaction::Transaction;
+use starcoin_vm_types::token::stc::STCUnit;
+use starcoin_vm_types::vm_status::KeptVMStatus;
+use test_helper::executor::{execute_and_apply, get_sequence_number, prepare_genesis};
+use vm_runtime::metrics::PARALLEL_EXECUTION_TXNS;
+use vm_runtime::starcoin_vm::StarcoinVM;
+
+#[stest::test]
+fn test_independent_transfers_speculation_valid() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let accounts: Vec<Account> = (0..16).map(|_| Account::new()).collect();
+    let seq_num = get_sequence_number(association_address(), &chain_state);
+    let expiration = net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME;
+    for (i, account) in accounts.iter().enumerate() {
+        let txn = create_account_txn_sent_as_association(
+            account,
+            seq_num + i as u64,
+            STCUnit::STC.value_of(100).scaling(),
+            expiration,
+            &net,
+        );
+        let output = execute_and_apply(&chain_state, Transaction::UserTransaction(txn));
+        assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    }
+    // every transfer has its own sender and receiver, they only share the transaction fee resource.
+    let txns: Vec<Transaction> = accounts
+        .chunks(2)
+        .map(|pair| {
+            Transaction::UserTransaction(peer_to_peer_txn(
+                &pair[0],
+                &pair[1],
+                0,
+                1000,
+                expiration,
+                net.chain_id(),
+            ))
+        })
+        .collect();
+
+    let expected =
+        StarcoinVM::new().execute_block_transactions(&chain_state, txns.clone(), None)?;
+    let valid = PARALLEL_EXECUTION_TXNS.with_label_values(&["valid"]).get();
+    let re_executed = PARALLEL_EXECUTION_TXNS
+        .with_label_values(&["re_executed"])
+        .get();
+    let outputs = StarcoinVM::new_with_parallel_executor(ParallelExecutor::new(4)?)
+        .execute_block_transactions_in_parallel(&chain_state, txns.clone(), None)?;
+    assert_eq!(expected, outputs);
+
+    let valid = PARALLEL_EXECUTION_TXNS.with_label_values(&["valid"]).get() - valid;
+    let re_executed = PARALLEL_EXECUTION_TXNS
+        .with_label_values(&["re_executed"])
+        .get()
+        - re_executed;
+    assert!(valid > 0);
+    assert_eq!(valid, txns.len() as u64);
+    assert_eq!(re_executed, 0);
+    Ok(())
+}
@@ -0,0 +1,191 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_config::ChainNetwork;
+use starcoin_executor::account::{create_account_txn_sent_as_association, peer_to_peer_txn};
+use starcoin_executor::{Account, ParallelExecutor, DEFAULT_EXPIRATION_TIME};
+use starcoin_state_api::{AccountStateReader, StateReaderExt};
+use starcoin_types::account_config::association_address;
+use starcoin_types::transaction::{Transaction, TransactionOutput, TransactionPayload};
+use starcoin_vm_types::gas_schedule::{GasAlgebra, InternalGasUnits};
+use starcoin_vm_types::on_chain_config::{vm_config_type_tag, VMConfig};
+use starcoin_vm_types::state_view::StateView;
+use starcoin_vm_types::token::stc::STCUnit;
+use starcoin_vm_types::vm_status::{KeptVMStatus, VMStatus};
+use statedb::ChainStateDB;
+use test_helper::dao::{
+    dao_vote_to_executable, execute_script_on_chain_config, vote_vm_config_script,
+};
+use test_helper::executor::{
+    build_raw_txn, execute_and_apply, get_sequence_number, prepare_genesis,
+};
+use vm_runtime::starcoin_vm::StarcoinVM;
+
+fn execute_with_concurrency(
+    state_view: &(dyn StateView + Sync),
+    txns: Vec<Transaction>,
+    block_gas_limit: Option<u64>,
+    concurrency_level: usize,
+) -> Result<Vec<(VMStatus, TransactionOutput)>> {
+    let mut vm = StarcoinVM::new_with_parallel_executor(ParallelExecutor::new(concurrency_level)?);
+    vm.execute_block_transactions_in_parallel(state_view, txns, block_gas_limit)
+}
+
+fn create_accounts(chain_state: &ChainStateDB, net: &ChainNetwork, count: usize) -> Vec<Account> {
+    let accounts: Vec<Account> = (0..count).map(|_| Account::new()).collect();
+    let seq_num = get_sequence_number(association_address(), chain_state);
+    for (i, account) in accounts.iter().enumerate() {
+        let txn = create_account_txn_sent_as_association(
+            account,
+            seq_num + i as u64,
+            STCUnit::STC.value_of(100).scaling(),
+            net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+            net,
+        );
+        let output = execute_and_apply(chain_state, Transaction::UserTransaction(txn));
+        assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    }
+    accounts
+}
+
+/// Independent transfers, transfers to the same receiver, txns of the same sender and a discarded txn.
+fn transfer_txns(accounts: &[Account], net: &ChainNetwork) -> Vec<Transaction> {
+    let expiration = net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME;
+    let mut txns = vec![];
+    for (i, sender) in accounts.iter().enumerate() {
+        let receiver = &accounts[(i + 1) % accounts.len()];
+        txns.push(peer_to_peer_txn(
+            sender,
+            receiver,
+            0,
+            1000,
+            expiration,
+            net.chain_id(),
+        ));
+        txns.push(peer_to_peer_txn(
+            sender,
+            &accounts[0],
+            1,
+            1000,
+            expiration,
+            net.chain_id(),
+        ));
+    }
+    txns.push(peer_to_peer_txn(
+        &accounts[1],
+        &accounts[2],
+        10,
+        1000,
+        expiration,
+        net.chain_id(),
+    ));
+    txns.into_iter().map(Transaction::UserTransaction).collect()
+}
+
+#[stest::test]
+fn test_parallel_execution_same_as_sequential() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let accounts = create_accounts(&chain_state, &net, 8);
+    let txns = transfer_txns(&accounts, &net);
+
+    let expected = execute_with_concurrency(&chain_state, txns.clone(), None, 1)?;
+    assert_eq!(txns.len(), expected.len());
+    for concurrency_level in [2, 4, 16] {
+        let outputs =
+            execute_with_concurrency(&chain_state, txns.clone(), None, concurrency_level)?;
+        assert_eq!(
+            expected, outputs,
+            "outputs of concurrency level {} differ from sequential execution",
+            concurrency_level
+        );
+    }
+    Ok(())
+}
+
+#[stest::test]
+fn test_parallel_execution_with_block_gas_limit() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let accounts = create_accounts(&chain_state, &net, 4);
+    let txns = transfer_txns(&accounts, &net);
+
+    let all_outputs = execute_with_concurrency(&chain_state, txns.clone(), None, 1)?;
+    let block_gas_limit = all_outputs
+        .iter()
+        .take(5)
+        .map(|(_, output)| output.gas_used())
+        .sum::<u64>()
+        + 1;
+
+    let expected = execute_with_concurrency(&chain_state, txns.clone(), Some(block_gas_limit), 1)?;
+    assert_eq!(5, expected.len());
+    let outputs = execute_with_concurrency(&chain_state, txns, Some(block_gas_limit), 4)?;
+    assert_eq!(expected, outputs);
+    Ok(())
+}
+
+#[stest::test]
+fn test_parallel_execution_with_reconfiguration() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let accounts = create_accounts(&chain_state, &net, 4);
+    let alice = Account::new();
+    let mut vm_config = AccountStateReader::new(&chain_state)
+        .get_on_chain_config::<VMConfig>()?
+        .unwrap();
+    vm_config
+        .gas_schedule
+        .gas_constants
+        .global_memory_per_byte_cost = InternalGasUnits::new(8);
+    vm_config
+        .gas_schedule
+        .gas_constants
+        .global_memory_per_byte_write_cost = InternalGasUnits::new(12);
+    dao_vote_to_executable(
+        &alice,
+        &chain_state,
+        &net,
+        vote_vm_config_script(&net, vm_config),
+        &vm_config_type_tag(),
+        0,
+    )?;
+
+    // The gas schedule changed by the proposal applies to the transfers after it.
+    let expiration = chain_state.get_timestamp()?.seconds() + DEFAULT_EXPIRATION_TIME;
+    let transfer = |i: usize| {
+        Transaction::UserTransaction(peer_to_peer_txn(
+            &accounts[i],
+            &accounts[(i + 1) % accounts.len()],
+            0,
+            1000,
+            expiration,
+            net.chain_id(),
+        ))
+    };
+    let execute_proposal = alice.sign_txn(build_raw_txn(
+        *alice.address(),
+        &chain_state,
+        TransactionPayload::ScriptFunction(execute_script_on_chain_config(
+            &net,
+            vm_config_type_tag(),
+            0,
+        )),
+        None,
+    ));
+    let txns = vec![
+        transfer(0),
+        transfer(1),
+        Transaction::UserTransaction(execute_proposal),
+        transfer(2),
+        transfer(3),
+    ];
+
+    let expected = execute_with_concurrency(&chain_state, txns.clone(), None, 1)?;
+    assert_eq!(txns.len(), expected.len());
+    for (_, output) in &expected {
+        assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    }
+    assert!(expected[3].1.gas_used() > expected[0].1.gas_used());
+    let outputs = execute_with_concurrency(&chain_state, txns, None, 4)?;
+    assert_eq!(expected, outputs);
+    Ok(())
+}
@@ -0,0 +1,81 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_config::ChainNetwork;
+use starcoin_executor::account::{create_account_txn_sent_as_association, peer_to_peer_txn};
+use starcoin_state_api::StateReaderExt;
+use starcoin_transaction_builder::DEFAULT_EXPIRATION_TIME;
+use starcoin_types::transaction::{Transaction, TransactionStatus};
+use starcoin_vm_types::transaction::authenticator::TransactionAuthenticator;
+use starcoin_vm_types::vm_status::{KeptVMStatus, StatusCode};
+use statedb::ChainStateDB;
+use test_helper::executor::*;
+use test_helper::Account;
+
+fn create_secp256k1_account(chain_state: &ChainStateDB, net: &ChainNetwork) -> Account {
+    let account = Account::new_secp256k1();
+    let txn = Transaction::UserTransaction(create_account_txn_sent_as_association(
+        &account,
+        0,
+        50_000_000,
+        net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+        net,
+    ));
+    let output = execute_and_apply(chain_state, txn);
+    assert_eq!(
+        &TransactionStatus::Keep(KeptVMStatus::Executed),
+        output.status()
+    );
+    account
+}
+
+#[stest::test]
+fn test_secp256k1_txn_gated_by_version() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let account = create_secp256k1_account(&chain_state, &net);
+
+    let txn = peer_to_peer_txn(
+        &account,
+        &Account::new(),
+        0,
+        1000,
+        net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+        net.chain_id(),
+    );
+    assert!(matches!(
+        txn.authenticator(),
+        TransactionAuthenticator::Secp256k1Ecdsa { .. }
+    ));
+    let output = execute_and_apply(&chain_state, Transaction::UserTransaction(txn));
+    assert_eq!(
+        &TransactionStatus::Discard(StatusCode::FEATURE_UNDER_GATING),
+        output.status()
+    );
+    Ok(())
+}
+
+#[stest::test]
+fn test_secp256k1_txn() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let account = create_secp256k1_account(&chain_state, &net);
+    set_version(&chain_state, 8)?;
+
+    let receiver = Account::new();
+    let txn = peer_to_peer_txn(
+        &account,
+        &receiver,
+        0,
+        1000,
+        net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+        net.chain_id(),
+    );
+    let output = execute_and_apply(&chain_state, Transaction::UserTransaction(txn));
+    assert_eq!(
+        &TransactionStatus::Keep(KeptVMStatus::Executed),
+        output.status()
+    );
+    assert_eq!(1, chain_state.get_sequence_number(*account.address())?);
+    assert_eq!(Some(1000), chain_state.get_balance(*receiver.address())?);
+    Ok(())
+}
@@ -96,8 +96,12 @@ impl StateView for RemoteChainStateReader {
         Ok(state_proof.state)
     }
 
-    fn multi_get(&self, _access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
-        unimplemented!()
+    /// The remote node has no batch api for states with proof, so read them one by one.
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        access_paths
+            .iter()
+            .map(|access_path| self.get(access_path))
+            .collect()
     }
 
     fn is_genesis(&self) -> bool {
@@ -216,6 +216,23 @@ impl PeerSelector {
         self.total_score.fetch_add(score as u64, Ordering::SeqCst);
     }
 
+    /// Decrease the score of a peer which is failed or timeout, the score is at least 1.
+    pub fn peer_penalty(&self, peer_id: &PeerId, score: u64) {
+        let decreased = self
+            .details
+            .lock()
+            .iter()
+            .filter(|peer| &peer.peer_id() == peer_id)
+            .fold(0u64, |decreased, peer| {
+                decreased.saturating_add(peer.score_counter.dec_by(score))
+            });
+        let _ = self
+            .total_score
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |total| {
+                Some(total.saturating_sub(decreased))
+            });
+    }
+
     pub fn peer_exist(&self, peer_id: &PeerId) -> bool {
         for peer in self.details.lock().iter() {
             if &peer.peer_id() == peer_id {
@@ -433,6 +450,30 @@ impl PeerSelector {
         None
     }
 
+    /// Select a peer weighted by score, skip the peers in `excludes`.
+    pub fn weighted_random_except(&self, excludes: &[PeerId]) -> Option<PeerId> {
+        let details = self.details.lock();
+        let candidates: Vec<&PeerDetail> = details
+            .iter()
+            .filter(|peer| !excludes.contains(&peer.peer_id()))
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        let total_score = candidates.iter().fold(0u64, |total, peer| {
+            total.saturating_add(peer.score().max(1))
+        });
+        let random_score: u64 = rand::thread_rng().gen_range(0..total_score);
+        let mut tmp_score: u64 = 0;
+        for peer_detail in candidates {
+            tmp_score = tmp_score.saturating_add(peer_detail.score().max(1));
+            if tmp_score > random_score {
+                return Some(peer_detail.peer_id());
+            }
+        }
+        None
+    }
+
     pub fn random_peer(&self) -> Option<PeerInfo> {
         self.details
             .lock()
@@ -22,6 +22,20 @@ impl ScoreCounter {
         self.count.fetch_add(1, Ordering::SeqCst);
     }
 
+    /// Decrease the score, the score is at least 1, return the decreased value.
+    pub fn dec_by(&self, score: u64) -> u64 {
+        let mut decreased = 0;
+        let _ = self
+            .score
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |current| {
+                let new_score = current.saturating_sub(score).max(1);
+                decreased = current.saturating_sub(new_score);
+                Some(new_score)
+            });
+        self.count.fetch_add(1, Ordering::SeqCst);
+        decreased
+    }
+
     pub fn score(&self) -> u64 {
         self.score.load(Ordering::SeqCst)
     }
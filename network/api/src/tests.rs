@@ -88,3 +88,36 @@ fn test_better_peer() {
             })
     }
 }
+
+#[test]
+fn test_weighted_random_except() {
+    let peers: Vec<PeerInfo> = (0..3).map(|_| PeerInfo::random()).collect();
+    let peer_ids: Vec<PeerId> = peers.iter().map(|peer| peer.peer_id()).collect();
+    let peer_selector = PeerSelector::new(peers, PeerStrategy::default());
+    for _ in 0..10 {
+        let selected = peer_selector
+            .weighted_random_except(&peer_ids[..1])
+            .expect("selected peer must exist.");
+        assert_ne!(selected, peer_ids[0]);
+    }
+    assert!(peer_selector.weighted_random_except(&peer_ids).is_none());
+}
+
+#[test]
+fn test_peer_penalty() {
+    let peers: Vec<PeerInfo> = (0..2).map(|_| PeerInfo::random()).collect();
+    let peer_ids: Vec<PeerId> = peers.iter().map(|peer| peer.peer_id()).collect();
+    let peer_selector = PeerSelector::new(peers, PeerStrategy::default());
+    peer_selector.peer_score(&peer_ids[0], 100);
+    peer_selector.peer_score(&peer_ids[1], 100);
+    peer_selector.peer_penalty(&peer_ids[0], 60);
+    let scores: std::collections::HashMap<PeerId, u64> =
+        peer_selector.scores().into_iter().collect();
+    assert_eq!(scores.get(&peer_ids[0]), Some(&41));
+    assert_eq!(scores.get(&peer_ids[1]), Some(&101));
+    // the score is at least 1.
+    peer_selector.peer_penalty(&peer_ids[0], 1000);
+    let scores: std::collections::HashMap<PeerId, u64> =
+        peer_selector.scores().into_iter().collect();
+    assert_eq!(scores.get(&peer_ids[0]), Some(&1));
+}
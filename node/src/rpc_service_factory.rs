@@ -11,10 +11,11 @@ use starcoin_genesis::Genesis;
 use starcoin_logger::LoggerHandle;
 use starcoin_miner::MinerService;
 use starcoin_network::NetworkServiceRef;
+use starcoin_rpc_server::auth::RpcAuthenticator;
 use starcoin_rpc_server::module::{
     AccountRpcImpl, ChainRpcImpl, ContractRpcImpl, DebugRpcImpl, MinerRpcImpl,
     NetworkManagerRpcImpl, NodeManagerRpcImpl, NodeRpcImpl, PubSubImpl, PubSubService,
-    StateRpcImpl, SyncManagerRpcImpl, TxPoolRpcImpl,
+    StateRpcImpl, SyncManagerRpcImpl, TxPoolManagerRpcImpl, TxPoolRpcImpl,
 };
 use starcoin_rpc_server::service::RpcService;
 use starcoin_service_registry::{ServiceContext, ServiceFactory};
@@ -34,10 +35,11 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
         let storage = ctx.get_shared::<Arc<Storage>>()?;
         let log_handler = ctx.get_shared::<Arc<LoggerHandle>>()?;
         let network_service = ctx.get_shared::<NetworkServiceRef>()?;
+        let authenticator = Arc::new(RpcAuthenticator::load(config.rpc.auth_keys_file())?);
         let node_api = NodeRpcImpl::new(config.clone(), Some(network_service.clone()));
         let node_manager_api = ctx
             .service_ref_opt::<NodeService>()?
-            .map(|service_ref| NodeManagerRpcImpl::new(service_ref.clone()));
+            .map(|service_ref| NodeManagerRpcImpl::new(service_ref.clone(), authenticator.clone()));
         let sync_manager_api = ctx
             .service_ref_opt::<SyncService>()?
             .map(|service_ref| SyncManagerRpcImpl::new(service_ref.clone()));
@@ -54,6 +56,7 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
             });
         let txpool_service = ctx.get_shared::<TxPoolService>()?;
         let txpool_api = Some(TxPoolRpcImpl::new(txpool_service.clone()));
+        let txpool_manager_api = Some(TxPoolManagerRpcImpl::new(txpool_service.clone()));
 
         let state_api = ctx
             .service_ref_opt::<ChainStateService>()?
@@ -72,7 +75,12 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
         });
         let pubsub_service = ctx.service_ref::<PubSubService>()?.clone();
         let pubsub_api = Some(PubSubImpl::new(pubsub_service));
-        let debug_api = Some(DebugRpcImpl::new(config.clone(), log_handler));
+        let debug_api = Some(DebugRpcImpl::new(
+            config.clone(),
+            log_handler,
+            storage.clone(),
+            chain_service.clone(),
+        ));
         let miner_api = ctx
             .service_ref_opt::<MinerService>()?
             .map(|service_ref| MinerRpcImpl::new(service_ref.clone()));
@@ -84,6 +92,7 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
                 account_service,
                 txpool_service,
                 chain_state_service,
+                chain_service,
                 dev_playground,
                 storage,
             )
@@ -91,12 +100,14 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
 
         Ok(RpcService::new_with_api(
             config,
+            authenticator,
             node_api,
             node_manager_api,
             sync_manager_api,
             Some(network_manager_api),
             chain_api,
             txpool_api,
+            txpool_manager_api,
             account_api,
             state_api,
             pubsub_api,
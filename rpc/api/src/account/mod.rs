@@ -9,6 +9,7 @@ use crate::FutureResult;
 use starcoin_account_api::AccountInfo;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::sign_message::SigningMessage;
+use starcoin_types::transaction::authenticator::TransactionAuthenticator;
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
 use starcoin_vm_types::token::token_code::TokenCode;
 #[rpc(client, server, schema)]
@@ -42,6 +43,15 @@ pub trait AccountApi {
         signer: AccountAddress,
     ) -> FutureResult<SignedUserTransaction>;
 
+    /// sign a sponsored txn as the sender or the fee payer, return the authenticator of the signer.
+    #[rpc(name = "account.sign_txn_with_fee_payer")]
+    fn sign_txn_with_fee_payer(
+        &self,
+        raw_txn: RawUserTransaction,
+        fee_payer: AccountAddress,
+        signer: AccountAddress,
+    ) -> FutureResult<TransactionAuthenticator>;
+
     /// unlock account for duration in seconds, default to u32::max.
     #[rpc(name = "account.unlock")]
     fn unlock(
@@ -1,7 +1,8 @@
 pub use self::gen_client::Client as ContractClient;
 use crate::types::{
     AnnotatedMoveStructView, AnnotatedMoveValueView, ContractCall, DryRunOutputView,
-    DryRunTransactionRequest, FunctionIdView, ModuleIdView, StrView, StructTagView,
+    DryRunTransactionRequest, FunctionIdView, GasEstimateView, ModuleIdView, SimulateBundleRequest,
+    StrView, StructTagView,
 };
 use crate::FutureResult;
 use jsonrpc_derive::rpc;
@@ -44,6 +45,16 @@ pub trait ContractApi {
         sender_public_key: StrView<AccountPublicKey>,
     ) -> FutureResult<DryRunOutputView>;
 
+    /// Estimate the gas of the txn in the same way as `dry_run`, search the minimal max_gas_amount
+    /// which is not greater than the max_gas_amount of the txn.
+    #[rpc(name = "contract.estimate_gas")]
+    fn estimate_gas(&self, txn: DryRunTransactionRequest) -> FutureResult<GasEstimateView>;
+
+    /// Simulate the txns in order on top of the head state, each txn sees the changes of the previous txns.
+    #[rpc(name = "contract.simulate_bundle")]
+    fn simulate_bundle(&self, bundle: SimulateBundleRequest)
+        -> FutureResult<Vec<DryRunOutputView>>;
+
     #[rpc(name = "contract.resolve_function")]
     fn resolve_function(&self, function_id: FunctionIdView) -> FutureResult<FunctionABI>;
     #[rpc(name = "contract.resolve_struct")]
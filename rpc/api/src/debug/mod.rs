@@ -6,7 +6,9 @@ use jsonrpc_derive::rpc;
 use starcoin_logger::LogPattern;
 
 pub use self::gen_client::Client as DebugClient;
-use crate::types::FactoryAction;
+use crate::types::{BlockReplayView, FactoryAction, TransactionTraceView};
+use crate::FutureResult;
+use starcoin_crypto::HashValue;
 #[rpc(client, server, schema)]
 pub trait DebugApi {
     /// Update log level, if logger_name is none, update global log level.
@@ -28,6 +30,15 @@ pub trait DebugApi {
     /// Get and set txn factory status.
     #[rpc(name = "txfactory.status")]
     fn txfactory_status(&self, action: FactoryAction) -> Result<bool>;
+
+    /// Re-execute the txn on the state of its parent block, and return the calls into Move.
+    #[rpc(name = "debug.trace_transaction")]
+    fn trace_transaction(&self, txn_hash: HashValue) -> FutureResult<TransactionTraceView>;
+
+    /// Re-execute the block on the state of its parent block without changing the storage,
+    /// and compare the results of the txns with the stored ones.
+    #[rpc(name = "debug.replay_block")]
+    fn replay_block(&self, block_hash: HashValue) -> FutureResult<BlockReplayView>;
 }
 #[test]
 fn test() {
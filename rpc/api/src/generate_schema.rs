@@ -1,7 +1,7 @@
 use anyhow::Result;
 use starcoin_rpc_api::{
     account, chain, contract_api, debug, miner, network_manager, node, node_manager, state,
-    sync_manager, txpool,
+    sync_manager, txpool, txpool_manager,
 };
 use std::fs::{create_dir_all, File};
 use std::path::Path;
@@ -48,6 +48,7 @@ fn main() {
         node_manager,
         state,
         sync_manager,
-        txpool
+        txpool,
+        txpool_manager
     );
 }
@@ -22,4 +22,5 @@ pub mod sync_manager;
 #[cfg(test)]
 mod tests;
 pub mod txpool;
+pub mod txpool_manager;
 pub mod types;
@@ -12,6 +12,8 @@ pub struct Metadata {
     /// Request PubSub Session
     pub session: Option<Arc<Session>>,
     pub user: Option<String>,
+    /// Auth token of the request, such as the bearer token of http Authorization header.
+    pub auth_token: Option<String>,
 }
 
 impl Metadata {
@@ -19,6 +21,7 @@ impl Metadata {
         Self {
             session: Some(session),
             user: None,
+            auth_token: None,
         }
     }
 }
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2
 
 pub use self::gen_client::Client as NodeManagerClient;
+use crate::types::RpcAuthKeyView;
 use crate::FutureResult;
 use jsonrpc_derive::rpc;
 use starcoin_crypto::HashValue;
@@ -45,6 +46,25 @@ pub trait NodeManagerApi {
     /// Delete failed block of block_id from failed block database
     #[rpc(name = "node_manager.delete_failed_block")]
     fn delete_failed_block(&self, block_hash: HashValue) -> FutureResult<()>;
+
+    /// List the rpc auth keys, the secrets are not returned.
+    #[rpc(name = "node_manager.list_rpc_auth_keys")]
+    fn list_rpc_auth_keys(&self) -> FutureResult<Vec<RpcAuthKeyView>>;
+
+    /// Add a rpc auth key of `kind`(api_key or jwt) which is allowed to call the `apis` and `methods`,
+    /// return the generated secret.
+    #[rpc(name = "node_manager.add_rpc_auth_key")]
+    fn add_rpc_auth_key(
+        &self,
+        name: String,
+        kind: String,
+        apis: Option<String>,
+        methods: Option<Vec<String>>,
+    ) -> FutureResult<String>;
+
+    /// Remove the rpc auth key, return false if the key does not exist.
+    #[rpc(name = "node_manager.remove_rpc_auth_key")]
+    fn remove_rpc_auth_key(&self, name: String) -> FutureResult<bool>;
 }
 #[test]
 fn test() {
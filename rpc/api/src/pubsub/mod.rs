@@ -6,6 +6,7 @@
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
 use jsonrpc_pubsub::{typed, SubscriptionId};
+use serde_json::Value;
 
 use crate::types::pubsub;
 
@@ -14,14 +15,19 @@ use crate::types::pubsub;
 /// ```bash
 /// $ netcat localhost 3030
 /// {"id":1,"jsonrpc":"2.0","method":"starcoin_subscribe","params":["newPendingTransactions"]}
+/// {"id":1,"jsonrpc":"2.0","method":"starcoin_subscribe","params":["newHeads", {"with_removed": true}]}
+/// {"id":1,"jsonrpc":"2.0","method":"starcoin_subscribe","params":["chainReorg"]}
 /// {"id":1,"jsonrpc":"2.0","method":"starcoin_subscribe","params":["events", {}]}
+/// {"id":1,"jsonrpc":"2.0","method":"starcoin_subscribe","params":["events", {"from_block": 100}]}
+/// {"id":1,"jsonrpc":"2.0","method":"starcoin_subscribe","params":["transactionStatus", {"senders": ["0x1"]}]}
+/// {"id":1,"jsonrpc":"2.0","method":"starcoin_subscribe","params":["resourceChanges", {"addrs": ["0x1"]}]}
 #[allow(clippy::needless_return)]
 #[rpc(server)]
 pub trait StarcoinPubSub {
     /// RPC Metadata
     type Metadata;
 
-    /// Subscribe to Starcoin subscription.
+    /// Subscribe to Starcoin subscription, `params` are parsed according to the `kind`.
     #[pubsub(
         subscription = "starcoin_subscription",
         subscribe,
@@ -32,7 +38,7 @@ pub trait StarcoinPubSub {
         meta: Self::Metadata,
         subscriber: typed::Subscriber<pubsub::Result>,
         kind: pubsub::Kind,
-        params: Option<pubsub::Params>,
+        params: Option<Value>,
     );
 
     /// Unsubscribe from existing Starcoin subscription.
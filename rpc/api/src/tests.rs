@@ -1,3 +1,4 @@
+use crate::types::pubsub::{Kind, Params};
 use crate::types::{ContractCall, TransactionArgumentView, TypeTagView};
 use starcoin_vm_types::token::stc::stc_type_tag;
 use starcoin_vm_types::transaction_argument::TransactionArgument;
@@ -33,6 +34,34 @@ fn test_deserialize() {
     let v = serde_json::from_str::<ContractCall>(s).unwrap();
     println!("{:?}", v);
 }
+
+#[test]
+fn test_parse_pubsub_params() {
+    let addrs = serde_json::json!({"addrs": ["0x1"]});
+    // events filter with addrs must not be taken as a resource change filter.
+    match Params::parse(&Kind::Events, addrs.clone()).unwrap() {
+        Params::Events(params) => {
+            assert_eq!(params.filter.addrs.unwrap().len(), 1);
+            assert!(!params.decode);
+        }
+        params => panic!("Unexpected params: {:?}", params),
+    }
+    match Params::parse(&Kind::ResourceChanges, addrs.clone()).unwrap() {
+        Params::ResourceChanges(filter) => assert_eq!(filter.addrs.unwrap().len(), 1),
+        params => panic!("Unexpected params: {:?}", params),
+    }
+    assert!(Params::parse(&Kind::TransactionStatus, addrs.clone()).is_err());
+    assert!(Params::parse(&Kind::SyncProgress, addrs).is_err());
+    assert_eq!(
+        Params::parse(&Kind::Events, serde_json::Value::Null).unwrap(),
+        Params::None
+    );
+    match Params::parse(&Kind::NewHeads, serde_json::json!({"with_removed": true})).unwrap() {
+        Params::NewHeads(params) => assert!(params.with_removed),
+        params => panic!("Unexpected params: {:?}", params),
+    }
+}
+
 fn assert_that_version_control_has_no_unstaged_changes() {
     let output = Command::new("git")
         .arg("status")
@@ -8,7 +8,7 @@ use starcoin_types::transaction::SignedUserTransaction;
 pub use self::gen_client::Client as TxPoolClient;
 use crate::types::{SignedUserTransactionView, StrView};
 use starcoin_crypto::HashValue;
-use starcoin_txpool_api::TxPoolStatus;
+use starcoin_txpool_api::{GasPriceStats, TxPoolStatus};
 use starcoin_types::account_address::AccountAddress;
 
 #[rpc(client, server, schema)]
@@ -19,9 +19,15 @@ pub trait TxPoolApi {
     #[rpc(name = "txpool.submit_hex_transaction")]
     fn submit_hex_transaction(&self, tx: String) -> FutureResult<HashValue>;
 
-    /// return current gas price
+    /// return current suggested gas price, which is likely to be included in the next block.
     #[rpc(name = "txpool.gas_price")]
     fn gas_price(&self) -> FutureResult<StrView<u64>>;
+
+    /// return suggested gas prices for slow/standard/fast confidence levels,
+    /// and the gas price distributions of recent blocks and pending txns.
+    #[rpc(name = "txpool.gas_price_stats")]
+    fn gas_price_stats(&self) -> FutureResult<GasPriceStats>;
+
     /// get all pending txns in txpool of given sender.
     /// no matter the state of txn is ready or in future.
     #[rpc(name = "txpool.pending_txns_of_sender")]
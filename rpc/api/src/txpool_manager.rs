@@ -0,0 +1,45 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2
+
+pub use self::gen_client::Client as TxPoolManagerClient;
+use crate::types::SignedUserTransactionView;
+use crate::FutureResult;
+use jsonrpc_derive::rpc;
+use starcoin_crypto::HashValue;
+use starcoin_txpool_api::SenderTxnsInspect;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::transaction::SignedUserTransaction;
+
+#[rpc(client, server, schema)]
+pub trait TxPoolManagerApi {
+    /// remove the txn from txpool, return the removed txn, or `None` if it's not in txpool.
+    #[rpc(name = "txpool.remove_txn")]
+    fn remove_txn(&self, txn_hash: HashValue) -> FutureResult<Option<SignedUserTransactionView>>;
+
+    /// inspect the ready and future txns of the given sender, or of all senders if sender is `None`.
+    #[rpc(name = "txpool.inspect")]
+    fn inspect(&self, sender: Option<AccountAddress>) -> FutureResult<Vec<SenderTxnsInspect>>;
+
+    /// remove all txns of the sender from txpool, return the removed txns' hash.
+    #[rpc(name = "txpool.clear_sender")]
+    fn clear_sender(&self, sender: AccountAddress) -> FutureResult<Vec<HashValue>>;
+
+    /// get all txns in txpool, no matter ready or in future, sorted by sender and sequence number.
+    /// skip `offset` txns, and return at most `limit` txns, `limit` default to 100.
+    #[rpc(name = "txpool.pending_all")]
+    fn pending_all(
+        &self,
+        offset: Option<u64>,
+        limit: Option<u64>,
+    ) -> FutureResult<Vec<SignedUserTransactionView>>;
+
+    /// submit the txn as a local txn, which is prioritized and journaled as the txns of this node.
+    #[rpc(name = "txpool.submit_local_transaction")]
+    fn submit_local_transaction(&self, txn: SignedUserTransaction) -> FutureResult<HashValue>;
+}
+#[test]
+fn test() {
+    let schema = rpc_impl_TxPoolManagerApi::gen_client::Client::gen_schema();
+    let j = serde_json::to_string_pretty(&schema).unwrap();
+    println!("{}", j);
+}
@@ -14,13 +14,14 @@ use starcoin_abi_decoder::{
     DecodedPackage, DecodedScript, DecodedScriptFunction, DecodedTransactionPayload,
 };
 use starcoin_abi_types::ModuleABI;
+use starcoin_config::RpcAuthKey;
 use starcoin_crypto::{CryptoMaterialError, HashValue, ValidCryptoMaterialStringExt};
 use starcoin_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue};
 use starcoin_service_registry::ServiceRequest;
 use starcoin_state_api::{StateProof, StateWithProof};
 use starcoin_types::block::{
-    Block, BlockBody, BlockHeader, BlockHeaderExtra, BlockInfo, BlockNumber, BlockSummary,
-    EpochUncleSummary, UncleSummary,
+    Block, BlockBody, BlockHeader, BlockHeaderExtra, BlockIdAndNumber, BlockInfo, BlockNumber,
+    BlockSummary, EpochUncleSummary, UncleSummary,
 };
 use starcoin_types::contract_event::{ContractEvent, ContractEventInfo};
 use starcoin_types::event::EventKey;
@@ -29,6 +30,7 @@ use starcoin_types::language_storage::TypeTag;
 use starcoin_types::peer_info::{PeerId, PeerInfo};
 use starcoin_types::proof::SparseMerkleProof;
 use starcoin_types::startup_info::ChainInfo;
+use starcoin_types::system_events::ChainReorg;
 use starcoin_types::transaction::authenticator::{AuthenticationKey, TransactionAuthenticator};
 use starcoin_types::transaction::{RawUserTransaction, ScriptFunction, TransactionArgument};
 use starcoin_types::vm_error::AbortLocation;
@@ -233,6 +235,67 @@ pub struct DryRunTransactionRequest {
     pub transaction: TransactionRequest,
     /// Sender's public key
     pub sender_public_key: StrView<AccountPublicKey>,
+    /// Dry run on the state after the main chain block of the number.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<BlockNumber>,
+    /// Dry run on the state root, can not be used together with `block_number`.
+    /// The latest state is used if both are absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_root: Option<HashValue>,
+    /// Overrides applied on top of the state before dry run.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub state_overrides: Vec<StateOverrideView>,
+    /// Return the calls into Move of the execution.
+    #[serde(default)]
+    pub trace: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct SimulateBundleRequest {
+    /// Txns to simulate, executed in order.
+    pub transactions: Vec<BundleTransactionRequest>,
+    /// If present, the txns are executed after a simulated block prologue on top of the head block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_prologue: Option<SimulatedBlockPrologue>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct BundleTransactionRequest {
+    /// RawUserTransaction's hex
+    pub raw_txn: String,
+    /// Sender's public key
+    pub sender_public_key: StrView<AccountPublicKey>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct SimulatedBlockPrologue {
+    /// Block timestamp in milliseconds, default to the head block timestamp + 1.
+    #[serde(default)]
+    pub timestamp: Option<u64>,
+    /// Block author, default to the head block author.
+    #[serde(default)]
+    pub author: Option<AccountAddress>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StateOverrideView {
+    /// Replace the resource with the bcs encoded `value`, or delete it if `value` is absent.
+    Resource {
+        address: AccountAddress,
+        struct_tag: StructTagView,
+        #[serde(default)]
+        value: Option<StrView<Vec<u8>>>,
+    },
+    /// Publish or replace the module.
+    Module { code: StrView<ByteCode> },
+    /// Set the token balance of the account, the token is STC if `token_code` is absent.
+    Balance {
+        address: AccountAddress,
+        #[serde(default)]
+        token_code: Option<String>,
+        amount: StrView<u128>,
+    },
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, JsonSchema)]
@@ -583,6 +646,10 @@ pub struct SignedUserTransactionView {
 
     /// Public key and signature to authenticate
     pub authenticator: TransactionAuthenticator,
+
+    /// The account which pays the gas fee of a sponsored transaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_payer: Option<AccountAddress>,
 }
 
 impl TryFrom<SignedUserTransaction> for SignedUserTransactionView {
@@ -591,10 +658,12 @@ impl TryFrom<SignedUserTransaction> for SignedUserTransactionView {
     fn try_from(txn: SignedUserTransaction) -> Result<Self, Self::Error> {
         let auth = txn.authenticator();
         let txn_hash = txn.id();
+        let fee_payer = txn.fee_payer();
         Ok(SignedUserTransactionView {
             transaction_hash: txn_hash,
             raw_txn: txn.into_raw_transaction().try_into()?,
             authenticator: auth,
+            fee_payer,
         })
     }
 }
@@ -986,6 +1055,74 @@ impl TransactionEventView {
     }
 }
 
+/// New value of a resource changed by a txn of the new head block.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct ResourceChangeView {
+    pub block_hash: HashValue,
+    pub block_number: StrView<BlockNumber>,
+    pub transaction_hash: HashValue,
+    // txn index in block
+    pub transaction_index: u32,
+    pub address: AccountAddress,
+    pub resource_type: StructTagView,
+    /// Bcs bytes of the new value, `None` if the resource is deleted.
+    pub raw: Option<StrView<Vec<u8>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json: Option<DecodedMoveValue>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct BlockIdAndNumberView {
+    pub id: HashValue,
+    pub number: StrView<BlockNumber>,
+}
+
+impl From<BlockIdAndNumber> for BlockIdAndNumberView {
+    fn from(block: BlockIdAndNumber) -> Self {
+        Self {
+            id: block.id,
+            number: block.number.into(),
+        }
+    }
+}
+
+/// The main chain switched from the retracted blocks to the enacted blocks.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct ChainReorgView {
+    pub common_ancestor: BlockIdAndNumberView,
+    pub retracted: Vec<BlockIdAndNumberView>,
+    pub enacted: Vec<BlockIdAndNumberView>,
+}
+
+impl From<ChainReorg> for ChainReorgView {
+    fn from(reorg: ChainReorg) -> Self {
+        Self {
+            common_ancestor: reorg.common_ancestor.into(),
+            retracted: reorg.retracted.into_iter().map(Into::into).collect(),
+            enacted: reorg.enacted.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Marker of a block removed from the main chain by a re-org.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct RemovedBlockView {
+    pub block_hash: HashValue,
+    pub number: StrView<BlockNumber>,
+    /// Always be true.
+    pub removed: bool,
+}
+
+impl From<BlockIdAndNumber> for RemovedBlockView {
+    fn from(block: BlockIdAndNumber) -> Self {
+        Self {
+            block_hash: block.id,
+            number: block.number.into(),
+            removed: true,
+        }
+    }
+}
+
 use schemars::gen::SchemaGenerator;
 use schemars::schema::{InstanceType, Schema, SchemaObject};
 use starcoin_types::account_address::AccountAddress;
@@ -997,6 +1134,102 @@ pub struct DryRunOutputView {
     pub explained_status: VmStatusExplainView,
     #[serde(flatten)]
     pub txn_output: TransactionOutputView,
+    /// Calls into Move, only present if the trace is requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Vec<CallTraceView>>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CallKindView {
+    BlockPrologue,
+    Prologue,
+    Script,
+    ScriptFunction,
+    InitScript,
+    Epilogue,
+}
+
+/// A call made by the VM into Move, the calls inside Move are not traced.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CallTraceView {
+    pub kind: CallKindView,
+    /// `None` for script.
+    pub function: Option<FunctionIdView>,
+    pub type_args: Vec<TypeTagView>,
+    /// Bcs encoded args, the signers of script and script function are not included.
+    pub args: Vec<StrView<Vec<u8>>>,
+    /// `None` if the args can not be decoded.
+    pub decoded_args: Option<Vec<DecodedMoveValue>>,
+    pub gas_used: StrView<u64>,
+    pub status: VmStatusExplainView,
+}
+
+/// The output and the calls of the txn, decoded on the state after the txn.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionTraceView {
+    pub transaction_hash: HashValue,
+    pub block_hash: HashValue,
+    pub block_number: StrView<BlockNumber>,
+    pub explained_status: VmStatusExplainView,
+    #[serde(flatten)]
+    pub txn_output: TransactionOutputView,
+    pub calls: Vec<CallTraceView>,
+}
+
+/// A state written by the stored or the replayed txn which differs between the stored and the replayed state after the txn.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct StateDiffView {
+    pub access_path: AccessPath,
+    /// The stored state after the txn, a deletion if the state does not exist.
+    pub expected: TransactionOutputAction,
+    /// The replayed state after the txn, a deletion if the state does not exist.
+    pub actual: TransactionOutputAction,
+}
+
+/// The first txn whose replayed result diverges from the stored one.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionDivergenceView {
+    pub transaction_hash: HashValue,
+    /// The index of the txn in the block, the block metadata txn is 0.
+    pub transaction_index: u32,
+    /// The stored txn info, `None` if the txn info is not stored.
+    pub expected: Option<TransactionInfoView>,
+    /// The replayed txn info, `None` if the txn is discarded or not executed.
+    pub actual: Option<TransactionInfoView>,
+    /// The status of the replayed txn, `None` if the txn is not executed.
+    pub actual_status: Option<TransactionStatusView>,
+    /// The diverged fields of the txn info, in `state_root_hash`, `event_root_hash`, `gas_used` and `status`.
+    pub diverged_fields: Vec<String>,
+    /// The states written by the stored or the replayed txn which differ between the stored and the replayed state
+    /// after the txn, sorted by access path. `None` if the txn info is not stored or the replayed txn is discarded.
+    pub state_diff: Option<Vec<StateDiffView>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BlockReplayView {
+    pub block_hash: HashValue,
+    pub block_number: StrView<BlockNumber>,
+    /// The state root of the block header.
+    pub expected_state_root: HashValue,
+    /// The state root after the replayed txns.
+    pub actual_state_root: HashValue,
+    /// The number of the replayed txns, include the block metadata txn.
+    pub replayed_txns: u32,
+    /// `None` if the results of all txns match the stored ones.
+    pub divergence: Option<TransactionDivergenceView>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GasEstimateView {
+    /// Gas used by the txn with enough max_gas_amount.
+    pub gas_used: StrView<u64>,
+    /// The minimal max_gas_amount to execute the txn successfully, `None` if the txn fails.
+    pub min_max_gas_amount: Option<StrView<u64>>,
+    /// The `min_max_gas_amount` with a safety margin, not more than the max gas amount of the chain.
+    pub suggested_max_gas_amount: Option<StrView<u64>>,
+    /// Why the txn fails even with enough max_gas_amount.
+    pub failure: Option<VmStatusExplainView>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
@@ -1014,27 +1247,7 @@ impl From<TransactionOutput> for TransactionOutputView {
             events: events.into_iter().map(Into::into).collect(),
             gas_used: gas_used.into(),
             status: status.into(),
-            write_set: write_set
-                .into_iter()
-                .map(|(p, w)| {
-                    let (action, value) = match w {
-                        WriteOp::Deletion => (WriteOpView::Deletion, None),
-                        WriteOp::Value(v) => (
-                            WriteOpView::Value,
-                            Some(if p.path.is_resource() {
-                                WriteOpValueView::Resource(v.into())
-                            } else {
-                                WriteOpValueView::Code(v.into())
-                            }),
-                        ),
-                    };
-                    TransactionOutputAction {
-                        access_path: p,
-                        action,
-                        value,
-                    }
-                })
-                .collect(),
+            write_set: write_set.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -1047,6 +1260,27 @@ pub struct TransactionOutputAction {
     pub value: Option<WriteOpValueView>,
 }
 
+impl From<(AccessPath, WriteOp)> for TransactionOutputAction {
+    fn from((access_path, write_op): (AccessPath, WriteOp)) -> Self {
+        let (action, value) = match write_op {
+            WriteOp::Deletion => (WriteOpView::Deletion, None),
+            WriteOp::Value(v) => (
+                WriteOpView::Value,
+                Some(if access_path.path.is_resource() {
+                    WriteOpValueView::Resource(v.into())
+                } else {
+                    WriteOpValueView::Code(v.into())
+                }),
+            ),
+        };
+        TransactionOutputAction {
+            access_path,
+            action,
+            value,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub enum WriteOpValueView {
     Code(CodeView),
@@ -1141,6 +1375,27 @@ impl From<PeerInfo> for PeerInfoView {
     }
 }
 
+/// Rpc auth key without the secret.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RpcAuthKeyView {
+    pub name: String,
+    /// api_key or jwt
+    pub kind: String,
+    pub apis: Option<String>,
+    pub methods: Vec<String>,
+}
+
+impl From<RpcAuthKey> for RpcAuthKeyView {
+    fn from(key: RpcAuthKey) -> Self {
+        Self {
+            name: key.name,
+            kind: key.kind.to_string(),
+            apis: key.apis.map(|apis| apis.to_string()),
+            methods: key.methods,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct StateWithProofView {
     pub state: Option<StrView<Vec<u8>>>,
@@ -2,19 +2,25 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::errors;
-use crate::types::{BlockView, TransactionEventResponse, TypeTagView};
+use crate::types::{
+    BlockView, ChainReorgView, RemovedBlockView, ResourceChangeView, StructTagView,
+    TransactionEventResponse, TypeTagView,
+};
 use jsonrpc_core::error::Error as JsonRpcError;
 use schemars::{self, JsonSchema};
 use serde::de::Error;
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::{Deserialize, Serialize, Serializer};
 use serde_json::{from_value, Value};
 use starcoin_crypto::HashValue;
+use starcoin_sync_api::SyncProgressReport;
+use starcoin_txpool_api::TxnStatusEvent;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::event::EventKey;
 use starcoin_types::filter::Filter;
 use starcoin_types::system_events::MintBlockEvent;
 use starcoin_types::U256;
 use starcoin_vm_types::genesis_config::ConsensusStrategy;
+use starcoin_vm_types::language_storage::StructTag;
 use std::convert::TryInto;
 /// Subscription kind.
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
@@ -30,10 +36,18 @@ pub enum Kind {
     NewPendingTransactions,
     /// New block for minting
     NewMintBlock,
+    /// Sync progress, published periodically while synchronizing.
+    SyncProgress,
+    /// Transaction status transitions, from pool admission to inclusion in a block.
+    TransactionStatus,
+    /// New values of watched resources, changed by txns of the new head block.
+    ResourceChanges,
+    /// Main chain switched to another branch.
+    ChainReorg,
 }
 
 /// Subscription result.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Result {
     /// New block.
     Block(Box<BlockView>),
@@ -41,6 +55,12 @@ pub enum Result {
     TransactionHash(Vec<HashValue>),
     Event(Box<TransactionEventResponse>),
     MintBlock(Box<MintBlockEvent>),
+    SyncProgress(Box<SyncProgressReport>),
+    TransactionStatus(Box<TxnStatusEvent>),
+    ResourceChange(Box<ResourceChangeView>),
+    ChainReorg(Box<ChainReorgView>),
+    /// Block removed from the main chain, sent to new heads subscribers who want it.
+    RemovedBlock(Box<RemovedBlockView>),
 }
 
 impl Serialize for Result {
@@ -52,7 +72,12 @@ impl Serialize for Result {
             Result::Block(ref header) => header.serialize(serializer),
             Result::Event(ref evt) => evt.serialize(serializer),
             Result::TransactionHash(ref hash) => hash.serialize(serializer),
-            Result::MintBlock(ref block) => block.serialize(serializer), // Result::SyncState(ref sync) => sync.serialize(serializer),
+            Result::MintBlock(ref block) => block.serialize(serializer),
+            Result::SyncProgress(ref report) => report.serialize(serializer),
+            Result::TransactionStatus(ref event) => event.serialize(serializer),
+            Result::ResourceChange(ref change) => change.serialize(serializer),
+            Result::ChainReorg(ref reorg) => reorg.serialize(serializer),
+            Result::RemovedBlock(ref block) => block.serialize(serializer),
         }
     }
 }
@@ -62,8 +87,14 @@ impl Serialize for Result {
 pub enum Params {
     /// No parameters passed.
     None,
+    /// New heads parameters.
+    NewHeads(NewHeadsParams),
     /// Log parameters.
     Events(EventParams),
+    /// Transaction status parameters.
+    TransactionStatus(TxnStatusFilter),
+    /// Resource change parameters.
+    ResourceChanges(ResourceChangeFilter),
 }
 
 impl Default for Params {
@@ -72,23 +103,34 @@ impl Default for Params {
     }
 }
 
-impl<'a> Deserialize<'a> for Params {
-    fn deserialize<D>(deserializer: D) -> ::std::result::Result<Params, D::Error>
-    where
-        D: Deserializer<'a>,
-    {
-        let v: Value = Deserialize::deserialize(deserializer)?;
-
-        if v.is_null() {
+impl Params {
+    /// Parse the params of the subscription `kind`. The params type is decided by the kind,
+    /// because the filters of different kinds may have the same fields, such as `addrs`.
+    pub fn parse(kind: &Kind, params: Value) -> ::std::result::Result<Params, serde_json::Error> {
+        if params.is_null() {
             return Ok(Params::None);
         }
-        // Err(D::Error::custom("Invalid Pub-Sub parameters"));
-        from_value(v)
-            .map(Params::Events)
-            .map_err(|e| D::Error::custom(format!("Invalid Pub-Sub parameters: {}", e)))
+        Ok(match kind {
+            Kind::NewHeads => Params::NewHeads(from_value(params)?),
+            Kind::Events => Params::Events(from_value(params)?),
+            Kind::TransactionStatus => Params::TransactionStatus(from_value(params)?),
+            Kind::ResourceChanges => Params::ResourceChanges(from_value(params)?),
+            // params of mint block subscription are ignored.
+            Kind::NewMintBlock => Params::None,
+            Kind::NewPendingTransactions | Kind::SyncProgress | Kind::ChainReorg => {
+                return Err(serde_json::Error::custom("Expected no parameters."));
+            }
+        })
     }
 }
 
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Eq, Hash, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct NewHeadsParams {
+    /// Also send removal markers of the blocks retracted by a re-org.
+    pub with_removed: bool,
+}
+
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq, Hash)]
 pub struct EventParams {
     #[serde(flatten)]
@@ -102,6 +144,7 @@ pub struct EventParams {
 #[serde(deny_unknown_fields)]
 pub struct EventFilter {
     /// From Block
+    /// When subscribing, events of the main chain blocks since `from_block` are sent before the new ones.
     #[serde(default)]
     pub from_block: Option<u64>,
     /// To Block
@@ -156,6 +199,80 @@ impl TryInto<Filter> for EventFilter {
     }
 }
 
+/// Transaction status filter
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Eq, Hash, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TxnStatusFilter {
+    /// Hashes of the transactions.
+    #[serde(default)]
+    pub txn_hashes: Option<Vec<HashValue>>,
+    /// Senders of the transactions.
+    #[serde(default)]
+    pub senders: Option<Vec<AccountAddress>>,
+}
+
+impl TxnStatusFilter {
+    pub fn is_empty(&self) -> bool {
+        self.txn_hashes.as_ref().map_or(true, Vec::is_empty)
+            && self.senders.as_ref().map_or(true, Vec::is_empty)
+    }
+
+    /// match if the transaction is any of the `txn_hashes`, or comes from any of the `senders`.
+    /// if both are empty, transaction always match.
+    pub fn matching(&self, event: &TxnStatusEvent) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        self.txn_hashes
+            .as_ref()
+            .map_or(false, |hashes| hashes.contains(&event.txn_hash))
+            || self
+                .senders
+                .as_ref()
+                .map_or(false, |senders| senders.contains(&event.sender))
+    }
+}
+
+/// A resource of an account.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq, Hash, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ResourceKey {
+    pub address: AccountAddress,
+    pub resource_type: StructTagView,
+}
+
+/// Resource change filter
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Eq, Hash, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ResourceChangeFilter {
+    /// Resources to watch.
+    #[serde(default)]
+    pub resources: Option<Vec<ResourceKey>>,
+    /// Accounts whose resources are all watched.
+    #[serde(default)]
+    pub addrs: Option<Vec<AccountAddress>>,
+}
+
+impl ResourceChangeFilter {
+    pub fn is_empty(&self) -> bool {
+        self.resources.as_ref().map_or(true, Vec::is_empty)
+            && self.addrs.as_ref().map_or(true, Vec::is_empty)
+    }
+
+    /// match if the resource is any of the `resources`, or belongs to any of the `addrs`.
+    /// if both are empty, nothing match.
+    pub fn matching(&self, address: &AccountAddress, resource_type: &StructTag) -> bool {
+        self.addrs
+            .as_ref()
+            .map_or(false, |addrs| addrs.contains(address))
+            || self.resources.as_ref().map_or(false, |resources| {
+                resources
+                    .iter()
+                    .any(|key| &key.address == address && &key.resource_type.0 == resource_type)
+            })
+    }
+}
+
 /// Block for minting
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -0,0 +1,1236 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2
+
+use crate::chain_watcher::{self, ChainWatcher, StartSubscribe, StopWatcher, WatchBlock, WatchTxn};
+use crate::remote_state_reader::AsyncRemoteStateReader;
+use crate::{map_err, ConnSource, RpcClientInner, StateRootOption};
+use actix::{Addr, System};
+use bcs_ext::BCSCodec;
+use futures::channel::oneshot;
+use futures::stream::MapErr;
+use futures::TryStreamExt;
+use jsonrpc_client_transports::RpcError;
+use jsonrpc_core::Params;
+use jsonrpc_core_client::TypedSubscriptionStream;
+use network_api::PeerStrategy;
+use network_p2p_types::network_state::NetworkState;
+use parking_lot::Mutex;
+use serde_json::Value;
+use starcoin_abi_types::{FunctionABI, ModuleABI, StructInstantiation};
+use starcoin_account_api::AccountInfo;
+use starcoin_crypto::HashValue;
+use starcoin_logger::{prelude::*, LogPattern};
+use starcoin_rpc_api::chain::{GetBlockOption, GetEventOption, GetTransactionOption};
+use starcoin_rpc_api::node::NodeInfo;
+use starcoin_rpc_api::service::RpcAsyncService;
+use starcoin_rpc_api::state::{
+    GetCodeOption, GetResourceOption, ListCodeOption, ListResourceOption,
+};
+use starcoin_rpc_api::types::pubsub::{EventFilter, ResourceChangeFilter, TxnStatusFilter};
+use starcoin_rpc_api::types::{
+    AccountStateSetView, AnnotatedMoveStructView, BlockHeaderView, BlockReplayView, BlockView,
+    ChainId, ChainInfoView, ChainReorgView, CodeView, ContractCall, DecodedMoveValue,
+    DryRunOutputView, DryRunTransactionRequest, FactoryAction, FunctionIdView, GasEstimateView,
+    ListCodeView, ListResourceView, MintedBlockView, ModuleIdView, PeerInfoView,
+    ResourceChangeView, ResourceView, RpcAuthKeyView, SignedMessageView, SignedUserTransactionView,
+    SimulateBundleRequest, StateWithProofView, StrView, StructTagView, TransactionEventResponse,
+    TransactionEventView, TransactionInfoView, TransactionRequest, TransactionTraceView,
+    TransactionView,
+};
+use starcoin_service_registry::{ServiceInfo, ServiceStatus};
+use starcoin_sync_api::{PeerScoreResponse, SyncProgressReport};
+use starcoin_txpool_api::{GasPriceStats, SenderTxnsInspect, TxPoolStatus, TxnStatusEvent};
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_state::AccountState;
+use starcoin_types::block::{BlockInfo, BlockNumber};
+use starcoin_types::peer_info::{Multiaddr, PeerId};
+use starcoin_types::sign_message::SigningMessage;
+use starcoin_types::sync_status::SyncStatus;
+use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::transaction::authenticator::TransactionAuthenticator;
+use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
+use starcoin_vm_types::language_storage::{ModuleId, StructTag};
+use starcoin_vm_types::token::token_code::TokenCode;
+use starcoin_vm_types::transaction::DryRunTransaction;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Subscription stream whose rpc errors are converted to anyhow errors.
+pub type SubscriptionStream<T> = MapErr<TypedSubscriptionStream<T>, fn(RpcError) -> anyhow::Error>;
+
+/// The async rpc client, it should be used in a tokio runtime.
+/// The connection of ws and ipc is re-established on the next call after it is lost,
+/// subscriptions of the chain watcher and the authentication are recovered at the same time.
+pub struct AsyncRpcClient {
+    conn_source: ConnSource,
+    inner: Mutex<Option<RpcClientInner>>,
+    request_timeout: Option<Duration>,
+    /// token used to re-authenticate the connection after reconnect.
+    auth_token: Mutex<Option<String>>,
+    chain_watcher: Addr<ChainWatcher>,
+    //hold the watch thread handle.
+    watcher_handle: JoinHandle<()>,
+}
+
+impl AsyncRpcClient {
+    pub(crate) async fn new(conn_source: ConnSource) -> anyhow::Result<Self> {
+        let (tx, rx) = oneshot::channel();
+        let inner: RpcClientInner = conn_source.connect().await.map_err(map_err)?.into();
+        let pubsub_client = inner.pubsub_client.clone();
+        let handle = std::thread::spawn(move || {
+            let sys = System::new("client-actix-system");
+            let watcher = ChainWatcher::launch();
+
+            tx.send(watcher).unwrap();
+            let _ = sys.run();
+        });
+        let watcher = rx.await.expect("Init chain watcher fail.");
+        if conn_source.support_pubsub() {
+            watcher.do_send(StartSubscribe {
+                client: pubsub_client,
+            });
+        }
+        Ok(Self {
+            conn_source,
+            inner: Mutex::new(Some(inner)),
+            request_timeout: None,
+            auth_token: Mutex::new(None),
+            chain_watcher: watcher,
+            watcher_handle: handle,
+        })
+    }
+
+    /// Connect by `ws://` or `wss://` url, the server certificate of wss is verified by system CA.
+    pub async fn connect_websocket(url: &str) -> anyhow::Result<Self> {
+        Self::new(ConnSource::WebSocket(url.to_string())).await
+    }
+
+    /// Connect by `wss://` url, the server certificate is verified by the PEM `ca_cert`.
+    pub async fn connect_websocket_with_ca_cert<P: AsRef<Path>>(
+        url: &str,
+        ca_cert: P,
+    ) -> anyhow::Result<Self> {
+        Self::new(ConnSource::WebSocketWithCaCert(
+            url.to_string(),
+            ca_cert.as_ref().to_path_buf(),
+        ))
+        .await
+    }
+
+    /// Connect by `http://` or `https://` url, subscription and watch are not supported by http.
+    pub async fn connect_http(url: &str) -> anyhow::Result<Self> {
+        Self::new(ConnSource::Http(
+            url.to_string(),
+            Arc::new(Mutex::new(None)),
+        ))
+        .await
+    }
+
+    pub async fn connect_local<S>(rpc_service: S) -> anyhow::Result<Self>
+    where
+        S: RpcAsyncService,
+    {
+        let client = rpc_service.connect_local().await?;
+        Self::new(ConnSource::Local(Box::new(client))).await
+    }
+
+    pub async fn connect_ipc<P: AsRef<Path>>(sock_path: P) -> anyhow::Result<Self> {
+        let path = sock_path.as_ref().to_path_buf();
+        Self::new(ConnSource::Ipc(path)).await
+    }
+
+    /// Every rpc call fails with timeout error if it is not finished in `timeout`,
+    /// subscriptions and watches are not limited by it.
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    pub fn request_timeout(&self) -> Option<Duration> {
+        self.request_timeout
+    }
+
+    pub async fn watch_txn(
+        &self,
+        txn_hash: HashValue,
+        timeout: Option<Duration>,
+    ) -> anyhow::Result<chain_watcher::ThinHeadBlock> {
+        let r = self.chain_watcher.send(WatchTxn { txn_hash }).await?;
+        match timeout {
+            Some(t) => async_std::future::timeout(t, r).await??,
+            None => r.await?,
+        }
+    }
+
+    pub async fn watch_block(
+        &self,
+        block_number: BlockNumber,
+    ) -> anyhow::Result<chain_watcher::ThinHeadBlock> {
+        let r = self.chain_watcher.send(WatchBlock(block_number)).await?;
+        r.await?
+    }
+
+    /// Authenticate the connection by api key or jwt token, return the authenticated identity.
+    /// The token is kept to authenticate the connection again after reconnect.
+    pub async fn authenticate(&self, token: String) -> anyhow::Result<String> {
+        let identity = self
+            .call_rpc(|inner| Self::authenticate_inner(inner, token.clone()))
+            .await
+            .map_err(map_err)?;
+        // http is not session based, the token is sent with every request.
+        if let ConnSource::Http(_, http_auth_token) = &self.conn_source {
+            *http_auth_token.lock() = Some(token.clone());
+        }
+        *self.auth_token.lock() = Some(token);
+        Ok(identity)
+    }
+
+    async fn authenticate_inner(inner: RpcClientInner, token: String) -> Result<String, RpcError> {
+        let identity = inner
+            .raw_client
+            .call_method(
+                "rpc.authenticate",
+                Params::Array(vec![Value::String(token)]),
+            )
+            .await?;
+        serde_json::from_value(identity).map_err(|e| RpcError::Client(e.to_string()))
+    }
+
+    pub async fn state_reader(
+        &self,
+        state_root_opt: StateRootOption,
+    ) -> anyhow::Result<AsyncRemoteStateReader<'_>> {
+        AsyncRemoteStateReader::new(self, state_root_opt).await
+    }
+
+    pub async fn node_status(&self) -> anyhow::Result<bool> {
+        self.call_rpc(|inner| inner.node_client.status())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn node_info(&self) -> anyhow::Result<NodeInfo> {
+        self.call_rpc(|inner| inner.node_client.info())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn node_metrics(&self) -> anyhow::Result<HashMap<String, String>> {
+        self.call_rpc(|inner| inner.node_client.metrics())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn node_peers(&self) -> anyhow::Result<Vec<PeerInfoView>> {
+        self.call_rpc(|inner| inner.node_client.peers())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn node_list_service(&self) -> anyhow::Result<Vec<ServiceInfo>> {
+        self.call_rpc(|inner| inner.node_manager_client.list_service())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn node_start_service(&self, service_name: String) -> anyhow::Result<()> {
+        self.call_rpc(|inner| inner.node_manager_client.start_service(service_name))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn node_check_service(&self, service_name: String) -> anyhow::Result<ServiceStatus> {
+        self.call_rpc(|inner| inner.node_manager_client.check_service(service_name))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn node_stop_service(&self, service_name: String) -> anyhow::Result<()> {
+        self.call_rpc(|inner| inner.node_manager_client.stop_service(service_name))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn node_shutdown_system(&self) -> anyhow::Result<()> {
+        self.call_rpc(|inner| inner.node_manager_client.shutdown_system())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn node_reset(&self, block_hash: HashValue) -> anyhow::Result<()> {
+        self.call_rpc(|inner| inner.node_manager_client.reset_to_block(block_hash))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn node_re_execute_block(&self, block_id: HashValue) -> anyhow::Result<()> {
+        self.call_rpc(|inner| inner.node_manager_client.re_execute_block(block_id))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn node_delete_block(&self, block_id: HashValue) -> anyhow::Result<()> {
+        self.call_rpc(|inner| inner.node_manager_client.delete_block(block_id))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn node_delete_failed_block(&self, block_id: HashValue) -> anyhow::Result<()> {
+        self.call_rpc(|inner| inner.node_manager_client.delete_failed_block(block_id))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn node_list_rpc_auth_keys(&self) -> anyhow::Result<Vec<RpcAuthKeyView>> {
+        self.call_rpc(|inner| inner.node_manager_client.list_rpc_auth_keys())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn node_add_rpc_auth_key(
+        &self,
+        name: String,
+        kind: String,
+        apis: Option<String>,
+        methods: Option<Vec<String>>,
+    ) -> anyhow::Result<String> {
+        self.call_rpc(|inner| {
+            inner
+                .node_manager_client
+                .add_rpc_auth_key(name, kind, apis, methods)
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn node_remove_rpc_auth_key(&self, name: String) -> anyhow::Result<bool> {
+        self.call_rpc(|inner| inner.node_manager_client.remove_rpc_auth_key(name))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn next_sequence_number_in_txpool(
+        &self,
+        address: AccountAddress,
+    ) -> anyhow::Result<Option<u64>> {
+        self.call_rpc(|inner| inner.txpool_client.next_sequence_number(address))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn submit_transaction(
+        &self,
+        txn: SignedUserTransaction,
+    ) -> anyhow::Result<HashValue> {
+        self.call_rpc(|inner| inner.txpool_client.submit_transaction(txn))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn get_pending_txn_by_hash(
+        &self,
+        txn_hash: HashValue,
+    ) -> anyhow::Result<Option<SignedUserTransactionView>> {
+        self.call_rpc(|inner| inner.txpool_client.pending_txn(txn_hash))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn get_pending_txns_of_sender(
+        &self,
+        sender: AccountAddress,
+        max_len: Option<u32>,
+    ) -> anyhow::Result<Vec<SignedUserTransactionView>> {
+        self.call_rpc(|inner| inner.txpool_client.pending_txns(sender, max_len))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn txpool_remove_txn(
+        &self,
+        txn_hash: HashValue,
+    ) -> anyhow::Result<Option<SignedUserTransactionView>> {
+        self.call_rpc(|inner| inner.txpool_manager_client.remove_txn(txn_hash))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn txpool_inspect(
+        &self,
+        sender: Option<AccountAddress>,
+    ) -> anyhow::Result<Vec<SenderTxnsInspect>> {
+        self.call_rpc(|inner| inner.txpool_manager_client.inspect(sender))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn txpool_clear_sender(
+        &self,
+        sender: AccountAddress,
+    ) -> anyhow::Result<Vec<HashValue>> {
+        self.call_rpc(|inner| inner.txpool_manager_client.clear_sender(sender))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn txpool_pending_all(
+        &self,
+        offset: Option<u64>,
+        limit: Option<u64>,
+    ) -> anyhow::Result<Vec<SignedUserTransactionView>> {
+        self.call_rpc(|inner| inner.txpool_manager_client.pending_all(offset, limit))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn txpool_submit_local_transaction(
+        &self,
+        txn: SignedUserTransaction,
+    ) -> anyhow::Result<HashValue> {
+        self.call_rpc(|inner| inner.txpool_manager_client.submit_local_transaction(txn))
+            .await
+            .map_err(map_err)
+    }
+
+    //TODO should split client for different api ?
+    // such as  RpcClient().account().default()
+    pub async fn account_default(&self) -> anyhow::Result<Option<AccountInfo>> {
+        self.call_rpc(|inner| inner.account_client.default())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn set_default_account(&self, addr: AccountAddress) -> anyhow::Result<AccountInfo> {
+        self.call_rpc(|inner| inner.account_client.set_default_account(addr))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn account_create(&self, password: String) -> anyhow::Result<AccountInfo> {
+        self.call_rpc(|inner| inner.account_client.create(password))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn account_list(&self) -> anyhow::Result<Vec<AccountInfo>> {
+        self.call_rpc(|inner| inner.account_client.list())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn account_get(
+        &self,
+        address: AccountAddress,
+    ) -> anyhow::Result<Option<AccountInfo>> {
+        self.call_rpc(|inner| inner.account_client.get(address))
+            .await
+            .map_err(map_err)
+    }
+
+    /// partial sign a multisig account's txn
+    pub async fn account_sign_multisig_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        signer_address: AccountAddress,
+    ) -> anyhow::Result<SignedUserTransaction> {
+        self.call_rpc(|inner| inner.account_client.sign_txn(raw_txn, signer_address))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn account_sign_txn_request(
+        &self,
+        txn_request: TransactionRequest,
+    ) -> anyhow::Result<SignedUserTransaction> {
+        self.call_rpc(|inner| inner.account_client.sign_txn_request(txn_request))
+            .await
+            .map_err(map_err)
+            .and_then(|d: String| {
+                hex::decode(d.as_str().strip_prefix("0x").unwrap_or_else(|| d.as_str()))
+                    .map_err(anyhow::Error::new)
+                    .and_then(|d| bcs_ext::from_bytes::<SignedUserTransaction>(d.as_slice()))
+            })
+    }
+
+    pub async fn account_sign_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+    ) -> anyhow::Result<SignedUserTransaction> {
+        let signer = raw_txn.sender();
+        self.call_rpc(|inner| inner.account_client.sign_txn(raw_txn, signer))
+            .await
+            .map_err(map_err)
+    }
+
+    /// sign a sponsored txn as the sender or the fee payer
+    pub async fn account_sign_txn_with_fee_payer(
+        &self,
+        raw_txn: RawUserTransaction,
+        fee_payer: AccountAddress,
+        signer: AccountAddress,
+    ) -> anyhow::Result<TransactionAuthenticator> {
+        self.call_rpc(|inner| {
+            inner
+                .account_client
+                .sign_txn_with_fee_payer(raw_txn, fee_payer, signer)
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn account_sign_message(
+        &self,
+        signer: AccountAddress,
+        message: SigningMessage,
+    ) -> anyhow::Result<SignedMessageView> {
+        self.call_rpc(|inner| inner.account_client.sign(signer, message))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn account_change_password(
+        &self,
+        address: AccountAddress,
+        new_password: String,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc(|inner| {
+            inner
+                .account_client
+                .change_account_password(address, new_password)
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn account_lock(&self, address: AccountAddress) -> anyhow::Result<AccountInfo> {
+        self.call_rpc(|inner| inner.account_client.lock(address))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn account_unlock(
+        &self,
+        address: AccountAddress,
+        password: String,
+        duration: std::time::Duration,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc(|inner| {
+            inner
+                .account_client
+                .unlock(address, password, Some(duration.as_secs() as u32))
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn account_export(
+        &self,
+        address: AccountAddress,
+        password: String,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.call_rpc(|inner| inner.account_client.export(address, password))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn account_import(
+        &self,
+        address: AccountAddress,
+        private_key: Vec<u8>,
+        password: String,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc(|inner| {
+            inner
+                .account_client
+                .import(address, StrView(private_key), password)
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn account_import_readonly(
+        &self,
+        address: AccountAddress,
+        public_key: Vec<u8>,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc(|inner| {
+            inner
+                .account_client
+                .import_readonly(address, StrView(public_key))
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn account_accepted_tokens(
+        &self,
+        address: AccountAddress,
+    ) -> anyhow::Result<Vec<TokenCode>> {
+        self.call_rpc(|inner| inner.account_client.accepted_tokens(address))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn account_remove(
+        &self,
+        address: AccountAddress,
+        password: Option<String>,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc(|inner| inner.account_client.remove(address, password))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn get_code(&self, module_id: ModuleId) -> anyhow::Result<Option<String>> {
+        let result: Option<StrView<Vec<u8>>> = self
+            .call_rpc_blocking(|inner| inner.contract_client.get_code(StrView(module_id)))
+            .await
+            .map_err(map_err)?;
+        Ok(result.map(|s| s.to_string()))
+    }
+
+    pub async fn get_resource(
+        &self,
+        addr: AccountAddress,
+        resource_type: StructTag,
+    ) -> anyhow::Result<Option<AnnotatedMoveStructView>> {
+        self.call_rpc(|inner| {
+            inner
+                .contract_client
+                .get_resource(addr, StrView(resource_type))
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn state_get(&self, access_path: AccessPath) -> anyhow::Result<Option<Vec<u8>>> {
+        self.call_rpc(|inner| inner.state_client.get(access_path))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn state_get_with_proof(
+        &self,
+        access_path: AccessPath,
+    ) -> anyhow::Result<StateWithProofView> {
+        self.call_rpc(|inner| inner.state_client.get_with_proof(access_path))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn state_get_with_proof_by_root(
+        &self,
+        access_path: AccessPath,
+        state_root: HashValue,
+    ) -> anyhow::Result<StateWithProofView> {
+        self.call_rpc(|inner| {
+            inner
+                .state_client
+                .get_with_proof_by_root(access_path, state_root)
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn state_get_state_root(&self) -> anyhow::Result<HashValue> {
+        self.call_rpc(|inner| inner.state_client.get_state_root())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn state_get_account_state(
+        &self,
+        address: AccountAddress,
+    ) -> anyhow::Result<Option<AccountState>> {
+        self.call_rpc(|inner| inner.state_client.get_account_state(address))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn state_get_account_state_set(
+        &self,
+        address: AccountAddress,
+        state_root: Option<HashValue>,
+    ) -> anyhow::Result<Option<AccountStateSetView>> {
+        self.call_rpc(|inner| {
+            inner
+                .state_client
+                .get_account_state_set(address, state_root)
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn state_get_resource(
+        &self,
+        address: AccountAddress,
+        resource_type: StructTag,
+        decode: bool,
+        state_root: Option<HashValue>,
+    ) -> anyhow::Result<Option<ResourceView>> {
+        self.call_rpc(|inner| {
+            inner.state_client.get_resource(
+                address,
+                StrView(resource_type),
+                Some(GetResourceOption { decode, state_root }),
+            )
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn state_list_resource(
+        &self,
+        address: AccountAddress,
+        decode: bool,
+        state_root: Option<HashValue>,
+    ) -> anyhow::Result<ListResourceView> {
+        self.call_rpc(|inner| {
+            inner
+                .state_client
+                .list_resource(address, Some(ListResourceOption { decode, state_root }))
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn state_get_code(
+        &self,
+        module_id: ModuleId,
+        resolve: bool,
+        state_root: Option<HashValue>,
+    ) -> anyhow::Result<Option<CodeView>> {
+        self.call_rpc(|inner| {
+            inner.state_client.get_code(
+                StrView(module_id),
+                Some(GetCodeOption {
+                    resolve,
+                    state_root,
+                }),
+            )
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn state_list_code(
+        &self,
+        address: AccountAddress,
+        resolve: bool,
+        state_root: Option<HashValue>,
+    ) -> anyhow::Result<ListCodeView> {
+        self.call_rpc(|inner| {
+            inner.state_client.list_code(
+                address,
+                Some(ListCodeOption {
+                    resolve,
+                    state_root,
+                }),
+            )
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn contract_call(&self, call: ContractCall) -> anyhow::Result<Vec<DecodedMoveValue>> {
+        self.call_rpc(|inner| inner.contract_client.call_v2(call))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn contract_resolve_function(
+        &self,
+        function_id: FunctionIdView,
+    ) -> anyhow::Result<FunctionABI> {
+        self.call_rpc(|inner| inner.contract_client.resolve_function(function_id))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn contract_resolve_struct(
+        &self,
+        struct_tag: StructTagView,
+    ) -> anyhow::Result<StructInstantiation> {
+        self.call_rpc(|inner| inner.contract_client.resolve_struct(struct_tag))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn contract_resolve_module(
+        &self,
+        module_id: ModuleIdView,
+    ) -> anyhow::Result<ModuleABI> {
+        self.call_rpc(|inner| inner.contract_client.resolve_module(module_id))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn debug_set_log_level(
+        &self,
+        logger_name: Option<String>,
+        level: Level,
+    ) -> anyhow::Result<()> {
+        self.call_rpc(|inner| {
+            inner
+                .debug_client
+                .set_log_level(logger_name, level.to_string())
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn debug_set_log_pattern(&self, pattern: LogPattern) -> anyhow::Result<()> {
+        self.call_rpc(|inner| inner.debug_client.set_log_pattern(pattern))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn debug_panic(&self) -> anyhow::Result<()> {
+        self.call_rpc(|inner| inner.debug_client.panic())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn debug_txfactory_status(&self, action: FactoryAction) -> anyhow::Result<bool> {
+        self.call_rpc(|inner| inner.debug_client.txfactory_status(action))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn debug_trace_transaction(
+        &self,
+        txn_hash: HashValue,
+    ) -> anyhow::Result<TransactionTraceView> {
+        self.call_rpc(|inner| inner.debug_client.trace_transaction(txn_hash))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn debug_replay_block(
+        &self,
+        block_hash: HashValue,
+    ) -> anyhow::Result<BlockReplayView> {
+        self.call_rpc(|inner| inner.debug_client.replay_block(block_hash))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn sleep(&self, time: u64) -> anyhow::Result<()> {
+        self.call_rpc(|inner| inner.debug_client.sleep(time))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn chain_id(&self) -> anyhow::Result<ChainId> {
+        self.call_rpc(|inner| inner.chain_client.id())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn chain_info(&self) -> anyhow::Result<ChainInfoView> {
+        self.call_rpc(|inner| inner.chain_client.info())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn get_headers(
+        &self,
+        block_hashes: Vec<HashValue>,
+    ) -> anyhow::Result<Vec<BlockHeaderView>> {
+        self.call_rpc(|inner| inner.chain_client.get_headers(block_hashes))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn chain_get_block_by_hash(
+        &self,
+        hash: HashValue,
+        option: Option<GetBlockOption>,
+    ) -> anyhow::Result<Option<BlockView>> {
+        self.call_rpc(|inner| inner.chain_client.get_block_by_hash(hash, option))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn chain_get_block_by_number(
+        &self,
+        number: BlockNumber,
+        option: Option<GetBlockOption>,
+    ) -> anyhow::Result<Option<BlockView>> {
+        self.call_rpc(|inner| inner.chain_client.get_block_by_number(number, option))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn chain_get_block_info_by_number(
+        &self,
+        number: BlockNumber,
+    ) -> anyhow::Result<Option<BlockInfo>> {
+        self.call_rpc(|inner| inner.chain_client.get_block_info_by_number(number))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn chain_get_blocks_by_number(
+        &self,
+        number: Option<BlockNumber>,
+        count: u64,
+    ) -> anyhow::Result<Vec<BlockView>> {
+        self.call_rpc(|inner| inner.chain_client.get_blocks_by_number(number, count))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn chain_get_transaction(
+        &self,
+        txn_id: HashValue,
+        option: Option<GetTransactionOption>,
+    ) -> anyhow::Result<Option<TransactionView>> {
+        self.call_rpc(|inner| inner.chain_client.get_transaction(txn_id, option))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn chain_get_transaction_info(
+        &self,
+        txn_hash: HashValue,
+    ) -> anyhow::Result<Option<TransactionInfoView>> {
+        self.call_rpc(|inner| inner.chain_client.get_transaction_info(txn_hash))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn chain_get_events_by_txn_hash(
+        &self,
+        txn_hash: HashValue,
+        option: Option<GetEventOption>,
+    ) -> anyhow::Result<Vec<TransactionEventResponse>> {
+        self.call_rpc(|inner| inner.chain_client.get_events_by_txn_hash(txn_hash, option))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn chain_get_block_txn_infos(
+        &self,
+        block_id: HashValue,
+    ) -> anyhow::Result<Vec<TransactionInfoView>> {
+        self.call_rpc(|inner| inner.chain_client.get_block_txn_infos(block_id))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn chain_get_txn_info_by_block_and_index(
+        &self,
+        block_id: HashValue,
+        idx: u64,
+    ) -> anyhow::Result<Option<TransactionInfoView>> {
+        self.call_rpc(|inner| {
+            inner
+                .chain_client
+                .get_txn_info_by_block_and_index(block_id, idx)
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn dry_run(&self, txn: DryRunTransactionRequest) -> anyhow::Result<DryRunOutputView> {
+        self.call_rpc(|inner| inner.contract_client.dry_run(txn))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn dry_run_raw(&self, txn: DryRunTransaction) -> anyhow::Result<DryRunOutputView> {
+        let DryRunTransaction {
+            raw_txn,
+            public_key,
+        } = txn;
+        let raw_txn_str = hex::encode(raw_txn.encode()?);
+        self.call_rpc(|inner| {
+            inner
+                .contract_client
+                .dry_run_raw(raw_txn_str, StrView(public_key))
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn estimate_gas(
+        &self,
+        txn: DryRunTransactionRequest,
+    ) -> anyhow::Result<GasEstimateView> {
+        self.call_rpc(|inner| inner.contract_client.estimate_gas(txn))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn simulate_bundle(
+        &self,
+        bundle: SimulateBundleRequest,
+    ) -> anyhow::Result<Vec<DryRunOutputView>> {
+        self.call_rpc(|inner| inner.contract_client.simulate_bundle(bundle))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn miner_submit(
+        &self,
+        minting_blob: String,
+        nonce: u32,
+        extra: String,
+    ) -> anyhow::Result<MintedBlockView> {
+        self.call_rpc(|inner| inner.miner_client.submit(minting_blob, nonce, extra))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn txpool_status(&self) -> anyhow::Result<TxPoolStatus> {
+        self.call_rpc(|inner| inner.txpool_client.state())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn txpool_gas_price_stats(&self) -> anyhow::Result<GasPriceStats> {
+        self.call_rpc(|inner| inner.txpool_client.gas_price_stats())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn sync_status(&self) -> anyhow::Result<SyncStatus> {
+        self.call_rpc(|inner| inner.sync_client.status())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn sync_progress(&self) -> anyhow::Result<Option<SyncProgressReport>> {
+        self.call_rpc(|inner| inner.sync_client.progress())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn sync_peer_score(&self) -> anyhow::Result<PeerScoreResponse> {
+        self.call_rpc(|inner| inner.sync_client.peer_score())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn sync_start(
+        &self,
+        force: bool,
+        peers: Vec<PeerId>,
+        skip_pow_verify: bool,
+        strategy: Option<PeerStrategy>,
+    ) -> anyhow::Result<()> {
+        self.call_rpc(|inner| {
+            inner
+                .sync_client
+                .start(force, peers, skip_pow_verify, strategy)
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn sync_cancel(&self) -> anyhow::Result<()> {
+        self.call_rpc(|inner| inner.sync_client.cancel())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn network_known_peers(&self) -> anyhow::Result<Vec<PeerId>> {
+        self.call_rpc(|inner| inner.network_client.known_peers())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn network_state(&self) -> anyhow::Result<NetworkState> {
+        self.call_rpc(|inner| inner.network_client.state())
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn network_get_address(&self, peer_id: String) -> anyhow::Result<Vec<Multiaddr>> {
+        self.call_rpc(|inner| inner.network_client.get_address(peer_id))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn network_add_peer(&self, peer: String) -> anyhow::Result<()> {
+        self.call_rpc(|inner| inner.network_client.add_peer(peer))
+            .await
+            .map_err(map_err)
+    }
+
+    pub async fn network_call_peer(
+        &self,
+        peer_id: String,
+        rpc_method: String,
+        message: StrView<Vec<u8>>,
+    ) -> anyhow::Result<StrView<Vec<u8>>> {
+        self.call_rpc(|inner| {
+            inner
+                .network_client
+                .call_peer(peer_id, rpc_method.into(), message)
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn subscribe_events(
+        &self,
+        filter: EventFilter,
+        decode: bool,
+    ) -> anyhow::Result<SubscriptionStream<TransactionEventView>> {
+        self.call_rpc(|inner| async move {
+            let res = inner.pubsub_client.subscribe_events(filter, decode).await;
+            res.map(map_stream_err)
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn subscribe_new_blocks(&self) -> anyhow::Result<SubscriptionStream<BlockView>> {
+        self.call_rpc(|inner| async move {
+            let res = inner.pubsub_client.subscribe_new_block().await;
+            res.map(map_stream_err)
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn subscribe_new_transactions(
+        &self,
+    ) -> anyhow::Result<SubscriptionStream<Vec<HashValue>>> {
+        self.call_rpc(|inner| async move {
+            let res = inner.pubsub_client.subscribe_new_transactions().await;
+            res.map(map_stream_err)
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn subscribe_new_mint_blocks(
+        &self,
+    ) -> anyhow::Result<SubscriptionStream<MintBlockEvent>> {
+        self.call_rpc(|inner| async move {
+            let res = inner.pubsub_client.subscribe_new_mint_block().await;
+            res.map(map_stream_err)
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn subscribe_sync_progress(
+        &self,
+    ) -> anyhow::Result<SubscriptionStream<SyncProgressReport>> {
+        self.call_rpc(|inner| async move {
+            let res = inner.pubsub_client.subscribe_sync_progress().await;
+            res.map(map_stream_err)
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn subscribe_chain_reorg(
+        &self,
+    ) -> anyhow::Result<SubscriptionStream<ChainReorgView>> {
+        self.call_rpc(|inner| async move {
+            let res = inner.pubsub_client.subscribe_chain_reorg().await;
+            res.map(map_stream_err)
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn subscribe_transaction_status(
+        &self,
+        filter: TxnStatusFilter,
+    ) -> anyhow::Result<SubscriptionStream<TxnStatusEvent>> {
+        self.call_rpc(|inner| async move {
+            let res = inner
+                .pubsub_client
+                .subscribe_transaction_status(filter)
+                .await;
+            res.map(map_stream_err)
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn subscribe_resource_changes(
+        &self,
+        filter: ResourceChangeFilter,
+    ) -> anyhow::Result<SubscriptionStream<ResourceChangeView>> {
+        self.call_rpc(|inner| async move {
+            let res = inner.pubsub_client.subscribe_resource_changes(filter).await;
+            res.map(map_stream_err)
+        })
+        .await
+        .map_err(map_err)
+    }
+
+    pub async fn call_raw_api(&self, api: &str, params: Params) -> anyhow::Result<Value> {
+        self.call_rpc(|inner| inner.raw_client.call_method(api, params))
+            .await
+            .map_err(map_err)
+    }
+
+    async fn connect(&self) -> Result<RpcClientInner, RpcError> {
+        info!(
+            "Connection is lost, try reconnect by {:?}",
+            &self.conn_source
+        );
+        let new_inner: RpcClientInner = self.conn_source.connect().await.map(|c| c.into())?;
+        let auth_token = self.auth_token.lock().clone();
+        if let Some(token) = auth_token {
+            Self::authenticate_inner(new_inner.clone(), token).await?;
+        }
+        *(self.inner.lock()) = Some(new_inner.clone());
+        if self.conn_source.support_pubsub() {
+            self.chain_watcher.do_send(StartSubscribe {
+                client: new_inner.pubsub_client.clone(),
+            });
+        }
+        Ok(new_inner)
+    }
+
+    async fn call_rpc<F, T>(
+        &self,
+        f: impl FnOnce(RpcClientInner) -> F + Send,
+    ) -> Result<T, RpcError>
+    where
+        F: std::future::Future<Output = Result<T, RpcError>> + Send,
+    {
+        let inner_opt = self.inner.lock().as_ref().cloned();
+        let inner = match inner_opt {
+            Some(inner) => inner,
+            None => self.connect().await?,
+        };
+        let result = match self.request_timeout {
+            Some(timeout) => async_std::future::timeout(timeout, f(inner))
+                .await
+                .unwrap_or_else(|_| Err(RpcError::Timeout)),
+            None => f(inner).await,
+        };
+        if let Err(RpcError::Other(e)) = &result {
+            error!("rpc error due to {}", e);
+            *(self.inner.lock()) = None;
+        }
+        result
+    }
+
+    pub fn close(self) {
+        if let Err(e) = self.chain_watcher.try_send(StopWatcher) {
+            error!("Try to stop chain watcher error: {:?}", e);
+        }
+        if let Err(e) = self.watcher_handle.join() {
+            error!("Wait chain watcher thread stop error: {:?}", e);
+        }
+    }
+}
+
+fn map_stream_err<T>(stream: TypedSubscriptionStream<T>) -> SubscriptionStream<T> {
+    stream.map_err(map_err as fn(RpcError) -> anyhow::Error)
+}
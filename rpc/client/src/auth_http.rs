@@ -0,0 +1,118 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2
+
+use anyhow::ensure;
+use futures::channel::mpsc;
+use futures::{FutureExt, SinkExt, StreamExt};
+use hyper::client::HttpConnector;
+use hyper::{header, Body, Client, Request, Uri};
+use hyper_tls::HttpsConnector;
+use jsonrpc_client_transports::RpcError;
+use jsonrpc_core::{Call, Error, ErrorCode, Failure, Output, Request as RpcRequest, Response};
+use jsonrpc_core_client::{transports::duplex, RpcChannel};
+use parking_lot::Mutex;
+use starcoin_logger::prelude::*;
+use std::sync::Arc;
+
+type HttpClient = Client<HttpsConnector<HttpConnector>>;
+
+/// Connect to a `http://` or `https://` server. Every request is sent with the
+/// `Authorization: Bearer` header when `auth_token` is set, http is not session based, so the
+/// token can not be bound to the connection by `rpc.authenticate`.
+pub(crate) async fn connect(
+    url: &str,
+    auth_token: Arc<Mutex<Option<String>>>,
+) -> Result<RpcChannel, RpcError> {
+    let uri: Uri = url.parse().map_err(|e| RpcError::Other(Box::new(e)))?;
+    let client: HttpClient = Client::builder().build(HttpsConnector::new());
+    let (request_tx, mut request_rx) = mpsc::unbounded::<String>();
+    let (response_tx, response_rx) = mpsc::unbounded::<String>();
+    tokio::spawn(async move {
+        while let Some(request) = request_rx.next().await {
+            let client = client.clone();
+            let uri = uri.clone();
+            let auth_token = auth_token.lock().clone();
+            let response_tx = response_tx.clone();
+            tokio::spawn(async move {
+                let response = match post(&client, uri, auth_token, request.clone()).await {
+                    Ok(response) => response,
+                    Err(e) => {
+                        error!("Http rpc request error: {:?}", e);
+                        // only fail the call of this request, the other calls keep going.
+                        failure_response(request.as_str(), e.to_string()).unwrap_or_default()
+                    }
+                };
+                // a notification has no response.
+                if !response.is_empty() {
+                    let _ = response_tx.unbounded_send(response);
+                }
+            });
+        }
+    });
+    let sink = request_tx.sink_map_err(|e| RpcError::Other(Box::new(e)));
+    let (rpc_client, sender) = duplex(Box::pin(sink), Box::pin(response_rx));
+    tokio::spawn(rpc_client.map(|result| {
+        if let Err(e) = result {
+            error!("Http rpc connection error: {:?}", e);
+        }
+    }));
+    Ok(sender)
+}
+
+/// Build the failure response of the request, return `None` if the request has no id.
+fn failure_response(request: &str, message: String) -> Option<String> {
+    // the duplex client sends every call as a single request.
+    let call = match serde_json::from_str::<RpcRequest>(request).ok()? {
+        RpcRequest::Single(Call::MethodCall(call)) => call,
+        _ => return None,
+    };
+    let output = Output::Failure(Failure {
+        jsonrpc: call.jsonrpc,
+        error: Error {
+            code: ErrorCode::InternalError,
+            message,
+            data: None,
+        },
+        id: call.id,
+    });
+    serde_json::to_string(&Response::Single(output)).ok()
+}
+
+async fn post(
+    client: &HttpClient,
+    uri: Uri,
+    auth_token: Option<String>,
+    request: String,
+) -> anyhow::Result<String> {
+    let mut builder = Request::post(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::ACCEPT, "application/json");
+    if let Some(token) = auth_token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let response = client.request(builder.body(Body::from(request))?).await?;
+    ensure!(
+        response.status().is_success(),
+        "Http rpc request failed, status: {}",
+        response.status()
+    );
+    let body = hyper::body::to_bytes(response.into_body()).await?;
+    Ok(String::from_utf8(body.to_vec())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failure_response() {
+        let request = r#"{"jsonrpc":"2.0","method":"chain.info","params":[],"id":3}"#;
+        let response = failure_response(request, "connection refused".to_string()).unwrap();
+        let response: serde_json::Value = serde_json::from_str(response.as_str()).unwrap();
+        assert_eq!(response["id"], 3);
+        assert_eq!(response["error"]["message"], "connection refused");
+
+        let notification = r#"{"jsonrpc":"2.0","method":"chain.info","params":[]}"#;
+        assert!(failure_response(notification, "connection refused".to_string()).is_none());
+    }
+}
@@ -1,14 +1,10 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
-use crate::chain_watcher::{ChainWatcher, StartSubscribe, WatchBlock, WatchTxn};
+pub use crate::async_client::{AsyncRpcClient, SubscriptionStream};
 use crate::pubsub_client::PubSubClient;
-pub use crate::remote_state_reader::{RemoteStateReader, StateRootOption};
-use actix::{Addr, System};
+pub use crate::remote_state_reader::{AsyncRemoteStateReader, RemoteStateReader, StateRootOption};
 use anyhow::anyhow;
-use bcs_ext::BCSCodec;
-use futures::channel::oneshot;
-use futures::{TryStream, TryStreamExt};
 use jsonrpc_client_transports::RawClient;
 pub use jsonrpc_core::Params;
 use jsonrpc_core_client::{transports::ipc, transports::ws, RpcChannel};
@@ -23,27 +19,25 @@ use starcoin_logger::{prelude::*, LogPattern};
 use starcoin_rpc_api::chain::{GetBlockOption, GetEventOption, GetTransactionOption};
 use starcoin_rpc_api::node::NodeInfo;
 use starcoin_rpc_api::service::RpcAsyncService;
-use starcoin_rpc_api::state::{
-    GetCodeOption, GetResourceOption, ListCodeOption, ListResourceOption,
-};
-use starcoin_rpc_api::types::pubsub::EventFilter;
+use starcoin_rpc_api::types::pubsub::{EventFilter, ResourceChangeFilter, TxnStatusFilter};
 use starcoin_rpc_api::types::{
-    AccountStateSetView, AnnotatedMoveStructView, BlockHeaderView, BlockView, ChainId,
-    ChainInfoView, CodeView, ContractCall, DecodedMoveValue, DryRunOutputView,
-    DryRunTransactionRequest, FactoryAction, FunctionIdView, ListCodeView, ListResourceView,
-    MintedBlockView, ModuleIdView, PeerInfoView, ResourceView, SignedMessageView,
-    SignedUserTransactionView, StateWithProofView, StrView, StructTagView,
-    TransactionEventResponse, TransactionInfoView, TransactionRequest, TransactionView,
+    AccountStateSetView, AnnotatedMoveStructView, BlockHeaderView, BlockReplayView, BlockView,
+    ChainId, ChainInfoView, ChainReorgView, CodeView, ContractCall, DecodedMoveValue,
+    DryRunOutputView, DryRunTransactionRequest, FactoryAction, FunctionIdView, GasEstimateView,
+    ListCodeView, ListResourceView, MintedBlockView, ModuleIdView, PeerInfoView,
+    ResourceChangeView, ResourceView, RpcAuthKeyView, SignedMessageView, SignedUserTransactionView,
+    SimulateBundleRequest, StateWithProofView, StrView, StructTagView, TransactionEventResponse,
+    TransactionInfoView, TransactionRequest, TransactionTraceView, TransactionView,
 };
 use starcoin_rpc_api::{
     account::AccountClient, chain::ChainClient, contract_api::ContractClient, debug::DebugClient,
     miner::MinerClient, network_manager::NetworkManagerClient, node::NodeClient,
     node_manager::NodeManagerClient, state::StateClient, sync_manager::SyncManagerClient,
-    txpool::TxPoolClient, types::TransactionEventView,
+    txpool::TxPoolClient, txpool_manager::TxPoolManagerClient, types::TransactionEventView,
 };
 use starcoin_service_registry::{ServiceInfo, ServiceStatus};
 use starcoin_sync_api::{PeerScoreResponse, SyncProgressReport};
-use starcoin_txpool_api::TxPoolStatus;
+use starcoin_txpool_api::{GasPriceStats, SenderTxnsInspect, TxPoolStatus, TxnStatusEvent};
 use starcoin_types::access_path::AccessPath;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_state::AccountState;
@@ -52,110 +46,107 @@ use starcoin_types::peer_info::{Multiaddr, PeerId};
 use starcoin_types::sign_message::SigningMessage;
 use starcoin_types::sync_status::SyncStatus;
 use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::transaction::authenticator::TransactionAuthenticator;
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
 use starcoin_vm_types::language_storage::{ModuleId, StructTag};
 use starcoin_vm_types::token::token_code::TokenCode;
 use starcoin_vm_types::transaction::DryRunTransaction;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::thread::JoinHandle;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::runtime::Runtime;
 
+mod async_client;
+mod auth_http;
 pub mod chain_watcher;
 mod pubsub_client;
 mod remote_state_reader;
+mod tls_ws;
 
 #[derive(Clone)]
 enum ConnSource {
     Ipc(PathBuf),
     WebSocket(String),
+    /// wss url and the CA certificate which signs the server certificate.
+    WebSocketWithCaCert(String, PathBuf),
+    /// http url and the token sent with every request.
+    Http(String, Arc<Mutex<Option<String>>>),
     Local(Box<RpcChannel>),
 }
 
+impl ConnSource {
+    /// Http connection does not support subscription.
+    fn support_pubsub(&self) -> bool {
+        !matches!(self, ConnSource::Http(..))
+    }
+
+    async fn connect(&self) -> Result<RpcChannel, jsonrpc_client_transports::RpcError> {
+        match self.clone() {
+            ConnSource::Ipc(sock_path) => ipc::connect(sock_path).await,
+            ConnSource::WebSocket(url) => ws::try_connect(url.as_str())?.await,
+            ConnSource::WebSocketWithCaCert(url, ca_cert) => {
+                tls_ws::connect_with_ca_cert(url.as_str(), ca_cert.as_path()).await
+            }
+            ConnSource::Http(url, auth_token) => auth_http::connect(url.as_str(), auth_token).await,
+            ConnSource::Local(channel) => Ok(*channel),
+        }
+    }
+}
+
 impl std::fmt::Debug for ConnSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ConnSource::Ipc(path) => write!(f, "Ipc({})", path.as_path().to_string_lossy()),
-            ConnSource::WebSocket(url) => write!(f, "WebSocket({})", url),
+            ConnSource::WebSocket(url) | ConnSource::WebSocketWithCaCert(url, _) => {
+                write!(f, "WebSocket({})", url)
+            }
+            ConnSource::Http(url, _) => write!(f, "Http({})", url),
             ConnSource::Local(_) => write!(f, "Local"),
         }
     }
 }
 
+/// The blocking rpc client, every call is run on the client's own runtime by `AsyncRpcClient`.
 pub struct RpcClient {
-    inner: Mutex<Option<RpcClientInner>>,
-    provider: ConnectionProvider,
-    chain_watcher: Addr<ChainWatcher>,
-    //hold the watch thread handle.
-    watcher_handle: JoinHandle<()>,
-}
-
-struct ConnectionProvider {
-    conn_source: ConnSource,
     runtime: Mutex<Runtime>,
-}
-
-impl ConnectionProvider {
-    fn new(conn_source: ConnSource, runtime: Runtime) -> Self {
-        Self {
-            conn_source,
-            runtime: Mutex::new(runtime),
-        }
-    }
-
-    fn block_on<F>(&self, future: F) -> F::Output
-    where
-        F: futures::Future + std::marker::Send,
-        F::Output: std::marker::Send,
-    {
-        self.runtime.lock().block_on(future)
-    }
-
-    fn get_rpc_channel(&self) -> anyhow::Result<RpcChannel, jsonrpc_client_transports::RpcError> {
-        self.block_on(async { self.get_rpc_channel_async().await })
-    }
-
-    async fn get_rpc_channel_async(
-        &self,
-    ) -> anyhow::Result<RpcChannel, jsonrpc_client_transports::RpcError> {
-        match self.conn_source.clone() {
-            ConnSource::Ipc(sock_path) => ipc::connect(sock_path).await,
-            ConnSource::WebSocket(url) => ws::try_connect(url.as_str())?.await,
-            ConnSource::Local(channel) => Ok(*channel),
-        }
-    }
+    client: AsyncRpcClient,
 }
 
 impl RpcClient {
-    pub(crate) fn new(conn_source: ConnSource) -> anyhow::Result<Self> {
-        let (tx, rx) = oneshot::channel();
-        let provider = ConnectionProvider::new(conn_source, Runtime::new()?);
-        let inner: RpcClientInner = provider.get_rpc_channel().map_err(map_err)?.into(); //Self::create_client_inner(conn_source.clone()).map_err(map_err)?;
-        let pubsub_client = inner.pubsub_client.clone();
-        let handle = std::thread::spawn(move || {
-            let sys = System::new("client-actix-system");
-            let watcher = ChainWatcher::launch();
-
-            tx.send(watcher).unwrap();
-            let _ = sys.run();
-        });
-        let watcher = futures::executor::block_on(rx).expect("Init chain watcher fail.");
-        watcher.do_send(StartSubscribe {
-            client: pubsub_client,
-        });
+    fn new(conn_source: ConnSource) -> anyhow::Result<Self> {
+        let mut runtime = Runtime::new()?;
+        let client = runtime.block_on(AsyncRpcClient::new(conn_source))?;
         Ok(Self {
-            inner: Mutex::new(Some(inner)),
-            provider,
-            chain_watcher: watcher,
-            watcher_handle: handle,
+            runtime: Mutex::new(runtime),
+            client,
         })
     }
 
+    /// Connect by `ws://` or `wss://` url, the server certificate of wss is verified by system CA.
     pub fn connect_websocket(url: &str) -> anyhow::Result<Self> {
         Self::new(ConnSource::WebSocket(url.to_string()))
     }
 
+    /// Connect by `wss://` url, the server certificate is verified by the PEM `ca_cert`.
+    pub fn connect_websocket_with_ca_cert<P: AsRef<Path>>(
+        url: &str,
+        ca_cert: P,
+    ) -> anyhow::Result<Self> {
+        Self::new(ConnSource::WebSocketWithCaCert(
+            url.to_string(),
+            ca_cert.as_ref().to_path_buf(),
+        ))
+    }
+
+    /// Connect by `http://` or `https://` url, subscription and watch are not supported by http.
+    pub fn connect_http(url: &str) -> anyhow::Result<Self> {
+        Self::new(ConnSource::Http(
+            url.to_string(),
+            Arc::new(Mutex::new(None)),
+        ))
+    }
+
     pub fn connect_local<S>(rpc_service: S) -> anyhow::Result<Self>
     where
         S: RpcAsyncService,
@@ -169,124 +160,206 @@ impl RpcClient {
         Self::new(ConnSource::Ipc(path))
     }
 
+    /// See `AsyncRpcClient::with_request_timeout`.
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.client = self.client.with_request_timeout(timeout);
+        self
+    }
+
+    /// The async client which this client is built on.
+    pub fn async_client(&self) -> &AsyncRpcClient {
+        &self.client
+    }
+
+    fn block_on<F: futures::Future>(&self, future: F) -> F::Output {
+        self.runtime.lock().block_on(future)
+    }
+
     pub fn watch_txn(
         &self,
         txn_hash: HashValue,
         timeout: Option<Duration>,
     ) -> anyhow::Result<chain_watcher::ThinHeadBlock> {
-        let chain_watcher = self.chain_watcher.clone();
-        let f = async move {
-            let r = chain_watcher.send(WatchTxn { txn_hash }).await?;
-            match timeout {
-                Some(t) => async_std::future::timeout(t, r).await??,
-                None => r.await?,
-            }
-        };
-        futures::executor::block_on(f)
+        futures::executor::block_on(self.client.watch_txn(txn_hash, timeout))
     }
 
     pub fn watch_block(
         &self,
         block_number: BlockNumber,
     ) -> anyhow::Result<chain_watcher::ThinHeadBlock> {
-        let chain_watcher = self.chain_watcher.clone();
-        let f = async move {
-            let r = chain_watcher.send(WatchBlock(block_number)).await?;
-            r.await?
-        };
-        futures::executor::block_on(f)
+        futures::executor::block_on(self.client.watch_block(block_number))
     }
 
-    pub fn node_status(&self) -> anyhow::Result<bool> {
-        self.call_rpc_blocking(|inner| inner.node_client.status())
-            .map_err(map_err)
+    /// Authenticate the connection by api key or jwt token, return the authenticated identity.
+    /// The connection is authenticated again by the token after reconnect.
+    pub fn authenticate(&self, token: String) -> anyhow::Result<String> {
+        self.block_on(self.client.authenticate(token))
     }
 
-    pub fn node_info(&self) -> anyhow::Result<NodeInfo> {
-        self.call_rpc_blocking(|inner| inner.node_client.info())
-            .map_err(map_err)
+    pub fn state_reader(
+        &self,
+        state_root_opt: StateRootOption,
+    ) -> anyhow::Result<RemoteStateReader> {
+        RemoteStateReader::new(self, state_root_opt)
     }
 
     pub async fn node_info_async(&self) -> anyhow::Result<NodeInfo> {
-        self.call_rpc_async(|inner| inner.node_client.info())
-            .await
-            .map_err(map_err)
+        self.client.node_info().await
+    }
+
+    pub async fn miner_submit_async(
+        &self,
+        minting_blob: String,
+        nonce: u32,
+        extra: String,
+    ) -> anyhow::Result<MintedBlockView> {
+        self.client.miner_submit(minting_blob, nonce, extra).await
+    }
+
+    pub fn subscribe_events(
+        &self,
+        filter: EventFilter,
+        decode: bool,
+    ) -> anyhow::Result<SubscriptionStream<TransactionEventView>> {
+        self.block_on(self.client.subscribe_events(filter, decode))
+    }
+
+    pub fn subscribe_new_blocks(&self) -> anyhow::Result<SubscriptionStream<BlockView>> {
+        self.block_on(self.client.subscribe_new_blocks())
+    }
+
+    pub fn subscribe_new_transactions(&self) -> anyhow::Result<SubscriptionStream<Vec<HashValue>>> {
+        self.block_on(self.client.subscribe_new_transactions())
+    }
+
+    pub fn subscribe_new_mint_blocks(&self) -> anyhow::Result<SubscriptionStream<MintBlockEvent>> {
+        self.block_on(self.client.subscribe_new_mint_blocks())
+    }
+
+    pub fn subscribe_sync_progress(
+        &self,
+    ) -> anyhow::Result<SubscriptionStream<SyncProgressReport>> {
+        self.block_on(self.client.subscribe_sync_progress())
+    }
+
+    pub fn subscribe_chain_reorg(&self) -> anyhow::Result<SubscriptionStream<ChainReorgView>> {
+        self.block_on(self.client.subscribe_chain_reorg())
+    }
+
+    pub fn subscribe_transaction_status(
+        &self,
+        filter: TxnStatusFilter,
+    ) -> anyhow::Result<SubscriptionStream<TxnStatusEvent>> {
+        self.block_on(self.client.subscribe_transaction_status(filter))
+    }
+
+    pub fn subscribe_resource_changes(
+        &self,
+        filter: ResourceChangeFilter,
+    ) -> anyhow::Result<SubscriptionStream<ResourceChangeView>> {
+        self.block_on(self.client.subscribe_resource_changes(filter))
+    }
+
+    pub async fn subscribe_new_mint_blocks_async(
+        &self,
+    ) -> anyhow::Result<SubscriptionStream<MintBlockEvent>> {
+        self.client.subscribe_new_mint_blocks().await
+    }
+
+    pub fn call_raw_api(&self, api: &str, params: Params) -> anyhow::Result<Value> {
+        self.block_on(self.client.call_raw_api(api, params))
+    }
+
+    pub fn close(self) {
+        self.client.close()
+    }
+
+    pub fn node_status(&self) -> anyhow::Result<bool> {
+        self.block_on(self.client.node_status())
+    }
+
+    pub fn node_info(&self) -> anyhow::Result<NodeInfo> {
+        self.block_on(self.client.node_info())
     }
 
     pub fn node_metrics(&self) -> anyhow::Result<HashMap<String, String>> {
-        self.call_rpc_blocking(|inner| inner.node_client.metrics())
-            .map_err(map_err)
+        self.block_on(self.client.node_metrics())
     }
 
     pub fn node_peers(&self) -> anyhow::Result<Vec<PeerInfoView>> {
-        self.call_rpc_blocking(|inner| inner.node_client.peers())
-            .map_err(map_err)
+        self.block_on(self.client.node_peers())
     }
 
     pub fn node_list_service(&self) -> anyhow::Result<Vec<ServiceInfo>> {
-        self.call_rpc_blocking(|inner| inner.node_manager_client.list_service())
-            .map_err(map_err)
+        self.block_on(self.client.node_list_service())
     }
 
     pub fn node_start_service(&self, service_name: String) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.node_manager_client.start_service(service_name))
-            .map_err(map_err)
+        self.block_on(self.client.node_start_service(service_name))
     }
 
     pub fn node_check_service(&self, service_name: String) -> anyhow::Result<ServiceStatus> {
-        self.call_rpc_blocking(|inner| inner.node_manager_client.check_service(service_name))
-            .map_err(map_err)
+        self.block_on(self.client.node_check_service(service_name))
     }
 
     pub fn node_stop_service(&self, service_name: String) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.node_manager_client.stop_service(service_name))
-            .map_err(map_err)
+        self.block_on(self.client.node_stop_service(service_name))
     }
 
     pub fn node_shutdown_system(&self) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.node_manager_client.shutdown_system())
-            .map_err(map_err)
+        self.block_on(self.client.node_shutdown_system())
     }
 
     pub fn node_reset(&self, block_hash: HashValue) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.node_manager_client.reset_to_block(block_hash))
-            .map_err(map_err)
+        self.block_on(self.client.node_reset(block_hash))
     }
 
     pub fn node_re_execute_block(&self, block_id: HashValue) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.node_manager_client.re_execute_block(block_id))
-            .map_err(map_err)
+        self.block_on(self.client.node_re_execute_block(block_id))
     }
 
     pub fn node_delete_block(&self, block_id: HashValue) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.node_manager_client.delete_block(block_id))
-            .map_err(map_err)
+        self.block_on(self.client.node_delete_block(block_id))
     }
 
     pub fn node_delete_failed_block(&self, block_id: HashValue) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.node_manager_client.delete_failed_block(block_id))
-            .map_err(map_err)
+        self.block_on(self.client.node_delete_failed_block(block_id))
+    }
+
+    pub fn node_list_rpc_auth_keys(&self) -> anyhow::Result<Vec<RpcAuthKeyView>> {
+        self.block_on(self.client.node_list_rpc_auth_keys())
+    }
+
+    pub fn node_add_rpc_auth_key(
+        &self,
+        name: String,
+        kind: String,
+        apis: Option<String>,
+        methods: Option<Vec<String>>,
+    ) -> anyhow::Result<String> {
+        self.block_on(self.client.node_add_rpc_auth_key(name, kind, apis, methods))
+    }
+
+    pub fn node_remove_rpc_auth_key(&self, name: String) -> anyhow::Result<bool> {
+        self.block_on(self.client.node_remove_rpc_auth_key(name))
     }
 
     pub fn next_sequence_number_in_txpool(
         &self,
         address: AccountAddress,
     ) -> anyhow::Result<Option<u64>> {
-        self.call_rpc_blocking(|inner| inner.txpool_client.next_sequence_number(address))
-            .map_err(map_err)
+        self.block_on(self.client.next_sequence_number_in_txpool(address))
     }
 
     pub fn submit_transaction(&self, txn: SignedUserTransaction) -> anyhow::Result<HashValue> {
-        self.call_rpc_blocking(|inner| inner.txpool_client.submit_transaction(txn))
-            .map_err(map_err)
+        self.block_on(self.client.submit_transaction(txn))
     }
 
     pub fn get_pending_txn_by_hash(
         &self,
         txn_hash: HashValue,
     ) -> anyhow::Result<Option<SignedUserTransactionView>> {
-        self.call_rpc_blocking(|inner| inner.txpool_client.pending_txn(txn_hash))
-            .map_err(map_err)
+        self.block_on(self.client.get_pending_txn_by_hash(txn_hash))
     }
 
     pub fn get_pending_txns_of_sender(
@@ -294,35 +367,62 @@ impl RpcClient {
         sender: AccountAddress,
         max_len: Option<u32>,
     ) -> anyhow::Result<Vec<SignedUserTransactionView>> {
-        self.call_rpc_blocking(|inner| inner.txpool_client.pending_txns(sender, max_len))
-            .map_err(map_err)
+        self.block_on(self.client.get_pending_txns_of_sender(sender, max_len))
+    }
+
+    pub fn txpool_remove_txn(
+        &self,
+        txn_hash: HashValue,
+    ) -> anyhow::Result<Option<SignedUserTransactionView>> {
+        self.block_on(self.client.txpool_remove_txn(txn_hash))
+    }
+
+    pub fn txpool_inspect(
+        &self,
+        sender: Option<AccountAddress>,
+    ) -> anyhow::Result<Vec<SenderTxnsInspect>> {
+        self.block_on(self.client.txpool_inspect(sender))
+    }
+
+    pub fn txpool_clear_sender(&self, sender: AccountAddress) -> anyhow::Result<Vec<HashValue>> {
+        self.block_on(self.client.txpool_clear_sender(sender))
+    }
+
+    pub fn txpool_pending_all(
+        &self,
+        offset: Option<u64>,
+        limit: Option<u64>,
+    ) -> anyhow::Result<Vec<SignedUserTransactionView>> {
+        self.block_on(self.client.txpool_pending_all(offset, limit))
+    }
+
+    pub fn txpool_submit_local_transaction(
+        &self,
+        txn: SignedUserTransaction,
+    ) -> anyhow::Result<HashValue> {
+        self.block_on(self.client.txpool_submit_local_transaction(txn))
     }
 
     //TODO should split client for different api ?
     // such as  RpcClient().account().default()
     pub fn account_default(&self) -> anyhow::Result<Option<AccountInfo>> {
-        self.call_rpc_blocking(|inner| inner.account_client.default())
-            .map_err(map_err)
+        self.block_on(self.client.account_default())
     }
 
     pub fn set_default_account(&self, addr: AccountAddress) -> anyhow::Result<AccountInfo> {
-        self.call_rpc_blocking(|inner| inner.account_client.set_default_account(addr))
-            .map_err(map_err)
+        self.block_on(self.client.set_default_account(addr))
     }
 
     pub fn account_create(&self, password: String) -> anyhow::Result<AccountInfo> {
-        self.call_rpc_blocking(|inner| inner.account_client.create(password))
-            .map_err(map_err)
+        self.block_on(self.client.account_create(password))
     }
 
     pub fn account_list(&self) -> anyhow::Result<Vec<AccountInfo>> {
-        self.call_rpc_blocking(|inner| inner.account_client.list())
-            .map_err(map_err)
+        self.block_on(self.client.account_list())
     }
 
     pub fn account_get(&self, address: AccountAddress) -> anyhow::Result<Option<AccountInfo>> {
-        self.call_rpc_blocking(|inner| inner.account_client.get(address))
-            .map_err(map_err)
+        self.block_on(self.client.account_get(address))
     }
 
     /// partial sign a multisig account's txn
@@ -331,30 +431,36 @@ impl RpcClient {
         raw_txn: RawUserTransaction,
         signer_address: AccountAddress,
     ) -> anyhow::Result<SignedUserTransaction> {
-        self.call_rpc_blocking(|inner| inner.account_client.sign_txn(raw_txn, signer_address))
-            .map_err(map_err)
+        self.block_on(
+            self.client
+                .account_sign_multisig_txn(raw_txn, signer_address),
+        )
     }
 
     pub fn account_sign_txn_request(
         &self,
         txn_request: TransactionRequest,
     ) -> anyhow::Result<SignedUserTransaction> {
-        self.call_rpc_blocking(|inner| inner.account_client.sign_txn_request(txn_request))
-            .map_err(map_err)
-            .and_then(|d: String| {
-                hex::decode(d.as_str().strip_prefix("0x").unwrap_or_else(|| d.as_str()))
-                    .map_err(anyhow::Error::new)
-                    .and_then(|d| bcs_ext::from_bytes::<SignedUserTransaction>(d.as_slice()))
-            })
+        self.block_on(self.client.account_sign_txn_request(txn_request))
     }
 
     pub fn account_sign_txn(
         &self,
         raw_txn: RawUserTransaction,
     ) -> anyhow::Result<SignedUserTransaction> {
-        let signer = raw_txn.sender();
-        self.call_rpc_blocking(|inner| inner.account_client.sign_txn(raw_txn, signer))
-            .map_err(map_err)
+        self.block_on(self.client.account_sign_txn(raw_txn))
+    }
+
+    pub fn account_sign_txn_with_fee_payer(
+        &self,
+        raw_txn: RawUserTransaction,
+        fee_payer: AccountAddress,
+        signer: AccountAddress,
+    ) -> anyhow::Result<TransactionAuthenticator> {
+        self.block_on(
+            self.client
+                .account_sign_txn_with_fee_payer(raw_txn, fee_payer, signer),
+        )
     }
 
     pub fn account_sign_message(
@@ -362,8 +468,7 @@ impl RpcClient {
         signer: AccountAddress,
         message: SigningMessage,
     ) -> anyhow::Result<SignedMessageView> {
-        self.call_rpc_blocking(|inner| inner.account_client.sign(signer, message))
-            .map_err(map_err)
+        self.block_on(self.client.account_sign_message(signer, message))
     }
 
     pub fn account_change_password(
@@ -371,51 +476,37 @@ impl RpcClient {
         address: AccountAddress,
         new_password: String,
     ) -> anyhow::Result<AccountInfo> {
-        self.call_rpc_blocking(|inner| {
-            inner
-                .account_client
-                .change_account_password(address, new_password)
-        })
-        .map_err(map_err)
+        self.block_on(self.client.account_change_password(address, new_password))
     }
 
     pub fn account_lock(&self, address: AccountAddress) -> anyhow::Result<AccountInfo> {
-        self.call_rpc_blocking(|inner| inner.account_client.lock(address))
-            .map_err(map_err)
+        self.block_on(self.client.account_lock(address))
     }
+
     pub fn account_unlock(
         &self,
         address: AccountAddress,
         password: String,
         duration: std::time::Duration,
     ) -> anyhow::Result<AccountInfo> {
-        self.call_rpc_blocking(|inner| {
-            inner
-                .account_client
-                .unlock(address, password, Some(duration.as_secs() as u32))
-        })
-        .map_err(map_err)
+        self.block_on(self.client.account_unlock(address, password, duration))
     }
+
     pub fn account_export(
         &self,
         address: AccountAddress,
         password: String,
     ) -> anyhow::Result<Vec<u8>> {
-        self.call_rpc_blocking(|inner| inner.account_client.export(address, password))
-            .map_err(map_err)
+        self.block_on(self.client.account_export(address, password))
     }
+
     pub fn account_import(
         &self,
         address: AccountAddress,
         private_key: Vec<u8>,
         password: String,
     ) -> anyhow::Result<AccountInfo> {
-        self.call_rpc_blocking(|inner| {
-            inner
-                .account_client
-                .import(address, StrView(private_key), password)
-        })
-        .map_err(map_err)
+        self.block_on(self.client.account_import(address, private_key, password))
     }
 
     pub fn account_import_readonly(
@@ -423,20 +514,14 @@ impl RpcClient {
         address: AccountAddress,
         public_key: Vec<u8>,
     ) -> anyhow::Result<AccountInfo> {
-        self.call_rpc_blocking(|inner| {
-            inner
-                .account_client
-                .import_readonly(address, StrView(public_key))
-        })
-        .map_err(map_err)
+        self.block_on(self.client.account_import_readonly(address, public_key))
     }
 
     pub fn account_accepted_tokens(
         &self,
         address: AccountAddress,
     ) -> anyhow::Result<Vec<TokenCode>> {
-        self.call_rpc_blocking(|inner| inner.account_client.accepted_tokens(address))
-            .map_err(map_err)
+        self.block_on(self.client.account_accepted_tokens(address))
     }
 
     pub fn account_remove(
@@ -444,15 +529,11 @@ impl RpcClient {
         address: AccountAddress,
         password: Option<String>,
     ) -> anyhow::Result<AccountInfo> {
-        self.call_rpc_blocking(|inner| inner.account_client.remove(address, password))
-            .map_err(map_err)
+        self.block_on(self.client.account_remove(address, password))
     }
 
     pub fn get_code(&self, module_id: ModuleId) -> anyhow::Result<Option<String>> {
-        let result: Option<StrView<Vec<u8>>> = self
-            .call_rpc_blocking(|inner| inner.contract_client.get_code(StrView(module_id)))
-            .map_err(map_err)?;
-        Ok(result.map(|s| s.to_string()))
+        self.block_on(self.client.get_code(module_id))
     }
 
     pub fn get_resource(
@@ -460,32 +541,18 @@ impl RpcClient {
         addr: AccountAddress,
         resource_type: StructTag,
     ) -> anyhow::Result<Option<AnnotatedMoveStructView>> {
-        self.call_rpc_blocking(|inner| {
-            inner
-                .contract_client
-                .get_resource(addr, StrView(resource_type))
-        })
-        .map_err(map_err)
-    }
-
-    pub fn state_reader(
-        &self,
-        state_root_opt: StateRootOption,
-    ) -> anyhow::Result<RemoteStateReader> {
-        RemoteStateReader::new(self, state_root_opt)
+        self.block_on(self.client.get_resource(addr, resource_type))
     }
 
     pub fn state_get(&self, access_path: AccessPath) -> anyhow::Result<Option<Vec<u8>>> {
-        self.call_rpc_blocking(|inner| inner.state_client.get(access_path))
-            .map_err(map_err)
+        self.block_on(self.client.state_get(access_path))
     }
 
     pub fn state_get_with_proof(
         &self,
         access_path: AccessPath,
     ) -> anyhow::Result<StateWithProofView> {
-        self.call_rpc_blocking(|inner| inner.state_client.get_with_proof(access_path))
-            .map_err(map_err)
+        self.block_on(self.client.state_get_with_proof(access_path))
     }
 
     pub fn state_get_with_proof_by_root(
@@ -493,25 +560,21 @@ impl RpcClient {
         access_path: AccessPath,
         state_root: HashValue,
     ) -> anyhow::Result<StateWithProofView> {
-        self.call_rpc_blocking(|inner| {
-            inner
-                .state_client
-                .get_with_proof_by_root(access_path, state_root)
-        })
-        .map_err(map_err)
+        self.block_on(
+            self.client
+                .state_get_with_proof_by_root(access_path, state_root),
+        )
     }
 
     pub fn state_get_state_root(&self) -> anyhow::Result<HashValue> {
-        self.call_rpc_blocking(|inner| inner.state_client.get_state_root())
-            .map_err(map_err)
+        self.block_on(self.client.state_get_state_root())
     }
 
     pub fn state_get_account_state(
         &self,
         address: AccountAddress,
     ) -> anyhow::Result<Option<AccountState>> {
-        self.call_rpc_blocking(|inner| inner.state_client.get_account_state(address))
-            .map_err(map_err)
+        self.block_on(self.client.state_get_account_state(address))
     }
 
     pub fn state_get_account_state_set(
@@ -519,12 +582,7 @@ impl RpcClient {
         address: AccountAddress,
         state_root: Option<HashValue>,
     ) -> anyhow::Result<Option<AccountStateSetView>> {
-        self.call_rpc_blocking(|inner| {
-            inner
-                .state_client
-                .get_account_state_set(address, state_root)
-        })
-        .map_err(map_err)
+        self.block_on(self.client.state_get_account_state_set(address, state_root))
     }
 
     pub fn state_get_resource(
@@ -534,14 +592,10 @@ impl RpcClient {
         decode: bool,
         state_root: Option<HashValue>,
     ) -> anyhow::Result<Option<ResourceView>> {
-        self.call_rpc_blocking(|inner| {
-            inner.state_client.get_resource(
-                address,
-                StrView(resource_type),
-                Some(GetResourceOption { decode, state_root }),
-            )
-        })
-        .map_err(map_err)
+        self.block_on(
+            self.client
+                .state_get_resource(address, resource_type, decode, state_root),
+        )
     }
 
     pub fn state_list_resource(
@@ -550,12 +604,7 @@ impl RpcClient {
         decode: bool,
         state_root: Option<HashValue>,
     ) -> anyhow::Result<ListResourceView> {
-        self.call_rpc_blocking(|inner| {
-            inner
-                .state_client
-                .list_resource(address, Some(ListResourceOption { decode, state_root }))
-        })
-        .map_err(map_err)
+        self.block_on(self.client.state_list_resource(address, decode, state_root))
     }
 
     pub fn state_get_code(
@@ -564,16 +613,7 @@ impl RpcClient {
         resolve: bool,
         state_root: Option<HashValue>,
     ) -> anyhow::Result<Option<CodeView>> {
-        self.call_rpc_blocking(|inner| {
-            inner.state_client.get_code(
-                StrView(module_id),
-                Some(GetCodeOption {
-                    resolve,
-                    state_root,
-                }),
-            )
-        })
-        .map_err(map_err)
+        self.block_on(self.client.state_get_code(module_id, resolve, state_root))
     }
 
     pub fn state_list_code(
@@ -582,42 +622,29 @@ impl RpcClient {
         resolve: bool,
         state_root: Option<HashValue>,
     ) -> anyhow::Result<ListCodeView> {
-        self.call_rpc_blocking(|inner| {
-            inner.state_client.list_code(
-                address,
-                Some(ListCodeOption {
-                    resolve,
-                    state_root,
-                }),
-            )
-        })
-        .map_err(map_err)
+        self.block_on(self.client.state_list_code(address, resolve, state_root))
     }
 
     pub fn contract_call(&self, call: ContractCall) -> anyhow::Result<Vec<DecodedMoveValue>> {
-        self.call_rpc_blocking(|inner| inner.contract_client.call_v2(call))
-            .map_err(map_err)
+        self.block_on(self.client.contract_call(call))
     }
 
     pub fn contract_resolve_function(
         &self,
         function_id: FunctionIdView,
     ) -> anyhow::Result<FunctionABI> {
-        self.call_rpc_blocking(|inner| inner.contract_client.resolve_function(function_id))
-            .map_err(map_err)
+        self.block_on(self.client.contract_resolve_function(function_id))
     }
 
     pub fn contract_resolve_struct(
         &self,
         struct_tag: StructTagView,
     ) -> anyhow::Result<StructInstantiation> {
-        self.call_rpc_blocking(|inner| inner.contract_client.resolve_struct(struct_tag))
-            .map_err(map_err)
+        self.block_on(self.client.contract_resolve_struct(struct_tag))
     }
 
     pub fn contract_resolve_module(&self, module_id: ModuleIdView) -> anyhow::Result<ModuleABI> {
-        self.call_rpc_blocking(|inner| inner.contract_client.resolve_module(module_id))
-            .map_err(map_err)
+        self.block_on(self.client.contract_resolve_module(module_id))
     }
 
     pub fn debug_set_log_level(
@@ -625,50 +652,49 @@ impl RpcClient {
         logger_name: Option<String>,
         level: Level,
     ) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| {
-            inner
-                .debug_client
-                .set_log_level(logger_name, level.to_string())
-        })
-        .map_err(map_err)
+        self.block_on(self.client.debug_set_log_level(logger_name, level))
     }
 
     pub fn debug_set_log_pattern(&self, pattern: LogPattern) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.debug_client.set_log_pattern(pattern))
-            .map_err(map_err)
+        self.block_on(self.client.debug_set_log_pattern(pattern))
     }
 
     pub fn debug_panic(&self) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.debug_client.panic())
-            .map_err(map_err)
+        self.block_on(self.client.debug_panic())
     }
 
     pub fn debug_txfactory_status(&self, action: FactoryAction) -> anyhow::Result<bool> {
-        self.call_rpc_blocking(|inner| inner.debug_client.txfactory_status(action))
-            .map_err(map_err)
+        self.block_on(self.client.debug_txfactory_status(action))
+    }
+
+    pub fn debug_trace_transaction(
+        &self,
+        txn_hash: HashValue,
+    ) -> anyhow::Result<TransactionTraceView> {
+        self.block_on(self.client.debug_trace_transaction(txn_hash))
+    }
+
+    pub fn debug_replay_block(&self, block_hash: HashValue) -> anyhow::Result<BlockReplayView> {
+        self.block_on(self.client.debug_replay_block(block_hash))
     }
 
     pub fn sleep(&self, time: u64) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.debug_client.sleep(time))
-            .map_err(map_err)
+        self.block_on(self.client.sleep(time))
     }
 
     pub fn chain_id(&self) -> anyhow::Result<ChainId> {
-        self.call_rpc_blocking(|inner| inner.chain_client.id())
-            .map_err(map_err)
+        self.block_on(self.client.chain_id())
     }
 
     pub fn chain_info(&self) -> anyhow::Result<ChainInfoView> {
-        self.call_rpc_blocking(|inner| inner.chain_client.info())
-            .map_err(map_err)
+        self.block_on(self.client.chain_info())
     }
 
     pub fn get_headers(
         &self,
         block_hashes: Vec<HashValue>,
     ) -> anyhow::Result<Vec<BlockHeaderView>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_headers(block_hashes))
-            .map_err(map_err)
+        self.block_on(self.client.get_headers(block_hashes))
     }
 
     pub fn chain_get_block_by_hash(
@@ -676,8 +702,7 @@ impl RpcClient {
         hash: HashValue,
         option: Option<GetBlockOption>,
     ) -> anyhow::Result<Option<BlockView>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_block_by_hash(hash, option))
-            .map_err(map_err)
+        self.block_on(self.client.chain_get_block_by_hash(hash, option))
     }
 
     pub fn chain_get_block_by_number(
@@ -685,16 +710,14 @@ impl RpcClient {
         number: BlockNumber,
         option: Option<GetBlockOption>,
     ) -> anyhow::Result<Option<BlockView>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_block_by_number(number, option))
-            .map_err(map_err)
+        self.block_on(self.client.chain_get_block_by_number(number, option))
     }
 
     pub fn chain_get_block_info_by_number(
         &self,
         number: BlockNumber,
     ) -> anyhow::Result<Option<BlockInfo>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_block_info_by_number(number))
-            .map_err(map_err)
+        self.block_on(self.client.chain_get_block_info_by_number(number))
     }
 
     pub fn chain_get_blocks_by_number(
@@ -702,8 +725,7 @@ impl RpcClient {
         number: Option<BlockNumber>,
         count: u64,
     ) -> anyhow::Result<Vec<BlockView>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_blocks_by_number(number, count))
-            .map_err(map_err)
+        self.block_on(self.client.chain_get_blocks_by_number(number, count))
     }
 
     pub fn chain_get_transaction(
@@ -711,16 +733,14 @@ impl RpcClient {
         txn_id: HashValue,
         option: Option<GetTransactionOption>,
     ) -> anyhow::Result<Option<TransactionView>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_transaction(txn_id, option))
-            .map_err(map_err)
+        self.block_on(self.client.chain_get_transaction(txn_id, option))
     }
 
     pub fn chain_get_transaction_info(
         &self,
         txn_hash: HashValue,
     ) -> anyhow::Result<Option<TransactionInfoView>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_transaction_info(txn_hash))
-            .map_err(map_err)
+        self.block_on(self.client.chain_get_transaction_info(txn_hash))
     }
 
     pub fn chain_get_events_by_txn_hash(
@@ -728,16 +748,14 @@ impl RpcClient {
         txn_hash: HashValue,
         option: Option<GetEventOption>,
     ) -> anyhow::Result<Vec<TransactionEventResponse>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_events_by_txn_hash(txn_hash, option))
-            .map_err(map_err)
+        self.block_on(self.client.chain_get_events_by_txn_hash(txn_hash, option))
     }
 
     pub fn chain_get_block_txn_infos(
         &self,
         block_id: HashValue,
     ) -> anyhow::Result<Vec<TransactionInfoView>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_block_txn_infos(block_id))
-            .map_err(map_err)
+        self.block_on(self.client.chain_get_block_txn_infos(block_id))
     }
 
     pub fn chain_get_txn_info_by_block_and_index(
@@ -745,167 +763,58 @@ impl RpcClient {
         block_id: HashValue,
         idx: u64,
     ) -> anyhow::Result<Option<TransactionInfoView>> {
-        self.call_rpc_blocking(|inner| {
-            inner
-                .chain_client
-                .get_txn_info_by_block_and_index(block_id, idx)
-        })
-        .map_err(map_err)
+        self.block_on(
+            self.client
+                .chain_get_txn_info_by_block_and_index(block_id, idx),
+        )
     }
 
     pub fn dry_run(&self, txn: DryRunTransactionRequest) -> anyhow::Result<DryRunOutputView> {
-        self.call_rpc_blocking(|inner| inner.contract_client.dry_run(txn))
-            .map_err(map_err)
+        self.block_on(self.client.dry_run(txn))
     }
+
     pub fn dry_run_raw(&self, txn: DryRunTransaction) -> anyhow::Result<DryRunOutputView> {
-        let DryRunTransaction {
-            raw_txn,
-            public_key,
-        } = txn;
-        let raw_txn_str = hex::encode(raw_txn.encode()?);
-        self.call_rpc_blocking(|inner| {
-            inner
-                .contract_client
-                .dry_run_raw(raw_txn_str, StrView(public_key))
-        })
-        .map_err(map_err)
+        self.block_on(self.client.dry_run_raw(txn))
     }
-    pub fn miner_submit(
+
+    pub fn estimate_gas(&self, txn: DryRunTransactionRequest) -> anyhow::Result<GasEstimateView> {
+        self.block_on(self.client.estimate_gas(txn))
+    }
+
+    pub fn simulate_bundle(
         &self,
-        minting_blob: String,
-        nonce: u32,
-        extra: String,
-    ) -> anyhow::Result<MintedBlockView> {
-        self.call_rpc_blocking(|inner| inner.miner_client.submit(minting_blob, nonce, extra))
-            .map_err(map_err)
+        bundle: SimulateBundleRequest,
+    ) -> anyhow::Result<Vec<DryRunOutputView>> {
+        self.block_on(self.client.simulate_bundle(bundle))
     }
-    pub async fn miner_submit_async(
+
+    pub fn miner_submit(
         &self,
         minting_blob: String,
         nonce: u32,
         extra: String,
     ) -> anyhow::Result<MintedBlockView> {
-        self.call_rpc_async(|inner| inner.miner_client.submit(minting_blob, nonce, extra))
-            .await
-            .map_err(map_err)
+        self.block_on(self.client.miner_submit(minting_blob, nonce, extra))
     }
 
     pub fn txpool_status(&self) -> anyhow::Result<TxPoolStatus> {
-        self.call_rpc_blocking(|inner| inner.txpool_client.state())
-            .map_err(map_err)
+        self.block_on(self.client.txpool_status())
     }
 
-    pub fn subscribe_events(
-        &self,
-        filter: EventFilter,
-        decode: bool,
-    ) -> anyhow::Result<impl TryStream<Ok = TransactionEventView, Error = anyhow::Error>> {
-        self.call_rpc_blocking(|inner| async move {
-            let res = inner.pubsub_client.subscribe_events(filter, decode).await;
-            res.map(|s| s.map_err(map_err))
-        })
-        .map_err(map_err)
-    }
-    pub fn subscribe_new_blocks(
-        &self,
-    ) -> anyhow::Result<impl TryStream<Ok = BlockView, Error = anyhow::Error>> {
-        self.call_rpc_blocking(|inner| async move {
-            let res = inner.pubsub_client.subscribe_new_block().await;
-            res.map(|s| s.map_err(map_err))
-        })
-        .map_err(map_err)
-    }
-    pub fn subscribe_new_transactions(
-        &self,
-    ) -> anyhow::Result<impl TryStream<Ok = Vec<HashValue>, Error = anyhow::Error>> {
-        self.call_rpc_blocking(|inner| async move {
-            let res = inner.pubsub_client.subscribe_new_transactions().await;
-            res.map(|s| s.map_err(map_err))
-        })
-        .map_err(map_err)
-    }
-
-    pub fn subscribe_new_mint_blocks(
-        &self,
-    ) -> anyhow::Result<impl TryStream<Ok = MintBlockEvent, Error = anyhow::Error>> {
-        self.call_rpc_blocking(|inner| async move {
-            let res = inner.pubsub_client.subscribe_new_mint_block().await;
-            res.map(|s| s.map_err(map_err))
-        })
-        .map_err(map_err)
-    }
-
-    pub async fn subscribe_new_mint_blocks_async(
-        &self,
-    ) -> anyhow::Result<impl TryStream<Ok = MintBlockEvent, Error = anyhow::Error>> {
-        self.call_rpc_async(|inner| async move {
-            let res = inner.pubsub_client.subscribe_new_mint_block().await;
-            res.map(|s| s.map_err(map_err))
-        })
-        .await
-        .map_err(map_err)
-    }
-
-    fn call_rpc_blocking<F, T>(
-        &self,
-        f: impl FnOnce(RpcClientInner) -> F + Send,
-    ) -> Result<T, jsonrpc_client_transports::RpcError>
-    where
-        T: Send,
-        F: std::future::Future<Output = Result<T, jsonrpc_client_transports::RpcError>> + Send,
-    {
-        self.provider
-            .block_on(async { self.call_rpc_async(f).await })
-    }
-
-    async fn call_rpc_async<F, T>(
-        &self,
-        f: impl FnOnce(RpcClientInner) -> F + Send,
-    ) -> Result<T, jsonrpc_client_transports::RpcError>
-    where
-        F: std::future::Future<Output = Result<T, jsonrpc_client_transports::RpcError>> + Send,
-    {
-        let inner_opt = self.inner.lock().as_ref().cloned();
-        let inner = match inner_opt {
-            Some(inner) => inner,
-            None => {
-                info!(
-                    "Connection is lost, try reconnect by {:?}",
-                    &self.provider.conn_source
-                );
-                let new_inner: RpcClientInner = self
-                    .provider
-                    .get_rpc_channel_async()
-                    .await
-                    .map(|c| c.into())?;
-                *(self.inner.lock()) = Some(new_inner.clone());
-                self.chain_watcher.do_send(StartSubscribe {
-                    client: new_inner.pubsub_client.clone(),
-                });
-                new_inner
-            }
-        };
-        let result = f(inner).await;
-        if let Err(jsonrpc_client_transports::RpcError::Other(e)) = &result {
-            error!("rpc error due to {}", e);
-            *(self.inner.lock()) = None;
-        }
-        result
+    pub fn txpool_gas_price_stats(&self) -> anyhow::Result<GasPriceStats> {
+        self.block_on(self.client.txpool_gas_price_stats())
     }
 
     pub fn sync_status(&self) -> anyhow::Result<SyncStatus> {
-        self.call_rpc_blocking(|inner| inner.sync_client.status())
-            .map_err(map_err)
+        self.block_on(self.client.sync_status())
     }
 
     pub fn sync_progress(&self) -> anyhow::Result<Option<SyncProgressReport>> {
-        self.call_rpc_blocking(|inner| inner.sync_client.progress())
-            .map_err(map_err)
+        self.block_on(self.client.sync_progress())
     }
 
     pub fn sync_peer_score(&self) -> anyhow::Result<PeerScoreResponse> {
-        self.call_rpc_blocking(|inner| inner.sync_client.peer_score())
-            .map_err(map_err)
+        self.block_on(self.client.sync_peer_score())
     }
 
     pub fn sync_start(
@@ -915,37 +824,30 @@ impl RpcClient {
         skip_pow_verify: bool,
         strategy: Option<PeerStrategy>,
     ) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| {
-            inner
-                .sync_client
-                .start(force, peers, skip_pow_verify, strategy)
-        })
-        .map_err(map_err)
+        self.block_on(
+            self.client
+                .sync_start(force, peers, skip_pow_verify, strategy),
+        )
     }
 
     pub fn sync_cancel(&self) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.sync_client.cancel())
-            .map_err(map_err)
+        self.block_on(self.client.sync_cancel())
     }
 
     pub fn network_known_peers(&self) -> anyhow::Result<Vec<PeerId>> {
-        self.call_rpc_blocking(|inner| inner.network_client.known_peers())
-            .map_err(map_err)
+        self.block_on(self.client.network_known_peers())
     }
 
     pub fn network_state(&self) -> anyhow::Result<NetworkState> {
-        self.call_rpc_blocking(|inner| inner.network_client.state())
-            .map_err(map_err)
+        self.block_on(self.client.network_state())
     }
 
     pub fn network_get_address(&self, peer_id: String) -> anyhow::Result<Vec<Multiaddr>> {
-        self.call_rpc_blocking(|inner| inner.network_client.get_address(peer_id))
-            .map_err(map_err)
+        self.block_on(self.client.network_get_address(peer_id))
     }
 
     pub fn network_add_peer(&self, peer: String) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.network_client.add_peer(peer))
-            .map_err(map_err)
+        self.block_on(self.client.network_add_peer(peer))
     }
 
     pub fn network_call_peer(
@@ -954,26 +856,7 @@ impl RpcClient {
         rpc_method: String,
         message: StrView<Vec<u8>>,
     ) -> anyhow::Result<StrView<Vec<u8>>> {
-        self.call_rpc_blocking(|inner| {
-            inner
-                .network_client
-                .call_peer(peer_id, rpc_method.into(), message)
-        })
-        .map_err(map_err)
-    }
-
-    pub fn call_raw_api(&self, api: &str, params: Params) -> anyhow::Result<Value> {
-        self.call_rpc_blocking(|inner| inner.raw_client.call_method(api, params))
-            .map_err(map_err)
-    }
-
-    pub fn close(self) {
-        if let Err(e) = self.chain_watcher.try_send(chain_watcher::StopWatcher) {
-            error!("Try to stop chain watcher error: {:?}", e);
-        }
-        if let Err(e) = self.watcher_handle.join() {
-            error!("Wait chain watcher thread stop error: {:?}", e);
-        }
+        self.block_on(self.client.network_call_peer(peer_id, rpc_method, message))
     }
 }
 
@@ -983,6 +866,7 @@ pub(crate) struct RpcClientInner {
     node_client: NodeClient,
     node_manager_client: NodeManagerClient,
     txpool_client: TxPoolClient,
+    txpool_manager_client: TxPoolManagerClient,
     account_client: AccountClient,
     state_client: StateClient,
     debug_client: DebugClient,
@@ -1001,6 +885,7 @@ impl RpcClientInner {
             node_client: channel.clone().into(),
             node_manager_client: channel.clone().into(),
             txpool_client: channel.clone().into(),
+            txpool_manager_client: channel.clone().into(),
             account_client: channel.clone().into(),
             state_client: channel.clone().into(),
             debug_client: channel.clone().into(),
@@ -3,8 +3,13 @@
 
 use jsonrpc_core_client::*;
 use starcoin_crypto::HashValue;
-use starcoin_rpc_api::types::pubsub::EventParams;
-use starcoin_rpc_api::types::{pubsub::EventFilter, pubsub::Kind, BlockView, TransactionEventView};
+use starcoin_rpc_api::types::pubsub::{EventParams, ResourceChangeFilter, TxnStatusFilter};
+use starcoin_rpc_api::types::{
+    pubsub::EventFilter, pubsub::Kind, BlockView, ChainReorgView, ResourceChangeView,
+    TransactionEventView,
+};
+use starcoin_sync_api::SyncProgressReport;
+use starcoin_txpool_api::TxnStatusEvent;
 use starcoin_types::system_events::MintBlockEvent;
 
 const STARCOIN_SUBSCRIPTION: &str = "starcoin_subscription";
@@ -76,4 +81,50 @@ impl PubSubClient {
             "MintBlockEvent",
         )
     }
+    pub async fn subscribe_sync_progress(
+        &self,
+    ) -> Result<TypedSubscriptionStream<SyncProgressReport>, RpcError> {
+        self.client.subscribe(
+            STARCOIN_SUBSCRIBE,
+            vec![Kind::SyncProgress],
+            STARCOIN_SUBSCRIPTION,
+            STARCOIN_UNSUBSCRIBE,
+            "SyncProgressReport",
+        )
+    }
+    pub async fn subscribe_transaction_status(
+        &self,
+        filter: TxnStatusFilter,
+    ) -> Result<TypedSubscriptionStream<TxnStatusEvent>, RpcError> {
+        self.client.subscribe(
+            STARCOIN_SUBSCRIBE,
+            (Kind::TransactionStatus, filter),
+            STARCOIN_SUBSCRIPTION,
+            STARCOIN_UNSUBSCRIBE,
+            "TxnStatusEvent",
+        )
+    }
+    pub async fn subscribe_resource_changes(
+        &self,
+        filter: ResourceChangeFilter,
+    ) -> Result<TypedSubscriptionStream<ResourceChangeView>, RpcError> {
+        self.client.subscribe(
+            STARCOIN_SUBSCRIBE,
+            (Kind::ResourceChanges, filter),
+            STARCOIN_SUBSCRIPTION,
+            STARCOIN_UNSUBSCRIBE,
+            "ResourceChangeView",
+        )
+    }
+    pub async fn subscribe_chain_reorg(
+        &self,
+    ) -> Result<TypedSubscriptionStream<ChainReorgView>, RpcError> {
+        self.client.subscribe(
+            STARCOIN_SUBSCRIBE,
+            vec![Kind::ChainReorg],
+            STARCOIN_SUBSCRIPTION,
+            STARCOIN_UNSUBSCRIBE,
+            "ChainReorgView",
+        )
+    }
 }
@@ -1,7 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
-use crate::RpcClient;
+use crate::{AsyncRpcClient, RpcClient};
 use anyhow::{format_err, Result};
 use starcoin_crypto::HashValue;
 use starcoin_state_api::{ChainStateReader, StateView, StateWithProof};
@@ -103,11 +103,67 @@ impl<'a> StateView for RemoteStateReader<'a> {
             .map(|v| v.0))
     }
 
-    fn multi_get(&self, _access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
-        unimplemented!()
+    /// The remote node has no batch api for states with proof, so read them one by one.
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        access_paths
+            .iter()
+            .map(|access_path| self.get(access_path))
+            .collect()
     }
 
     fn is_genesis(&self) -> bool {
         false
     }
 }
+
+/// Async version of `RemoteStateReader`, read states of a fixed state root by `AsyncRpcClient`.
+pub struct AsyncRemoteStateReader<'a> {
+    client: &'a AsyncRpcClient,
+    state_root: HashValue,
+}
+
+impl<'a> AsyncRemoteStateReader<'a> {
+    pub(crate) async fn new(
+        client: &'a AsyncRpcClient,
+        state_root_opt: StateRootOption,
+    ) -> Result<AsyncRemoteStateReader<'a>> {
+        let state_root = match state_root_opt {
+            StateRootOption::Latest => client.state_get_state_root().await?,
+            StateRootOption::BlockHash(block_hash) => {
+                let block = client
+                    .chain_get_block_by_hash(block_hash, None)
+                    .await?
+                    .ok_or_else(|| format_err!("Can not find block by hash:{}", block_hash))?;
+                block.header.state_root
+            }
+            StateRootOption::BlockNumber(block_number) => {
+                let block = client
+                    .chain_get_block_by_number(block_number, None)
+                    .await?
+                    .ok_or_else(|| format_err!("Can not find block by number: {}", block_number))?;
+                block.header.state_root
+            }
+        };
+        Ok(Self { client, state_root })
+    }
+
+    pub fn state_root(&self) -> HashValue {
+        self.state_root
+    }
+
+    pub async fn get_with_proof(&self, access_path: &AccessPath) -> Result<StateWithProof> {
+        self.client
+            .state_get_with_proof_by_root(access_path.clone(), self.state_root)
+            .await
+            .map(Into::into)
+    }
+
+    pub async fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        Ok(self
+            .client
+            .state_get_with_proof_by_root(access_path.clone(), self.state_root)
+            .await?
+            .state
+            .map(|v| v.0))
+    }
+}
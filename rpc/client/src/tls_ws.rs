@@ -0,0 +1,59 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2
+
+use futures::compat::{Future01CompatExt, Sink01CompatExt, Stream01CompatExt};
+use futures::{FutureExt, SinkExt, StreamExt};
+use jsonrpc_client_transports::RpcError;
+use jsonrpc_core_client::{transports::duplex, RpcChannel};
+use native_tls::{Certificate, TlsConnector};
+use starcoin_logger::prelude::*;
+use std::path::Path;
+use websocket::futures::Stream;
+use websocket::{ClientBuilder, OwnedMessage};
+
+fn other_err<E>(e: E) -> RpcError
+where
+    E: std::error::Error + Send + 'static,
+{
+    RpcError::Other(Box::new(e))
+}
+
+/// Connect to a `wss://` server whose certificate is signed by the PEM `ca_cert`,
+/// such as a self-signed certificate.
+pub(crate) async fn connect_with_ca_cert(
+    url: &str,
+    ca_cert: &Path,
+) -> Result<RpcChannel, RpcError> {
+    let ca_cert = std::fs::read(ca_cert).map_err(other_err)?;
+    let connector = TlsConnector::builder()
+        .add_root_certificate(Certificate::from_pem(&ca_cert).map_err(other_err)?)
+        .build()
+        .map_err(other_err)?;
+    let (client, _) = ClientBuilder::new(url)
+        .map_err(other_err)?
+        .async_connect(Some(connector))
+        .compat()
+        .await
+        .map_err(other_err)?;
+    let (sink, stream) = client.split();
+    let sink = sink
+        .sink_compat()
+        .sink_map_err(other_err)
+        .with(|msg: String| futures::future::ok::<_, RpcError>(OwnedMessage::Text(msg)));
+    let stream = stream
+        .compat()
+        .take_while(|msg| futures::future::ready(msg.is_ok()))
+        .filter_map(|msg| {
+            futures::future::ready(match msg {
+                Ok(OwnedMessage::Text(text)) => Some(text),
+                _ => None,
+            })
+        });
+    let (rpc_client, sender) = duplex(Box::pin(sink), Box::pin(stream));
+    tokio::spawn(rpc_client.map(|result| {
+        if let Err(e) = result {
+            error!("Websocket tls connection error: {:?}", e);
+        }
+    }));
+    Ok(sender)
+}
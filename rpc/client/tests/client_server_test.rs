@@ -3,10 +3,19 @@
 
 use anyhow::Result;
 use futures::{StreamExt, TryStreamExt};
-use starcoin_config::NodeConfig;
+use starcoin_config::{
+    temp_path, BuiltinNetworkID, NodeConfig, RpcAuthKey, RpcAuthKeys, RpcAuthKind, StarcoinOpt,
+};
 use starcoin_logger::prelude::*;
-use starcoin_rpc_client::RpcClient;
+use starcoin_rpc_api::types::{
+    ArgumentsView, DryRunTransactionRequest, ScriptData, StrView, TransactionArgumentView,
+    TransactionRequest, TransactionStatusView, TypeTagView,
+};
+use starcoin_rpc_client::{AsyncRpcClient, RpcClient, StateRootOption};
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_config::{account_struct_tag, genesis_address};
 use starcoin_types::system_events::MintBlockEvent;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -139,3 +148,214 @@ fn test_client_reconnect_subscribe() -> Result<()> {
     assert_ne!(events2.len(), 0);
     Ok(())
 }
+
+#[stest::test]
+fn test_client_tls() -> Result<()> {
+    let data_dir = temp_path();
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()])?;
+    let cert_file = data_dir.path().join("rpc_cert.pem");
+    let key_file = data_dir.path().join("rpc_key.pem");
+    std::fs::write(cert_file.as_path(), cert.serialize_pem()?)?;
+    std::fs::write(key_file.as_path(), cert.serialize_private_key_pem())?;
+
+    let mut opt = StarcoinOpt {
+        net: Some(BuiltinNetworkID::Test.into()),
+        base_data_dir: Some(data_dir.path().to_path_buf()),
+        ..StarcoinOpt::default()
+    };
+    opt.rpc.ws.tls_cert = Some(cert_file.clone());
+    opt.rpc.ws.tls_key = Some(key_file);
+    let config = Arc::new(NodeConfig::load_with_opt(&opt)?);
+    let ws_address = config.rpc.get_ws_address().unwrap();
+    assert_eq!(ws_address.protocol, "wss");
+    let http_address = config.rpc.get_http_address().unwrap();
+
+    let node_handle = test_helper::run_node_by_config(config)?;
+    std::thread::sleep(Duration::from_millis(300));
+
+    let url = format!("wss://localhost:{}", ws_address.port);
+    // the self-signed certificate is not trusted by system CA.
+    assert!(RpcClient::connect_websocket(url.as_str()).is_err());
+    let wss_client = RpcClient::connect_websocket_with_ca_cert(url.as_str(), cert_file)?;
+    let info = wss_client.node_info()?;
+    info!("wss_client node_info: {:?}", info);
+    // plaintext websocket is not accepted by the tls listener.
+    assert!(
+        RpcClient::connect_websocket(format!("ws://localhost:{}", ws_address.port).as_str())
+            .is_err()
+    );
+
+    let http_client =
+        RpcClient::connect_http(format!("http://localhost:{}", http_address.port).as_str())?;
+    assert_eq!(http_client.node_info()?.net, info.net);
+
+    wss_client.close();
+    http_client.close();
+    if let Err(e) = node_handle.stop() {
+        error!("node stop error: {:?}", e)
+    }
+    Ok(())
+}
+
+#[stest::test]
+fn test_client_auth() -> Result<()> {
+    let data_dir = temp_path();
+    let mut opt = StarcoinOpt {
+        net: Some(BuiltinNetworkID::Test.into()),
+        base_data_dir: Some(data_dir.path().to_path_buf()),
+        ..StarcoinOpt::default()
+    };
+    opt.rpc.http.auth = Some(true);
+    opt.rpc.ws.auth = Some(true);
+    let config = Arc::new(NodeConfig::load_with_opt(&opt)?);
+    let secret = "reader_secret".to_string();
+    RpcAuthKeys {
+        keys: vec![RpcAuthKey {
+            name: "reader".to_string(),
+            kind: RpcAuthKind::ApiKey,
+            secret: secret.clone(),
+            apis: Some("node,chain".parse()?),
+            methods: vec![],
+        }],
+    }
+    .save(config.rpc.auth_keys_file())?;
+    let http_address = config.rpc.get_http_address().unwrap();
+    let ws_address = config.rpc.get_ws_address().unwrap();
+
+    let node_handle = test_helper::run_node_by_config(config)?;
+    std::thread::sleep(Duration::from_millis(300));
+
+    // http is authenticated by the bearer token sent with every request.
+    let http_client =
+        RpcClient::connect_http(format!("http://localhost:{}", http_address.port).as_str())?;
+    assert!(http_client.node_info().is_err());
+    assert!(http_client.authenticate("invalid".to_string()).is_err());
+    assert_eq!(http_client.authenticate(secret.clone())?, "reader");
+    let info = http_client.node_info()?;
+    // the api which is not allowed by the key.
+    assert!(http_client.account_default().is_err());
+
+    // websocket session is bound to the identity by rpc.authenticate.
+    let ws_client = RpcClient::connect_websocket(ws_address.to_string().as_str())?;
+    assert!(ws_client.node_info().is_err());
+    assert_eq!(ws_client.authenticate(secret)?, "reader");
+    assert_eq!(ws_client.node_info()?.net, info.net);
+
+    http_client.close();
+    ws_client.close();
+    if let Err(e) = node_handle.stop() {
+        error!("node stop error: {:?}", e)
+    }
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+fn test_async_client() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let ipc_file = config.rpc.get_ipc_file();
+    let url = config.rpc.get_ws_address().unwrap();
+    let node_handle = test_helper::run_node_by_config(config)?;
+    std::thread::sleep(Duration::from_millis(300));
+
+    let mut rt = tokio::runtime::Runtime::new()?;
+    rt.block_on(async {
+        let ws_client = AsyncRpcClient::connect_websocket(url.to_string().as_str())
+            .await?
+            .with_request_timeout(Duration::from_secs(10));
+        let ipc_client = AsyncRpcClient::connect_ipc(ipc_file).await?;
+        let info = ws_client.node_info().await?;
+        assert_eq!(ipc_client.node_info().await?.net, info.net);
+
+        let reader = ws_client.state_reader(StateRootOption::Latest).await?;
+        let access_path = AccessPath::resource_access_path(genesis_address(), account_struct_tag());
+        assert!(reader.get(&access_path).await?.is_some());
+
+        let mut blocks = ws_client.subscribe_new_mint_blocks().await?;
+        node_handle.generate_block()?;
+        let event = blocks.try_next().await?;
+        assert!(event.is_some());
+
+        ws_client.close();
+        ipc_client.close();
+        Ok::<_, anyhow::Error>(())
+    })?;
+    if let Err(e) = node_handle.stop() {
+        error!("node stop error: {:?}", e)
+    }
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+fn test_dry_run_on_history_state() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let ipc_file = config.rpc.get_ipc_file();
+    let node_handle = test_helper::run_node_by_config(config)?;
+    std::thread::sleep(Duration::from_millis(300));
+    let client = RpcClient::connect_ipc(ipc_file).expect("connect ipc fail.");
+
+    // mint blocks to get the reward of the default account.
+    for _ in 0..3 {
+        node_handle.generate_block()?;
+    }
+    let sender = client
+        .account_default()?
+        .expect("default account should exist");
+    let transfer = |sequence_number: Option<u64>| -> Result<TransactionRequest> {
+        Ok(TransactionRequest {
+            sender: Some(sender.address),
+            sequence_number,
+            script: Some(ScriptData {
+                code: StrView::from_str("0x1::TransferScripts::peer_to_peer_v2")?,
+                type_args: vec![TypeTagView::from_str("0x1::STC::STC")?],
+                args: ArgumentsView::HumanReadable(vec![
+                    TransactionArgumentView::from_str("0x1")?,
+                    TransactionArgumentView::from_str("100u128")?,
+                ]),
+            }),
+            ..Default::default()
+        })
+    };
+    let before_txn = client.chain_info()?.head;
+    let txn = client.account_sign_txn_request(transfer(None)?)?;
+    client.submit_transaction(txn)?;
+    node_handle.generate_block()?;
+
+    let dry_run_request = |block_number, state_root| -> Result<DryRunTransactionRequest> {
+        Ok(DryRunTransactionRequest {
+            transaction: transfer(None)?,
+            sender_public_key: StrView(sender.public_key.clone()),
+            block_number,
+            state_root,
+            state_overrides: vec![],
+            trace: false,
+        })
+    };
+    // the sequence number of the sender is filled from the requested state.
+    let output = client.dry_run(dry_run_request(Some(before_txn.number.0), None)?)?;
+    assert_eq!(output.txn_output.status, TransactionStatusView::Executed);
+    let output = client.dry_run(dry_run_request(None, Some(before_txn.state_root))?)?;
+    assert_eq!(output.txn_output.status, TransactionStatusView::Executed);
+    let output = client.dry_run(dry_run_request(None, None)?)?;
+    assert_eq!(output.txn_output.status, TransactionStatusView::Executed);
+    // the latest sequence number is too new for the history state.
+    let mut request = dry_run_request(Some(before_txn.number.0), None)?;
+    request.transaction = transfer(Some(1))?;
+    let output = client.dry_run(request)?;
+    assert_ne!(output.txn_output.status, TransactionStatusView::Executed);
+
+    assert!(client
+        .dry_run(dry_run_request(
+            Some(before_txn.number.0),
+            Some(before_txn.state_root)
+        )?)
+        .is_err());
+    assert!(client
+        .estimate_gas(dry_run_request(Some(before_txn.number.0), None)?)
+        .is_ok());
+
+    client.close();
+    if let Err(e) = node_handle.stop() {
+        error!("node stop error: {:?}", e)
+    }
+    Ok(())
+}
@@ -1,14 +1,21 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
+use crate::auth::RpcAuthenticator;
+use crate::auth_middleware::RpcAuthMiddleware;
 use crate::rate_limit_middleware::JsonApiRateLimitMiddleware;
 use jsonrpc_core::{MetaIoHandler, RemoteProcedure};
 use starcoin_config::{Api, ApiQuotaConfiguration};
 use starcoin_rpc_api::metadata::Metadata;
 use starcoin_rpc_middleware::MetricMiddleware;
 use std::collections::HashMap;
+use std::sync::Arc;
 
-type Middlewares = (MetricMiddleware, JsonApiRateLimitMiddleware);
+type Middlewares = (
+    MetricMiddleware,
+    RpcAuthMiddleware,
+    JsonApiRateLimitMiddleware,
+);
 
 pub struct ApiRegistry {
     apis: HashMap<Api, MetaIoHandler<Metadata, Middlewares>>,
@@ -31,23 +38,33 @@ impl ApiRegistry {
         let io_handler = self.apis.entry(api_type).or_insert_with(|| {
             MetaIoHandler::<Metadata, Middlewares>::with_middleware((
                 MetricMiddleware,
+                RpcAuthMiddleware::new(None, HashMap::new()),
                 rate_limit_middleware,
             ))
         });
         io_handler.extend_with(apis);
     }
 
+    /// Build the handler of `api_types`, calls are authenticated if `authenticator` is some.
     pub fn get_apis(
         &self,
         api_types: impl IntoIterator<Item = Api>,
+        authenticator: Option<Arc<RpcAuthenticator>>,
     ) -> MetaIoHandler<Metadata, Middlewares> {
         let rate_limit_middleware = JsonApiRateLimitMiddleware::from_config(self.quotas.clone());
+        let method_apis = self
+            .apis
+            .iter()
+            .flat_map(|(api_type, apis)| apis.iter().map(move |(k, _)| (k.clone(), *api_type)))
+            .collect();
+        let auth_middleware = RpcAuthMiddleware::new(authenticator, method_apis);
         api_types
             .into_iter()
             .map(|api_type| self.apis.get(&api_type))
             .fold(
                 MetaIoHandler::<Metadata, Middlewares>::with_middleware((
                     MetricMiddleware,
+                    auth_middleware,
                     rate_limit_middleware,
                 )),
                 |mut init, apis| {
@@ -0,0 +1,307 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, ensure, format_err, Result};
+use hmac::{Hmac, Mac};
+use jsonrpc_pubsub::Session;
+use parking_lot::RwLock;
+use serde::Deserialize;
+use sha2::Sha256;
+use starcoin_config::{Api, ApiSet, RpcAuthKey, RpcAuthKeys, RpcAuthKind};
+use starcoin_crypto::HashValue;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Deserialize)]
+struct JwtHeader {
+    alg: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JwtClaims {
+    sub: String,
+    /// The expiration time in seconds since the unix epoch, a jwt without `exp` is rejected.
+    exp: u64,
+}
+
+/// Authenticate rpc callers by api key or HS256 jwt, and check the api permission of the
+/// authenticated identity.
+pub struct RpcAuthenticator {
+    keys_file: Option<PathBuf>,
+    keys: RwLock<Vec<RpcAuthKey>>,
+    /// identity of authenticated sessions, keyed by the session address.
+    sessions: Arc<RwLock<HashMap<usize, String>>>,
+}
+
+impl RpcAuthenticator {
+    pub fn new(keys: Vec<RpcAuthKey>) -> Self {
+        Self {
+            keys_file: None,
+            keys: RwLock::new(keys),
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Load keys from the keys file, keys changed by admin api are persisted to the file.
+    pub fn load<P: AsRef<Path>>(keys_file: P) -> Result<Self> {
+        let keys = RpcAuthKeys::load(keys_file.as_ref())?;
+        let mut authenticator = Self::new(keys.keys);
+        authenticator.keys_file = Some(keys_file.as_ref().to_path_buf());
+        Ok(authenticator)
+    }
+
+    /// List keys, the secret of key is hidden.
+    pub fn list_keys(&self) -> Vec<RpcAuthKey> {
+        self.keys
+            .read()
+            .iter()
+            .cloned()
+            .map(|mut key| {
+                key.secret = String::new();
+                key
+            })
+            .collect()
+    }
+
+    /// Add a new key with a random secret, return the secret.
+    pub fn add_key(
+        &self,
+        name: String,
+        kind: RpcAuthKind,
+        apis: Option<ApiSet>,
+        methods: Vec<String>,
+    ) -> Result<String> {
+        let mut keys = self.keys.write();
+        ensure!(
+            keys.iter().all(|key| key.name != name),
+            "Rpc auth key {} already exists",
+            name
+        );
+        let secret = HashValue::random().to_hex();
+        let mut new_keys = keys.clone();
+        new_keys.push(RpcAuthKey {
+            name,
+            kind,
+            secret: secret.clone(),
+            apis,
+            methods,
+        });
+        // persist the keys first, the keys in memory are unchanged if the save fails.
+        self.save(new_keys.as_slice())?;
+        *keys = new_keys;
+        Ok(secret)
+    }
+
+    /// Remove the key by name, return false if the key does not exist.
+    pub fn remove_key(&self, name: &str) -> Result<bool> {
+        let mut keys = self.keys.write();
+        let new_keys: Vec<RpcAuthKey> = keys
+            .iter()
+            .filter(|key| key.name != name)
+            .cloned()
+            .collect();
+        if new_keys.len() == keys.len() {
+            return Ok(false);
+        }
+        self.save(new_keys.as_slice())?;
+        *keys = new_keys;
+        self.sessions.write().retain(|_, identity| identity != name);
+        Ok(true)
+    }
+
+    fn save(&self, keys: &[RpcAuthKey]) -> Result<()> {
+        if let Some(keys_file) = self.keys_file.as_ref() {
+            RpcAuthKeys {
+                keys: keys.to_vec(),
+            }
+            .save(keys_file)?;
+        }
+        Ok(())
+    }
+
+    /// Authenticate the token, return the identity(key name) if success.
+    pub fn authenticate(&self, token: &str) -> Result<String> {
+        let token = token.trim();
+        if token.split('.').count() == 3 {
+            self.authenticate_jwt(token)
+        } else {
+            self.keys
+                .read()
+                .iter()
+                .find(|key| {
+                    key.kind == RpcAuthKind::ApiKey
+                        && constant_time_eq(key.secret.as_bytes(), token.as_bytes())
+                })
+                .map(|key| key.name.clone())
+                .ok_or_else(|| format_err!("Invalid api key"))
+        }
+    }
+
+    fn authenticate_jwt(&self, token: &str) -> Result<String> {
+        let parts: Vec<&str> = token.split('.').collect();
+        let header: JwtHeader = serde_json::from_slice(&decode_base64(parts[0])?)?;
+        ensure!(header.alg == "HS256", "Unsupported jwt alg: {}", header.alg);
+        let claims: JwtClaims = serde_json::from_slice(&decode_base64(parts[1])?)?;
+        let keys = self.keys.read();
+        let key = keys
+            .iter()
+            .find(|key| key.kind == RpcAuthKind::Jwt && key.name == claims.sub)
+            .ok_or_else(|| format_err!("Unknown jwt subject: {}", claims.sub))?;
+        let mut mac = Hmac::<Sha256>::new_varkey(key.secret.as_bytes())
+            .map_err(|e| format_err!("Invalid jwt secret: {:?}", e))?;
+        mac.input(format!("{}.{}", parts[0], parts[1]).as_bytes());
+        if mac.verify(&decode_base64(parts[2])?).is_err() {
+            bail!("Invalid jwt signature");
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        ensure!(claims.exp > now, "Jwt expired");
+        Ok(key.name.clone())
+    }
+
+    /// Bind the identity to the session, the binding is removed when the session is dropped.
+    pub fn bind_session(&self, session: &Arc<Session>, identity: String) {
+        let id = Arc::as_ptr(session) as usize;
+        let prev = self.sessions.write().insert(id, identity);
+        if prev.is_none() {
+            let sessions = self.sessions.clone();
+            session.on_drop(move || {
+                sessions.write().remove(&id);
+            });
+        }
+    }
+
+    pub fn session_identity(&self, session: &Arc<Session>) -> Option<String> {
+        let id = Arc::as_ptr(session) as usize;
+        self.sessions.read().get(&id).cloned()
+    }
+
+    /// Check whether the identity is allowed to call the method of the api.
+    pub fn is_allowed(&self, identity: &str, api: Option<Api>, method: &str) -> bool {
+        self.keys
+            .read()
+            .iter()
+            .find(|key| key.name == identity)
+            .map(|key| key.is_allowed(api, method))
+            .unwrap_or(false)
+    }
+}
+
+fn decode_base64(input: &str) -> Result<Vec<u8>> {
+    Ok(base64::decode_config(input, base64::URL_SAFE_NO_PAD)?)
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter()
+        .zip(b.iter())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jwt(secret: &str, claims: &str) -> String {
+        let header =
+            base64::encode_config(r#"{"alg":"HS256","typ":"JWT"}"#, base64::URL_SAFE_NO_PAD);
+        let claims = base64::encode_config(claims, base64::URL_SAFE_NO_PAD);
+        let mut mac = Hmac::<Sha256>::new_varkey(secret.as_bytes()).unwrap();
+        mac.input(format!("{}.{}", header, claims).as_bytes());
+        let signature = base64::encode_config(mac.result().code(), base64::URL_SAFE_NO_PAD);
+        format!("{}.{}.{}", header, claims, signature)
+    }
+
+    #[test]
+    fn test_rpc_auth() {
+        let authenticator = RpcAuthenticator::new(vec![]);
+        let api_secret = authenticator
+            .add_key(
+                "reader".to_string(),
+                RpcAuthKind::ApiKey,
+                Some("chain,node".parse().unwrap()),
+                vec!["account.list".to_string()],
+            )
+            .unwrap();
+        let jwt_secret = authenticator
+            .add_key(
+                "admin".to_string(),
+                RpcAuthKind::Jwt,
+                Some(ApiSet::All),
+                vec![],
+            )
+            .unwrap();
+
+        assert_eq!(authenticator.authenticate(&api_secret).unwrap(), "reader");
+        assert!(authenticator.authenticate("invalid").is_err());
+        // the jwt secret can not be used as api key.
+        assert!(authenticator.authenticate(&jwt_secret).is_err());
+
+        let exp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + 3600;
+        let token = jwt(
+            &jwt_secret,
+            format!(r#"{{"sub":"admin","exp":{}}}"#, exp).as_str(),
+        );
+        assert_eq!(authenticator.authenticate(&token).unwrap(), "admin");
+        // jwt without exp never expires, so it is rejected.
+        let token = jwt(&jwt_secret, r#"{"sub":"admin"}"#);
+        assert!(authenticator.authenticate(&token).is_err());
+        let token = jwt(&jwt_secret, r#"{"sub":"admin","exp":1}"#);
+        assert!(authenticator.authenticate(&token).is_err());
+        let token = jwt(
+            &api_secret,
+            format!(r#"{{"sub":"admin","exp":{}}}"#, exp).as_str(),
+        );
+        assert!(authenticator.authenticate(&token).is_err());
+        let token = jwt(
+            &api_secret,
+            format!(r#"{{"sub":"reader","exp":{}}}"#, exp).as_str(),
+        );
+        assert!(authenticator.authenticate(&token).is_err());
+
+        assert!(authenticator.is_allowed("reader", Some(Api::Chain), "chain.info"));
+        assert!(authenticator.is_allowed("reader", Some(Api::Account), "account.list"));
+        assert!(!authenticator.is_allowed("reader", Some(Api::Account), "account.sign"));
+        assert!(authenticator.is_allowed("admin", Some(Api::NodeManager), "node_manager.stop"));
+
+        assert!(authenticator
+            .list_keys()
+            .iter()
+            .all(|key| key.secret.is_empty()));
+        assert!(authenticator.remove_key("reader").unwrap());
+        assert!(!authenticator.remove_key("reader").unwrap());
+        assert!(authenticator.authenticate(&api_secret).is_err());
+    }
+    #[test]
+    fn test_rpc_auth_keys_unchanged_if_save_fails() {
+        let temp_dir = starcoin_config::temp_path();
+        let keys_file = temp_dir.path().join("rpc_auth_keys.toml");
+        let authenticator = RpcAuthenticator::load(&keys_file).unwrap();
+        let secret = authenticator
+            .add_key("reader".to_string(), RpcAuthKind::ApiKey, None, vec![])
+            .unwrap();
+
+        // the keys file can not be written after its path is taken by a directory.
+        std::fs::remove_file(&keys_file).unwrap();
+        std::fs::create_dir(&keys_file).unwrap();
+        assert!(authenticator
+            .add_key("writer".to_string(), RpcAuthKind::ApiKey, None, vec![])
+            .is_err());
+        assert!(authenticator.remove_key("reader").is_err());
+        let names: Vec<String> = authenticator
+            .list_keys()
+            .into_iter()
+            .map(|key| key.name)
+            .collect();
+        assert_eq!(names, vec!["reader".to_string()]);
+        assert_eq!(authenticator.authenticate(&secret).unwrap(), "reader");
+    }
+}
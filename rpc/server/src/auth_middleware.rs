@@ -0,0 +1,251 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::auth::RpcAuthenticator;
+use jsonrpc_core::futures::future::Either;
+use jsonrpc_core::futures::Future;
+use jsonrpc_core::middleware::NoopCallFuture;
+use jsonrpc_core::{
+    Call, Error, ErrorCode, Failure, FutureResponse, Id, Middleware, Output, Params, Success,
+    Value, Version,
+};
+use starcoin_config::Api;
+use starcoin_rpc_api::metadata::Metadata;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The method for session based endpoints(ws, tcp, ipc) to authenticate the session.
+pub const AUTHENTICATE_METHOD: &str = "rpc.authenticate";
+
+const UNAUTHORIZED_ERROR: i64 = -10001;
+const FORBIDDEN_ERROR: i64 = -10002;
+
+pub struct RpcAuthMiddleware {
+    authenticator: Option<Arc<RpcAuthenticator>>,
+    method_apis: HashMap<String, Api>,
+}
+
+impl RpcAuthMiddleware {
+    pub fn new(
+        authenticator: Option<Arc<RpcAuthenticator>>,
+        method_apis: HashMap<String, Api>,
+    ) -> Self {
+        Self {
+            authenticator,
+            method_apis,
+        }
+    }
+
+    /// The failure output of the call, a notification(the `id` is `None`) has no output.
+    fn failure(
+        jsonrpc: Option<Version>,
+        id: Option<Id>,
+        code: i64,
+        message: String,
+    ) -> Option<Output> {
+        id.map(|id| {
+            Output::Failure(Failure {
+                jsonrpc,
+                error: Error {
+                    code: ErrorCode::ServerError(code),
+                    message,
+                    data: None,
+                },
+                id,
+            })
+        })
+    }
+
+    fn authenticate_session(
+        authenticator: &RpcAuthenticator,
+        params: &Params,
+        meta: &Metadata,
+    ) -> Result<String, String> {
+        let token = match params {
+            Params::Array(values) => values.get(0).and_then(Value::as_str),
+            _ => None,
+        }
+        .ok_or_else(|| "Invalid params: expect [token]".to_string())?;
+        let identity = authenticator
+            .authenticate(token)
+            .map_err(|e| e.to_string())?;
+        if let Some(session) = meta.session.as_ref() {
+            authenticator.bind_session(session, identity.clone());
+        }
+        Ok(identity)
+    }
+}
+
+impl Middleware<Metadata> for RpcAuthMiddleware {
+    type Future = FutureResponse;
+    type CallFuture = NoopCallFuture;
+
+    fn on_call<F, X>(&self, call: Call, mut meta: Metadata, next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, Metadata) -> X + Send + Sync,
+        X: Future<Output = Option<Output>> + Send + 'static,
+    {
+        let authenticator = match self.authenticator.as_ref() {
+            Some(authenticator) => authenticator,
+            None => return Either::Right(next(call, meta)),
+        };
+        let (method, params, jsonrpc, id) = match &call {
+            Call::MethodCall(m) => (m.method.clone(), &m.params, m.jsonrpc, Some(m.id.clone())),
+            Call::Notification(n) => (n.method.clone(), &n.params, n.jsonrpc, None),
+            Call::Invalid { .. } => return Either::Right(next(call, meta)),
+        };
+
+        if method == AUTHENTICATE_METHOD {
+            let output = match Self::authenticate_session(authenticator, params, &meta) {
+                Ok(identity) => id.map(|id| {
+                    Output::Success(Success {
+                        jsonrpc,
+                        result: Value::String(identity),
+                        id,
+                    })
+                }),
+                Err(e) => Self::failure(jsonrpc, id, UNAUTHORIZED_ERROR, e),
+            };
+            return Either::Left(Box::pin(futures::future::ready(output)));
+        }
+
+        let identity = match meta.auth_token.as_ref() {
+            Some(token) => match authenticator.authenticate(token) {
+                Ok(identity) => Some(identity),
+                Err(e) => {
+                    let output = Self::failure(jsonrpc, id, UNAUTHORIZED_ERROR, e.to_string());
+                    return Either::Left(Box::pin(futures::future::ready(output)));
+                }
+            },
+            None => meta
+                .session
+                .as_ref()
+                .and_then(|session| authenticator.session_identity(session)),
+        };
+        let output = match identity {
+            None => Self::failure(
+                jsonrpc,
+                id,
+                UNAUTHORIZED_ERROR,
+                "Unauthorized, please provide an api key or jwt token".to_string(),
+            ),
+            Some(identity) => {
+                let api = self.method_apis.get(&method).cloned();
+                if authenticator.is_allowed(&identity, api, &method) {
+                    // quotas of user are counted by the authenticated identity.
+                    meta.user = Some(identity);
+                    return Either::Right(next(call, meta));
+                }
+                Self::failure(
+                    jsonrpc,
+                    id,
+                    FORBIDDEN_ERROR,
+                    format!("{} is not allowed to call {}", identity, method),
+                )
+            }
+        };
+        Either::Left(Box::pin(futures::future::ready(output)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpc_core::MetaIoHandler;
+    use jsonrpc_pubsub::Session;
+    use starcoin_config::RpcAuthKind;
+
+    fn call(
+        io: &MetaIoHandler<Metadata, RpcAuthMiddleware>,
+        request: &str,
+        meta: Metadata,
+    ) -> serde_json::Value {
+        let response = futures::executor::block_on(io.handle_request(request, meta))
+            .expect("method call should have response");
+        serde_json::from_str(response.as_str()).unwrap()
+    }
+
+    fn error_code(response: &serde_json::Value) -> Option<i64> {
+        response["error"]["code"].as_i64()
+    }
+
+    #[test]
+    fn test_auth_middleware() {
+        let authenticator = Arc::new(RpcAuthenticator::new(vec![]));
+        let secret = authenticator
+            .add_key(
+                "reader".to_string(),
+                RpcAuthKind::ApiKey,
+                Some("chain".parse().unwrap()),
+                vec![],
+            )
+            .unwrap();
+        let method_apis = vec![
+            ("chain.info".to_string(), Api::Chain),
+            ("account.sign".to_string(), Api::Account),
+        ]
+        .into_iter()
+        .collect();
+        let mut io = MetaIoHandler::with_middleware(RpcAuthMiddleware::new(
+            Some(authenticator),
+            method_apis,
+        ));
+        for method in &["chain.info", "account.sign"] {
+            io.add_method_with_meta(method, |_params, meta: Metadata| async move {
+                Ok(Value::String(meta.user.unwrap_or_default()))
+            });
+        }
+        let chain_info = r#"{"jsonrpc":"2.0","method":"chain.info","params":[],"id":1}"#;
+        let account_sign = r#"{"jsonrpc":"2.0","method":"account.sign","params":[],"id":1}"#;
+
+        // no token.
+        let response = call(&io, chain_info, Metadata::default());
+        assert_eq!(error_code(&response), Some(UNAUTHORIZED_ERROR));
+
+        // invalid token.
+        let meta = Metadata {
+            auth_token: Some("invalid".to_string()),
+            ..Metadata::default()
+        };
+        let response = call(&io, chain_info, meta);
+        assert_eq!(error_code(&response), Some(UNAUTHORIZED_ERROR));
+
+        // the token of request, such as http bearer token.
+        let meta = Metadata {
+            auth_token: Some(secret.clone()),
+            ..Metadata::default()
+        };
+        let response = call(&io, chain_info, meta.clone());
+        assert_eq!(response["result"], "reader");
+        let response = call(&io, account_sign, meta);
+        assert_eq!(error_code(&response), Some(FORBIDDEN_ERROR));
+
+        // the session is bound to the identity by rpc.authenticate.
+        let (tx, _rx) = futures::channel::mpsc::unbounded();
+        let meta = Metadata::new(Arc::new(Session::new(tx)));
+        let response = call(&io, chain_info, meta.clone());
+        assert_eq!(error_code(&response), Some(UNAUTHORIZED_ERROR));
+        let authenticate = format!(
+            r#"{{"jsonrpc":"2.0","method":"{}","params":["invalid"],"id":1}}"#,
+            AUTHENTICATE_METHOD
+        );
+        let response = call(&io, authenticate.as_str(), meta.clone());
+        assert_eq!(error_code(&response), Some(UNAUTHORIZED_ERROR));
+        let authenticate = format!(
+            r#"{{"jsonrpc":"2.0","method":"{}","params":["{}"],"id":1}}"#,
+            AUTHENTICATE_METHOD, secret
+        );
+        let response = call(&io, authenticate.as_str(), meta.clone());
+        assert_eq!(response["result"], "reader");
+        let response = call(&io, chain_info, meta.clone());
+        assert_eq!(response["result"], "reader");
+        let response = call(&io, account_sign, meta);
+        assert_eq!(error_code(&response), Some(FORBIDDEN_ERROR));
+
+        // notifications have no response, even if they are rejected.
+        let notification = r#"{"jsonrpc":"2.0","method":"chain.info","params":[]}"#;
+        let response =
+            futures::executor::block_on(io.handle_request(notification, Metadata::default()));
+        assert_eq!(response, None);
+    }
+}
@@ -14,23 +14,35 @@ pub struct RpcExtractor {
 }
 
 impl jsonrpc_http_server::MetaExtractor<Metadata> for RpcExtractor {
-    fn read_metadata(&self, _req: &hyper::Request<hyper::Body>) -> Metadata {
-        let mut client_ip = None;
-        for header in self.http_ip_headers.iter() {
-            if let Some(v) = _req.headers().get(header) {
-                if let Ok(s) = v.to_str() {
-                    // if it's an valid ip.
-                    if let Some(Ok(ip)) = s.split(',').next().map(|s| s.trim().parse::<IpAddr>()) {
-                        client_ip = Some(ip);
-                    }
+    fn read_metadata(&self, req: &hyper::Request<hyper::Body>) -> Metadata {
+        read_http_metadata(self.http_ip_headers.as_slice(), req.headers())
+    }
+}
+
+/// Read the client ip from `ip_headers` and the bearer token from the Authorization header.
+pub(crate) fn read_http_metadata(ip_headers: &[String], headers: &hyper::HeaderMap) -> Metadata {
+    let mut client_ip = None;
+    for header in ip_headers.iter() {
+        if let Some(v) = headers.get(header) {
+            if let Ok(s) = v.to_str() {
+                // if it's an valid ip.
+                if let Some(Ok(ip)) = s.split(',').next().map(|s| s.trim().parse::<IpAddr>()) {
+                    client_ip = Some(ip);
                 }
             }
         }
+    }
 
-        Metadata {
-            session: None,
-            user: client_ip.map(|ip| ip.to_string()),
-        }
+    let auth_token = headers
+        .get(hyper::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.strip_prefix("Bearer "))
+        .map(|s| s.trim().to_string());
+
+    Metadata {
+        session: None,
+        user: client_ip.map(|ip| ip.to_string()),
+        auth_token,
     }
 }
 impl jsonrpc_ipc_server::MetaExtractor<Metadata> for RpcExtractor {
@@ -38,6 +50,7 @@ impl jsonrpc_ipc_server::MetaExtractor<Metadata> for RpcExtractor {
         Metadata {
             session: Some(Arc::new(Session::new(req.sender.clone()))),
             user: None,
+            auth_token: None,
         }
     }
 }
@@ -46,6 +59,7 @@ impl jsonrpc_tcp_server::MetaExtractor<Metadata> for RpcExtractor {
         Metadata {
             session: Some(Arc::new(Session::new(context.sender.clone()))),
             user: Some(context.peer_addr.ip().to_string()),
+            auth_token: None,
         }
     }
 }
@@ -57,6 +71,7 @@ impl jsonrpc_ws_server::MetaExtractor<Metadata> for WsExtractor {
         Metadata {
             session,
             user: None,
+            auth_token: None,
         }
     }
 }
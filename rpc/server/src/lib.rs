@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2
 
 mod api_registry;
+pub mod auth;
+mod auth_middleware;
 mod extractors;
 pub mod module;
 mod rate_limit_middleware;
 pub mod service;
+mod tls;
@@ -15,6 +15,7 @@ use starcoin_txpool_api::TxPoolSyncService;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::token_code::TokenCode;
 use starcoin_types::sign_message::SigningMessage;
+use starcoin_types::transaction::authenticator::TransactionAuthenticator;
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
 use std::sync::Arc;
 use std::time::Duration;
@@ -167,6 +168,23 @@ where
         Box::pin(fut.boxed())
     }
 
+    fn sign_txn_with_fee_payer(
+        &self,
+        raw_txn: RawUserTransaction,
+        fee_payer: AccountAddress,
+        signer: AccountAddress,
+    ) -> FutureResult<TransactionAuthenticator> {
+        let service = self.account.clone();
+        let fut = async move {
+            let result = service
+                .sign_txn_with_fee_payer(raw_txn, fee_payer, signer)
+                .await?;
+            Ok(result)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
     fn unlock(
         &self,
         address: AccountAddress,
@@ -1,24 +1,28 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::module::helpers::TransactionRequestFiller;
+use crate::module::helpers::{TransactionDefaults, TransactionRequestFiller};
 use crate::module::map_err;
-use anyhow::format_err;
+use anyhow::{bail, format_err};
 use futures::future::TryFutureExt;
 use futures::FutureExt;
 use starcoin_abi_decoder::{decode_move_value, DecodedMoveValue};
 use starcoin_abi_resolver::ABIResolver;
 use starcoin_abi_types::{FunctionABI, ModuleABI, StructInstantiation, TypeInstantiation};
 use starcoin_account_api::AccountAsyncService;
+use starcoin_chain_service::ChainAsyncService;
 use starcoin_config::NodeConfig;
-use starcoin_dev::playground::{call_contract, PlaygroudService};
+use starcoin_crypto::HashValue;
+use starcoin_dev::playground::{call_contract, estimate_gas, simulate_bundle, PlaygroudService};
+use starcoin_dev::state_overlay::OverlayStateView;
 use starcoin_resource_viewer::module_cache::ModuleCache;
 use starcoin_resource_viewer::MoveValueAnnotator;
 use starcoin_rpc_api::contract_api::ContractApi;
 use starcoin_rpc_api::types::{
-    AnnotatedMoveStructView, AnnotatedMoveValueView, ContractCall, DryRunOutputView,
-    DryRunTransactionRequest, FunctionIdView, ModuleIdView, StrView, StructTagView,
-    TransactionOutputView, WriteOpValueView,
+    AnnotatedMoveStructView, AnnotatedMoveValueView, CallKindView, CallTraceView, ContractCall,
+    DryRunOutputView, DryRunTransactionRequest, FunctionIdView, GasEstimateView, ModuleIdView,
+    SimulateBundleRequest, StateOverrideView, StrView, StructTagView, TransactionOutputView,
+    WriteOpValueView,
 };
 use starcoin_rpc_api::FutureResult;
 use starcoin_state_api::ChainStateAsyncService;
@@ -26,35 +30,48 @@ use starcoin_statedb::ChainStateDB;
 use starcoin_storage::Storage;
 use starcoin_txpool_api::TxPoolSyncService;
 use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::BlockNumber;
 use starcoin_types::language_storage::{ModuleId, StructTag};
-use starcoin_types::transaction::{DryRunTransaction, RawUserTransaction, TransactionPayload};
+use starcoin_types::transaction::{
+    DryRunTransaction, RawUserTransaction, TransactionOutput, TransactionPayload,
+};
+use starcoin_vm_runtime::tracer::{CallKind, CallTrace};
 use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::block_metadata::BlockMetadata;
 use starcoin_vm_types::file_format::CompiledModule;
+use starcoin_vm_types::language_storage::FunctionId;
 use starcoin_vm_types::state_view::StateView;
+use starcoin_vm_types::token::stc::STC_TOKEN_CODE;
+use starcoin_vm_types::token::token_code::TokenCode;
 use starcoin_vm_types::transaction::authenticator::AccountPublicKey;
+use starcoin_vm_types::vm_status::VMStatus;
+use std::convert::TryInto;
 use std::str::FromStr;
 use std::sync::Arc;
 
-pub struct ContractRpcImpl<Account, Pool, State> {
+pub struct ContractRpcImpl<Account, Pool, State, Chain> {
     pub(crate) account: Option<Account>,
     pub(crate) pool: Pool,
     pub(crate) chain_state: State,
+    pub(crate) chain: Chain,
     pub(crate) node_config: Arc<NodeConfig>,
     playground: PlaygroudService,
     storage: Arc<Storage>,
 }
 
-impl<Account, Pool, State> ContractRpcImpl<Account, Pool, State>
+impl<Account, Pool, State, Chain> ContractRpcImpl<Account, Pool, State, Chain>
 where
     Account: AccountAsyncService + 'static,
     Pool: TxPoolSyncService + 'static,
     State: ChainStateAsyncService + 'static,
+    Chain: ChainAsyncService + 'static,
 {
     pub fn new(
         node_config: Arc<NodeConfig>,
         account: Option<Account>,
         pool: Pool,
         chain_state: State,
+        chain: Chain,
         playground: PlaygroudService,
         storage: Arc<Storage>,
     ) -> Self {
@@ -62,6 +79,7 @@ where
             account,
             pool,
             chain_state,
+            chain,
             node_config,
             playground,
             storage,
@@ -77,11 +95,12 @@ where
     }
 }
 
-impl<Account, Pool, State> ContractApi for ContractRpcImpl<Account, Pool, State>
+impl<Account, Pool, State, Chain> ContractApi for ContractRpcImpl<Account, Pool, State, Chain>
 where
     Account: AccountAsyncService + 'static,
     Pool: TxPoolSyncService + 'static,
     State: ChainStateAsyncService + 'static,
+    Chain: ChainAsyncService + 'static,
 {
     fn get_code(&self, module_id: StrView<ModuleId>) -> FutureResult<Option<StrView<Vec<u8>>>> {
         let service = self.chain_state.clone();
@@ -172,24 +191,52 @@ where
 
     fn dry_run(&self, txn: DryRunTransactionRequest) -> FutureResult<DryRunOutputView> {
         let service = self.chain_state.clone();
+        let chain = self.chain.clone();
         let storage = self.storage.clone();
         let txn_builder = self.txn_request_filler();
         let f = async move {
-            let state_root = service.state_root().await?;
             let DryRunTransactionRequest {
                 transaction,
                 sender_public_key,
+                block_number,
+                state_root,
+                state_overrides,
+                trace,
             } = txn;
+            // the defaults of the txn on a historical state are filled from the state.
+            let historical = block_number.is_some() || state_root.is_some();
+            let state_root = dry_run_state_root(service, chain, block_number, state_root).await?;
+            let sender = txn_builder.txn_sender(transaction.sender).await?;
+            let latest_defaults = if historical {
+                None
+            } else {
+                Some(
+                    txn_builder
+                        .latest_defaults(sender, transaction.sequence_number)
+                        .await?,
+                )
+            };
 
-            let txn = txn_builder.fill_transaction(transaction).await?;
-            let state_view = ChainStateDB::new(storage, Some(state_root));
-            dry_run(
-                &state_view,
-                DryRunTransaction {
-                    raw_txn: txn,
-                    public_key: sender_public_key.0,
-                },
-            )
+            let state = ChainStateDB::new(storage, Some(state_root));
+            let state_view = overlay_state_view(&state, state_overrides)?;
+            let defaults = match latest_defaults {
+                Some(defaults) => defaults,
+                None => TransactionDefaults::from_state(
+                    &state_view,
+                    sender,
+                    transaction.sequence_number,
+                )?,
+            };
+            let txn = txn_builder.build_transaction(transaction, sender, defaults)?;
+            let txn = DryRunTransaction {
+                raw_txn: txn,
+                public_key: sender_public_key.0,
+            };
+            if trace {
+                dry_run_with_trace(&state_view, txn)
+            } else {
+                dry_run(&state_view, txn)
+            }
         }
         .map_err(map_err);
         Box::pin(f.boxed())
@@ -218,6 +265,121 @@ where
         Box::pin(f.boxed())
     }
 
+    fn estimate_gas(&self, txn: DryRunTransactionRequest) -> FutureResult<GasEstimateView> {
+        let service = self.chain_state.clone();
+        let chain = self.chain.clone();
+        let storage = self.storage.clone();
+        let txn_builder = self.txn_request_filler();
+        let f = async move {
+            let DryRunTransactionRequest {
+                transaction,
+                sender_public_key,
+                block_number,
+                state_root,
+                state_overrides,
+                ..
+            } = txn;
+            // the defaults of the txn on a historical state are filled from the state.
+            let historical = block_number.is_some() || state_root.is_some();
+            let state_root = dry_run_state_root(service, chain, block_number, state_root).await?;
+            let sender = txn_builder.txn_sender(transaction.sender).await?;
+            let latest_defaults = if historical {
+                None
+            } else {
+                Some(
+                    txn_builder
+                        .latest_defaults(sender, transaction.sequence_number)
+                        .await?,
+                )
+            };
+
+            let state = ChainStateDB::new(storage, Some(state_root));
+            let state_view = overlay_state_view(&state, state_overrides)?;
+            let defaults = match latest_defaults {
+                Some(defaults) => defaults,
+                None => TransactionDefaults::from_state(
+                    &state_view,
+                    sender,
+                    transaction.sequence_number,
+                )?,
+            };
+            let txn = txn_builder.build_transaction(transaction, sender, defaults)?;
+            let estimate = estimate_gas(
+                &state_view,
+                DryRunTransaction {
+                    raw_txn: txn,
+                    public_key: sender_public_key.0,
+                },
+            )?;
+            let failure = match estimate.vm_status {
+                VMStatus::Executed => None,
+                vm_status => Some(vm_status_translator::explain_vm_status(
+                    &state_view,
+                    vm_status,
+                )?),
+            };
+            Ok(GasEstimateView {
+                gas_used: estimate.gas_used.into(),
+                min_max_gas_amount: estimate.min_max_gas_amount.map(Into::into),
+                suggested_max_gas_amount: estimate.suggested_max_gas_amount.map(Into::into),
+                failure,
+            })
+        }
+        .map_err(map_err);
+        Box::pin(f.boxed())
+    }
+
+    fn simulate_bundle(
+        &self,
+        bundle: SimulateBundleRequest,
+    ) -> FutureResult<Vec<DryRunOutputView>> {
+        let chain = self.chain.clone();
+        let storage = self.storage.clone();
+        let f = async move {
+            let head = chain.main_head_header().await?;
+            let SimulateBundleRequest {
+                transactions,
+                block_prologue,
+            } = bundle;
+            let txns = transactions
+                .into_iter()
+                .map(|txn| {
+                    Ok(DryRunTransaction {
+                        raw_txn: RawUserTransaction::from_str(txn.raw_txn.as_str())?,
+                        public_key: txn.sender_public_key.0,
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            let block_metadata = block_prologue.map(|prologue| {
+                BlockMetadata::new(
+                    head.id(),
+                    prologue
+                        .timestamp
+                        .unwrap_or_else(|| head.timestamp().saturating_add(1)),
+                    prologue.author.unwrap_or_else(|| head.author()),
+                    None,
+                    0,
+                    head.number().saturating_add(1),
+                    head.chain_id(),
+                    head.gas_used(),
+                )
+            });
+            let state = ChainStateDB::new(storage, Some(head.state_root()));
+            let outputs = simulate_bundle(&state, block_metadata, txns.clone())?;
+            // decode every output on the state changed by the txn and the previous txns.
+            let mut state_view = OverlayStateView::new(&state);
+            txns.into_iter()
+                .zip(outputs)
+                .map(|(txn, (vm_status, output))| {
+                    state_view.apply_write_set(output.write_set());
+                    dry_run_output_view(&state_view, txn.raw_txn.into_payload(), vm_status, output)
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        }
+        .map_err(map_err);
+        Box::pin(f.boxed())
+    }
+
     fn resolve_function(&self, function_id: FunctionIdView) -> FutureResult<FunctionABI> {
         let service = self.chain_state.clone();
         let storage = self.storage.clone();
@@ -253,18 +415,148 @@ where
     }
 }
 
+/// Resolve the state root to dry run on, the latest one if both `block_number` and `state_root` are absent.
+async fn dry_run_state_root<State, Chain>(
+    chain_state: State,
+    chain: Chain,
+    block_number: Option<BlockNumber>,
+    state_root: Option<HashValue>,
+) -> anyhow::Result<HashValue>
+where
+    State: ChainStateAsyncService,
+    Chain: ChainAsyncService,
+{
+    match (block_number, state_root) {
+        (Some(_), Some(_)) => bail!("block_number and state_root can not be used together"),
+        (Some(number), None) => Ok(chain
+            .main_block_header_by_number(number)
+            .await?
+            .ok_or_else(|| format_err!("Can not find block by number {}", number))?
+            .state_root()),
+        (None, Some(state_root)) => Ok(state_root),
+        (None, None) => chain_state.state_root().await,
+    }
+}
+
+fn overlay_state_view(
+    state_view: &dyn StateView,
+    state_overrides: Vec<StateOverrideView>,
+) -> anyhow::Result<OverlayStateView<'_>> {
+    let mut overlay = OverlayStateView::new(state_view);
+    for state_override in state_overrides {
+        match state_override {
+            StateOverrideView::Resource {
+                address,
+                struct_tag,
+                value,
+            } => overlay.set_resource(address, struct_tag.0, value.map(|v| v.0)),
+            StateOverrideView::Module { code } => overlay.set_module(code.0)?,
+            StateOverrideView::Balance {
+                address,
+                token_code,
+                amount,
+            } => {
+                let token_code = match token_code {
+                    Some(token_code) => TokenCode::from_str(token_code.as_str())?,
+                    None => STC_TOKEN_CODE.clone(),
+                };
+                overlay.set_balance(address, token_code.try_into()?, amount.0)?
+            }
+        }
+    }
+    Ok(overlay)
+}
+
 pub fn dry_run(
     state_view: &dyn StateView,
     txn: DryRunTransaction,
 ) -> anyhow::Result<DryRunOutputView> {
-    let (vm_status, output) = starcoin_dev::playground::dry_run(state_view, txn.clone())?;
+    let payload = txn.raw_txn.payload().clone();
+    let (vm_status, output) = starcoin_dev::playground::dry_run(state_view, txn)?;
+    dry_run_output_view(state_view, payload, vm_status, output)
+}
+
+/// Dry run the txn, and return the calls into Move with the output.
+pub fn dry_run_with_trace(
+    state_view: &dyn StateView,
+    txn: DryRunTransaction,
+) -> anyhow::Result<DryRunOutputView> {
+    let payload = txn.raw_txn.payload().clone();
+    let (vm_status, output, calls) = starcoin_dev::playground::dry_run_with_trace(state_view, txn)?;
+    let mut output_view = dry_run_output_view(state_view, payload, vm_status, output)?;
+    output_view.trace = Some(
+        calls
+            .into_iter()
+            .map(|call| call_trace_view(state_view, call))
+            .collect::<anyhow::Result<Vec<_>>>()?,
+    );
+    Ok(output_view)
+}
+
+pub(crate) fn call_trace_view(
+    state_view: &dyn StateView,
+    call: CallTrace,
+) -> anyhow::Result<CallTraceView> {
+    let decoded_args = decode_call_args(state_view, &call);
+    let CallTrace {
+        kind,
+        module,
+        function,
+        type_args,
+        args,
+        gas_used,
+        status,
+    } = call;
+    Ok(CallTraceView {
+        kind: match kind {
+            CallKind::BlockPrologue => CallKindView::BlockPrologue,
+            CallKind::Prologue => CallKindView::Prologue,
+            CallKind::Script => CallKindView::Script,
+            CallKind::ScriptFunction => CallKindView::ScriptFunction,
+            CallKind::InitScript => CallKindView::InitScript,
+            CallKind::Epilogue => CallKindView::Epilogue,
+        },
+        function: module
+            .zip(function)
+            .map(|(module, function)| StrView(FunctionId { module, function })),
+        type_args: type_args.into_iter().map(StrView).collect(),
+        args: args.into_iter().map(StrView).collect(),
+        decoded_args,
+        gas_used: gas_used.into(),
+        status: vm_status_translator::explain_vm_status(state_view, status)?,
+    })
+}
+
+/// Decode the args by the function abi, return `None` if any arg can not be decoded.
+fn decode_call_args(state_view: &dyn StateView, call: &CallTrace) -> Option<Vec<DecodedMoveValue>> {
+    let module = call.module.as_ref()?;
+    let function = call.function.as_ref()?;
+    let abi = ABIResolver::new(state_view)
+        .resolve_function_instantiation(module, function.as_ident_str(), &call.type_args)
+        .ok()?;
+    // the signers of script function are passed by the VM, and not in the args.
+    let arg_abis = &abi.args()[abi.args().len().checked_sub(call.args.len())?..];
+    arg_abis
+        .iter()
+        .zip(call.args.iter())
+        .map(|(arg_abi, arg)| decode_move_value(arg_abi.type_abi(), arg).ok())
+        .collect()
+}
+
+/// Explain the vm status and decode the write set of the dry run output.
+pub(crate) fn dry_run_output_view(
+    state_view: &dyn StateView,
+    payload: TransactionPayload,
+    vm_status: VMStatus,
+    output: TransactionOutput,
+) -> anyhow::Result<DryRunOutputView> {
     let vm_status_explain = vm_status_translator::explain_vm_status(state_view, vm_status)?;
     let mut txn_output: TransactionOutputView = output.into();
 
     let resolver = {
         let module_cache = ModuleCache::new();
         // If the txn is package txn, we need to use modules in the package to resolve transaction output.
-        if let TransactionPayload::Package(p) = txn.raw_txn.into_payload() {
+        if let TransactionPayload::Package(p) = payload {
             let modules = p
                 .modules()
                 .iter()
@@ -299,5 +591,6 @@ pub fn dry_run(
     Ok(DryRunOutputView {
         explained_status: vm_status_explain,
         txn_output,
+        trace: None,
     })
 }
@@ -1,29 +1,69 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::module::to_invalid_param_err;
+use crate::module::contract_rpc::{call_trace_view, dry_run_output_view};
 use crate::module::txfactory_rpc::TxFactoryStatusHandle;
+use crate::module::{map_err, to_invalid_param_err};
+use anyhow::format_err;
+use futures::future::TryFutureExt;
+use futures::FutureExt;
 use jsonrpc_core::Result;
+use starcoin_chain_service::ChainAsyncService;
 use starcoin_config::NodeConfig;
+use starcoin_crypto::HashValue;
+use starcoin_dev::playground::trace_block_transaction;
+use starcoin_executor::block_replay;
 use starcoin_logger::prelude::LevelFilter;
 use starcoin_logger::{LogPattern, LoggerHandle};
 use starcoin_rpc_api::debug::DebugApi;
-use starcoin_rpc_api::types::FactoryAction;
+use starcoin_rpc_api::types::{
+    BlockReplayView, FactoryAction, StateDiffView, TransactionDivergenceView, TransactionInfoView,
+    TransactionTraceView,
+};
+use starcoin_rpc_api::FutureResult;
+use starcoin_state_api::{ChainStateReader, StateReaderExt, StateView};
+use starcoin_statedb::ChainStateDB;
+use starcoin_storage::Storage;
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::transaction::{Transaction, TransactionInfo};
+use starcoin_types::write_set::{WriteOp, WriteSet};
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 
-pub struct DebugRpcImpl {
+pub struct DebugRpcImpl<S>
+where
+    S: ChainAsyncService + 'static,
+{
     config: Arc<NodeConfig>,
     log_handle: Arc<LoggerHandle>,
+    storage: Arc<Storage>,
+    chain: S,
 }
 
-impl DebugRpcImpl {
-    pub fn new(config: Arc<NodeConfig>, log_handle: Arc<LoggerHandle>) -> Self {
-        Self { config, log_handle }
+impl<S> DebugRpcImpl<S>
+where
+    S: ChainAsyncService,
+{
+    pub fn new(
+        config: Arc<NodeConfig>,
+        log_handle: Arc<LoggerHandle>,
+        storage: Arc<Storage>,
+        chain: S,
+    ) -> Self {
+        Self {
+            config,
+            log_handle,
+            storage,
+            chain,
+        }
     }
 }
 
-impl DebugApi for DebugRpcImpl {
+impl<S> DebugApi for DebugRpcImpl<S>
+where
+    S: ChainAsyncService,
+{
     fn set_log_level(&self, logger_name: Option<String>, level: String) -> Result<()> {
         let logger_name = logger_name.and_then(|s| {
             let s = s.trim();
@@ -67,4 +107,339 @@ impl DebugApi for DebugRpcImpl {
     fn txfactory_status(&self, action: FactoryAction) -> Result<bool> {
         Ok(TxFactoryStatusHandle::handle_action(action))
     }
+
+    fn trace_transaction(&self, txn_hash: HashValue) -> FutureResult<TransactionTraceView> {
+        let chain = self.chain.clone();
+        let storage = self.storage.clone();
+        let f = async move {
+            let block = chain
+                .get_transaction_block(txn_hash)
+                .await?
+                .ok_or_else(|| format_err!("Can not find block of txn {}", txn_hash))?;
+            let parent_hash = block.header().parent_hash();
+            let parent = chain
+                .get_header_by_hash(&parent_hash)
+                .await?
+                .ok_or_else(|| format_err!("Can not find block header by hash {}", parent_hash))?;
+            let index = block
+                .transactions()
+                .iter()
+                .position(|txn| txn.id() == txn_hash)
+                .ok_or_else(|| format_err!("Txn {} is not a user txn", txn_hash))?;
+            // the txns of block before the traced txn are executed first.
+            let txns = std::iter::once(Transaction::BlockMetadata(
+                block.to_metadata(parent.gas_used()),
+            ))
+            .chain(
+                block.transactions()[..=index]
+                    .iter()
+                    .cloned()
+                    .map(Transaction::UserTransaction),
+            )
+            .collect();
+            let (vm_status, output, calls) = trace_block_transaction(
+                &ChainStateDB::new(storage.clone(), Some(parent.state_root())),
+                txns,
+            )?;
+            // decode the output and calls on the state after the txn,
+            // the first txn info of the block is the block metadata's.
+            let txn_info = chain
+                .get_block_txn_infos(block.id())
+                .await?
+                .into_iter()
+                .nth(index + 1)
+                .ok_or_else(|| format_err!("Can not find txn info of txn {}", txn_hash))?;
+            let state = ChainStateDB::new(storage, Some(txn_info.state_root_hash()));
+            let payload = block.transactions()[index].payload().clone();
+            let output_view = dry_run_output_view(&state, payload, vm_status, output)?;
+            Ok(TransactionTraceView {
+                transaction_hash: txn_hash,
+                block_hash: block.id(),
+                block_number: block.header().number().into(),
+                explained_status: output_view.explained_status,
+                txn_output: output_view.txn_output,
+                calls: calls
+                    .into_iter()
+                    .map(|call| call_trace_view(&state, call))
+                    .collect::<anyhow::Result<Vec<_>>>()?,
+            })
+        }
+        .map_err(map_err);
+        Box::pin(f.boxed())
+    }
+
+    fn replay_block(&self, block_hash: HashValue) -> FutureResult<BlockReplayView> {
+        let chain = self.chain.clone();
+        let storage = self.storage.clone();
+        let f = async move {
+            let block = chain
+                .get_block_by_hash(block_hash)
+                .await?
+                .ok_or_else(|| format_err!("Can not find block by hash {}", block_hash))?;
+            if block.header().is_genesis() {
+                return Err(format_err!("Can not replay the genesis block"));
+            }
+            let parent_hash = block.header().parent_hash();
+            let parent = chain
+                .get_header_by_hash(&parent_hash)
+                .await?
+                .ok_or_else(|| format_err!("Can not find block header by hash {}", parent_hash))?;
+            let expected_txn_infos = chain.get_block_txn_infos(block_hash).await?;
+            let txns: Vec<_> = std::iter::once(Transaction::BlockMetadata(
+                block.to_metadata(parent.gas_used()),
+            ))
+            .chain(
+                block
+                    .transactions()
+                    .iter()
+                    .cloned()
+                    .map(Transaction::UserTransaction),
+            )
+            .collect();
+            // the replayed states are only committed to the memory of the statedb, never flushed.
+            let state = ChainStateDB::new(storage.clone(), Some(parent.state_root()));
+            let block_gas_limit = state.get_epoch()?.block_gas_limit();
+            let replayed_txns = block_replay(&state, txns.clone(), block_gas_limit)?;
+
+            let mut divergence = None;
+            for (index, txn) in txns.iter().enumerate() {
+                let expected = expected_txn_infos.get(index).map(|info| (**info).clone());
+                let replayed = replayed_txns.get(index);
+                let actual = replayed.and_then(|replayed| replayed.txn_info.clone());
+                let fields = match (&expected, &actual) {
+                    (Some(expected), Some(actual)) => diverged_fields(expected, actual),
+                    (None, None) => continue,
+                    // the txn is discarded, not executed or its txn info is not stored.
+                    _ => vec!["status".to_string()],
+                };
+                if fields.is_empty() {
+                    continue;
+                }
+                // the write sets of executed txns are not persisted, so the stored writes are
+                // the states which differ between the stored states before and after the txn.
+                let state_diff = match (&expected, replayed) {
+                    (Some(expected), Some(replayed)) if replayed.txn_info.is_some() => {
+                        let state_root_before = match index.checked_sub(1) {
+                            Some(prev) => expected_txn_infos
+                                .get(prev)
+                                .map(|info| info.state_root_hash())
+                                .ok_or_else(|| {
+                                    format_err!("Can not find the txn info of txn {}", prev)
+                                })?,
+                            None => parent.state_root(),
+                        };
+                        let state_before =
+                            ChainStateDB::new(storage.clone(), Some(state_root_before));
+                        let stored_writes = state_before.diff(expected.state_root_hash())?;
+                        Some(state_diff(
+                            &state_before,
+                            stored_writes,
+                            &replayed.write_set,
+                        )?)
+                    }
+                    _ => None,
+                };
+                divergence = Some(TransactionDivergenceView {
+                    transaction_hash: txn.id(),
+                    transaction_index: index as u32,
+                    expected: expected
+                        .map(|info| TransactionInfoView::new(info, &block))
+                        .transpose()?,
+                    actual: actual
+                        .map(|info| TransactionInfoView::new(info, &block))
+                        .transpose()?,
+                    actual_status: replayed.map(|replayed| replayed.status.clone().into()),
+                    diverged_fields: fields,
+                    state_diff,
+                });
+                break;
+            }
+            Ok(BlockReplayView {
+                block_hash,
+                block_number: block.header().number().into(),
+                expected_state_root: block.header().state_root(),
+                actual_state_root: state.state_root(),
+                replayed_txns: replayed_txns.len() as u32,
+                divergence,
+            })
+        }
+        .map_err(map_err);
+        Box::pin(f.boxed())
+    }
+}
+
+/// Return the names of the fields which the replayed txn info diverges from the stored one.
+fn diverged_fields(expected: &TransactionInfo, actual: &TransactionInfo) -> Vec<String> {
+    let mut fields = vec![];
+    if expected.state_root_hash() != actual.state_root_hash() {
+        fields.push("state_root_hash".to_string());
+    }
+    if expected.event_root_hash() != actual.event_root_hash() {
+        fields.push("event_root_hash".to_string());
+    }
+    if expected.gas_used() != actual.gas_used() {
+        fields.push("gas_used".to_string());
+    }
+    if expected.status() != actual.status() {
+        fields.push("status".to_string());
+    }
+    fields
+}
+
+/// Return the states which differ between the stored state and the replayed state after the txn,
+/// sorted by access path. The states written by either the stored txn or the replayed txn are
+/// compared, `stored_writes` are the states changed by the stored txn, with the values before and
+/// after the txn, `state_before` is the stored state before the txn.
+#[allow(clippy::type_complexity)]
+fn state_diff(
+    state_before: &dyn StateView,
+    stored_writes: Vec<(AccessPath, Option<Vec<u8>>, Option<Vec<u8>>)>,
+    write_set: &WriteSet,
+) -> anyhow::Result<Vec<StateDiffView>> {
+    let mut stored_writes: HashMap<AccessPath, (Option<Vec<u8>>, Option<Vec<u8>>)> = stored_writes
+        .into_iter()
+        .map(|(access_path, before, after)| (access_path, (before, after)))
+        .collect();
+    let to_write_op =
+        |value: Option<Vec<u8>>| value.map(WriteOp::Value).unwrap_or(WriteOp::Deletion);
+    let mut diff = vec![];
+    for (access_path, write_op) in write_set {
+        let stored = match stored_writes.remove(access_path) {
+            Some((_, after)) => after,
+            None => state_before.get(access_path)?,
+        };
+        let replayed = match write_op {
+            WriteOp::Value(value) => Some(value),
+            WriteOp::Deletion => None,
+        };
+        if stored.as_ref() != replayed {
+            diff.push(StateDiffView {
+                access_path: access_path.clone(),
+                expected: (access_path.clone(), to_write_op(stored)).into(),
+                actual: (access_path.clone(), write_op.clone()).into(),
+            });
+        }
+    }
+    // the states written by the stored txn only, they are unchanged by the replayed txn.
+    for (access_path, (before, after)) in stored_writes {
+        diff.push(StateDiffView {
+            access_path: access_path.clone(),
+            expected: (access_path.clone(), to_write_op(after)).into(),
+            actual: (access_path, to_write_op(before)).into(),
+        });
+    }
+    diff.sort_by(|a, b| a.access_path.cmp(&b.access_path));
+    Ok(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_rpc_api::types::WriteOpView;
+    use starcoin_state_api::ChainStateWriter;
+    use starcoin_types::contract_event::ContractEvent;
+    use starcoin_types::event::EventKey;
+    use starcoin_types::language_storage::TypeTag;
+    use starcoin_types::write_set::WriteSetMut;
+    use starcoin_vm_types::vm_status::KeptVMStatus;
+
+    #[test]
+    fn test_diverged_fields() {
+        let txn_hash = HashValue::random();
+        let state_root = HashValue::random();
+        let expected = TransactionInfo::new(txn_hash, state_root, &[], 100, KeptVMStatus::Executed);
+        assert!(diverged_fields(&expected, &expected.clone()).is_empty());
+
+        let event = ContractEvent::new(EventKey::random(), 0, TypeTag::U64, vec![]);
+        let actual = TransactionInfo::new(
+            txn_hash,
+            HashValue::random(),
+            &[event],
+            100,
+            KeptVMStatus::Executed,
+        );
+        assert_eq!(
+            diverged_fields(&expected, &actual),
+            vec!["state_root_hash", "event_root_hash"]
+        );
+
+        let actual = TransactionInfo::new(txn_hash, state_root, &[], 200, KeptVMStatus::OutOfGas);
+        assert_eq!(
+            diverged_fields(&expected, &actual),
+            vec!["gas_used", "status"]
+        );
+    }
+
+    #[test]
+    fn test_state_diff() -> anyhow::Result<()> {
+        let same_value = AccessPath::random_resource();
+        let changed_value = AccessPath::random_resource();
+        let deleted = AccessPath::random_resource();
+        let not_stored = AccessPath::random_resource();
+        let not_replayed = AccessPath::random_resource();
+        let state_before = ChainStateDB::mock();
+        state_before.apply_write_set(
+            WriteSetMut::new(vec![
+                (same_value.clone(), WriteOp::Value(vec![0])),
+                (changed_value.clone(), WriteOp::Value(vec![0])),
+                (deleted.clone(), WriteOp::Value(vec![0])),
+            ])
+            .freeze()?,
+        )?;
+        state_before.commit()?;
+        state_before.flush()?;
+
+        let stored_state = state_before.fork();
+        stored_state.apply_write_set(
+            WriteSetMut::new(vec![
+                (same_value.clone(), WriteOp::Value(vec![1])),
+                (changed_value.clone(), WriteOp::Value(vec![2])),
+                (deleted.clone(), WriteOp::Value(vec![3])),
+                (not_replayed.clone(), WriteOp::Value(vec![6])),
+            ])
+            .freeze()?,
+        )?;
+        let stored_state_root = stored_state.commit()?;
+        stored_state.flush()?;
+        let stored_writes = state_before.diff(stored_state_root)?;
+
+        let write_set = WriteSetMut::new(vec![
+            (same_value, WriteOp::Value(vec![1])),
+            (changed_value.clone(), WriteOp::Value(vec![4])),
+            (deleted.clone(), WriteOp::Deletion),
+            (not_stored.clone(), WriteOp::Value(vec![5])),
+        ])
+        .freeze()?;
+        let diff = state_diff(&state_before, stored_writes, &write_set)?;
+        let mut expected_paths = vec![
+            changed_value.clone(),
+            deleted.clone(),
+            not_stored.clone(),
+            not_replayed.clone(),
+        ];
+        expected_paths.sort();
+        assert_eq!(
+            diff.iter()
+                .map(|diff| diff.access_path.clone())
+                .collect::<Vec<_>>(),
+            expected_paths
+        );
+        for diff in diff {
+            if diff.access_path == changed_value {
+                assert!(matches!(diff.expected.action, WriteOpView::Value));
+                assert!(matches!(diff.actual.action, WriteOpView::Value));
+            } else if diff.access_path == deleted {
+                assert!(matches!(diff.expected.action, WriteOpView::Value));
+                assert!(matches!(diff.actual.action, WriteOpView::Deletion));
+            } else if diff.access_path == not_stored {
+                assert!(matches!(diff.expected.action, WriteOpView::Deletion));
+                assert!(matches!(diff.actual.action, WriteOpView::Value));
+            } else {
+                // written by the stored txn only, the replayed state is the state before the txn.
+                assert!(matches!(diff.expected.action, WriteOpView::Value));
+                assert!(matches!(diff.actual.action, WriteOpView::Deletion));
+            }
+        }
+        Ok(())
+    }
 }
@@ -1,10 +1,16 @@
+use serde::de::DeserializeOwned;
 use starcoin_account_api::AccountAsyncService;
 use starcoin_config::NodeConfig;
 use starcoin_rpc_api::types::TransactionRequest;
 use starcoin_state_api::ChainStateAsyncService;
 use starcoin_txpool_api::TxPoolSyncService;
-use starcoin_types::account_config::AccountResource;
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::{genesis_address, AccountResource};
 use starcoin_types::transaction::{Module, Package, RawUserTransaction, TransactionPayload};
+use starcoin_vm_types::move_resource::MoveResource;
+use starcoin_vm_types::on_chain_resource::GlobalTimeOnChain;
+use starcoin_vm_types::state_view::StateView;
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -24,6 +30,65 @@ where
     pub(crate) async fn fill_transaction(
         &self,
         txn_request: TransactionRequest,
+    ) -> anyhow::Result<RawUserTransaction> {
+        let sender = self.txn_sender(txn_request.sender).await?;
+        let defaults = self
+            .latest_defaults(sender, txn_request.sequence_number)
+            .await?;
+        self.build_transaction(txn_request, sender, defaults)
+    }
+
+    /// The defaults of the txn of `sender` from the txpool and the latest state.
+    pub(crate) async fn latest_defaults(
+        &self,
+        sender: AccountAddress,
+        sequence_number: Option<u64>,
+    ) -> anyhow::Result<TransactionDefaults> {
+        let next_seq_number =
+            match sequence_number.or_else(|| self.pool.next_sequence_number(sender)) {
+                Some(n) => n,
+                None => match self
+                    .chain_state
+                    .clone()
+                    .get_resource::<AccountResource>(sender)
+                    .await?
+                {
+                    Some(r) => r.sequence_number(),
+                    None => anyhow::bail!("cannot find account {} onchain", sender),
+                },
+            };
+        Ok(TransactionDefaults {
+            sequence_number: next_seq_number,
+            now_secs: self.node_config.net().time_service().now_secs(),
+        })
+    }
+
+    /// The sender of the txn request, default to the default account.
+    pub(crate) async fn txn_sender(
+        &self,
+        sender: Option<AccountAddress>,
+    ) -> anyhow::Result<AccountAddress> {
+        Ok(match sender {
+            Some(s) => s,
+            None => match self.account.as_ref() {
+                None => anyhow::bail!("please set txn request's sender"),
+                Some(account_service) => {
+                    account_service
+                        .get_default_account()
+                        .await?
+                        .ok_or_else(|| anyhow::anyhow!("cannot find default account"))?
+                        .address
+                }
+            },
+        })
+    }
+
+    /// Build the txn from the request, the absent fields are filled with `defaults`.
+    pub(crate) fn build_transaction(
+        &self,
+        txn_request: TransactionRequest,
+        sender: AccountAddress,
+        defaults: TransactionDefaults,
     ) -> anyhow::Result<RawUserTransaction> {
         let payload = if !txn_request.modules.is_empty() {
             let modules = txn_request
@@ -45,40 +110,11 @@ where
             script.into()
         };
 
-        let sender = match txn_request.sender {
-            Some(s) => s,
-            None => match self.account.as_ref() {
-                None => anyhow::bail!("please set txn request's sender"),
-                Some(account_service) => {
-                    account_service
-                        .get_default_account()
-                        .await?
-                        .ok_or_else(|| anyhow::anyhow!("cannot find default account"))?
-                        .address
-                }
-            },
-        };
-
-        let next_seq_number = match txn_request
-            .sequence_number
-            .or_else(|| self.pool.next_sequence_number(sender))
-        {
-            Some(n) => n,
-            None => match self
-                .chain_state
-                .clone()
-                .get_resource::<AccountResource>(sender)
-                .await?
-            {
-                Some(r) => r.sequence_number(),
-                None => anyhow::bail!("cannot find account {} onchain", sender),
-            },
-        };
         let max_gas_amount = txn_request.max_gas_amount.unwrap_or(1000000); // default 10_00000
         let max_gas_price = txn_request.gas_unit_price.unwrap_or(1);
         let expire = txn_request
             .expiration_timestamp_secs
-            .unwrap_or_else(|| self.node_config.net().time_service().now_secs() + 60 * 60 * 12); // default to 0.5d
+            .unwrap_or_else(|| defaults.now_secs + 60 * 60 * 12); // default to 0.5d
 
         let chain_id = self.node_config.net().chain_id();
         if let Some(cid) = txn_request.chain_id {
@@ -93,7 +129,7 @@ where
 
         let raw_txn = RawUserTransaction::new_with_default_gas_token(
             sender,
-            next_seq_number,
+            defaults.sequence_number,
             payload,
             max_gas_amount,
             max_gas_price,
@@ -103,3 +139,46 @@ where
         Ok(raw_txn)
     }
 }
+
+/// The defaults of a txn request which depend on the state the txn is executed on.
+pub(crate) struct TransactionDefaults {
+    /// Used if the sequence number of the request is absent.
+    pub sequence_number: u64,
+    /// The expiration time of the txn defaults to the time after it.
+    pub now_secs: u64,
+}
+
+impl TransactionDefaults {
+    /// The defaults of the txn of `sender` from the `state_view`, which may be a historical state,
+    /// so the defaults do not depend on the current head and txpool.
+    pub fn from_state(
+        state_view: &dyn StateView,
+        sender: AccountAddress,
+        sequence_number: Option<u64>,
+    ) -> anyhow::Result<Self> {
+        let sequence_number = match sequence_number {
+            Some(n) => n,
+            None => match get_resource::<AccountResource>(state_view, sender)? {
+                Some(r) => r.sequence_number(),
+                None => anyhow::bail!("cannot find account {} in the state", sender),
+            },
+        };
+        let now_secs = get_resource::<GlobalTimeOnChain>(state_view, genesis_address())?
+            .ok_or_else(|| anyhow::anyhow!("cannot find the on chain time in the state"))?
+            .seconds();
+        Ok(Self {
+            sequence_number,
+            now_secs,
+        })
+    }
+}
+
+fn get_resource<R>(state_view: &dyn StateView, address: AccountAddress) -> anyhow::Result<Option<R>>
+where
+    R: MoveResource + DeserializeOwned,
+{
+    state_view
+        .get(&AccessPath::resource_access_path(address, R::struct_tag()))?
+        .map(|data| bcs_ext::from_bytes(data.as_slice()))
+        .transpose()
+}
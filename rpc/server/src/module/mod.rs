@@ -14,6 +14,7 @@ mod pubsub;
 mod state_rpc;
 mod sync_manager_rpc;
 mod txfactory_rpc;
+mod txpool_manager_rpc;
 mod txpool_rpc;
 
 pub use self::account_rpc::AccountRpcImpl;
@@ -28,6 +29,7 @@ pub use self::pubsub::{PubSubImpl, PubSubService, PubSubServiceFactory};
 pub use self::state_rpc::StateRpcImpl;
 pub use self::sync_manager_rpc::SyncManagerRpcImpl;
 pub use self::txfactory_rpc::TxFactoryStatusHandle;
+pub use self::txpool_manager_rpc::TxPoolManagerRpcImpl;
 pub use self::txpool_rpc::TxPoolRpcImpl;
 
 use actix::MailboxError;
@@ -118,6 +120,7 @@ impl From<TransactionError> for RpcError {
             | TransactionError::Old
             | TransactionError::InsufficientGasPrice { .. }
             | TransactionError::TooCheapToReplace { .. }
+            | TransactionError::TooManyReplacements { .. }
             | TransactionError::InsufficientGas { .. }
             | TransactionError::InsufficientBalance { .. }
             | TransactionError::GasLimitExceeded { .. }
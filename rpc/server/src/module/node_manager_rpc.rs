@@ -1,28 +1,37 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::module::map_err;
+use crate::auth::RpcAuthenticator;
+use crate::module::{map_err, to_invalid_param_err};
 use futures::future::TryFutureExt;
 use futures::FutureExt;
+use starcoin_config::{ApiSet, RpcAuthKind};
 use starcoin_crypto::HashValue;
 use starcoin_node_api::node_service::NodeAsyncService;
 use starcoin_rpc_api::node_manager::NodeManagerApi;
+use starcoin_rpc_api::types::RpcAuthKeyView;
 use starcoin_rpc_api::FutureResult;
 use starcoin_service_registry::{ServiceInfo, ServiceStatus};
+use std::str::FromStr;
+use std::sync::Arc;
 
 pub struct NodeManagerRpcImpl<S>
 where
     S: NodeAsyncService + 'static,
 {
     service: S,
+    authenticator: Arc<RpcAuthenticator>,
 }
 
 impl<S> NodeManagerRpcImpl<S>
 where
     S: NodeAsyncService,
 {
-    pub fn new(service: S) -> Self {
-        Self { service }
+    pub fn new(service: S, authenticator: Arc<RpcAuthenticator>) -> Self {
+        Self {
+            service,
+            authenticator,
+        }
     }
 }
 
@@ -115,4 +124,40 @@ where
         .map_err(map_err);
         Box::pin(fut.boxed())
     }
+
+    fn list_rpc_auth_keys(&self) -> FutureResult<Vec<RpcAuthKeyView>> {
+        let keys = self
+            .authenticator
+            .list_keys()
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        Box::pin(futures::future::ok(keys))
+    }
+
+    fn add_rpc_auth_key(
+        &self,
+        name: String,
+        kind: String,
+        apis: Option<String>,
+        methods: Option<Vec<String>>,
+    ) -> FutureResult<String> {
+        let authenticator = self.authenticator.clone();
+        let fut = async move {
+            let kind = RpcAuthKind::from_str(kind.as_str()).map_err(to_invalid_param_err)?;
+            let apis = apis
+                .map(|apis| ApiSet::from_str(apis.as_str()))
+                .transpose()
+                .map_err(|e| to_invalid_param_err(anyhow::format_err!("{}", e)))?;
+            authenticator
+                .add_key(name, kind, apis, methods.unwrap_or_default())
+                .map_err(map_err)
+        };
+        Box::pin(fut.boxed())
+    }
+
+    fn remove_rpc_auth_key(&self, name: String) -> FutureResult<bool> {
+        let result = self.authenticator.remove_key(name.as_str());
+        Box::pin(futures::future::ready(result.map_err(map_err)))
+    }
 }
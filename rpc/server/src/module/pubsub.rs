@@ -2,32 +2,45 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::module::map_err;
-use anyhow::Result;
+use anyhow::{format_err, Result};
 use futures::channel::mpsc;
 use futures::future::AbortHandle;
 use futures::StreamExt;
 use jsonrpc_pubsub::typed::Subscriber;
 use jsonrpc_pubsub::SubscriptionId;
 use parking_lot::RwLock;
+use serde_json::Value;
 use starcoin_abi_decoder::decode_move_value;
 use starcoin_abi_resolver::ABIResolver;
-use starcoin_chain_notify::message::{ContractEventNotification, Notification, ThinBlock};
+use starcoin_chain_notify::block_events;
+use starcoin_chain_notify::message::{
+    ContractEventNotification, Notification, ThinBlock, WriteSetNotification,
+};
+use starcoin_config::NodeConfig;
 use starcoin_crypto::HashValue;
+use starcoin_dev::playground::view_resource;
 use starcoin_logger::prelude::*;
 use starcoin_miner::{MinerService, UpdateSubscriberNumRequest};
 use starcoin_rpc_api::metadata::Metadata;
-use starcoin_rpc_api::types::{BlockView, TransactionEventResponse, TransactionEventView};
+use starcoin_rpc_api::types::{
+    BlockView, DecodedMoveValue, ResourceChangeView, StrView, TransactionEventResponse,
+    TransactionEventView,
+};
 use starcoin_rpc_api::{errors, pubsub::StarcoinPubSub, types::pubsub};
 use starcoin_service_registry::{
     ActorService, EventHandler as ActorEventHandler, ServiceContext, ServiceFactory,
     ServiceHandler, ServiceRef, ServiceRequest,
 };
 use starcoin_statedb::ChainStateDB;
-use starcoin_storage::Storage;
+use starcoin_storage::{BlockStore, Storage};
+use starcoin_sync_api::SyncProgressEvent;
 use starcoin_txpool::TxPoolService;
-use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_txpool_api::{TxPoolSyncService, TxnStatusEvent};
+use starcoin_types::access_path::DataPath;
+use starcoin_types::block::{BlockHeader, BlockNumber};
 use starcoin_types::filter::Filter;
-use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::system_events::{ChainReorg, MintBlockEvent};
+use starcoin_types::write_set::WriteOp;
 use std::collections::HashMap;
 use std::convert::TryInto;
 use std::fmt::Debug;
@@ -73,7 +86,20 @@ impl PubSubImpl {
         match (kind, params) {
             (pubsub::Kind::NewHeads, None) => self
                 .service
-                .try_send(SubscribeNewHeads(subscriber))
+                .try_send(SubscribeNewHeads(subscriber, false))
+                .map_err(|e| {
+                    let msg = map_send_err(&e);
+                    (
+                        match e {
+                            TrySendError::Disconnected(t) => t.0,
+                            TrySendError::Full(t) => t.0,
+                        },
+                        msg,
+                    )
+                }),
+            (pubsub::Kind::NewHeads, Some(pubsub::Params::NewHeads(params))) => self
+                .service
+                .try_send(SubscribeNewHeads(subscriber, params.with_removed))
                 .map_err(|e| {
                     let msg = map_send_err(&e);
                     (
@@ -86,7 +112,7 @@ impl PubSubImpl {
                 }),
             (pubsub::Kind::NewHeads, _) => Err((
                 subscriber,
-                errors::invalid_params("newHeads", "Expected no parameters."),
+                errors::invalid_params("newHeads", "Expected no parameters or with_removed."),
             )),
             (pubsub::Kind::NewPendingTransactions, None) => self
                 .service
@@ -106,6 +132,7 @@ impl PubSubImpl {
                 errors::invalid_params("newPendingTransactions", "Expected no parameters."),
             )),
             (pubsub::Kind::Events, Some(pubsub::Params::Events(param))) => {
+                let from_block = param.filter.from_block;
                 match param.filter.try_into() {
                     Ok(f) => self
                         .service
@@ -113,6 +140,7 @@ impl PubSubImpl {
                             subscriber,
                             filter: f,
                             decode: param.decode,
+                            from_block,
                         })
                         .map_err(|e| {
                             let msg = map_send_err(&e);
@@ -131,6 +159,76 @@ impl PubSubImpl {
                 subscriber,
                 errors::invalid_params("events", "Expected a filter object."),
             )),
+            (pubsub::Kind::SyncProgress, None) => self
+                .service
+                .try_send(SubscribeSyncProgress(subscriber))
+                .map_err(|e| {
+                    let msg = map_send_err(&e);
+                    (
+                        match e {
+                            TrySendError::Disconnected(t) => t.0,
+                            TrySendError::Full(t) => t.0,
+                        },
+                        msg,
+                    )
+                }),
+            (pubsub::Kind::SyncProgress, _) => Err((
+                subscriber,
+                errors::invalid_params("syncProgress", "Expected no parameters."),
+            )),
+            (pubsub::Kind::TransactionStatus, None) => {
+                self.subscribe_txn_status(subscriber, pubsub::TxnStatusFilter::default())
+            }
+            (pubsub::Kind::TransactionStatus, Some(pubsub::Params::TransactionStatus(filter))) => {
+                self.subscribe_txn_status(subscriber, filter)
+            }
+            (pubsub::Kind::TransactionStatus, _) => Err((
+                subscriber,
+                errors::invalid_params(
+                    "transactionStatus",
+                    "Expected a filter object with txn_hashes or senders.",
+                ),
+            )),
+            (pubsub::Kind::ResourceChanges, Some(pubsub::Params::ResourceChanges(filter)))
+                if !filter.is_empty() =>
+            {
+                self.service
+                    .try_send(SubscribeResourceChanges { subscriber, filter })
+                    .map_err(|e| {
+                        let msg = map_send_err(&e);
+                        (
+                            match e {
+                                TrySendError::Disconnected(t) => t.subscriber,
+                                TrySendError::Full(t) => t.subscriber,
+                            },
+                            msg,
+                        )
+                    })
+            }
+            (pubsub::Kind::ResourceChanges, _) => Err((
+                subscriber,
+                errors::invalid_params(
+                    "resourceChanges",
+                    "Expected a filter object with resources or addrs.",
+                ),
+            )),
+            (pubsub::Kind::ChainReorg, None) => self
+                .service
+                .try_send(SubscribeChainReorg(subscriber))
+                .map_err(|e| {
+                    let msg = map_send_err(&e);
+                    (
+                        match e {
+                            TrySendError::Disconnected(t) => t.0,
+                            TrySendError::Full(t) => t.0,
+                        },
+                        msg,
+                    )
+                }),
+            (pubsub::Kind::ChainReorg, _) => Err((
+                subscriber,
+                errors::invalid_params("chainReorg", "Expected no parameters."),
+            )),
             (pubsub::Kind::NewMintBlock, _) => self
                 .service
                 .try_send(SubscribeMintBlock(subscriber))
@@ -148,6 +246,27 @@ impl PubSubImpl {
     }
 }
 
+impl PubSubImpl {
+    fn subscribe_txn_status(
+        &self,
+        subscriber: Subscriber<pubsub::Result>,
+        filter: pubsub::TxnStatusFilter,
+    ) -> Result<(), (Subscriber<pubsub::Result>, jsonrpc_core::Error)> {
+        self.service
+            .try_send(SubscribeTxnStatus { subscriber, filter })
+            .map_err(|e| {
+                let msg = map_send_err(&e);
+                (
+                    match e {
+                        TrySendError::Disconnected(t) => t.subscriber,
+                        TrySendError::Full(t) => t.subscriber,
+                    },
+                    msg,
+                )
+            })
+    }
+}
+
 impl StarcoinPubSub for PubSubImpl {
     type Metadata = Metadata;
     fn subscribe(
@@ -155,8 +274,18 @@ impl StarcoinPubSub for PubSubImpl {
         _meta: Metadata,
         subscriber: Subscriber<pubsub::Result>,
         kind: pubsub::Kind,
-        params: Option<pubsub::Params>,
+        params: Option<Value>,
     ) {
+        let params = match params
+            .map(|params| pubsub::Params::parse(&kind, params))
+            .transpose()
+        {
+            Ok(params) => params,
+            Err(e) => {
+                let _ = subscriber.reject(errors::invalid_params("params", e.to_string()));
+                return;
+            }
+        };
         if let Err((subscriber, error)) = self.inner_subscribe(_meta, subscriber, kind, params) {
             let _ = subscriber.reject(error);
         }
@@ -189,10 +318,12 @@ impl ServiceFactory<PubSubService> for PubSubServiceFactory {
     fn create(ctx: &mut ServiceContext<PubSubService>) -> Result<PubSubService> {
         let miner_service = ctx.service_ref::<MinerService>()?.clone();
         let storage = ctx.get_shared::<Arc<Storage>>()?;
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
         Ok(PubSubService::new(
             ctx.get_shared::<TxPoolService>()?,
             miner_service,
             storage,
+            config.rpc.block_query_max_range(),
         ))
     }
 }
@@ -202,11 +333,19 @@ pub struct PubSubService {
     txpool: TxPoolService,
     miner_service: ServiceRef<MinerService>,
     storage: Arc<Storage>,
-    new_header_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<NewHeadNotification>>,
+    block_query_max_range: u64,
+    new_header_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<HeadChange>>,
+    chain_reorg_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<ChainReorg>>,
     new_event_subscribers:
         HashMap<SubscriptionId, mpsc::UnboundedSender<ContractEventNotification>>,
+    /// event subscriptions which replay from a historical block, they are driven by new heads.
+    resumed_event_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<NewHeadNotification>>,
     mint_block_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<MintBlockEvent>>,
+    sync_progress_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<SyncProgressEvent>>,
+    resource_change_subscribers:
+        HashMap<SubscriptionId, mpsc::UnboundedSender<WriteSetNotification>>,
     new_pending_txn_tasks: Arc<RwLock<HashMap<SubscriptionId, AbortHandle>>>,
+    txn_status_tasks: Arc<RwLock<HashMap<SubscriptionId, AbortHandle>>>,
 }
 
 impl PubSubService {
@@ -214,6 +353,7 @@ impl PubSubService {
         txpool: TxPoolService,
         miner_service: ServiceRef<MinerService>,
         storage: Arc<Storage>,
+        block_query_max_range: u64,
     ) -> Self {
         let subscriber_id = Arc::new(atomic::AtomicU64::new(0));
         Self {
@@ -221,10 +361,16 @@ impl PubSubService {
             txpool,
             miner_service,
             storage,
+            block_query_max_range,
             new_event_subscribers: Default::default(),
+            resumed_event_subscribers: Default::default(),
             new_header_subscribers: Default::default(),
+            chain_reorg_subscribers: Default::default(),
             mint_block_subscribers: Default::default(),
+            sync_progress_subscribers: Default::default(),
+            resource_change_subscribers: Default::default(),
             new_pending_txn_tasks: Arc::new(RwLock::new(HashMap::default())),
+            txn_status_tasks: Arc::new(RwLock::new(HashMap::default())),
         }
     }
     fn next_id(&self) -> SubscriptionId {
@@ -234,14 +380,24 @@ impl PubSubService {
 }
 
 type NewHeadNotification = Notification<ThinBlock>;
+
+/// Changes of the main chain head, the re-org is sent before the new head.
+#[derive(Clone, Debug)]
+enum HeadChange {
+    NewHead(NewHeadNotification),
+    Reorg(ChainReorg),
+}
 // type NewTxns = Arc<[HashValue]>;
 
 impl ActorService for PubSubService {
     fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.set_mailbox_capacity(1024);
         ctx.subscribe::<NewHeadNotification>();
+        ctx.subscribe::<ChainReorg>();
         ctx.subscribe::<ContractEventNotification>();
         ctx.subscribe::<MintBlockEvent>();
+        ctx.subscribe::<SyncProgressEvent>();
+        ctx.subscribe::<WriteSetNotification>();
 
         Ok(())
     }
@@ -249,7 +405,18 @@ impl ActorService for PubSubService {
 
 impl ActorEventHandler<Self, NewHeadNotification> for PubSubService {
     fn handle_event(&mut self, msg: NewHeadNotification, _ctx: &mut ServiceContext<PubSubService>) {
-        send_to_all(&mut self.new_header_subscribers, msg);
+        send_to_all(&mut self.resumed_event_subscribers, msg.clone());
+        send_to_all(&mut self.new_header_subscribers, HeadChange::NewHead(msg));
+    }
+}
+
+impl ActorEventHandler<Self, ChainReorg> for PubSubService {
+    fn handle_event(&mut self, msg: ChainReorg, _ctx: &mut ServiceContext<PubSubService>) {
+        send_to_all(
+            &mut self.new_header_subscribers,
+            HeadChange::Reorg(msg.clone()),
+        );
+        send_to_all(&mut self.chain_reorg_subscribers, msg);
     }
 }
 
@@ -269,8 +436,25 @@ impl ActorEventHandler<Self, MintBlockEvent> for PubSubService {
     }
 }
 
+impl ActorEventHandler<Self, SyncProgressEvent> for PubSubService {
+    fn handle_event(&mut self, msg: SyncProgressEvent, _ctx: &mut ServiceContext<PubSubService>) {
+        send_to_all(&mut self.sync_progress_subscribers, msg);
+    }
+}
+
+impl ActorEventHandler<Self, WriteSetNotification> for PubSubService {
+    fn handle_event(
+        &mut self,
+        msg: WriteSetNotification,
+        _ctx: &mut ServiceContext<PubSubService>,
+    ) {
+        send_to_all(&mut self.resource_change_subscribers, msg);
+    }
+}
+
+/// Subscribe new heads, and the removed blocks if the flag is true.
 #[derive(Debug)]
-struct SubscribeNewHeads(Subscriber<pubsub::Result>);
+struct SubscribeNewHeads(Subscriber<pubsub::Result>, bool);
 
 impl ServiceRequest for SubscribeNewHeads {
     type Response = ();
@@ -278,7 +462,7 @@ impl ServiceRequest for SubscribeNewHeads {
 
 impl ServiceHandler<Self, SubscribeNewHeads> for PubSubService {
     fn handle(&mut self, msg: SubscribeNewHeads, ctx: &mut ServiceContext<Self>) {
-        let SubscribeNewHeads(sink) = msg;
+        let SubscribeNewHeads(sink, with_removed) = msg;
         let (sender, receiver) = mpsc::unbounded();
         let subscriber_id = self.next_id();
         self.new_header_subscribers
@@ -287,7 +471,30 @@ impl ServiceHandler<Self, SubscribeNewHeads> for PubSubService {
             receiver,
             subscriber_id,
             sink,
-            NewHeadHandler,
+            NewHeadHandler { with_removed },
+        ));
+    }
+}
+
+#[derive(Debug)]
+struct SubscribeChainReorg(Subscriber<pubsub::Result>);
+
+impl ServiceRequest for SubscribeChainReorg {
+    type Response = ();
+}
+
+impl ServiceHandler<Self, SubscribeChainReorg> for PubSubService {
+    fn handle(&mut self, msg: SubscribeChainReorg, ctx: &mut ServiceContext<Self>) {
+        let SubscribeChainReorg(subscriber) = msg;
+        let (sender, receiver) = mpsc::unbounded();
+        let subscriber_id = self.next_id();
+        self.chain_reorg_subscribers
+            .insert(subscriber_id.clone(), sender);
+        ctx.spawn(run_subscription(
+            receiver,
+            subscriber_id,
+            subscriber,
+            ChainReorgHandler,
         ));
     }
 }
@@ -333,11 +540,36 @@ impl ServiceHandler<Self, SubscribeMintBlock> for PubSubService {
     }
 }
 
+#[derive(Debug)]
+struct SubscribeSyncProgress(Subscriber<pubsub::Result>);
+
+impl ServiceRequest for SubscribeSyncProgress {
+    type Response = ();
+}
+
+impl ServiceHandler<Self, SubscribeSyncProgress> for PubSubService {
+    fn handle(&mut self, msg: SubscribeSyncProgress, ctx: &mut ServiceContext<Self>) {
+        let SubscribeSyncProgress(subscriber) = msg;
+        let (sender, receiver) = mpsc::unbounded();
+        let subscriber_id = self.next_id();
+        self.sync_progress_subscribers
+            .insert(subscriber_id.clone(), sender);
+        ctx.spawn(run_subscription(
+            receiver,
+            subscriber_id,
+            subscriber,
+            SyncProgressHandler,
+        ));
+    }
+}
+
 #[derive(Debug)]
 struct SubscribeEvents {
     subscriber: Subscriber<pubsub::Result>,
     filter: Filter,
     decode: bool,
+    /// replay the events from the block before the new ones if specified.
+    from_block: Option<BlockNumber>,
 }
 
 impl ServiceRequest for SubscribeEvents {
@@ -350,19 +582,99 @@ impl ServiceHandler<Self, SubscribeEvents> for PubSubService {
             subscriber,
             filter,
             decode,
+            from_block,
         } = msg;
+        let event_handler = ContractEventHandler {
+            storage: self.storage.clone(),
+            filter,
+            decode,
+        };
+        let from_block = match from_block {
+            Some(from_block) => from_block,
+            None => {
+                let (sender, receiver) = mpsc::unbounded();
+                let subscriber_id = self.next_id();
+                self.new_event_subscribers
+                    .insert(subscriber_id.clone(), sender);
+                ctx.spawn(run_subscription(
+                    receiver,
+                    subscriber_id,
+                    subscriber,
+                    event_handler,
+                ));
+                return;
+            }
+        };
+        let head = match self.main_head_header() {
+            Ok(head) => head,
+            Err(e) => {
+                let _ = subscriber.reject(map_err(e));
+                return;
+            }
+        };
+        if head.number().saturating_sub(from_block) > self.block_query_max_range {
+            let _ = subscriber.reject(errors::invalid_params(
+                "events",
+                format!(
+                    "from_block is too far, max block range is {}",
+                    self.block_query_max_range
+                ),
+            ));
+            return;
+        }
         let (sender, receiver) = mpsc::unbounded();
+        // the current head goes first, the handler replays the history blocks before it.
+        if head.number() >= from_block {
+            let _ = sender.unbounded_send(Notification(ThinBlock::new(head, vec![])));
+        }
         let subscriber_id = self.next_id();
-        self.new_event_subscribers
+        self.resumed_event_subscribers
             .insert(subscriber_id.clone(), sender);
         ctx.spawn(run_subscription(
             receiver,
             subscriber_id,
             subscriber,
-            ContractEventHandler {
+            ResumedEventHandler::new(event_handler, from_block, self.block_query_max_range),
+        ));
+    }
+}
+
+impl PubSubService {
+    fn main_head_header(&self) -> Result<BlockHeader> {
+        let startup_info = self
+            .storage
+            .get_startup_info()?
+            .ok_or_else(|| format_err!("Startup info should exist."))?;
+        self.storage
+            .get_block_header_by_hash(startup_info.main)?
+            .ok_or_else(|| format_err!("Cannot find header of main head {}", startup_info.main))
+    }
+}
+
+#[derive(Debug)]
+struct SubscribeResourceChanges {
+    subscriber: Subscriber<pubsub::Result>,
+    filter: pubsub::ResourceChangeFilter,
+}
+
+impl ServiceRequest for SubscribeResourceChanges {
+    type Response = ();
+}
+
+impl ServiceHandler<Self, SubscribeResourceChanges> for PubSubService {
+    fn handle(&mut self, msg: SubscribeResourceChanges, ctx: &mut ServiceContext<Self>) {
+        let SubscribeResourceChanges { subscriber, filter } = msg;
+        let (sender, receiver) = mpsc::unbounded();
+        let subscriber_id = self.next_id();
+        self.resource_change_subscribers
+            .insert(subscriber_id.clone(), sender);
+        ctx.spawn(run_subscription(
+            receiver,
+            subscriber_id,
+            subscriber,
+            ResourceChangeHandler {
                 storage: self.storage.clone(),
                 filter,
-                decode,
             },
         ));
     }
@@ -406,6 +718,45 @@ impl ServiceHandler<Self, SubscribeNewPendingTxns> for PubSubService {
     }
 }
 
+#[derive(Debug)]
+struct SubscribeTxnStatus {
+    subscriber: Subscriber<pubsub::Result>,
+    filter: pubsub::TxnStatusFilter,
+}
+
+impl ServiceRequest for SubscribeTxnStatus {
+    type Response = ();
+}
+
+impl ServiceHandler<Self, SubscribeTxnStatus> for PubSubService {
+    fn handle(&mut self, msg: SubscribeTxnStatus, ctx: &mut ServiceContext<Self>) {
+        let SubscribeTxnStatus { subscriber, filter } = msg;
+        let subscriber_id = self.next_id();
+        let tasks = self.txn_status_tasks.clone();
+        let subscriber_id_clone = subscriber_id.clone();
+        let receiver = self.txpool.subscribe_txn_status();
+        let (f, abort_handle) = futures::future::abortable(async move {
+            run_subscription(
+                receiver,
+                subscriber_id_clone.clone(),
+                subscriber,
+                TxnStatusHandler { filter },
+            )
+            .await;
+            // remove self from task list.
+            tasks.write().remove(&subscriber_id_clone);
+        });
+
+        ctx.spawn(async move {
+            let _ = f.await;
+        });
+
+        self.txn_status_tasks
+            .write()
+            .insert(subscriber_id, abort_handle);
+    }
+}
+
 #[derive(Debug)]
 struct Unsubscribe(SubscriptionId);
 
@@ -416,14 +767,21 @@ impl ServiceRequest for Unsubscribe {
 impl ServiceHandler<Self, Unsubscribe> for PubSubService {
     fn handle(&mut self, msg: Unsubscribe, _ctx: &mut ServiceContext<Self>) {
         self.new_header_subscribers.remove(&msg.0);
+        self.chain_reorg_subscribers.remove(&msg.0);
         self.new_event_subscribers.remove(&msg.0);
+        self.resumed_event_subscribers.remove(&msg.0);
         self.mint_block_subscribers.remove(&msg.0);
+        self.sync_progress_subscribers.remove(&msg.0);
+        self.resource_change_subscribers.remove(&msg.0);
         self.miner_service.do_send(UpdateSubscriberNumRequest {
             number: Some(self.mint_block_subscribers.len() as u32),
         });
         if let Some(h) = self.new_pending_txn_tasks.write().remove(&msg.0) {
             h.abort();
         }
+        if let Some(h) = self.txn_status_tasks.write().remove(&msg.0) {
+            h.abort();
+        }
     }
 }
 
@@ -461,6 +819,7 @@ async fn run_subscription<M, Handler>(
     M: Send + 'static,
     Handler: EventHandler<M> + Send + 'static,
 {
+    let mut event_handler = event_handler;
     // TODO: should we use assgin_id_async?
     if let Ok(sink) = subscriber.assign_id(subscriber_id.clone()) {
         let forward = msg_channel
@@ -477,29 +836,65 @@ async fn run_subscription<M, Handler>(
 }
 
 trait EventHandler<M> {
-    fn handle(&self, msg: M) -> Vec<jsonrpc_core::Result<pubsub::Result>>;
+    fn handle(&mut self, msg: M) -> Vec<jsonrpc_core::Result<pubsub::Result>>;
 }
 
 #[derive(Copy, Clone, Debug)]
 pub struct TxnEventHandler;
 
 impl EventHandler<Arc<[HashValue]>> for TxnEventHandler {
-    fn handle(&self, msg: Arc<[HashValue]>) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+    fn handle(&mut self, msg: Arc<[HashValue]>) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
         vec![Ok(pubsub::Result::TransactionHash(msg.to_vec()))]
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct TxnStatusHandler {
+    filter: pubsub::TxnStatusFilter,
+}
+
+impl EventHandler<Arc<[TxnStatusEvent]>> for TxnStatusHandler {
+    fn handle(&mut self, msg: Arc<[TxnStatusEvent]>) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+        msg.iter()
+            .filter(|event| self.filter.matching(event))
+            .map(|event| Ok(pubsub::Result::TransactionStatus(Box::new(event.clone()))))
+            .collect()
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
-pub struct NewHeadHandler;
+pub struct NewHeadHandler {
+    with_removed: bool,
+}
 
-impl EventHandler<Notification<ThinBlock>> for NewHeadHandler {
-    fn handle(&self, msg: Notification<ThinBlock>) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
-        let Notification(block) = msg;
-        vec![Ok(pubsub::Result::Block(Box::new(BlockView {
-            header: block.header.into(),
-            body: block.body.into(),
-            uncles: vec![],
-        })))]
+impl EventHandler<HeadChange> for NewHeadHandler {
+    fn handle(&mut self, msg: HeadChange) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+        match msg {
+            HeadChange::NewHead(Notification(block)) => {
+                vec![Ok(pubsub::Result::Block(Box::new(BlockView {
+                    header: block.header.into(),
+                    body: block.body.into(),
+                    uncles: vec![],
+                })))]
+            }
+            // the latest retracted block is removed first.
+            HeadChange::Reorg(reorg) if self.with_removed => reorg
+                .retracted
+                .into_iter()
+                .rev()
+                .map(|block| Ok(pubsub::Result::RemovedBlock(Box::new(block.into()))))
+                .collect(),
+            HeadChange::Reorg(_) => vec![],
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct ChainReorgHandler;
+
+impl EventHandler<ChainReorg> for ChainReorgHandler {
+    fn handle(&mut self, msg: ChainReorg) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+        vec![Ok(pubsub::Result::ChainReorg(Box::new(msg.into())))]
     }
 }
 
@@ -507,11 +902,20 @@ impl EventHandler<Notification<ThinBlock>> for NewHeadHandler {
 pub struct NewMintBlockHandler;
 
 impl EventHandler<MintBlockEvent> for NewMintBlockHandler {
-    fn handle(&self, msg: MintBlockEvent) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+    fn handle(&mut self, msg: MintBlockEvent) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
         vec![Ok(pubsub::Result::MintBlock(Box::new(msg)))]
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub struct SyncProgressHandler;
+
+impl EventHandler<SyncProgressEvent> for SyncProgressHandler {
+    fn handle(&mut self, msg: SyncProgressEvent) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+        vec![Ok(pubsub::Result::SyncProgress(Box::new(msg.0)))]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ContractEventHandler {
     filter: Filter,
@@ -520,7 +924,10 @@ pub struct ContractEventHandler {
 }
 
 impl EventHandler<ContractEventNotification> for ContractEventHandler {
-    fn handle(&self, msg: ContractEventNotification) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+    fn handle(
+        &mut self,
+        msg: ContractEventNotification,
+    ) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
         let Notification((state_root, events)) = msg;
         let filtered = events
             .as_ref()
@@ -569,3 +976,142 @@ impl EventHandler<ContractEventNotification> for ContractEventHandler {
             .collect()
     }
 }
+
+/// Handle the events subscription which resumes from a history block.
+/// The events of every main chain block from `from_block` are sent once, in the order of block
+/// number, blocks missed between two new heads (such as the history blocks before the first head,
+/// or the new branch of a re-org) are loaded from storage.
+pub struct ResumedEventHandler {
+    inner: ContractEventHandler,
+    from_block: BlockNumber,
+    /// how many blocks back from the latest sent one are tracked, also bounds the re-org depth.
+    window: u64,
+    /// the blocks whose events have been sent.
+    sent_blocks: HashMap<HashValue, BlockNumber>,
+    latest_sent: BlockNumber,
+}
+
+impl ResumedEventHandler {
+    pub fn new(inner: ContractEventHandler, from_block: BlockNumber, window: u64) -> Self {
+        Self {
+            inner,
+            from_block,
+            window,
+            sent_blocks: HashMap::new(),
+            latest_sent: 0,
+        }
+    }
+
+    /// Collect the blocks not sent yet from the head back, in the order of block number.
+    fn unsent_blocks(&self, head: BlockHeader) -> Result<Vec<BlockHeader>> {
+        let mut blocks = vec![];
+        let mut current = Some(head);
+        while let Some(header) = current {
+            if self.sent_blocks.contains_key(&header.id())
+                || header.number() < self.from_block
+                || header.number().saturating_add(self.window) < self.latest_sent
+            {
+                break;
+            }
+            current = if header.number() > 0 {
+                Some(
+                    self.inner
+                        .storage
+                        .get_block_header_by_hash(header.parent_hash())?
+                        .ok_or_else(|| {
+                            format_err!("Cannot find block header {}", header.parent_hash())
+                        })?,
+                )
+            } else {
+                None
+            };
+            blocks.push(header);
+        }
+        blocks.reverse();
+        Ok(blocks)
+    }
+}
+
+impl EventHandler<Notification<ThinBlock>> for ResumedEventHandler {
+    fn handle(
+        &mut self,
+        msg: Notification<ThinBlock>,
+    ) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+        let Notification(block) = msg;
+        let blocks = match self.unsent_blocks(block.header) {
+            Ok(blocks) => blocks,
+            Err(e) => return vec![Err(map_err(e))],
+        };
+        let mut results = vec![];
+        for header in blocks {
+            match block_events(self.inner.storage.as_ref(), header.id(), header.number()) {
+                Ok(events) => results.extend(
+                    self.inner
+                        .handle(Notification((header.state_root(), events.into()))),
+                ),
+                Err(e) => {
+                    results.push(Err(map_err(e)));
+                    break;
+                }
+            }
+            self.latest_sent = self.latest_sent.max(header.number());
+            self.sent_blocks.insert(header.id(), header.number());
+        }
+        let (latest_sent, window) = (self.latest_sent, self.window);
+        self.sent_blocks
+            .retain(|_, number| number.saturating_add(window) >= latest_sent);
+        results
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ResourceChangeHandler {
+    filter: pubsub::ResourceChangeFilter,
+    storage: Arc<Storage>,
+}
+
+impl EventHandler<WriteSetNotification> for ResourceChangeHandler {
+    fn handle(&mut self, msg: WriteSetNotification) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+        let Notification((state_root, write_sets)) = msg;
+        // resolve the abi of resources by the state of the block.
+        let state = ChainStateDB::new(self.storage.clone(), Some(state_root));
+        let mut changes = vec![];
+        for txn_write_set in write_sets.iter() {
+            for (access_path, write_op) in txn_write_set.write_set.iter() {
+                let resource_type = match &access_path.path {
+                    DataPath::Resource(struct_tag) => struct_tag,
+                    DataPath::Code(_) => continue,
+                };
+                if !self.filter.matching(&access_path.address, resource_type) {
+                    continue;
+                }
+                let change = match write_op {
+                    WriteOp::Value(value) => {
+                        view_resource(&state, resource_type.clone(), value.as_slice()).map(
+                            |decoded| {
+                                (
+                                    Some(StrView(value.clone())),
+                                    Some(DecodedMoveValue::from(decoded)),
+                                )
+                            },
+                        )
+                    }
+                    WriteOp::Deletion => Ok((None, None)),
+                };
+                changes.push(change.map_err(map_err).map(|(raw, json)| {
+                    pubsub::Result::ResourceChange(Box::new(ResourceChangeView {
+                        block_hash: txn_write_set.block_hash,
+                        block_number: txn_write_set.block_number.into(),
+                        transaction_hash: txn_write_set.transaction_hash,
+                        transaction_index: txn_write_set.transaction_index,
+                        address: access_path.address,
+                        resource_type: StrView(resource_type.clone()),
+                        raw,
+                        json,
+                    }))
+                }));
+            }
+        }
+        changes
+    }
+}
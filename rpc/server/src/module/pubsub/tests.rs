@@ -10,20 +10,25 @@ use serde_json::Value;
 use starcoin_account_api::AccountInfo;
 use starcoin_chain::BlockChain;
 use starcoin_chain::{ChainReader, ChainWriter};
-use starcoin_chain_notify::ChainNotifyHandlerService;
+use starcoin_chain_notify::{block_events, ChainNotifyHandlerService};
 use starcoin_consensus::Consensus;
 use starcoin_crypto::{ed25519::Ed25519PrivateKey, Genesis, HashValue, PrivateKey};
 use starcoin_executor::DEFAULT_EXPIRATION_TIME;
 use starcoin_logger::prelude::*;
 use starcoin_rpc_api::metadata::Metadata;
 use starcoin_rpc_api::pubsub::StarcoinPubSub;
+use starcoin_rpc_api::types::{ChainReorgView, RemovedBlockView};
 use starcoin_service_registry::bus::{Bus, BusService};
 use starcoin_service_registry::RegistryAsyncService;
 use starcoin_state_api::StateReaderExt;
 use starcoin_storage::BlockStore;
+use starcoin_sync_api::{SyncProgressEvent, SyncProgressReport, TaskProgressReport};
 use starcoin_txpool_api::TxPoolSyncService;
-use starcoin_types::system_events::MintBlockEvent;
-use starcoin_types::system_events::NewHeadBlock;
+use starcoin_types::block::BlockIdAndNumber;
+use starcoin_types::peer_info::PeerId;
+use starcoin_types::startup_info::StartupInfo;
+use starcoin_types::system_events::{BlockWriteSets, NewHeadBlock};
+use starcoin_types::system_events::{ChainReorg, MintBlockEvent};
 use starcoin_types::{account_address, U256};
 use starcoin_vm_types::genesis_config::ConsensusStrategy;
 use std::sync::Arc;
@@ -108,7 +113,7 @@ pub async fn test_subscribe_to_events() -> Result<()> {
 
     // send block
     let block_detail = Arc::new(executed_block);
-    bus.broadcast(NewHeadBlock(block_detail))?;
+    bus.broadcast(NewHeadBlock(block_detail, None))?;
 
     let mut receiver = receiver;
 
@@ -129,6 +134,93 @@ pub async fn test_subscribe_to_events() -> Result<()> {
     Ok(())
 }
 
+#[stest::test]
+pub async fn test_subscribe_to_events_from_block() -> Result<()> {
+    let (_txpool_service, storage, config, _, registry) = test_helper::start_txpool().await;
+    let startup_info = storage.get_startup_info()?.unwrap();
+    let net = config.net();
+    let mut block_chain = BlockChain::new(net.time_service(), startup_info.main, storage.clone())?;
+    let miner_account = AccountInfo::random();
+    let mut blocks = vec![];
+    for seq_num in 0..2 {
+        let txn = starcoin_executor::build_transfer_from_association(
+            AccountInfo::random().address,
+            seq_num,
+            10000,
+            net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+            net,
+        );
+        let (block_template, _) = block_chain.create_block_template(
+            *miner_account.address(),
+            None,
+            vec![txn.as_signed_user_txn()?.clone()],
+            vec![],
+            None,
+        )?;
+        let new_block = block_chain
+            .consensus()
+            .create_block(block_template, net.time_service().as_ref())?;
+        blocks.push(block_chain.apply(new_block)?);
+    }
+    let event_count = |number: usize| -> Result<usize> {
+        let block = blocks[number - 1].block();
+        Ok(block_events(storage.as_ref(), block.id(), block.header().number())?.len())
+    };
+    // the block 1 is the main head when subscribing, block 2 comes later.
+    storage.save_startup_info(StartupInfo::new(blocks[0].block().id()))?;
+
+    let bus = registry.service_ref::<BusService>().await?;
+    let _notify_service = registry.register::<ChainNotifyHandlerService>().await?;
+    let service = registry
+        .register_by_factory::<PubSubService, PubSubServiceFactory>()
+        .await?;
+    let pubsub = PubSubImpl::new(service);
+    let pubsub = pubsub.to_delegate();
+
+    let mut io = MetaIoHandler::default();
+    io.extend_with(pubsub);
+
+    let mut metadata = Metadata::default();
+    let (sender, mut receiver) = futures::channel::mpsc::unbounded();
+    metadata.session = Some(Arc::new(Session::new(sender)));
+
+    let request = r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"events"}, {"from_block": 1}], "id": 1}"#;
+    let response = r#"{"jsonrpc":"2.0","result":0,"id":1}"#;
+    let resp = io.handle_request(request, metadata.clone()).await;
+    assert_eq!(resp, Some(response.to_owned()));
+
+    let mut block_numbers = vec![];
+    while let Ok(Some(res)) = timeout(Duration::from_secs(2), receiver.next()).await {
+        let r: Value = serde_json::from_str(&res)?;
+        block_numbers.push(
+            r["params"]["result"]["block_number"]
+                .as_str()
+                .unwrap()
+                .to_string(),
+        );
+    }
+    // history events are replayed.
+    assert!(event_count(1)? > 0);
+    assert_eq!(block_numbers, vec!["1".to_string(); event_count(1)?]);
+
+    // the late notification of the replayed block is ignored.
+    for block in blocks.iter() {
+        bus.broadcast(NewHeadBlock(Arc::new(block.clone()), None))?;
+    }
+    let mut block_numbers = vec![];
+    while let Ok(Some(res)) = timeout(Duration::from_secs(2), receiver.next()).await {
+        let r: Value = serde_json::from_str(&res)?;
+        block_numbers.push(
+            r["params"]["result"]["block_number"]
+                .as_str()
+                .unwrap()
+                .to_string(),
+        );
+    }
+    assert_eq!(block_numbers, vec!["2".to_string(); event_count(2)?]);
+    Ok(())
+}
+
 #[stest::test]
 pub async fn test_subscribe_to_pending_transactions() -> Result<()> {
     // given
@@ -189,6 +281,68 @@ pub async fn test_subscribe_to_pending_transactions() -> Result<()> {
     Ok(())
 }
 
+#[stest::test]
+pub async fn test_subscribe_to_transaction_status() -> Result<()> {
+    let (txpool_service, _, config, _, registry) = test_helper::start_txpool().await;
+    let service = registry
+        .register_by_factory::<PubSubService, PubSubServiceFactory>()
+        .await?;
+    let pubsub = PubSubImpl::new(service);
+    let pubsub = pubsub.to_delegate();
+
+    let mut io = MetaIoHandler::default();
+    io.extend_with(pubsub);
+
+    let mut metadata = Metadata::default();
+    let (sender, receiver) = futures::channel::mpsc::unbounded();
+    metadata.session = Some(Arc::new(Session::new(sender)));
+
+    let txns = (0..2u64)
+        .map(|seq| {
+            let account = AccountInfo::random();
+            let txn = starcoin_executor::build_transfer_from_association(
+                account.address,
+                seq,
+                10000,
+                DEFAULT_EXPIRATION_TIME,
+                config.net(),
+            );
+            txn.as_signed_user_txn().map(|txn| txn.clone())
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Subscribe the status of the second txn only.
+    let request = format!(
+        r#"{{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{{"type_name":"transactionStatus"}}, {{"txn_hashes": ["0x{}"]}}], "id": 1}}"#,
+        txns[1].id().to_hex()
+    );
+    let response = r#"{"jsonrpc":"2.0","result":0,"id":1}"#;
+    let resp = io.handle_request(request.as_str(), metadata.clone()).await;
+    assert_eq!(resp, Some(response.to_owned()));
+
+    for txn in txns.clone() {
+        txpool_service.add_txns(vec![txn]).pop().unwrap().unwrap();
+    }
+    let mut receiver = receiver;
+    let res = receiver.next().await.unwrap();
+    let res: Value = serde_json::from_str(res.as_str())?;
+    let result = &res["params"]["result"];
+    assert_eq!(
+        result["txn_hash"].as_str(),
+        Some(format!("0x{}", txns[1].id().to_hex()).as_str())
+    );
+    assert_eq!(result["status"].as_str(), Some("added"));
+
+    let request = r#"{"jsonrpc": "2.0", "method": "starcoin_unsubscribe", "params": [0], "id": 1}"#;
+    let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+    let resp = io.handle_request(request, metadata).await;
+    assert_eq!(resp, Some(response.to_owned()));
+
+    let res = timeout(Duration::from_secs(1), receiver.next()).await?;
+    assert_eq!(res, None);
+    Ok(())
+}
+
 #[stest::test]
 pub async fn test_subscribe_to_mint_block() -> Result<()> {
     let (_txpool_service, .., registry) = test_helper::start_txpool().await;
@@ -238,3 +392,216 @@ pub async fn test_subscribe_to_mint_block() -> Result<()> {
     assert_eq!(resp, Some(response.to_owned()));
     Ok(())
 }
+
+#[stest::test]
+pub async fn test_subscribe_to_sync_progress() -> Result<()> {
+    let (_txpool_service, .., registry) = test_helper::start_txpool().await;
+    let bus = registry.service_ref::<BusService>().await?;
+    let service = registry
+        .register_by_factory::<PubSubService, PubSubServiceFactory>()
+        .await?;
+    let pubsub = PubSubImpl::new(service);
+    let pubsub = pubsub.to_delegate();
+
+    let mut io = MetaIoHandler::default();
+    io.extend_with(pubsub);
+
+    let mut metadata = Metadata::default();
+    let (sender, mut receiver) = futures::channel::mpsc::unbounded();
+    metadata.session = Some(Arc::new(Session::new(sender)));
+
+    // Fail if params are provided
+    let request = r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"syncProgress"}, {}], "id": 1}"#;
+    let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: syncProgress","data":"\"Expected no parameters.\""},"id":1}"#;
+    let resp = io.handle_request(request, metadata.clone()).await;
+    assert_eq!(resp, Some(response.to_owned()));
+
+    // Subscribe
+    let request = r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"syncProgress"}], "id": 1}"#;
+    let response = r#"{"jsonrpc":"2.0","result":0,"id":1}"#;
+    let resp = io.handle_request(request, metadata.clone()).await;
+    assert_eq!(resp, Some(response.to_owned()));
+
+    let report = SyncProgressReport {
+        target_id: HashValue::random(),
+        begin_number: Some(10),
+        target_number: 110,
+        target_difficulty: U256::from(1024),
+        target_peers: vec![PeerId::random()],
+        current: TaskProgressReport::new(
+            "BlockSyncTask".to_string(),
+            5,
+            0,
+            5,
+            0,
+            Some(100),
+            50,
+            10,
+        ),
+        stages: vec![],
+        blocks_per_second: 5.0,
+        txns_per_second: 10.0,
+        eta_seconds: Some(10),
+        peer_contributions: vec![],
+        stalled: false,
+    };
+    bus.broadcast(SyncProgressEvent(report.clone())).unwrap();
+    let res = timeout(Duration::from_secs(1), receiver.next())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Empty value"))?;
+    let r: Value = serde_json::from_str(&res).unwrap();
+    let v = r["params"]["result"].clone();
+    let received: SyncProgressReport = serde_json::from_value(v).unwrap();
+    assert_eq!(received, report);
+
+    // Unsubscribe
+    let request = r#"{"jsonrpc": "2.0", "method": "starcoin_unsubscribe", "params": [0], "id": 1}"#;
+    let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+    let resp = io.handle_request(request, metadata).await;
+    assert_eq!(resp, Some(response.to_owned()));
+    Ok(())
+}
+
+#[stest::test]
+pub async fn test_subscribe_to_resource_changes() -> Result<()> {
+    let (_txpool_service, storage, config, _, registry) = test_helper::start_txpool().await;
+    let startup_info = storage.get_startup_info()?.unwrap();
+    let net = config.net();
+    let mut block_chain = BlockChain::new(net.time_service(), startup_info.main, storage)?;
+    let miner_account = AccountInfo::random();
+    let receiver_account = AccountInfo::random();
+    let txn = {
+        let txn = starcoin_executor::build_transfer_from_association(
+            receiver_account.address,
+            0,
+            10000,
+            net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+            net,
+        );
+        txn.as_signed_user_txn()?.clone()
+    };
+    let (block_template, _) = block_chain.create_block_template(
+        *miner_account.address(),
+        None,
+        vec![txn],
+        vec![],
+        None,
+    )?;
+    let new_block = block_chain
+        .consensus()
+        .create_block(block_template, net.time_service().as_ref())?;
+    let (executed_block, write_sets) = block_chain.apply_with_write_sets(new_block)?;
+
+    let bus = registry.service_ref::<BusService>().await?;
+    let _notify_service = registry.register::<ChainNotifyHandlerService>().await?;
+    let service = registry
+        .register_by_factory::<PubSubService, PubSubServiceFactory>()
+        .await?;
+    let pubsub = PubSubImpl::new(service);
+    let pubsub = pubsub.to_delegate();
+
+    let mut io = MetaIoHandler::default();
+    io.extend_with(pubsub);
+
+    let mut metadata = Metadata::default();
+    let (sender, mut receiver) = futures::channel::mpsc::unbounded();
+    metadata.session = Some(Arc::new(Session::new(sender)));
+
+    // Fail if no filter is provided
+    let request = r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"resourceChanges"}], "id": 1}"#;
+    let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: resourceChanges","data":"\"Expected a filter object with resources or addrs.\""},"id":1}"#;
+    let resp = io.handle_request(request, metadata.clone()).await;
+    assert_eq!(resp, Some(response.to_owned()));
+
+    // Subscribe the balance of the receiver.
+    let request = format!(
+        r#"{{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{{"type_name":"resourceChanges"}}, {{"resources": [{{"address": "{}", "resource_type": "0x1::Account::Balance<0x1::STC::STC>"}}]}}], "id": 1}}"#,
+        receiver_account.address
+    );
+    let response = r#"{"jsonrpc":"2.0","result":0,"id":1}"#;
+    let resp = io.handle_request(request.as_str(), metadata.clone()).await;
+    assert_eq!(resp, Some(response.to_owned()));
+
+    let block_write_sets = BlockWriteSets {
+        header: executed_block.header().clone(),
+        write_sets: write_sets.into(),
+    };
+    bus.broadcast(NewHeadBlock(
+        Arc::new(executed_block),
+        Some(vec![block_write_sets].into()),
+    ))?;
+    let res = timeout(Duration::from_secs(5), receiver.next())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Empty value"))?;
+    let r: Value = serde_json::from_str(&res)?;
+    let result = &r["params"]["result"];
+    assert_eq!(result["block_number"].as_str(), Some("1"));
+    let address: account_address::AccountAddress =
+        serde_json::from_value(result["address"].clone())?;
+    assert_eq!(address, receiver_account.address);
+    assert!(result["raw"].is_string());
+    assert!(result["json"].is_object());
+    Ok(())
+}
+
+#[stest::test]
+pub async fn test_subscribe_to_chain_reorg() -> Result<()> {
+    let (_txpool_service, .., registry) = test_helper::start_txpool().await;
+    let bus = registry.service_ref::<BusService>().await?;
+    let service = registry
+        .register_by_factory::<PubSubService, PubSubServiceFactory>()
+        .await?;
+    let pubsub = PubSubImpl::new(service);
+    let pubsub = pubsub.to_delegate();
+
+    let mut io = MetaIoHandler::default();
+    io.extend_with(pubsub);
+
+    let mut metadata = Metadata::default();
+    let (sender, mut receiver) = futures::channel::mpsc::unbounded();
+    metadata.session = Some(Arc::new(Session::new(sender)));
+
+    let request = r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"chainReorg"}], "id": 1}"#;
+    let response = r#"{"jsonrpc":"2.0","result":0,"id":1}"#;
+    let resp = io.handle_request(request, metadata.clone()).await;
+    assert_eq!(resp, Some(response.to_owned()));
+
+    let mut head_metadata = Metadata::default();
+    let (head_sender, mut head_receiver) = futures::channel::mpsc::unbounded();
+    head_metadata.session = Some(Arc::new(Session::new(head_sender)));
+    let request = r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"newHeads"}, {"with_removed": true}], "id": 1}"#;
+    let response = r#"{"jsonrpc":"2.0","result":1,"id":1}"#;
+    let resp = io.handle_request(request, head_metadata).await;
+    assert_eq!(resp, Some(response.to_owned()));
+
+    let reorg = ChainReorg {
+        common_ancestor: BlockIdAndNumber::new(HashValue::random(), 1),
+        retracted: vec![
+            BlockIdAndNumber::new(HashValue::random(), 2),
+            BlockIdAndNumber::new(HashValue::random(), 3),
+        ],
+        enacted: (2..5)
+            .map(|number| BlockIdAndNumber::new(HashValue::random(), number))
+            .collect(),
+    };
+    bus.broadcast(reorg.clone())?;
+
+    let res = timeout(Duration::from_secs(1), receiver.next())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Empty value"))?;
+    let r: Value = serde_json::from_str(&res)?;
+    let received: ChainReorgView = serde_json::from_value(r["params"]["result"].clone())?;
+    assert_eq!(received, reorg.clone().into());
+
+    // the latest retracted block is removed first.
+    for block in reorg.retracted.iter().rev() {
+        let res = timeout(Duration::from_secs(1), head_receiver.next())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Empty value"))?;
+        let r: Value = serde_json::from_str(&res)?;
+        let received: RemovedBlockView = serde_json::from_value(r["params"]["result"].clone())?;
+        assert_eq!(received, (*block).into());
+        assert!(received.removed);
+    }
+    Ok(())
+}
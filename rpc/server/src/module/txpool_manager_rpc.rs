@@ -0,0 +1,116 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::module::{convert_to_rpc_error, map_err};
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::txpool_manager::TxPoolManagerApi;
+use starcoin_rpc_api::types::SignedUserTransactionView;
+use starcoin_rpc_api::FutureResult;
+use starcoin_txpool_api::{SenderTxnsInspect, TxPoolSyncService};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::transaction::SignedUserTransaction;
+use std::convert::TryInto;
+
+const DEFAULT_PENDING_ALL_LIMIT: u64 = 100;
+
+pub struct TxPoolManagerRpcImpl<S>
+where
+    S: TxPoolSyncService + 'static,
+{
+    service: S,
+}
+
+impl<S> TxPoolManagerRpcImpl<S>
+where
+    S: TxPoolSyncService,
+{
+    pub fn new(service: S) -> Self {
+        Self { service }
+    }
+}
+
+impl<S> TxPoolManagerApi for TxPoolManagerRpcImpl<S>
+where
+    S: TxPoolSyncService,
+{
+    fn remove_txn(&self, txn_hash: HashValue) -> FutureResult<Option<SignedUserTransactionView>> {
+        let txn = self
+            .service
+            .remove_txn(txn_hash, false)
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(map_err);
+        Box::pin(futures::future::ready(txn))
+    }
+
+    fn inspect(&self, sender: Option<AccountAddress>) -> FutureResult<Vec<SenderTxnsInspect>> {
+        let result = self.service.inspect(sender);
+        Box::pin(futures::future::ok(result))
+    }
+
+    fn clear_sender(&self, sender: AccountAddress) -> FutureResult<Vec<HashValue>> {
+        let result = self.service.clear_sender(sender);
+        Box::pin(futures::future::ok(result))
+    }
+
+    fn pending_all(
+        &self,
+        offset: Option<u64>,
+        limit: Option<u64>,
+    ) -> FutureResult<Vec<SignedUserTransactionView>> {
+        let txns: Result<Vec<SignedUserTransactionView>, _> = self
+            .service
+            .all_txns(
+                offset.unwrap_or(0) as usize,
+                limit.unwrap_or(DEFAULT_PENDING_ALL_LIMIT) as usize,
+            )
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect();
+        Box::pin(futures::future::ready(txns.map_err(map_err)))
+    }
+
+    fn submit_local_transaction(&self, txn: SignedUserTransaction) -> FutureResult<HashValue> {
+        let txn_hash = txn.id();
+        let result = self
+            .service
+            .add_local_txns(vec![txn])
+            .pop()
+            .expect("txpool should return result")
+            .map(|_| txn_hash)
+            .map_err(convert_to_rpc_error);
+        Box::pin(futures::future::ready(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use starcoin_txpool_mock_service::MockTxPoolService;
+
+    #[test]
+    fn test_pending_all() {
+        let txns = vec![
+            SignedUserTransaction::mock(),
+            SignedUserTransaction::mock(),
+            SignedUserTransaction::mock(),
+        ];
+        let rpc = TxPoolManagerRpcImpl::new(MockTxPoolService::new_with_txns(txns.clone()));
+        let page = block_on(rpc.pending_all(Some(1), Some(10))).unwrap();
+        assert_eq!(page.len(), 2);
+        let page = block_on(rpc.pending_all(None, Some(1))).unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].transaction_hash, txns[0].id());
+    }
+    #[test]
+    fn test_submit_local_transaction() {
+        let txn = SignedUserTransaction::mock();
+        let rpc = TxPoolManagerRpcImpl::new(MockTxPoolService::new());
+        let txn_hash = block_on(rpc.submit_local_transaction(txn.clone())).unwrap();
+        assert_eq!(txn_hash, txn.id());
+        let page = block_on(rpc.pending_all(None, None)).unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].transaction_hash, txn_hash);
+    }
+}
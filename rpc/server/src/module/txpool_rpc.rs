@@ -8,7 +8,7 @@ use starcoin_crypto::HashValue;
 pub use starcoin_rpc_api::txpool::*;
 use starcoin_rpc_api::types::{SignedUserTransactionView, StrView};
 use starcoin_rpc_api::{txpool::TxPoolApi, FutureResult};
-use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService};
+use starcoin_txpool_api::{GasPriceStats, TxPoolStatus, TxPoolSyncService};
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::transaction::SignedUserTransaction;
 use std::convert::TryInto;
@@ -66,10 +66,15 @@ where
     }
 
     fn gas_price(&self) -> FutureResult<StrView<u64>> {
-        let gas_price = 1u64;
+        let gas_price = self.service.gas_price_stats().standard;
         Box::pin(futures::future::ok(gas_price.into()))
     }
 
+    fn gas_price_stats(&self) -> FutureResult<GasPriceStats> {
+        let stats = self.service.gas_price_stats();
+        Box::pin(futures::future::ok(stats))
+    }
+
     fn pending_txns(
         &self,
         addr: AccountAddress,
@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::api_registry::ApiRegistry;
+use crate::auth::RpcAuthenticator;
 use crate::extractors::{RpcExtractor, WsExtractor};
+use crate::tls::TlsServer;
 use anyhow::Result;
 use futures::stream::*;
 use futures::{FutureExt, StreamExt};
@@ -22,6 +24,7 @@ use starcoin_rpc_api::metadata::Metadata;
 use starcoin_rpc_api::network_manager::NetworkManagerApi;
 use starcoin_rpc_api::node_manager::NodeManagerApi;
 use starcoin_rpc_api::sync_manager::SyncManagerApi;
+use starcoin_rpc_api::txpool_manager::TxPoolManagerApi;
 use starcoin_rpc_api::types::ConnectLocal;
 use starcoin_rpc_api::{
     account::AccountApi, chain::ChainApi, debug::DebugApi, miner::MinerApi, node::NodeApi,
@@ -35,10 +38,12 @@ use std::sync::Arc;
 pub struct RpcService {
     config: Arc<NodeConfig>,
     api_registry: ApiRegistry,
+    authenticator: Arc<RpcAuthenticator>,
     ipc: Option<jsonrpc_ipc_server::Server>,
     http: Option<jsonrpc_http_server::Server>,
     tcp: Option<jsonrpc_tcp_server::Server>,
     ws: Option<jsonrpc_ws_server::Server>,
+    tls_servers: Vec<TlsServer>,
 }
 
 impl ActorService for RpcService {
@@ -57,26 +62,34 @@ impl ActorService for RpcService {
 }
 
 impl RpcService {
-    pub fn new(config: Arc<NodeConfig>, api_registry: ApiRegistry) -> Self {
+    pub fn new(
+        config: Arc<NodeConfig>,
+        api_registry: ApiRegistry,
+        authenticator: Arc<RpcAuthenticator>,
+    ) -> Self {
         Self {
             config,
             api_registry,
+            authenticator,
             ipc: None,
             http: None,
             tcp: None,
             ws: None,
+            tls_servers: vec![],
         }
     }
 
     #[allow(clippy::too_many_arguments)]
-    pub fn new_with_api<C, N, NM, SM, NWM, T, A, S, D, P, M, Contract>(
+    pub fn new_with_api<C, N, NM, SM, NWM, T, TM, A, S, D, P, M, Contract>(
         config: Arc<NodeConfig>,
+        authenticator: Arc<RpcAuthenticator>,
         node_api: N,
         node_manager_api: Option<NM>,
         sync_manager_api: Option<SM>,
         network_manager_api: Option<NWM>,
         chain_api: Option<C>,
         txpool_api: Option<T>,
+        txpool_manager_api: Option<TM>,
         account_api: Option<A>,
         state_api: Option<S>,
         pubsub_api: Option<P>,
@@ -91,6 +104,7 @@ impl RpcService {
         NWM: NetworkManagerApi,
         C: ChainApi,
         T: TxPoolApi,
+        TM: TxPoolManagerApi,
         A: AccountApi,
         S: StateApi,
         P: StarcoinPubSub<Metadata = Metadata>,
@@ -125,6 +139,12 @@ impl RpcService {
         if let Some(txpool_api) = txpool_api {
             api_registry.register(Api::TxPool, TxPoolApi::to_delegate(txpool_api));
         }
+        if let Some(txpool_manager_api) = txpool_manager_api {
+            api_registry.register(
+                Api::TxPoolManager,
+                TxPoolManagerApi::to_delegate(txpool_manager_api),
+            );
+        }
         if let Some(account_api) = account_api {
             api_registry.register(Api::Account, AccountApi::to_delegate(account_api));
         }
@@ -143,7 +163,15 @@ impl RpcService {
         if let Some(contract_api) = contract_api {
             api_registry.register(Api::Contract, ContractApi::to_delegate(contract_api));
         }
-        Self::new(config, api_registry)
+        Self::new(config, api_registry, authenticator)
+    }
+
+    fn authenticator(&self, auth: bool) -> Option<Arc<RpcAuthenticator>> {
+        if auth {
+            Some(self.authenticator.clone())
+        } else {
+            None
+        }
     }
 
     fn start_ipc(&self) -> Result<Option<jsonrpc_ipc_server::Server>> {
@@ -152,7 +180,9 @@ impl RpcService {
         } else {
             let ipc_file = self.config.rpc.get_ipc_file();
             let apis: HashSet<Api> = self.config.rpc.ipc.apis().list_apis();
-            let io_handler = self.api_registry.get_apis(apis);
+            let io_handler = self
+                .api_registry
+                .get_apis(apis, self.authenticator(self.config.rpc.ipc.auth()));
 
             info!("Ipc rpc server start at :{:?}", ipc_file);
             Some(
@@ -163,11 +193,24 @@ impl RpcService {
         })
     }
 
-    fn start_http(&self) -> Result<Option<jsonrpc_http_server::Server>> {
+    fn start_http(&mut self) -> Result<Option<jsonrpc_http_server::Server>> {
         Ok(if let Some(addr) = self.config.rpc.get_http_address() {
             let address = addr.into();
             let apis = self.config.rpc.http.apis().list_apis();
-            let io_handler = self.api_registry.get_apis(apis);
+            let io_handler = self
+                .api_registry
+                .get_apis(apis, self.authenticator(self.config.rpc.http.auth()));
+            if let Some(tls) = self.config.rpc.http.tls() {
+                self.tls_servers.push(TlsServer::start_http(
+                    address,
+                    &tls,
+                    io_handler,
+                    self.config.rpc.http.ip_headers(),
+                    self.config.rpc.http.max_request_body_size(),
+                )?);
+                info!("Rpc: https server start at :{}", address);
+                return Ok(None);
+            }
             let http = jsonrpc_http_server::ServerBuilder::new(io_handler)
                 .meta_extractor(RpcExtractor {
                     http_ip_headers: self.config.rpc.http.ip_headers(),
@@ -187,12 +230,19 @@ impl RpcService {
         })
     }
 
-    fn start_tcp(&self) -> Result<Option<jsonrpc_tcp_server::Server>> {
+    fn start_tcp(&mut self) -> Result<Option<jsonrpc_tcp_server::Server>> {
         Ok(if let Some(addr) = self.config.rpc.get_tcp_address() {
             let address = addr.into();
             let apis = self.config.rpc.tcp.apis().list_apis();
-
-            let io_handler = self.api_registry.get_apis(apis);
+            let io_handler = self
+                .api_registry
+                .get_apis(apis, self.authenticator(self.config.rpc.tcp.auth()));
+            if let Some(tls) = self.config.rpc.tcp.tls() {
+                self.tls_servers
+                    .push(TlsServer::start_tcp(address, &tls, io_handler)?);
+                info!("Rpc: tcp tls server start at: {}", address);
+                return Ok(None);
+            }
             let tcp_server = jsonrpc_tcp_server::ServerBuilder::new(io_handler)
                 .session_meta_extractor(RpcExtractor::default())
                 .start(&address)?;
@@ -203,11 +253,23 @@ impl RpcService {
         })
     }
 
-    fn start_ws(&self) -> Result<Option<jsonrpc_ws_server::Server>> {
+    fn start_ws(&mut self) -> Result<Option<jsonrpc_ws_server::Server>> {
         Ok(if let Some(addr) = self.config.rpc.get_ws_address() {
             let address = addr.into();
             let apis = self.config.rpc.ws.apis().list_apis();
-            let io_handler = self.api_registry.get_apis(apis);
+            let io_handler = self
+                .api_registry
+                .get_apis(apis, self.authenticator(self.config.rpc.ws.auth()));
+            if let Some(tls) = self.config.rpc.ws.tls() {
+                self.tls_servers.push(TlsServer::start_ws(
+                    address,
+                    &tls,
+                    io_handler,
+                    self.config.rpc.ws.max_request_body_size(),
+                )?);
+                info!("Rpc: websocket tls server start at: {}", address);
+                return Ok(None);
+            }
             let ws_server = jsonrpc_ws_server::ServerBuilder::new(io_handler)
                 .session_meta_extractor(WsExtractor)
                 .max_payload(self.config.rpc.ws.max_request_body_size())
@@ -232,6 +294,9 @@ impl RpcService {
         if let Some(ws) = self.ws.take() {
             ws.close();
         }
+        for mut server in self.tls_servers.drain(..) {
+            server.close();
+        }
         info!("Rpc Sever is closed.");
     }
 }
@@ -264,7 +329,7 @@ pub fn connect_local(
 impl ServiceHandler<Self, ConnectLocal> for RpcService {
     fn handle(&mut self, _msg: ConnectLocal, ctx: &mut ServiceContext<RpcService>) -> RpcChannel {
         let apis = ApiSet::All.list_apis();
-        let io_handler = self.api_registry.get_apis(apis);
+        let io_handler = self.api_registry.get_apis(apis, None);
         //remove middleware.
         let mut local_io_handler = MetaIoHandler::default();
         local_io_handler.extend_with(io_handler.iter().map(|(n, f)| (n.clone(), f.clone())));
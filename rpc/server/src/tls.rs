@@ -0,0 +1,744 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::extractors::read_http_metadata;
+use anyhow::{ensure, format_err, Result};
+use futures::channel::{mpsc, oneshot};
+use futures::future::{self, Either};
+use futures::{Future, SinkExt, StreamExt};
+use jsonrpc_core::{
+    Call, Id, MetaIoHandler, MethodCall, Middleware, Output, Params, Request as RpcRequest,
+    Response as RpcResponse, Version,
+};
+use jsonrpc_http_server::hyper::header::{
+    HeaderValue, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
+    ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_REQUEST_HEADERS, ALLOW, CONTENT_LENGTH,
+    CONTENT_TYPE, ORIGIN,
+};
+use jsonrpc_http_server::hyper::server::conn::Http;
+use jsonrpc_http_server::hyper::service::service_fn;
+use jsonrpc_http_server::hyper::{self, Body, Method, Request, Response, StatusCode};
+use jsonrpc_pubsub::Session;
+use starcoin_config::RpcTlsConfig;
+use starcoin_logger::prelude::*;
+use starcoin_rpc_api::metadata::Metadata;
+use std::fs::File;
+use std::io::BufReader;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::rustls::internal::pemfile::{certs, pkcs8_private_keys, rsa_private_keys};
+use tokio_rustls::rustls::{
+    AllowAnyAuthenticatedClient, Certificate, NoClientAuth, PrivateKey, RootCertStore, ServerConfig,
+};
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
+use tokio_tungstenite::tungstenite::Message;
+
+const HEALTH_API_PATH: &str = "/status";
+const HEALTH_API_METHOD: &str = "status";
+
+/// A rpc server which terminates tls itself and passes the requests to the io handler directly,
+/// so the client certificate is checked on the public listener and the peer address is kept.
+pub struct TlsServer {
+    shutdown: Option<oneshot::Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl TlsServer {
+    /// Serve json rpc over https, the client ip is read from `ip_headers` first, then the
+    /// peer address.
+    pub fn start_http<M: Middleware<Metadata>>(
+        address: SocketAddr,
+        tls: &RpcTlsConfig,
+        io_handler: MetaIoHandler<Metadata, M>,
+        ip_headers: Vec<String>,
+        max_request_body_size: usize,
+    ) -> Result<Self> {
+        let io_handler = Arc::new(io_handler);
+        let ip_headers = Arc::new(ip_headers);
+        Self::start("http", address, tls, move |stream, peer| {
+            let io_handler = io_handler.clone();
+            let ip_headers = ip_headers.clone();
+            async move {
+                let service = service_fn(move |req| {
+                    handle_http_request(
+                        io_handler.clone(),
+                        ip_headers.clone(),
+                        peer,
+                        max_request_body_size,
+                        req,
+                    )
+                });
+                Http::new().serve_connection(stream, service).await?;
+                Ok(())
+            }
+        })
+    }
+
+    /// Serve line delimited json rpc over tls, as the plaintext tcp server does.
+    pub fn start_tcp<M: Middleware<Metadata>>(
+        address: SocketAddr,
+        tls: &RpcTlsConfig,
+        io_handler: MetaIoHandler<Metadata, M>,
+    ) -> Result<Self> {
+        let io_handler = Arc::new(io_handler);
+        Self::start("tcp", address, tls, move |stream, peer| {
+            serve_tcp(io_handler.clone(), stream, peer)
+        })
+    }
+
+    /// Serve json rpc over websocket with tls.
+    pub fn start_ws<M: Middleware<Metadata>>(
+        address: SocketAddr,
+        tls: &RpcTlsConfig,
+        io_handler: MetaIoHandler<Metadata, M>,
+        max_payload: usize,
+    ) -> Result<Self> {
+        let io_handler = Arc::new(io_handler);
+        Self::start("websocket", address, tls, move |stream, peer| {
+            serve_ws(io_handler.clone(), stream, peer, max_payload)
+        })
+    }
+
+    fn start<F, Fut>(
+        name: &'static str,
+        address: SocketAddr,
+        tls: &RpcTlsConfig,
+        serve: F,
+    ) -> Result<Self>
+    where
+        F: Fn(TlsStream<TcpStream>, SocketAddr) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let acceptor = TlsAcceptor::from(Arc::new(load_server_config(tls)?));
+        let serve = Arc::new(serve);
+        // bind before spawn the server thread, so the bind error is returned to the caller.
+        let std_listener = std::net::TcpListener::bind(address)?;
+        std_listener.set_nonblocking(true)?;
+        let mut runtime = tokio::runtime::Builder::new()
+            .threaded_scheduler()
+            .enable_all()
+            .thread_name(format!("rpc-{}-tls", name))
+            .build()?;
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let handle = std::thread::Builder::new()
+            .name(format!("rpc-{}-tls", name))
+            .spawn(move || {
+                runtime.block_on(async move {
+                    let mut listener = match TcpListener::from_std(std_listener) {
+                        Ok(listener) => listener,
+                        Err(e) => {
+                            error!("Rpc {} tls listener error: {}", name, e);
+                            return;
+                        }
+                    };
+                    let accept_loop = async move {
+                        loop {
+                            match listener.accept().await {
+                                Ok((stream, peer)) => {
+                                    let acceptor = acceptor.clone();
+                                    let serve = serve.clone();
+                                    let connection = async move {
+                                        let stream = acceptor.accept(stream).await?;
+                                        serve(stream, peer).await
+                                    };
+                                    tokio::spawn(async move {
+                                        if let Err(e) = connection.await {
+                                            debug!(
+                                                "Rpc {} tls connection from {} error: {}",
+                                                name, peer, e
+                                            );
+                                        }
+                                    });
+                                }
+                                Err(e) => error!("Rpc {} tls accept error: {}", name, e),
+                            }
+                        }
+                    };
+                    futures::pin_mut!(accept_loop);
+                    future::select(accept_loop, shutdown_rx).await;
+                })
+            })?;
+        Ok(Self {
+            shutdown: Some(shutdown_tx),
+            handle: Some(handle),
+        })
+    }
+
+    pub fn close(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(handle) = self.handle.take() {
+            if handle.join().is_err() {
+                error!("Rpc tls server thread panic.");
+            }
+        }
+    }
+}
+
+impl Drop for TlsServer {
+    fn drop(&mut self) {
+        self.close()
+    }
+}
+
+async fn handle_http_request<M: Middleware<Metadata>>(
+    io_handler: Arc<MetaIoHandler<Metadata, M>>,
+    ip_headers: Arc<Vec<String>>,
+    peer: SocketAddr,
+    max_request_body_size: usize,
+    req: Request<Body>,
+) -> Result<Response<Body>, hyper::Error> {
+    let mut meta = read_http_metadata(ip_headers.as_slice(), req.headers());
+    if meta.user.is_none() {
+        meta.user = Some(peer.ip().to_string());
+    }
+    // any origin is allowed, as the cors of the plaintext http server.
+    let has_origin = req.headers().contains_key(ORIGIN);
+    let method = req.method().clone();
+    let mut response = if method == Method::POST {
+        rpc_response(io_handler.as_ref(), meta, max_request_body_size, req).await?
+    } else if method == Method::OPTIONS {
+        preflight_response(&req)
+    } else if method == Method::GET && req.uri().path() == HEALTH_API_PATH {
+        health_response(io_handler.as_ref(), meta).await
+    } else {
+        status_response(StatusCode::METHOD_NOT_ALLOWED)
+    };
+    if has_origin {
+        response
+            .headers_mut()
+            .insert(ACCESS_CONTROL_ALLOW_ORIGIN, HeaderValue::from_static("*"));
+    }
+    Ok(response)
+}
+
+async fn rpc_response<M: Middleware<Metadata>>(
+    io_handler: &MetaIoHandler<Metadata, M>,
+    meta: Metadata,
+    max_request_body_size: usize,
+    req: Request<Body>,
+) -> Result<Response<Body>, hyper::Error> {
+    let content_length = req
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok());
+    if content_length.unwrap_or_default() > max_request_body_size {
+        return Ok(status_response(StatusCode::PAYLOAD_TOO_LARGE));
+    }
+    // the content length may be absent or wrong, so the size is also checked while reading.
+    let mut body = req.into_body();
+    let mut request = Vec::with_capacity(content_length.unwrap_or_default());
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk?;
+        if request.len().saturating_add(chunk.len()) > max_request_body_size {
+            return Ok(status_response(StatusCode::PAYLOAD_TOO_LARGE));
+        }
+        request.extend_from_slice(chunk.as_ref());
+    }
+    let request = match std::str::from_utf8(request.as_slice()) {
+        Ok(request) => request,
+        Err(_) => return Ok(status_response(StatusCode::BAD_REQUEST)),
+    };
+    let response = io_handler
+        .handle_request(request, meta)
+        .await
+        .unwrap_or_default();
+    Ok(json_response(StatusCode::OK, response))
+}
+
+/// Call the health method, as the `/status` health api of the plaintext http server.
+async fn health_response<M: Middleware<Metadata>>(
+    io_handler: &MetaIoHandler<Metadata, M>,
+    meta: Metadata,
+) -> Response<Body> {
+    let call = RpcRequest::Single(Call::MethodCall(MethodCall {
+        jsonrpc: Some(Version::V2),
+        method: HEALTH_API_METHOD.to_string(),
+        params: Params::None,
+        id: Id::Num(1),
+    }));
+    match io_handler.handle_rpc_request(call, meta).await {
+        Some(RpcResponse::Single(Output::Success(success))) => json_response(
+            StatusCode::OK,
+            serde_json::to_string(&success.result).unwrap_or_default(),
+        ),
+        Some(RpcResponse::Single(Output::Failure(failure))) => json_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            serde_json::to_string(&failure.error).unwrap_or_default(),
+        ),
+        _ => status_response(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+fn preflight_response(req: &Request<Body>) -> Response<Body> {
+    let mut response = status_response(StatusCode::OK);
+    let headers = response.headers_mut();
+    headers.insert(ALLOW, HeaderValue::from_static("OPTIONS, POST"));
+    headers.insert(
+        ACCESS_CONTROL_ALLOW_METHODS,
+        HeaderValue::from_static("OPTIONS, POST"),
+    );
+    if let Some(request_headers) = req.headers().get(ACCESS_CONTROL_REQUEST_HEADERS) {
+        headers.insert(ACCESS_CONTROL_ALLOW_HEADERS, request_headers.clone());
+    }
+    response
+}
+
+fn json_response(status: StatusCode, body: String) -> Response<Body> {
+    let mut response = Response::new(Body::from(body));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+fn status_response(status: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = status;
+    response
+}
+
+fn session_metadata(sender: mpsc::UnboundedSender<String>, peer: SocketAddr) -> Metadata {
+    Metadata {
+        session: Some(Arc::new(Session::new(sender))),
+        user: Some(peer.ip().to_string()),
+        auth_token: None,
+    }
+}
+
+async fn serve_tcp<M: Middleware<Metadata>>(
+    io_handler: Arc<MetaIoHandler<Metadata, M>>,
+    stream: TlsStream<TcpStream>,
+    peer: SocketAddr,
+) -> Result<()> {
+    let (reader, mut writer) = tokio::io::split(stream);
+    // responses and subscription notifications of the session.
+    let (sender, mut receiver) = mpsc::unbounded::<String>();
+    let meta = session_metadata(sender.clone(), peer);
+    let write = async move {
+        while let Some(message) = receiver.next().await {
+            writer.write_all(message.as_bytes()).await?;
+            writer.write_all(b"\n").await?;
+        }
+        Ok::<_, anyhow::Error>(())
+    };
+    let read = async move {
+        let mut lines = tokio::io::BufReader::new(reader).lines();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = io_handler.handle_request(line.as_str(), meta.clone());
+            let sender = sender.clone();
+            tokio::spawn(async move {
+                if let Some(response) = response.await {
+                    let _ = sender.unbounded_send(response);
+                }
+            });
+        }
+        Ok::<_, anyhow::Error>(())
+    };
+    futures::pin_mut!(read, write);
+    // the connection is finished when either side is closed.
+    match future::select(read, write).await {
+        Either::Left((result, _)) | Either::Right((result, _)) => result,
+    }
+}
+
+async fn serve_ws<M: Middleware<Metadata>>(
+    io_handler: Arc<MetaIoHandler<Metadata, M>>,
+    stream: TlsStream<TcpStream>,
+    peer: SocketAddr,
+    max_payload: usize,
+) -> Result<()> {
+    let config = WebSocketConfig {
+        max_message_size: Some(max_payload),
+        ..WebSocketConfig::default()
+    };
+    let (mut ws_sink, mut ws_stream) =
+        tokio_tungstenite::accept_async_with_config(stream, Some(config))
+            .await?
+            .split();
+    // responses and subscription notifications of the session.
+    let (sender, mut receiver) = mpsc::unbounded::<String>();
+    let meta = session_metadata(sender.clone(), peer);
+    let write = async move {
+        while let Some(message) = receiver.next().await {
+            ws_sink.send(Message::Text(message)).await?;
+        }
+        Ok::<_, anyhow::Error>(())
+    };
+    let read = async move {
+        while let Some(message) = ws_stream.next().await {
+            let request = match message? {
+                Message::Text(text) => text,
+                Message::Binary(bytes) => String::from_utf8(bytes)?,
+                Message::Close(_) => break,
+                // ping is replied by the websocket stream.
+                Message::Ping(_) | Message::Pong(_) => continue,
+            };
+            let response = io_handler.handle_request(request.as_str(), meta.clone());
+            let sender = sender.clone();
+            tokio::spawn(async move {
+                if let Some(response) = response.await {
+                    let _ = sender.unbounded_send(response);
+                }
+            });
+        }
+        Ok::<_, anyhow::Error>(())
+    };
+    futures::pin_mut!(read, write);
+    match future::select(read, write).await {
+        Either::Left((result, _)) | Either::Right((result, _)) => result,
+    }
+}
+
+fn load_server_config(tls: &RpcTlsConfig) -> Result<ServerConfig> {
+    let mut config = match tls.client_ca.as_ref() {
+        Some(client_ca) => {
+            let mut roots = RootCertStore::empty();
+            for cert in load_certs(client_ca)? {
+                roots
+                    .add(&cert)
+                    .map_err(|e| format_err!("Invalid tls client ca {:?}: {}", client_ca, e))?;
+            }
+            ServerConfig::new(AllowAnyAuthenticatedClient::new(roots))
+        }
+        None => ServerConfig::new(NoClientAuth::new()),
+    };
+    config.set_single_cert(load_certs(&tls.cert)?, load_key(&tls.key)?)?;
+    Ok(config)
+}
+
+fn load_certs(path: &Path) -> Result<Vec<Certificate>> {
+    let certs = certs(&mut BufReader::new(File::open(path)?))
+        .map_err(|_| format_err!("Invalid tls certificate file {:?}", path))?;
+    ensure!(!certs.is_empty(), "No certificate in {:?}", path);
+    Ok(certs)
+}
+
+fn load_key(path: &Path) -> Result<PrivateKey> {
+    let mut keys = pkcs8_private_keys(&mut BufReader::new(File::open(path)?))
+        .map_err(|_| format_err!("Invalid tls private key file {:?}", path))?;
+    if keys.is_empty() {
+        keys = rsa_private_keys(&mut BufReader::new(File::open(path)?))
+            .map_err(|_| format_err!("Invalid tls private key file {:?}", path))?;
+    }
+    keys.into_iter()
+        .next()
+        .ok_or_else(|| format_err!("No private key in {:?}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpc_core::{Params, Value};
+    use starcoin_config::{get_random_available_port, temp_path, DataDirPath};
+    use std::net::Ipv4Addr;
+    use std::time::Duration;
+    use tokio::io::AsyncReadExt;
+    use tokio_rustls::rustls::ClientConfig;
+    use tokio_rustls::webpki::DNSNameRef;
+    use tokio_rustls::TlsConnector;
+
+    struct TestCerts {
+        dir: DataDirPath,
+        ca: rcgen::Certificate,
+    }
+
+    impl TestCerts {
+        fn new() -> Self {
+            let mut params = rcgen::CertificateParams::new(vec![]);
+            params.distinguished_name = rcgen::DistinguishedName::new();
+            params
+                .distinguished_name
+                .push(rcgen::DnType::CommonName, "starcoin rpc test ca");
+            params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+            Self {
+                dir: temp_path(),
+                ca: rcgen::Certificate::from_params(params).unwrap(),
+            }
+        }
+
+        fn issue(&self, purpose: rcgen::ExtendedKeyUsagePurpose) -> rcgen::Certificate {
+            let mut params = rcgen::CertificateParams::new(vec!["localhost".to_string()]);
+            params.extended_key_usages = vec![purpose];
+            rcgen::Certificate::from_params(params).unwrap()
+        }
+
+        fn tls_config(&self, verify_client: bool) -> RpcTlsConfig {
+            let cert = self.issue(rcgen::ExtendedKeyUsagePurpose::ServerAuth);
+            let path = self.dir.path();
+            std::fs::write(
+                path.join("cert.pem"),
+                cert.serialize_pem_with_signer(&self.ca).unwrap(),
+            )
+            .unwrap();
+            std::fs::write(path.join("key.pem"), cert.serialize_private_key_pem()).unwrap();
+            std::fs::write(path.join("ca.pem"), self.ca.serialize_pem().unwrap()).unwrap();
+            RpcTlsConfig {
+                cert: path.join("cert.pem"),
+                key: path.join("key.pem"),
+                client_ca: if verify_client {
+                    Some(path.join("ca.pem"))
+                } else {
+                    None
+                },
+            }
+        }
+
+        fn client_config(&self, with_client_cert: bool) -> ClientConfig {
+            let mut config = ClientConfig::new();
+            config
+                .root_store
+                .add(&Certificate(self.ca.serialize_der().unwrap()))
+                .unwrap();
+            if with_client_cert {
+                let cert = self.issue(rcgen::ExtendedKeyUsagePurpose::ClientAuth);
+                config
+                    .set_single_client_cert(
+                        vec![Certificate(
+                            cert.serialize_der_with_signer(&self.ca).unwrap(),
+                        )],
+                        PrivateKey(cert.serialize_private_key_der()),
+                    )
+                    .unwrap();
+            }
+            config
+        }
+    }
+
+    /// The `user` method returns the client identity of the request metadata.
+    fn io_handler() -> MetaIoHandler<Metadata> {
+        let mut io_handler = MetaIoHandler::default();
+        io_handler.add_method_with_meta("user", |_params: Params, meta: Metadata| async move {
+            Ok(meta.user.map(Value::String).unwrap_or(Value::Null))
+        });
+        io_handler.add_method("status", |_params: Params| async { Ok(Value::Bool(true)) });
+        io_handler
+    }
+
+    async fn connect(
+        port: u16,
+        config: ClientConfig,
+    ) -> Result<tokio_rustls::client::TlsStream<TcpStream>> {
+        let stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).await?;
+        let domain = DNSNameRef::try_from_ascii_str("localhost")?;
+        Ok(TlsConnector::from(Arc::new(config))
+            .connect(domain, stream)
+            .await?)
+    }
+
+    async fn tcp_call(port: u16, config: ClientConfig) -> Result<serde_json::Value> {
+        let mut stream = connect(port, config).await?;
+        stream
+            .write_all(b"{\"jsonrpc\":\"2.0\",\"method\":\"user\",\"params\":[],\"id\":1}\n")
+            .await?;
+        let mut line = String::new();
+        tokio::io::BufReader::new(stream)
+            .read_line(&mut line)
+            .await?;
+        ensure!(!line.is_empty(), "Connection closed");
+        Ok(serde_json::from_str(line.as_str())?)
+    }
+
+    /// Send the raw http request, and return the raw response.
+    async fn http_request(port: u16, config: ClientConfig, request: String) -> Result<String> {
+        let mut stream = connect(port, config).await?;
+        stream.write_all(request.as_bytes()).await?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+        Ok(response)
+    }
+
+    async fn http_call(
+        port: u16,
+        config: ClientConfig,
+        headers: &str,
+    ) -> Result<serde_json::Value> {
+        let body = r#"{"jsonrpc":"2.0","method":"user","params":[],"id":1}"#;
+        let request = format!(
+            "POST / HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nConnection: close\r\nContent-Length: {}\r\n{}\r\n{}",
+            body.len(),
+            headers,
+            body
+        );
+        let response = http_request(port, config, request).await?;
+        ensure!(
+            response.starts_with("HTTP/1.1 200"),
+            "Unexpected response: {}",
+            response
+        );
+        let body = response
+            .split("\r\n\r\n")
+            .nth(1)
+            .ok_or_else(|| format_err!("Invalid http response: {}", response))?;
+        Ok(serde_json::from_str(body)?)
+    }
+
+    #[stest::test]
+    async fn test_https() {
+        let certs = TestCerts::new();
+        let port = get_random_available_port();
+        let mut server = TlsServer::start_http(
+            SocketAddr::new(Ipv4Addr::LOCALHOST.into(), port),
+            &certs.tls_config(false),
+            io_handler(),
+            vec!["X-Real-IP".to_string()],
+            1024,
+        )
+        .unwrap();
+        // the peer address is the client identity.
+        let response = http_call(port, certs.client_config(false), "")
+            .await
+            .unwrap();
+        assert_eq!(response["result"], "127.0.0.1");
+        // the ip header has priority over the peer address.
+        let response = http_call(port, certs.client_config(false), "X-Real-IP: 10.0.0.1\r\n")
+            .await
+            .unwrap();
+        assert_eq!(response["result"], "10.0.0.1");
+        // the server certificate is not trusted without the CA.
+        assert!(http_call(port, ClientConfig::new(), "").await.is_err());
+        server.close();
+    }
+
+    #[stest::test]
+    async fn test_https_max_request_body_size() {
+        let certs = TestCerts::new();
+        let port = get_random_available_port();
+        let mut server = TlsServer::start_http(
+            SocketAddr::new(Ipv4Addr::LOCALHOST.into(), port),
+            &certs.tls_config(false),
+            io_handler(),
+            vec![],
+            64,
+        )
+        .unwrap();
+        let body = format!(
+            r#"{{"jsonrpc":"2.0","method":"user","params":["{}"],"id":1}}"#,
+            "a".repeat(64)
+        );
+        // the request is rejected by the content length.
+        let request = format!(
+            "POST / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let response = http_request(port, certs.client_config(false), request)
+            .await
+            .unwrap();
+        assert!(response.starts_with("HTTP/1.1 413"), "{}", response);
+        // the chunked request without content length is rejected while reading.
+        let request = format!(
+            "POST / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{}\r\n0\r\n\r\n",
+            body.len(),
+            body
+        );
+        let response = http_request(port, certs.client_config(false), request)
+            .await
+            .unwrap();
+        assert!(response.starts_with("HTTP/1.1 413"), "{}", response);
+        server.close();
+    }
+
+    #[stest::test]
+    async fn test_https_health_api_and_cors() {
+        let certs = TestCerts::new();
+        let port = get_random_available_port();
+        let mut server = TlsServer::start_http(
+            SocketAddr::new(Ipv4Addr::LOCALHOST.into(), port),
+            &certs.tls_config(false),
+            io_handler(),
+            vec![],
+            1024,
+        )
+        .unwrap();
+        let request = "GET /status HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+        let response = http_request(port, certs.client_config(false), request.to_string())
+            .await
+            .unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.ends_with("\r\n\r\ntrue"), "{}", response);
+
+        let request = "OPTIONS / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nOrigin: http://example.com\r\nAccess-Control-Request-Method: POST\r\nAccess-Control-Request-Headers: content-type\r\n\r\n";
+        let response = http_request(port, certs.client_config(false), request.to_string())
+            .await
+            .unwrap()
+            .to_lowercase();
+        assert!(response.starts_with("http/1.1 200"), "{}", response);
+        assert!(response.contains("access-control-allow-origin: *"));
+        assert!(response.contains("access-control-allow-methods: options, post"));
+        assert!(response.contains("access-control-allow-headers: content-type"));
+
+        let body = r#"{"jsonrpc":"2.0","method":"user","params":[],"id":1}"#;
+        let request = format!(
+            "POST / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nOrigin: http://example.com\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let response = http_request(port, certs.client_config(false), request)
+            .await
+            .unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response
+            .to_lowercase()
+            .contains("access-control-allow-origin: *"));
+        server.close();
+    }
+
+    #[stest::test]
+    async fn test_tcp_tls() {
+        let certs = TestCerts::new();
+        let port = get_random_available_port();
+        let mut server = TlsServer::start_tcp(
+            SocketAddr::new(Ipv4Addr::LOCALHOST.into(), port),
+            &certs.tls_config(false),
+            io_handler(),
+        )
+        .unwrap();
+        let response = tcp_call(port, certs.client_config(false)).await.unwrap();
+        assert_eq!(response["result"], "127.0.0.1");
+        // plaintext request is not accepted by the tls listener.
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port))
+            .await
+            .unwrap();
+        stream
+            .write_all(b"{\"jsonrpc\":\"2.0\",\"method\":\"user\",\"params\":[],\"id\":1}\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        let read =
+            tokio::time::timeout(Duration::from_secs(5), stream.read_to_string(&mut response))
+                .await
+                .unwrap();
+        assert!(read.is_err() || !response.contains("127.0.0.1"));
+        server.close();
+    }
+
+    #[stest::test]
+    async fn test_tls_client_cert() {
+        let certs = TestCerts::new();
+        let port = get_random_available_port();
+        let mut server = TlsServer::start_tcp(
+            SocketAddr::new(Ipv4Addr::LOCALHOST.into(), port),
+            &certs.tls_config(true),
+            io_handler(),
+        )
+        .unwrap();
+        // the client without certificate is rejected.
+        assert!(tcp_call(port, certs.client_config(false)).await.is_err());
+        let response = tcp_call(port, certs.client_config(true)).await.unwrap();
+        assert_eq!(response["result"], "127.0.0.1");
+        server.close();
+    }
+}
@@ -110,7 +110,7 @@ impl ServiceHandler<Self, StateRequest> for ChainStateService {
 
 impl EventHandler<Self, NewHeadBlock> for ChainStateService {
     fn handle_event(&mut self, msg: NewHeadBlock, _ctx: &mut ServiceContext<ChainStateService>) {
-        let NewHeadBlock(block) = msg;
+        let NewHeadBlock(block, _) = msg;
 
         let state_root = block.header().state_root();
         debug!("ChainStateActor change StateRoot to : {:?}", state_root);
@@ -211,8 +211,8 @@ impl StateView for Inner {
         self.state_db.get(access_path)
     }
 
-    fn multi_get(&self, _access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
-        unimplemented!()
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        self.state_db.multi_get(access_paths)
     }
 
     fn is_genesis(&self) -> bool {
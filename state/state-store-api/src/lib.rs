@@ -52,6 +52,10 @@ impl Serialize for StateNode {
 
 pub trait StateNodeStore: std::marker::Send + std::marker::Sync {
     fn get(&self, hash: &HashValue) -> Result<Option<StateNode>>;
+    /// Get the nodes of the hashes, the store can override it to read in one batch.
+    fn multi_get(&self, hashes: &[HashValue]) -> Result<Vec<Option<StateNode>>> {
+        hashes.iter().map(|hash| self.get(hash)).collect()
+    }
     fn put(&self, key: HashValue, node: StateNode) -> Result<()>;
     fn write_nodes(&self, nodes: BTreeMap<HashValue, StateNode>) -> Result<()>;
 }
@@ -1,6 +1,5 @@
 use anyhow::Result;
 use forkable_jellyfish_merkle::blob::Blob;
-use forkable_jellyfish_merkle::iterator::JellyfishMerkleIterator;
 use forkable_jellyfish_merkle::node_type::{Node, NodeKey};
 use forkable_jellyfish_merkle::proof::SparseMerkleProof;
 use forkable_jellyfish_merkle::{
@@ -134,6 +133,41 @@ where
         Ok(self.get_with_proof(key)?.0)
     }
 
+    /// Read the values of keys in one batch, the values are in the order of the keys.
+    /// This will also read un-committed modification.
+    pub fn multi_get(&self, keys: &[K]) -> Result<Vec<Option<Vec<u8>>>> {
+        let updates_guard = self.updates.read();
+        let mut values = vec![None; keys.len()];
+        let mut unchanged_indexes = vec![];
+        let mut unchanged_key_hashes = vec![];
+        for (index, key) in keys.iter().enumerate() {
+            match updates_guard.get(key) {
+                Some(uncomputed) => values[index] = uncomputed.clone().map(|b| b.into()),
+                None => {
+                    unchanged_indexes.push(index);
+                    unchanged_key_hashes.push(key.key_hash());
+                }
+            }
+        }
+        if unchanged_key_hashes.is_empty() {
+            return Ok(values);
+        }
+
+        let mut cache_guard = self.cache.lock();
+        let cache = cache_guard.deref_mut();
+        let cur_root_hash = cache.root_hash;
+        let reader = CachedTreeReader {
+            store: self.storage.as_ref(),
+            cache,
+        };
+        let tree = JellyfishMerkleTree::new(&reader);
+        let blobs = tree.multi_get(cur_root_hash, &unchanged_key_hashes)?;
+        for (index, blob) in unchanged_indexes.into_iter().zip(blobs) {
+            values[index] = blob.map(|b| b.into());
+        }
+        Ok(values)
+    }
+
     pub fn contains(&self, key: &K) -> Result<bool> {
         self.get(key).map(|result| result.is_some())
     }
@@ -203,6 +237,30 @@ where
         Ok(())
     }
 
+    /// The keys whose values differ between the committed tree and the tree of `new_root_hash`,
+    /// with the values in the committed tree and in the new tree.
+    #[allow(clippy::type_complexity)]
+    pub fn diff(
+        &self,
+        new_root_hash: HashValue,
+    ) -> Result<Vec<(K, Option<Vec<u8>>, Option<Vec<u8>>)>> {
+        let cur_root_hash = self.root_hash();
+        let mut cache_guard = self.cache.lock();
+        let cache = cache_guard.deref_mut();
+        let reader = CachedTreeReader {
+            store: self.storage.as_ref(),
+            cache,
+        };
+        let tree = JellyfishMerkleTree::new(&reader);
+        Ok(tree
+            .diff(cur_root_hash, new_root_hash)?
+            .into_iter()
+            .map(|(key, old_blob, new_blob)| {
+                (key, old_blob.map(Into::into), new_blob.map(Into::into))
+            })
+            .collect())
+    }
+
     /// Dump tree to state set.
     pub fn dump(&self) -> Result<StateSet> {
         let cur_root_hash = self.root_hash();
@@ -212,12 +270,12 @@ where
             store: self.storage.as_ref(),
             cache,
         };
-        let iterator = JellyfishMerkleIterator::new(&reader, cur_root_hash, HashValue::zero())?;
-        let mut states = vec![];
-        for item in iterator {
-            let item = item?;
-            states.push((item.0.encode_key()?, item.1.into()));
-        }
+        let tree = JellyfishMerkleTree::new(&reader);
+        let states = tree
+            .dump(cur_root_hash)?
+            .into_iter()
+            .map(|(key, blob)| Ok((key.encode_key()?, blob.into())))
+            .collect::<Result<Vec<_>>>()?;
         Ok(StateSet::new(states))
     }
 
@@ -313,4 +371,29 @@ where
             Err(e) => Err(e),
         }
     }
+
+    fn multi_get_node_option(&self, node_keys: &[NodeKey]) -> Result<Vec<Option<Node<K>>>> {
+        let mut nodes = vec![None; node_keys.len()];
+        let mut missed_indexes = vec![];
+        let mut missed_keys = vec![];
+        for (index, node_key) in node_keys.iter().enumerate() {
+            if node_key == &*SPARSE_MERKLE_PLACEHOLDER_HASH {
+                nodes[index] = Some(Node::new_null());
+            } else if let Some(n) = self.cache.change_set.node_batch.get(node_key).cloned() {
+                nodes[index] = Some(n);
+            } else {
+                missed_indexes.push(index);
+                missed_keys.push(*node_key);
+            }
+        }
+        if !missed_keys.is_empty() {
+            for (index, n) in missed_indexes
+                .into_iter()
+                .zip(self.store.multi_get(&missed_keys)?)
+            {
+                nodes[index] = n.map(|n| n.try_into()).transpose()?;
+            }
+        }
+        Ok(nodes)
+    }
 }
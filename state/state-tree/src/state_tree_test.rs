@@ -153,6 +153,34 @@ pub fn test_state_commit() -> Result<()> {
     Ok(())
 }
 
+#[test]
+pub fn test_state_multi_get() -> Result<()> {
+    let s = MockStateNodeStore::new();
+    let state = StateTree::new(Arc::new(s), None);
+    let hash_value = HashValue::random().into();
+    let account1 = update_nibble(&hash_value, 0, 1);
+    let account2 = update_nibble(&account1, 2, 3);
+    let account3 = update_nibble(&account1, 2, 10);
+    assert_eq!(state.multi_get(&[account1, account3])?, vec![None, None]);
+
+    state.put(account1, vec![0, 0, 1]);
+    state.put(account2, vec![0, 0, 2]);
+    state.commit()?;
+    state.flush()?;
+    // un-committed modification is visible.
+    state.put(account1, vec![1, 1, 1]);
+    assert_eq!(
+        state.multi_get(&[account1, account2, account3, account2])?,
+        vec![
+            Some(vec![1, 1, 1]),
+            Some(vec![0, 0, 2]),
+            None,
+            Some(vec![0, 0, 2])
+        ]
+    );
+    Ok(())
+}
+
 #[test]
 pub fn test_state_dump() -> Result<()> {
     let s = MockStateNodeStore::new();
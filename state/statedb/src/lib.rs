@@ -8,7 +8,7 @@ use forkable_jellyfish_merkle::proof::SparseMerkleProof;
 use forkable_jellyfish_merkle::RawKey;
 use lru::LruCache;
 use parking_lot::{Mutex, RwLock};
-use starcoin_crypto::HashValue;
+use starcoin_crypto::{hash::SPARSE_MERKLE_PLACEHOLDER_HASH, HashValue};
 use starcoin_logger::prelude::*;
 pub use starcoin_state_api::{
     ChainState, ChainStateReader, ChainStateWriter, StateProof, StateWithProof,
@@ -25,7 +25,7 @@ use starcoin_types::{
 use starcoin_vm_types::access_path::{DataPath, ModuleName};
 use starcoin_vm_types::language_storage::StructTag;
 use starcoin_vm_types::state_view::StateView;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 use std::sync::Arc;
 use thiserror::Error;
@@ -101,6 +101,46 @@ impl AccountStateObject {
         }
     }
 
+    /// Read the data of the data paths, the codes and the resources are read in batch respectively.
+    pub fn multi_get(&self, data_paths: &[&DataPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        let mut values = vec![None; data_paths.len()];
+        let mut code_indexes = vec![];
+        let mut module_names = vec![];
+        let mut resource_indexes = vec![];
+        let mut struct_tags = vec![];
+        for (index, data_path) in data_paths.iter().enumerate() {
+            match data_path {
+                DataPath::Code(module_name) => {
+                    code_indexes.push(index);
+                    module_names.push(module_name.clone());
+                }
+                DataPath::Resource(struct_tag) => {
+                    resource_indexes.push(index);
+                    struct_tags.push(struct_tag.clone());
+                }
+            }
+        }
+        if !module_names.is_empty() {
+            if let Some(code_tree) = self.code_tree.lock().as_ref() {
+                for (index, value) in code_indexes
+                    .into_iter()
+                    .zip(code_tree.multi_get(&module_names)?)
+                {
+                    values[index] = value;
+                }
+            }
+        }
+        if !struct_tags.is_empty() {
+            for (index, value) in resource_indexes
+                .into_iter()
+                .zip(self.resource_tree.lock().multi_get(&struct_tags)?)
+            {
+                values[index] = value;
+            }
+        }
+        Ok(values)
+    }
+
     /// return value with it proof.
     /// NOTICE: Any un-committed modification will not visible to the method.
     pub fn get_with_proof(
@@ -245,6 +285,46 @@ impl ChainStateDB {
         }
     }
 
+    /// The states which differ between the committed state and the state of `new_state_root`,
+    /// with the values in the committed state and in the new state.
+    #[allow(clippy::type_complexity)]
+    pub fn diff(
+        &self,
+        new_state_root: HashValue,
+    ) -> Result<Vec<(AccessPath, Option<Vec<u8>>, Option<Vec<u8>>)>> {
+        let mut diff = vec![];
+        for (address, old_state, new_state) in self.state_tree.diff(new_state_root)? {
+            let old_state = old_state
+                .map(|state| AccountState::decode(state.as_slice()))
+                .transpose()?;
+            let new_state = new_state
+                .map(|state| AccountState::decode(state.as_slice()))
+                .transpose()?;
+            let storage_root = |state: &Option<AccountState>, data_type: DataType| {
+                state
+                    .as_ref()
+                    .and_then(|state| state.storage_roots()[data_type.storage_index()])
+                    .unwrap_or(*SPARSE_MERKLE_PLACEHOLDER_HASH)
+            };
+            let code_diff = self
+                .new_state_tree::<ModuleName>(storage_root(&old_state, DataType::CODE))
+                .diff(storage_root(&new_state, DataType::CODE))?
+                .into_iter()
+                .map(|(name, old, new)| (DataPath::Code(name), old, new));
+            let resource_diff = self
+                .new_state_tree::<StructTag>(storage_root(&old_state, DataType::RESOURCE))
+                .diff(storage_root(&new_state, DataType::RESOURCE))?
+                .into_iter()
+                .map(|(tag, old, new)| (DataPath::Resource(tag), old, new));
+            diff.extend(
+                code_diff
+                    .chain(resource_diff)
+                    .map(|(path, old, new)| (AccessPath::new(address, path), old, new)),
+            );
+        }
+        Ok(diff)
+    }
+
     fn new_state_tree<K: RawKey>(&self, root_hash: HashValue) -> StateTree<K> {
         StateTree::new(self.store.clone(), Some(root_hash))
     }
@@ -299,6 +379,50 @@ impl ChainStateDB {
         Ok(object)
     }
 
+    /// Like `get_account_state_object_option`, but the missed accounts are read in one batch.
+    fn multi_get_account_state_object_option(
+        &self,
+        account_addresses: &[AccountAddress],
+    ) -> Result<Vec<Option<Arc<AccountStateObject>>>> {
+        let mut cache = self.cache.lock();
+        let mut objects = Vec::with_capacity(account_addresses.len());
+        let mut missed_indexes = vec![];
+        let mut missed_addresses = vec![];
+        for (index, account_address) in account_addresses.iter().enumerate() {
+            match cache.get(account_address) {
+                Some(item) => objects.push(item.as_object()),
+                None => {
+                    objects.push(None);
+                    missed_indexes.push(index);
+                    missed_addresses.push(*account_address);
+                }
+            }
+        }
+        if missed_addresses.is_empty() {
+            return Ok(objects);
+        }
+        let account_states = self.state_tree.multi_get(&missed_addresses)?;
+        for ((index, account_address), account_state) in missed_indexes
+            .into_iter()
+            .zip(missed_addresses)
+            .zip(account_states)
+        {
+            let object = account_state
+                .map(|v| AccountState::decode(v.as_slice()))
+                .transpose()?
+                .map(|account_state| {
+                    Arc::new(AccountStateObject::new(account_state, self.store.clone()))
+                });
+            let cache_item = match &object {
+                Some(object) => CacheItem::new(object.clone()),
+                None => CacheItem::AccountNotExist(),
+            };
+            cache.put(account_address, cache_item);
+            objects[index] = object;
+        }
+        Ok(objects)
+    }
+
     fn get_account_state(&self, account_address: &AccountAddress) -> Result<Option<AccountState>> {
         self.state_tree
             .get(account_address)
@@ -323,11 +447,42 @@ impl StateView for ChainStateDB {
     }
 
     /// Gets state data for a list of access paths.
+    /// The accounts, and the data of every account are read in batch.
     fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
-        access_paths
-            .iter()
-            .map(|access_path| self.get(access_path))
-            .collect()
+        // The indexes of access paths grouped by account, in the order of first appearance.
+        let mut account_addresses = vec![];
+        let mut indexes_of_account: HashMap<AccountAddress, Vec<usize>> = HashMap::new();
+        for (index, access_path) in access_paths.iter().enumerate() {
+            indexes_of_account
+                .entry(access_path.address)
+                .or_insert_with(|| {
+                    account_addresses.push(access_path.address);
+                    vec![]
+                })
+                .push(index);
+        }
+        let account_state_objects =
+            self.multi_get_account_state_object_option(&account_addresses)?;
+
+        let mut values = vec![None; access_paths.len()];
+        for (account_address, account_state_object) in
+            account_addresses.iter().zip(account_state_objects)
+        {
+            if let Some(account_state_object) = account_state_object {
+                let indexes = &indexes_of_account[account_address];
+                let data_paths = indexes
+                    .iter()
+                    .map(|index| &access_paths[*index].path)
+                    .collect::<Vec<_>>();
+                for (index, value) in indexes
+                    .iter()
+                    .zip(account_state_object.multi_get(&data_paths)?)
+                {
+                    values[*index] = value;
+                }
+            }
+        }
+        Ok(values)
     }
 
     fn is_genesis(&self) -> bool {
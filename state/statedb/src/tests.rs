@@ -53,6 +53,137 @@ fn test_state_db() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_state_db_multi_get() -> Result<()> {
+    let storage = Arc::new(MockStateNodeStore::new());
+    let chain_state_db = ChainStateDB::new(storage.clone(), None);
+    let access_path1 = AccessPath::random_resource();
+    let access_path2 = AccessPath::random_code();
+    let access_path3 = AccessPath::new(access_path1.address, access_path2.path.clone());
+    let write_set = WriteSetMut::new(vec![
+        (access_path1.clone(), WriteOp::Value(random_bytes())),
+        (access_path2.clone(), WriteOp::Value(random_bytes())),
+    ])
+    .freeze()?;
+    chain_state_db.apply_write_set(write_set)?;
+    let state_root = chain_state_db.commit()?;
+    chain_state_db.flush()?;
+
+    // a not existed account, an account without code, and duplicated access paths.
+    let access_paths = vec![
+        access_path1.clone(),
+        AccessPath::random_resource(),
+        access_path2,
+        access_path3,
+        access_path1,
+    ];
+    let expect = access_paths
+        .iter()
+        .map(|access_path| chain_state_db.get(access_path))
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(expect, chain_state_db.multi_get(&access_paths)?);
+    // read from store instead of the account cache.
+    let chain_state_db = ChainStateDB::new(storage, Some(state_root));
+    assert_eq!(expect, chain_state_db.multi_get(&access_paths)?);
+    assert!(expect[0].is_some() && expect[2].is_some());
+    assert!(expect[1].is_none() && expect[3].is_none());
+    Ok(())
+}
+
+#[test]
+fn test_state_db_get_account_state_set() -> Result<()> {
+    let storage = Arc::new(MockStateNodeStore::new());
+    let chain_state_db = ChainStateDB::new(storage.clone(), None);
+    let address = AccountAddress::random();
+    let write_set = (0..100)
+        .map(|i| {
+            let access_path = if i % 10 == 0 {
+                AccessPath::new(address, AccessPath::random_code().path)
+            } else {
+                AccessPath::new(address, AccessPath::random_resource().path)
+            };
+            (access_path, WriteOp::Value(random_bytes()))
+        })
+        .collect::<WriteSetMut>()
+        .freeze()?;
+    chain_state_db.apply_write_set(write_set.clone())?;
+    let state_root = chain_state_db.commit()?;
+    chain_state_db.flush()?;
+
+    // read from store instead of the account cache.
+    let chain_state_db = ChainStateDB::new(storage, Some(state_root));
+    let state_set = chain_state_db
+        .get_account_state_set(&address)?
+        .expect("account state set should exist");
+    let code_set = state_set.code_set().expect("code set should exist");
+    let resource_set = state_set.resource_set().expect("resource set should exist");
+    assert_eq!(code_set.len(), 10);
+    assert_eq!(resource_set.len(), 90);
+    for (access_path, write_op) in write_set.iter() {
+        let data_set = match &access_path.path {
+            DataPath::Code(_) => code_set,
+            DataPath::Resource(_) => resource_set,
+        };
+        let value = match write_op {
+            WriteOp::Value(value) => value,
+            WriteOp::Deletion => unreachable!(),
+        };
+        assert!(data_set.iter().any(|(_, state)| state == value));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_state_db_diff() -> Result<()> {
+    let storage = Arc::new(MockStateNodeStore::new());
+    let chain_state_db = ChainStateDB::new(storage.clone(), None);
+    let unchanged = AccessPath::random_resource();
+    let changed = AccessPath::random_resource();
+    let deleted = AccessPath::random_code();
+    let added = AccessPath::new(changed.address, AccessPath::random_code().path);
+    chain_state_db.apply_write_set(
+        WriteSetMut::new(vec![
+            (unchanged.clone(), WriteOp::Value(random_bytes())),
+            (changed.clone(), WriteOp::Value(vec![1])),
+            (deleted.clone(), WriteOp::Value(vec![2])),
+        ])
+        .freeze()?,
+    )?;
+    let old_root = chain_state_db.commit()?;
+    chain_state_db.flush()?;
+    assert!(chain_state_db.diff(old_root)?.is_empty());
+
+    chain_state_db.apply_write_set(
+        WriteSetMut::new(vec![
+            (changed.clone(), WriteOp::Value(vec![3])),
+            (deleted.clone(), WriteOp::Deletion),
+            (added.clone(), WriteOp::Value(vec![4])),
+        ])
+        .freeze()?,
+    )?;
+    let new_root = chain_state_db.commit()?;
+    chain_state_db.flush()?;
+
+    let mut diff = ChainStateDB::new(storage.clone(), Some(old_root)).diff(new_root)?;
+    diff.sort_by(|a, b| a.0.cmp(&b.0));
+    let mut expected = vec![
+        (changed.clone(), Some(vec![1]), Some(vec![3])),
+        (deleted.clone(), Some(vec![2]), None),
+        (added.clone(), None, Some(vec![4])),
+    ];
+    expected.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(diff, expected);
+
+    let mut reversed = ChainStateDB::new(storage, Some(new_root)).diff(old_root)?;
+    reversed.sort_by(|a, b| a.0.cmp(&b.0));
+    let expected = expected
+        .into_iter()
+        .map(|(access_path, old, new)| (access_path, new, old))
+        .collect::<Vec<_>>();
+    assert_eq!(reversed, expected);
+    Ok(())
+}
+
 #[test]
 fn test_state_db_dump_and_apply() -> Result<()> {
     let storage = MockStateNodeStore::new();
@@ -33,6 +33,24 @@ impl CacheStorage {
         })
     }
 
+    pub fn multi_get_obj(
+        &self,
+        prefix_name: &str,
+        keys: Vec<Vec<u8>>,
+    ) -> Result<Vec<Option<CacheObject>>> {
+        record_metrics("cache", prefix_name, "multi_get").end_with(|| {
+            let mut cache = self.cache.lock();
+            Ok(keys
+                .into_iter()
+                .map(|key| {
+                    cache
+                        .get(&compose_key(prefix_name.to_string(), key))
+                        .cloned()
+                })
+                .collect())
+        })
+    }
+
     pub fn put_obj(&self, prefix_name: &str, key: Vec<u8>, obj: CacheObject) -> Result<()> {
         record_metrics("cache", prefix_name, "put").end_with(|| {
             let mut cache = self.cache.lock();
@@ -79,6 +97,20 @@ impl InnerStore for CacheStorage {
         })
     }
 
+    fn multi_get(&self, prefix_name: &str, keys: Vec<Vec<u8>>) -> Result<Vec<Option<Vec<u8>>>> {
+        record_metrics("cache", prefix_name, "multi_get").end_with(|| {
+            let mut cache = self.cache.lock();
+            Ok(keys
+                .into_iter()
+                .map(|key| {
+                    cache
+                        .get(&compose_key(prefix_name.to_string(), key))
+                        .and_then(|v| v.into())
+                })
+                .collect())
+        })
+    }
+
     fn put(&self, prefix_name: &str, key: Vec<u8>, value: Vec<u8>) -> Result<()> {
         record_metrics("cache", prefix_name, "put").end_with(|| {
             let mut cache = self.cache.lock();
@@ -272,6 +272,18 @@ impl InnerStore for DBStorage {
         })
     }
 
+    fn multi_get(&self, prefix_name: &str, keys: Vec<Vec<u8>>) -> Result<Vec<Option<Vec<u8>>>> {
+        record_metrics("db", prefix_name, "multi_get").end_with(|| {
+            let cf_handle = self.get_cf_handle(prefix_name)?;
+            let values = self
+                .db
+                .multi_get_cf(keys.iter().map(|key| (cf_handle, key.as_slice())))
+                .into_iter()
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(values)
+        })
+    }
+
     fn put(&self, prefix_name: &str, key: Vec<u8>, value: Vec<u8>) -> Result<()> {
         STORAGE_ITER_BYTES
             .with_label_values(&[prefix_name])
@@ -227,6 +227,10 @@ impl StateNodeStore for Storage {
         self.state_node_storage.get(*hash)
     }
 
+    fn multi_get(&self, hashes: &[HashValue]) -> Result<Vec<Option<StateNode>>> {
+        self.state_node_storage.multiple_get(hashes.to_vec())
+    }
+
     fn put(&self, key: HashValue, node: StateNode) -> Result<()> {
         self.state_node_storage.put(key, node)
     }
@@ -21,7 +21,6 @@ pub type ColumnFamilyName = &'static str;
 pub trait KVStore: Send + Sync {
     fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>>;
     fn multiple_get(&self, keys: Vec<Vec<u8>>) -> Result<Vec<Option<Vec<u8>>>> {
-        //TODO optimize
         keys.into_iter().map(|k| self.get(k.as_slice())).collect()
     }
     fn put(&self, key: Vec<u8>, value: Vec<u8>) -> Result<()>;
@@ -34,6 +33,8 @@ pub trait KVStore: Send + Sync {
 
 pub trait InnerStore: Send + Sync {
     fn get(&self, prefix_name: &str, key: Vec<u8>) -> Result<Option<Vec<u8>>>;
+    /// Get the values of the keys in one batch, the values are in the order of the keys.
+    fn multi_get(&self, prefix_name: &str, keys: Vec<Vec<u8>>) -> Result<Vec<Option<Vec<u8>>>>;
     fn put(&self, prefix_name: &str, key: Vec<u8>, value: Vec<u8>) -> Result<()>;
     fn contains_key(&self, prefix_name: &str, key: Vec<u8>) -> Result<bool>;
     fn remove(&self, prefix_name: &str, key: Vec<u8>) -> Result<()>;
@@ -140,6 +141,45 @@ impl InnerStore for StorageInstance {
         }
     }
 
+    fn multi_get(&self, prefix_name: &str, keys: Vec<Vec<u8>>) -> Result<Vec<Option<Vec<u8>>>> {
+        match self {
+            StorageInstance::CACHE { cache } => cache.multi_get(prefix_name, keys),
+            StorageInstance::DB { db } => db.multi_get(prefix_name, keys),
+            StorageInstance::CacheAndDb { cache, db } => {
+                // first get from cache, then get the missed keys from db in one batch
+                let cache_objs = cache.multi_get_obj(prefix_name, keys.clone())?;
+                let mut values = Vec::with_capacity(keys.len());
+                let mut missed_indexes = vec![];
+                let mut missed_keys = vec![];
+                for (index, (key, cache_obj)) in keys.into_iter().zip(cache_objs).enumerate() {
+                    match cache_obj {
+                        Some(cache_obj) => values.push((&cache_obj).into()),
+                        None => {
+                            values.push(None);
+                            missed_indexes.push(index);
+                            missed_keys.push(key);
+                        }
+                    }
+                }
+                if !missed_keys.is_empty() {
+                    let db_values = db.multi_get(prefix_name, missed_keys.clone())?;
+                    for ((index, key), value) in
+                        missed_indexes.into_iter().zip(missed_keys).zip(db_values)
+                    {
+                        let cache_obj = match &value {
+                            Some(value) => CacheObject::Value(value.clone()),
+                            // put null vec to cache for avoid repeatedly querying non-existent data from db
+                            None => CACHE_NONE_OBJECT.clone(),
+                        };
+                        cache.put_obj(prefix_name, key, cache_obj)?;
+                        values[index] = value;
+                    }
+                }
+                Ok(values)
+            }
+        }
+    }
+
     fn put(&self, prefix_name: &str, key: Vec<u8>, value: Vec<u8>) -> Result<()> {
         match self {
             StorageInstance::CACHE { cache } => cache.put(prefix_name, key, value),
@@ -246,6 +286,10 @@ where
         self.instance.get(self.prefix_name, key.to_vec())
     }
 
+    fn multiple_get(&self, keys: Vec<Vec<u8>>) -> Result<Vec<Option<Vec<u8>>>> {
+        self.instance.multi_get(self.prefix_name, keys)
+    }
+
     fn put(&self, key: Vec<u8>, value: Vec<u8>) -> Result<()> {
         self.instance.put(self.prefix_name, key, value)
     }
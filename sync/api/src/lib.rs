@@ -63,7 +63,7 @@ impl ServiceRequest for SyncStatusRequest {
 #[derive(Debug, Clone)]
 pub struct SyncProgressRequest;
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct SyncProgressReport {
     pub target_id: HashValue,
     pub begin_number: Option<BlockNumber>,
@@ -72,8 +72,48 @@ pub struct SyncProgressReport {
     pub target_difficulty: U256,
     pub target_peers: Vec<PeerId>,
     pub current: TaskProgressReport,
+    /// progress of every sync stage: find_ancestor, accumulator_sync, block_fetch, execution.
+    #[serde(default)]
+    pub stages: Vec<SyncStageReport>,
+    /// moving average of executed blocks per second.
+    #[serde(default)]
+    pub blocks_per_second: f64,
+    /// moving average of executed transactions per second.
+    #[serde(default)]
+    pub txns_per_second: f64,
+    /// estimated seconds to reach the target, None if the throughput is unknown.
+    #[serde(default)]
+    pub eta_seconds: Option<u64>,
+    #[serde(default)]
+    pub peer_contributions: Vec<PeerContribution>,
+    /// no block is fetched or executed in a while.
+    #[serde(default)]
+    pub stalled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SyncStageReport {
+    pub stage: String,
+    pub processed_items: u64,
+    pub total_items: Option<u64>,
+    pub use_seconds: u64,
+    pub items_per_second: f64,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PeerContribution {
+    pub peer_id: PeerId,
+    pub blocks: u64,
+    pub requests: u64,
+    pub failed_requests: u64,
+    /// average fetch latency of the succeed requests.
+    pub avg_fetch_millis: u64,
+}
+
+/// Broadcast periodically while synchronizing.
+#[derive(Debug, Clone)]
+pub struct SyncProgressEvent(pub SyncProgressReport);
+
 impl ServiceRequest for SyncProgressRequest {
     type Response = Option<SyncProgressReport>;
 }
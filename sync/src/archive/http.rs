@@ -0,0 +1,59 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::archive::{chunk_file_name, BlockArchive, BlockArchiveManifest, MANIFEST_FILE_NAME};
+use anyhow::{ensure, Result};
+use bcs_ext::BCSCodec;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use hyper::client::HttpConnector;
+use hyper::{Client, Uri};
+use hyper_tls::HttpsConnector;
+use starcoin_types::block::Block;
+
+/// Block archive served by a http(s) static mirror, the files layout is same as `LocalBlockArchive`.
+pub struct HttpBlockArchive {
+    base_url: String,
+    client: Client<HttpsConnector<HttpConnector>>,
+}
+
+impl HttpBlockArchive {
+    pub fn new(base_url: &str) -> Result<Self> {
+        // check the url is valid.
+        let _uri: Uri = base_url.parse()?;
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client: Client::builder().build(HttpsConnector::new()),
+        })
+    }
+
+    async fn get(&self, file_name: String) -> Result<Vec<u8>> {
+        let uri: Uri = format!("{}/{}", self.base_url, file_name).parse()?;
+        let resp = self.client.get(uri.clone()).await?;
+        ensure!(
+            resp.status().is_success(),
+            "Request block archive file {} failed, status: {}",
+            uri,
+            resp.status()
+        );
+        Ok(hyper::body::to_bytes(resp.into_body()).await?.to_vec())
+    }
+}
+
+impl BlockArchive for HttpBlockArchive {
+    fn manifest(&self) -> BoxFuture<Result<BlockArchiveManifest>> {
+        async move {
+            let bytes = self.get(MANIFEST_FILE_NAME.to_string()).await?;
+            BlockArchiveManifest::decode(bytes.as_slice())
+        }
+        .boxed()
+    }
+
+    fn chunk(&self, index: u64) -> BoxFuture<Result<Vec<Block>>> {
+        async move {
+            let bytes = self.get(chunk_file_name(index)).await?;
+            Vec::<Block>::decode(bytes.as_slice())
+        }
+        .boxed()
+    }
+}
@@ -0,0 +1,43 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::archive::{chunk_file_name, BlockArchive, BlockArchiveManifest, MANIFEST_FILE_NAME};
+use anyhow::{Context, Result};
+use bcs_ext::BCSCodec;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use starcoin_types::block::Block;
+use std::path::PathBuf;
+
+/// Block archive in a local directory.
+pub struct LocalBlockArchive {
+    dir: PathBuf,
+}
+
+impl LocalBlockArchive {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn read(&self, file_name: &str) -> Result<Vec<u8>> {
+        let path = self.dir.join(file_name);
+        std::fs::read(path.as_path())
+            .with_context(|| format!("Read block archive file {:?} failed", path))
+    }
+}
+
+impl BlockArchive for LocalBlockArchive {
+    fn manifest(&self) -> BoxFuture<Result<BlockArchiveManifest>> {
+        let result = self
+            .read(MANIFEST_FILE_NAME)
+            .and_then(|bytes| BlockArchiveManifest::decode(bytes.as_slice()));
+        async move { result }.boxed()
+    }
+
+    fn chunk(&self, index: u64) -> BoxFuture<Result<Vec<Block>>> {
+        let result = self
+            .read(chunk_file_name(index).as_str())
+            .and_then(|bytes| Vec::<Block>::decode(bytes.as_slice()));
+        async move { result }.boxed()
+    }
+}
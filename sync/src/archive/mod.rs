@@ -0,0 +1,275 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Block archive is a peer-independent block source for sync, such as a local directory or a
+//! http static mirror. The archive is made up of a manifest and numbered bcs encoded block chunks,
+//! the chunk `i` contains the blocks `[i * chunk_size, (i + 1) * chunk_size)`.
+
+use crate::tasks::{BlockFetcher, BlockIdFetcher, BlockInfoFetcher, PeerOperator, SyncFetcher};
+use anyhow::{ensure, format_err, Result};
+use bcs_ext::BCSCodec;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use network_api::{PeerSelector, PeerStrategy};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use starcoin_chain_api::ChainReader;
+use starcoin_crypto::HashValue;
+use starcoin_sync_api::SyncTarget;
+use starcoin_types::block::{Block, BlockIdAndNumber, BlockInfo, BlockNumber};
+use starcoin_types::peer_info::PeerId;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+mod http;
+mod local;
+#[cfg(test)]
+mod tests;
+
+pub use http::HttpBlockArchive;
+pub use local::LocalBlockArchive;
+
+pub const MANIFEST_FILE_NAME: &str = "manifest.bcs";
+
+const MAX_CACHED_CHUNKS: usize = 16;
+
+pub fn chunk_file_name(index: u64) -> String {
+    format!("{}.bcs", index)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BlockArchiveManifest {
+    /// block count of every chunk.
+    pub chunk_size: u64,
+    /// the latest block of the archive.
+    pub head: BlockIdAndNumber,
+    /// the block info of the head block, it is the sync target of the archive.
+    pub head_block_info: BlockInfo,
+}
+
+pub trait BlockArchive: Send + Sync {
+    fn manifest(&self) -> BoxFuture<Result<BlockArchiveManifest>>;
+
+    fn chunk(&self, index: u64) -> BoxFuture<Result<Vec<Block>>>;
+}
+
+/// Open a block archive by source, a `http://` or `https://` url means a http mirror, other is a
+/// local directory.
+pub fn open_block_archive(source: &str) -> Result<Arc<dyn BlockArchive>> {
+    if source.starts_with("http://") || source.starts_with("https://") {
+        Ok(Arc::new(HttpBlockArchive::new(source)?))
+    } else {
+        Ok(Arc::new(LocalBlockArchive::new(source)))
+    }
+}
+
+/// Export the blocks of the chain to the archive dir.
+pub fn write_block_archive(
+    chain: &dyn ChainReader,
+    dir: &Path,
+    chunk_size: u64,
+) -> Result<BlockArchiveManifest> {
+    ensure!(chunk_size > 0, "Block archive chunk size should > 0");
+    std::fs::create_dir_all(dir)?;
+    let status = chain.status();
+    let head_number = status.head.number();
+    let mut index = 0u64;
+    let mut start_number = 0u64;
+    while start_number <= head_number {
+        let end_number = std::cmp::min(
+            start_number.saturating_add(chunk_size.saturating_sub(1)),
+            head_number,
+        );
+        let blocks = (start_number..=end_number)
+            .map(|number| {
+                chain
+                    .get_block_by_number(number)?
+                    .ok_or_else(|| format_err!("Can not find block by number: {}", number))
+            })
+            .collect::<Result<Vec<Block>>>()?;
+        std::fs::write(dir.join(chunk_file_name(index)), blocks.encode()?)?;
+        index = index.saturating_add(1);
+        start_number = end_number.saturating_add(1);
+    }
+    let manifest = BlockArchiveManifest {
+        chunk_size,
+        head: BlockIdAndNumber::new(status.head.id(), head_number),
+        head_block_info: status.info,
+    };
+    // write manifest at last, a archive without manifest is incomplete.
+    std::fs::write(dir.join(MANIFEST_FILE_NAME), manifest.encode()?)?;
+    Ok(manifest)
+}
+
+/// Adapt a block archive to the sync fetchers, so the archive can be verified and applied by the
+/// same sync tasks as peers.
+pub struct ArchiveBlockFetcher {
+    archive: Arc<dyn BlockArchive>,
+    manifest: BlockArchiveManifest,
+    peer_selector: PeerSelector,
+    chunks: Mutex<HashMap<u64, Arc<Vec<Block>>>>,
+    block_numbers: Mutex<HashMap<HashValue, BlockNumber>>,
+}
+
+impl ArchiveBlockFetcher {
+    pub async fn open(archive: Arc<dyn BlockArchive>) -> Result<Self> {
+        let manifest = archive.manifest().await?;
+        ensure!(
+            manifest.chunk_size > 0,
+            "Invalid block archive manifest, chunk size should > 0"
+        );
+        ensure!(
+            manifest.head.id == manifest.head_block_info.block_id,
+            "Invalid block archive manifest, head block info mismatch with head {:?}",
+            manifest.head
+        );
+        Ok(Self {
+            archive,
+            manifest,
+            peer_selector: PeerSelector::new(vec![], PeerStrategy::default()),
+            chunks: Mutex::new(HashMap::new()),
+            block_numbers: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn manifest(&self) -> &BlockArchiveManifest {
+        &self.manifest
+    }
+
+    pub fn sync_target(&self) -> SyncTarget {
+        SyncTarget {
+            target_id: self.manifest.head,
+            block_info: self.manifest.head_block_info.clone(),
+            peers: vec![],
+        }
+    }
+
+    async fn get_chunk(&self, index: u64) -> Result<Arc<Vec<Block>>> {
+        if let Some(chunk) = self.chunks.lock().get(&index) {
+            return Ok(chunk.clone());
+        }
+        let chunk = Arc::new(self.archive.chunk(index).await?);
+        let mut chunks = self.chunks.lock();
+        if chunks.len() >= MAX_CACHED_CHUNKS {
+            chunks.clear();
+        }
+        chunks.insert(index, chunk.clone());
+        Ok(chunk)
+    }
+
+    async fn get_block_by_number(&self, number: BlockNumber) -> Result<Block> {
+        let chunk_size = self.manifest.chunk_size;
+        let index = number
+            .checked_div(chunk_size)
+            .ok_or_else(|| format_err!("Invalid chunk size: {}", chunk_size))?;
+        let offset = number
+            .checked_rem(chunk_size)
+            .ok_or_else(|| format_err!("Invalid chunk size: {}", chunk_size))?;
+        let block = self
+            .get_chunk(index)
+            .await?
+            .get(offset as usize)
+            .cloned()
+            .ok_or_else(|| {
+                format_err!("Can not find block {} in archive chunk {}", number, index)
+            })?;
+        ensure!(
+            block.header().number() == number,
+            "Invalid archive chunk {}, expect block number {}, but got {}",
+            index,
+            number,
+            block.header().number()
+        );
+        Ok(block)
+    }
+}
+
+impl PeerOperator for ArchiveBlockFetcher {
+    fn peer_selector(&self) -> PeerSelector {
+        self.peer_selector.clone()
+    }
+}
+
+impl BlockIdFetcher for ArchiveBlockFetcher {
+    fn fetch_block_ids(
+        &self,
+        _peer: Option<PeerId>,
+        start_number: BlockNumber,
+        reverse: bool,
+        max_size: u64,
+    ) -> BoxFuture<Result<Vec<HashValue>>> {
+        async move {
+            let head_number = self.manifest.head.number;
+            let numbers: Vec<BlockNumber> = if reverse {
+                (0..=std::cmp::min(start_number, head_number))
+                    .rev()
+                    .take(max_size as usize)
+                    .collect()
+            } else {
+                (start_number..=head_number)
+                    .take(max_size as usize)
+                    .collect()
+            };
+            let mut block_ids = Vec::with_capacity(numbers.len());
+            for number in numbers {
+                let block_id = self.get_block_by_number(number).await?.id();
+                self.block_numbers.lock().insert(block_id, number);
+                block_ids.push(block_id);
+            }
+            Ok(block_ids)
+        }
+        .boxed()
+    }
+}
+
+impl BlockFetcher for ArchiveBlockFetcher {
+    fn fetch_blocks(
+        &self,
+        block_ids: Vec<HashValue>,
+    ) -> BoxFuture<Result<Vec<(Block, Option<PeerId>)>>> {
+        async move {
+            let mut blocks = Vec::with_capacity(block_ids.len());
+            for block_id in block_ids {
+                let number = self
+                    .block_numbers
+                    .lock()
+                    .get(&block_id)
+                    .cloned()
+                    .ok_or_else(|| format_err!("Can not find block by id: {}", block_id))?;
+                let block = self.get_block_by_number(number).await?;
+                ensure!(
+                    block.id() == block_id,
+                    "Archive block {} mismatch with id: {}",
+                    number,
+                    block_id
+                );
+                blocks.push((block, None));
+            }
+            Ok(blocks)
+        }
+        .boxed()
+    }
+}
+
+impl BlockInfoFetcher for ArchiveBlockFetcher {
+    fn fetch_block_infos(
+        &self,
+        _peer_id: Option<PeerId>,
+        block_ids: Vec<HashValue>,
+    ) -> BoxFuture<Result<Vec<Option<BlockInfo>>>> {
+        let block_infos = block_ids
+            .into_iter()
+            .map(|block_id| {
+                if block_id == self.manifest.head.id {
+                    Some(self.manifest.head_block_info.clone())
+                } else {
+                    None
+                }
+            })
+            .collect();
+        async move { Ok(block_infos) }.boxed()
+    }
+}
+
+impl SyncFetcher for ArchiveBlockFetcher {}
@@ -0,0 +1,168 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::archive::{
+    chunk_file_name, write_block_archive, ArchiveBlockFetcher, BlockArchive, HttpBlockArchive,
+    LocalBlockArchive,
+};
+use crate::tasks::mock::SyncNodeMocker;
+use crate::tasks::{full_sync_task, BlockFetchOptions, SyncProgressTracker};
+use anyhow::Result;
+use config::{temp_path, BuiltinNetworkID, ChainNetwork};
+use executor::ParallelExecutor;
+use futures::channel::mpsc::unbounded;
+use futures::future;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server, StatusCode};
+use starcoin_chain::BlockChain;
+use starcoin_chain_api::ChainReader;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use test_helper::DummyNetworkService;
+
+async fn sync_from_archive(
+    node: SyncNodeMocker,
+    archive: Arc<dyn BlockArchive>,
+) -> Result<(SyncNodeMocker, BlockChain)> {
+    let fetcher = Arc::new(ArchiveBlockFetcher::open(archive).await?);
+    let target = fetcher.sync_target();
+    let net = node.chain_mocker.net().clone();
+    let current_block_header = node.chain().current_header();
+    let storage = node.chain().get_storage();
+    let (sender, receiver) = unbounded();
+    let (ancestor_sender, _ancestor_receiver) = unbounded();
+    let (sync_task, _task_handle, _task_event_counter) = full_sync_task(
+        current_block_header.id(),
+        target,
+        false,
+        net.time_service(),
+        storage,
+        sender,
+        fetcher,
+        ancestor_sender,
+        DummyNetworkService::default(),
+        3,
+        BlockFetchOptions::default(),
+        Arc::new(SyncProgressTracker::new()),
+        ParallelExecutor::default(),
+    )?;
+    let join_handle = node.process_block_connect_event(receiver).await;
+    let branch = sync_task.await?;
+    let node = join_handle.await;
+    Ok((node, branch))
+}
+
+#[stest::test]
+async fn test_sync_from_local_archive_then_peer() -> Result<()> {
+    let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut node1 = SyncNodeMocker::new(net1, 1, 0)?;
+    node1.produce_block(20)?;
+    let archive_dir = temp_path();
+    let manifest = write_block_archive(node1.chain(), archive_dir.path(), 6)?;
+    assert_eq!(manifest.head.number, 20);
+    node1.produce_block(5)?;
+
+    let net2 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let node2 = SyncNodeMocker::new(net2.clone(), 1, 0)?;
+    let archive = Arc::new(LocalBlockArchive::new(archive_dir.path()));
+    let (node2, branch) = sync_from_archive(node2, archive).await?;
+    assert_eq!(branch.current_header().id(), manifest.head.id);
+    assert_eq!(node2.chain().current_header().id(), manifest.head.id);
+
+    // sync the tail from peer.
+    let arc_node1 = Arc::new(node1);
+    let target = arc_node1.sync_target();
+    let storage = node2.chain().get_storage();
+    let (sender_1, receiver_1) = unbounded();
+    let (sender_2, _receiver_2) = unbounded();
+    let (sync_task, _task_handle, _task_event_counter) = full_sync_task(
+        node2.chain().current_header().id(),
+        target.clone(),
+        false,
+        net2.time_service(),
+        storage,
+        sender_1,
+        arc_node1.clone(),
+        sender_2,
+        DummyNetworkService::default(),
+        15,
+        BlockFetchOptions::default(),
+        Arc::new(SyncProgressTracker::new()),
+        ParallelExecutor::default(),
+    )?;
+    let join_handle = node2.process_block_connect_event(receiver_1).await;
+    let branch = sync_task.await?;
+    let node2 = join_handle.await;
+    assert_eq!(branch.current_header().id(), target.target_id.id());
+    assert_eq!(node2.chain().current_header().id(), target.target_id.id());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_sync_from_invalid_archive() -> Result<()> {
+    let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut node1 = SyncNodeMocker::new(net1.clone(), 1, 0)?;
+    node1.produce_block(20)?;
+    let archive_dir = temp_path();
+    write_block_archive(node1.chain(), archive_dir.path(), 6)?;
+
+    // replace a chunk with another chain's blocks.
+    let mut node3 = SyncNodeMocker::new(net1, 1, 0)?;
+    node3.produce_block(20)?;
+    let other_archive_dir = temp_path();
+    write_block_archive(node3.chain(), other_archive_dir.path(), 6)?;
+    std::fs::copy(
+        other_archive_dir.path().join(chunk_file_name(2)),
+        archive_dir.path().join(chunk_file_name(2)),
+    )?;
+
+    let net2 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let node2 = SyncNodeMocker::new(net2, 1, 0)?;
+    let archive = Arc::new(LocalBlockArchive::new(archive_dir.path()));
+    let result = sync_from_archive(node2, archive).await;
+    assert!(result.is_err());
+    Ok(())
+}
+
+fn serve_archive(dir: PathBuf) -> Result<SocketAddr> {
+    let make_service = make_service_fn(move |_| {
+        let dir = dir.clone();
+        future::ok::<_, hyper::Error>(service_fn(move |req: Request<Body>| {
+            let path = dir.join(req.uri().path().trim_start_matches('/'));
+            async move {
+                let resp = match std::fs::read(path) {
+                    Ok(bytes) => Response::new(Body::from(bytes)),
+                    Err(_) => {
+                        let mut resp = Response::new(Body::empty());
+                        *resp.status_mut() = StatusCode::NOT_FOUND;
+                        resp
+                    }
+                };
+                Ok::<_, hyper::Error>(resp)
+            }
+        }))
+    });
+    let server = Server::try_bind(&SocketAddr::from(([127, 0, 0, 1], 0)))?.serve(make_service);
+    let addr = server.local_addr();
+    tokio::task::spawn(server);
+    Ok(addr)
+}
+
+#[stest::test]
+async fn test_sync_from_http_archive() -> Result<()> {
+    let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut node1 = SyncNodeMocker::new(net1, 1, 0)?;
+    node1.produce_block(15)?;
+    let archive_dir = temp_path();
+    let manifest = write_block_archive(node1.chain(), archive_dir.path(), 4)?;
+    let addr = serve_archive(archive_dir.path().to_path_buf())?;
+
+    let net2 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let node2 = SyncNodeMocker::new(net2, 1, 0)?;
+    let archive = Arc::new(HttpBlockArchive::new(format!("http://{}/", addr).as_str())?);
+    let (node2, branch) = sync_from_archive(node2, archive).await?;
+    assert_eq!(branch.current_header().id(), manifest.head.id);
+    assert_eq!(node2.chain().current_header().id(), manifest.head.id);
+    Ok(())
+}
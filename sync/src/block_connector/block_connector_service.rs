@@ -6,6 +6,7 @@ use crate::sync::{CheckSyncEvent, SyncService};
 use crate::tasks::BlockConnectedEvent;
 use anyhow::{format_err, Result};
 use config::NodeConfig;
+use executor::ParallelExecutor;
 use logger::prelude::*;
 use network::NetworkServiceRef;
 use network_api::PeerProvider;
@@ -51,8 +52,11 @@ impl ServiceFactory<Self> for BlockConnectorService {
         let startup_info = storage
             .get_startup_info()?
             .ok_or_else(|| format_err!("Startup info should exist."))?;
+        let parallel_executor =
+            ctx.get_shared_or_put(|| ParallelExecutor::new(config.executor.concurrency_level()))?;
         let chain_service =
-            WriteBlockChainService::new(config, startup_info, storage, txpool, bus)?;
+            WriteBlockChainService::new(config, startup_info, storage, txpool, bus)?
+                .with_parallel_executor(parallel_executor);
 
         Ok(Self::new(chain_service))
     }
@@ -4,18 +4,21 @@
 use crate::block_connector::WriteBlockChainService;
 use config::NodeConfig;
 use consensus::Consensus;
+use futures::StreamExt;
 use starcoin_account_api::AccountInfo;
 use starcoin_chain::{BlockChain, ChainReader};
 use starcoin_chain_service::WriteableChainService;
 use starcoin_genesis::Genesis as StarcoinGenesis;
-use starcoin_service_registry::bus::BusService;
+use starcoin_service_registry::bus::{Bus, BusService};
 use starcoin_service_registry::{RegistryAsyncService, RegistryService};
 use starcoin_storage::Store;
 use starcoin_txpool_mock_service::MockTxPoolService;
 use starcoin_types::block::Block;
 use starcoin_types::startup_info::StartupInfo;
+use starcoin_types::system_events::NewHeadBlock;
 use starcoin_vm_types::time::TimeService;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub async fn create_writeable_block_chain() -> (
     WriteBlockChainService<MockTxPoolService>,
@@ -240,3 +243,58 @@ async fn test_block_chain_reset() -> anyhow::Result<()> {
         .is_some());
     Ok(())
 }
+
+#[stest::test]
+async fn test_block_chain_reorg_write_sets() -> anyhow::Result<()> {
+    let node_config = Arc::new(NodeConfig::random_for_test());
+    let (storage, chain_info, _) = StarcoinGenesis::init_storage_for_test(node_config.net())?;
+    let registry = RegistryService::launch();
+    let bus = registry.service_ref::<BusService>().await?;
+    let mut new_head_receiver = bus.channel::<NewHeadBlock>().await?;
+    let mut writeable_block_chain_service = WriteBlockChainService::new(
+        node_config.clone(),
+        StartupInfo::new(chain_info.head().id()),
+        storage,
+        MockTxPoolService::new(),
+        bus,
+    )?;
+    let net = node_config.net();
+    gen_blocks(
+        3,
+        &mut writeable_block_chain_service,
+        net.time_service().as_ref(),
+    );
+    gen_fork_block_chain(
+        1,
+        node_config.clone(),
+        4,
+        &mut writeable_block_chain_service,
+    );
+    let main = writeable_block_chain_service.get_main();
+    let head_id = main.current_header().id();
+    assert_eq!(main.current_header().number(), 5);
+
+    let reorg_write_sets = loop {
+        let event = tokio::time::timeout(Duration::from_secs(5), new_head_receiver.next())
+            .await?
+            .expect("NewHeadBlock channel should not be closed");
+        if event.0.header().id() == head_id {
+            break event
+                .1
+                .expect("write sets of the enacted blocks should be collected");
+        }
+    };
+    // the fork blocks 2, 3 are executed as branch blocks, and enacted with the block 4 or 5.
+    assert!(reorg_write_sets.len() > 1);
+    assert_eq!(
+        reorg_write_sets.last().map(|w| w.header.id()),
+        Some(head_id)
+    );
+    for block_write_sets in reorg_write_sets.iter() {
+        let header = &block_write_sets.header;
+        assert_eq!(main.get_hash_by_number(header.number())?, Some(header.id()));
+        let txn_info_ids = main.get_storage().get_block_txn_info_ids(header.id())?;
+        assert_eq!(block_write_sets.write_sets.len(), txn_info_ids.len());
+    }
+    Ok(())
+}
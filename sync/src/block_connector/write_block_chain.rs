@@ -2,26 +2,34 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::metrics::WRITE_BLOCK_CHAIN_METRICS;
-use anyhow::{format_err, Result};
+use anyhow::{ensure, format_err, Result};
 use config::NodeConfig;
+use executor::ParallelExecutor;
 use logger::prelude::*;
 use starcoin_chain::BlockChain;
 use starcoin_chain_api::{ChainReader, ChainWriter, ConnectBlockError, WriteableChainService};
 use starcoin_crypto::HashValue;
 use starcoin_service_registry::bus::{Bus, BusService};
 use starcoin_service_registry::ServiceRef;
+use starcoin_state_api::StateReaderExt;
+use starcoin_statedb::ChainStateDB;
 use starcoin_storage::Store;
 use starcoin_txpool_api::TxPoolSyncService;
-use starcoin_types::block::BlockInfo;
+use starcoin_types::block::{BlockIdAndNumber, BlockInfo};
 use starcoin_types::{
     block::{Block, BlockHeader, ExecutedBlock},
     startup_info::StartupInfo,
-    system_events::{NewBranch, NewHeadBlock},
+    system_events::{BlockWriteSets, ChainReorg, NewBranch, NewHeadBlock},
+    transaction::Transaction,
+    write_set::WriteSet,
 };
 use starcoin_vm_types::on_chain_config::GlobalTimeOnChain;
+use std::collections::VecDeque;
 use std::sync::Arc;
 
 const MAX_ROLL_BACK_BLOCK: usize = 10;
+/// Max count of the recently executed blocks whose write sets are cached for the later head selection.
+const MAX_CACHED_WRITE_SETS: usize = 64;
 
 pub struct WriteBlockChainService<P>
 where
@@ -33,6 +41,9 @@ where
     storage: Arc<dyn Store>,
     txpool: P,
     bus: ServiceRef<BusService>,
+    parallel_executor: ParallelExecutor,
+    /// write sets of the recently executed blocks, the write sets are not persisted.
+    write_sets_cache: VecDeque<(HashValue, Arc<[WriteSet]>)>,
 }
 
 impl<P> WriteableChainService for WriteBlockChainService<P>
@@ -64,9 +75,28 @@ where
             storage,
             txpool,
             bus,
+            parallel_executor: ParallelExecutor::default(),
+            write_sets_cache: VecDeque::with_capacity(MAX_CACHED_WRITE_SETS),
         })
     }
 
+    /// Execute the user txns of blocks in parallel with the threads of `parallel_executor`.
+    pub fn with_parallel_executor(mut self, parallel_executor: ParallelExecutor) -> Self {
+        self.main.set_parallel_executor(parallel_executor.clone());
+        self.parallel_executor = parallel_executor;
+        self
+    }
+
+    fn new_chain(&self, head_block_hash: HashValue) -> Result<BlockChain> {
+        let mut chain = BlockChain::new(
+            self.config.net().time_service(),
+            head_block_hash,
+            self.storage.clone(),
+        )?;
+        chain.set_parallel_executor(self.parallel_executor.clone());
+        Ok(chain)
+    }
+
     fn find_or_fork(
         &self,
         header: &BlockHeader,
@@ -81,20 +111,10 @@ where
             if self.is_main_head(&header.parent_hash()) {
                 None
             } else {
-                let net = self.config.net();
-                Some(BlockChain::new(
-                    net.time_service(),
-                    block_id,
-                    self.storage.clone(),
-                )?)
+                Some(self.new_chain(block_id)?)
             }
         } else if self.block_exist(header.parent_hash())? {
-            let net = self.config.net();
-            Some(BlockChain::new(
-                net.time_service(),
-                header.parent_hash(),
-                self.storage.clone(),
-            )?)
+            Some(self.new_chain(header.parent_hash())?)
         } else {
             None
         };
@@ -122,10 +142,26 @@ where
                 } else {
                     (1, vec![executed_block.block.clone()], 0, vec![])
                 };
+            let chain_reorg = if !parent_is_main_head {
+                Some(self.find_chain_reorg(&new_branch)?)
+            } else {
+                None
+            };
+            // all the enacted blocks, without the `MAX_ROLL_BACK_BLOCK` limit of `enacted_blocks`.
+            let enacted_ids = match chain_reorg.as_ref() {
+                Some(chain_reorg) => chain_reorg.enacted.iter().map(|block| block.id).collect(),
+                None => vec![executed_block.header().id()],
+            };
             self.main = new_branch;
 
+            if let Some(chain_reorg) = chain_reorg {
+                if !chain_reorg.retracted.is_empty() {
+                    self.broadcast_chain_reorg(chain_reorg);
+                }
+            }
             self.do_new_head(
                 executed_block,
+                enacted_ids,
                 enacted_count,
                 enacted_blocks,
                 retracted_count,
@@ -141,6 +177,7 @@ where
     fn do_new_head(
         &mut self,
         executed_block: ExecutedBlock,
+        enacted_ids: Vec<HashValue>,
         enacted_count: u64,
         enacted_blocks: Vec<Block>,
         retracted_count: u64,
@@ -164,21 +201,95 @@ where
             .time_service()
             .adjust(GlobalTimeOnChain::new(executed_block.header().timestamp()));
         info!("[chain] Select new head, id: {}, number: {}, total_difficulty: {}, enacted_block_count: {}, retracted_block_count: {}", executed_block.header().id(), executed_block.header().number(), executed_block.block_info().total_difficulty, enacted_count, retracted_count);
-        self.broadcast_new_head(executed_block);
+        // the head is selected already, so the new head is still broadcast without write sets.
+        let write_sets = match self.collect_write_sets(enacted_ids.as_slice()) {
+            Ok(write_sets) => Some(write_sets),
+            Err(e) => {
+                error!("Collect write sets of the enacted blocks error: {:?}", e);
+                None
+            }
+        };
+        self.broadcast_new_head(executed_block, write_sets);
         Ok(())
     }
 
+    fn cache_write_sets(&mut self, block_id: HashValue, write_sets: Vec<WriteSet>) {
+        if self.write_sets_cache.len() >= MAX_CACHED_WRITE_SETS {
+            self.write_sets_cache.pop_front();
+        }
+        self.write_sets_cache
+            .push_back((block_id, write_sets.into()));
+    }
+
+    /// Collect the write sets of the enacted blocks, the write sets which are not cached are
+    /// loaded by re-executing the block.
+    fn collect_write_sets(&self, enacted_ids: &[HashValue]) -> Result<Arc<[BlockWriteSets]>> {
+        enacted_ids
+            .iter()
+            .map(|block_id| {
+                let cached = self
+                    .write_sets_cache
+                    .iter()
+                    .find(|(id, _)| id == block_id)
+                    .map(|(_, write_sets)| write_sets.clone());
+                let write_sets = match cached {
+                    Some(write_sets) => write_sets,
+                    None => self.load_write_sets(*block_id)?,
+                };
+                let header = self
+                    .storage
+                    .get_block_header_by_hash(*block_id)?
+                    .ok_or_else(|| format_err!("Can not find block header by id {}", block_id))?;
+                Ok(BlockWriteSets { header, write_sets })
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Into::into)
+    }
+
+    /// Re-execute the txns of the block on the parent state to get the write sets.
+    fn load_write_sets(&self, block_id: HashValue) -> Result<Arc<[WriteSet]>> {
+        let block = self
+            .storage
+            .get_block(block_id)?
+            .ok_or_else(|| format_err!("Can not find block {:?}.", block_id))?;
+        let parent_hash = block.header().parent_hash();
+        let parent = self
+            .storage
+            .get_block_header_by_hash(parent_hash)?
+            .ok_or_else(|| format_err!("Can not find block header by id {}", parent_hash))?;
+        let txns: Vec<Transaction> = std::iter::once(Transaction::BlockMetadata(
+            block.to_metadata(parent.gas_used()),
+        ))
+        .chain(
+            block
+                .transactions()
+                .iter()
+                .cloned()
+                .map(Transaction::UserTransaction),
+        )
+        .collect();
+        // the states are only committed to the memory of the statedb, never flushed.
+        let state = ChainStateDB::new(
+            self.storage.clone().into_super_arc(),
+            Some(parent.state_root()),
+        );
+        let block_gas_limit = state.get_epoch()?.block_gas_limit();
+        let executed_data = executor::block_execute(&state, txns, block_gas_limit)?;
+        ensure!(
+            executed_data.state_root == block.header().state_root(),
+            "The re-executed state root of block {} mismatch",
+            block_id
+        );
+        Ok(executed_data.txn_write_sets.into())
+    }
+
     /// Reset the node to `block_id`, and replay blocks after the block
     pub fn reset(&mut self, block_id: HashValue) -> Result<()> {
         let new_head_block = self
             .main
             .get_block(block_id)?
             .ok_or_else(|| format_err!("Can not find block {} in main chain", block_id,))?;
-        let new_branch = BlockChain::new(
-            self.config.net().time_service(),
-            block_id,
-            self.storage.clone(),
-        )?;
+        let new_branch = self.new_chain(block_id)?;
 
         // delete block since from block.number + 1 to latest.
         let start = new_head_block.header().number().saturating_add(1);
@@ -196,10 +307,12 @@ where
 
         self.main = new_branch;
 
+        let enacted_ids = vec![executed_block.header().id()];
         let (enacted_count, enacted_blocks, retracted_count, retracted_blocks) =
             (1, vec![executed_block.block.clone()], 0, vec![]);
         self.do_new_head(
             executed_block,
+            enacted_ids,
             enacted_count,
             enacted_blocks,
             retracted_count,
@@ -210,11 +323,7 @@ where
 
     ///Directly execute the block and save result, do not try to connect.
     pub fn execute(&mut self, block: Block) -> Result<ExecutedBlock> {
-        let chain = BlockChain::new(
-            self.config.net().time_service(),
-            block.header().parent_hash(),
-            self.storage.clone(),
-        )?;
+        let chain = self.new_chain(block.header().parent_hash())?;
         let verify_block = chain.verify(block)?;
         chain.execute(verify_block)
     }
@@ -279,6 +388,32 @@ where
         Ok((enacted_count, enacted, retracted_count, retracted))
     }
 
+    /// Collect the ids of blocks switched by the new branch, without the `MAX_ROLL_BACK_BLOCK` limit.
+    fn find_chain_reorg(&self, new_branch: &BlockChain) -> Result<ChainReorg> {
+        let common_ancestor = self.main.find_ancestor(new_branch)?.ok_or_else(|| {
+            format_err!(
+                "Can not find ancestors between main chain: {:?} and branch: {:?}",
+                self.main.status(),
+                new_branch.status()
+            )
+        })?;
+        let block_ids = |chain: &BlockChain| -> Result<Vec<BlockIdAndNumber>> {
+            (common_ancestor.number.saturating_add(1)..=chain.current_header().number())
+                .map(|number| {
+                    chain
+                        .get_hash_by_number(number)?
+                        .map(|id| BlockIdAndNumber::new(id, number))
+                        .ok_or_else(|| format_err!("Can not find block hash by number {}", number))
+                })
+                .collect()
+        };
+        Ok(ChainReorg {
+            common_ancestor,
+            retracted: block_ids(&self.main)?,
+            enacted: block_ids(new_branch)?,
+        })
+    }
+
     fn find_blocks_until(
         &self,
         from: HashValue,
@@ -306,12 +441,21 @@ where
         Ok(blocks)
     }
 
-    fn broadcast_new_head(&self, block: ExecutedBlock) {
-        if let Err(e) = self.bus.broadcast(NewHeadBlock(Arc::new(block))) {
+    fn broadcast_new_head(&self, block: ExecutedBlock, write_sets: Option<Arc<[BlockWriteSets]>>) {
+        if let Err(e) = self
+            .bus
+            .broadcast(NewHeadBlock(Arc::new(block), write_sets))
+        {
             error!("Broadcast NewHeadBlock error: {:?}", e);
         }
     }
 
+    fn broadcast_chain_reorg(&self, chain_reorg: ChainReorg) {
+        if let Err(e) = self.bus.broadcast(chain_reorg) {
+            error!("Broadcast ChainReorg error: {:?}", e);
+        }
+    }
+
     fn broadcast_new_branch(&self, block: ExecutedBlock) {
         if let Err(e) = self.bus.broadcast(NewBranch(Arc::new(block))) {
             error!("Broadcast NewBranch error: {:?}", e);
@@ -327,15 +471,18 @@ where
         if self.main.current_header().id() == block.header().parent_hash()
             && !self.block_exist(block_id)?
         {
-            let executed_block = self.main.apply(block).map_err(|e| {
-                WRITE_BLOCK_CHAIN_METRICS
-                    .block_connect_count
-                    .with_label_values(&["verify_failed"])
-                    .inc();
-                e
-            })?;
+            let (executed_block, write_sets) =
+                self.main.apply_with_write_sets(block).map_err(|e| {
+                    WRITE_BLOCK_CHAIN_METRICS
+                        .block_connect_count
+                        .with_label_values(&["verify_failed"])
+                        .inc();
+                    e
+                })?;
+            let block_id = executed_block.header().id();
+            self.cache_write_sets(block_id, write_sets);
             let enacted_blocks = vec![executed_block.block().clone()];
-            self.do_new_head(executed_block, 1, enacted_blocks, 0, vec![])?;
+            self.do_new_head(executed_block, vec![block_id], 1, enacted_blocks, 0, vec![])?;
             return Ok(());
         }
         let (block_info, fork) = self.find_or_fork(block.header())?;
@@ -360,7 +507,7 @@ where
                     block: block.clone(),
                     block_info,
                 })?;
-                self.do_new_head(executed_block, 1, vec![block], 0, vec![])?;
+                self.do_new_head(executed_block, vec![block_id], 1, vec![block], 0, vec![])?;
                 Ok(())
             }
             (None, Some(mut branch)) => {
@@ -368,14 +515,16 @@ where
                     .exe_block_time
                     .with_label_values(&["time"])
                     .start_timer();
-                let _executed_block = branch.apply(block).map_err(|e| {
-                    WRITE_BLOCK_CHAIN_METRICS
-                        .block_connect_count
-                        .with_label_values(&["verify_failed"])
-                        .inc();
-                    e
-                })?;
+                let (executed_block, write_sets) =
+                    branch.apply_with_write_sets(block).map_err(|e| {
+                        WRITE_BLOCK_CHAIN_METRICS
+                            .block_connect_count
+                            .with_label_values(&["verify_failed"])
+                            .inc();
+                        e
+                    })?;
                 timer.observe_duration();
+                self.cache_write_sets(executed_block.header().id(), write_sets);
                 self.select_head(branch)?;
                 Ok(())
             }
@@ -3,6 +3,7 @@
 
 #![deny(clippy::integer_arithmetic)]
 pub mod announcement;
+pub mod archive;
 pub mod block_connector;
 pub mod sync;
 pub mod sync_metrics;
@@ -1,12 +1,17 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::archive::{open_block_archive, ArchiveBlockFetcher, BlockArchive};
 use crate::block_connector::BlockConnectorService;
 use crate::sync_metrics::SYNC_METRICS;
-use crate::tasks::{full_sync_task, AncestorEvent, SyncFetcher};
+use crate::tasks::{
+    full_sync_task, AncestorEvent, BlockFetchOptions, PeerOperator, SyncFetcher,
+    SyncProgressTracker,
+};
 use crate::verified_rpc_client::{RpcVerifyError, VerifiedRpcClient};
 use anyhow::{format_err, Result};
 use config::NodeConfig;
+use executor::ParallelExecutor;
 use futures::FutureExt;
 use futures_timer::Delay;
 use logger::prelude::*;
@@ -21,7 +26,7 @@ use starcoin_service_registry::{
 use starcoin_storage::block_info::BlockInfoStore;
 use starcoin_storage::{BlockStore, Storage};
 use starcoin_sync_api::{
-    PeerScoreRequest, PeerScoreResponse, SyncCancelRequest, SyncProgressReport,
+    PeerScoreRequest, PeerScoreResponse, SyncCancelRequest, SyncProgressEvent, SyncProgressReport,
     SyncProgressRequest, SyncServiceHandler, SyncStartRequest, SyncStatusRequest, SyncTarget,
 };
 use starcoin_types::block::BlockIdAndNumber;
@@ -34,6 +39,8 @@ use std::time::Duration;
 use stream_task::{TaskError, TaskEventCounterHandle, TaskHandle};
 
 const REPUTATION_THRESHOLD: i32 = -1000;
+/// The interval of broadcast SyncProgressEvent while synchronizing.
+const SYNC_PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
 
 //TODO combine task_handle and task_event_handle in stream_task
 pub struct SyncTaskHandle {
@@ -42,6 +49,7 @@ pub struct SyncTaskHandle {
     task_handle: TaskHandle,
     task_event_handle: Arc<TaskEventCounterHandle>,
     peer_selector: PeerSelector,
+    progress_tracker: Arc<SyncProgressTracker>,
 }
 
 pub enum SyncStage {
@@ -57,6 +65,8 @@ pub struct SyncService {
     stage: SyncStage,
     config: Arc<NodeConfig>,
     storage: Arc<Storage>,
+    // sync from the block archive before sync from peers, only used once.
+    block_archive: Option<Arc<dyn BlockArchive>>,
 }
 
 impl SyncService {
@@ -71,12 +81,18 @@ impl SyncService {
         let head_block_info = storage
             .get_block_info(head_block_hash)?
             .ok_or_else(|| format_err!("can't get block info by hash {}", head_block_hash))?;
+        let block_archive = config
+            .sync
+            .block_archive_source()
+            .map(open_block_archive)
+            .transpose()?;
 
         Ok(Self {
             sync_status: SyncStatus::new(ChainStatus::new(head_block.header, head_block_info)),
             stage: SyncStage::NotStart,
             config,
             storage,
+            block_archive,
         })
     }
 
@@ -119,7 +135,64 @@ impl SyncService {
         let self_ref = ctx.self_ref();
         let connector_service = ctx.service_ref::<BlockConnectorService>()?.clone();
         let config = self.config.clone();
+        let parallel_executor =
+            ctx.get_shared_or_put(|| ParallelExecutor::new(config.executor.concurrency_level()))?;
+        let block_archive = self.block_archive.take();
         let fut = async move {
+            if let Some(block_archive) = block_archive {
+                match ArchiveBlockFetcher::open(block_archive).await {
+                    Ok(fetcher) => {
+                        let fetcher = Arc::new(fetcher);
+                        let target = fetcher.sync_target();
+                        let current_block_id = storage
+                            .get_startup_info()?
+                            .ok_or_else(|| format_err!("Startup info should exist."))?
+                            .main;
+                        let current_block_info =
+                            storage.get_block_info(current_block_id)?.ok_or_else(|| {
+                                format_err!("Can not find block info by id: {}", current_block_id)
+                            })?;
+                        if target.block_info.total_difficulty > current_block_info.total_difficulty
+                        {
+                            info!(
+                                "[sync] Sync from block archive, target({}), current head({})",
+                                target.target_id.id(),
+                                current_block_id
+                            );
+                            let progress_tracker = Arc::new(SyncProgressTracker::new());
+                            let (fut, task_handle, task_event_handle) = full_sync_task(
+                                current_block_id,
+                                target.clone(),
+                                skip_pow_verify,
+                                config.net().time_service(),
+                                storage.clone(),
+                                connector_service.clone(),
+                                fetcher.clone(),
+                                self_ref.clone(),
+                                network.clone(),
+                                config.sync.max_retry_times(),
+                                BlockFetchOptions::from(&config.sync),
+                                progress_tracker.clone(),
+                                parallel_executor.clone(),
+                            )?;
+                            self_ref.notify(SyncBeginEvent {
+                                target,
+                                task_handle,
+                                task_event_handle,
+                                peer_selector: fetcher.peer_selector(),
+                                progress_tracker,
+                            })?;
+                            SYNC_METRICS.sync_times.with_label_values(&["start"]).inc();
+                            return Ok(Some(fut.await?));
+                        }
+                        info!("[sync] Block archive's target is not better than current head, skip it.");
+                    }
+                    Err(e) => {
+                        warn!("[sync] Open block archive failed, skip it: {:?}", e);
+                    }
+                }
+            }
+
             let peer_select_strategy =
                 peer_strategy.unwrap_or_else(|| config.sync.peer_select_strategy());
 
@@ -188,6 +261,7 @@ impl SyncService {
             {
                 info!("[sync] Find target({}), total_difficulty:{}, current head({})'s total_difficulty({})", target.target_id.id(), target.block_info.total_difficulty, current_block_id, current_block_info.total_difficulty);
 
+                let progress_tracker = Arc::new(SyncProgressTracker::new());
                 let (fut, task_handle, task_event_handle) = full_sync_task(
                     current_block_id,
                     target.clone(),
@@ -199,6 +273,9 @@ impl SyncService {
                     self_ref.clone(),
                     network.clone(),
                     config.sync.max_retry_times(),
+                    BlockFetchOptions::from(&config.sync),
+                    progress_tracker.clone(),
+                    parallel_executor,
                 )?;
 
                 self_ref.notify(SyncBeginEvent {
@@ -206,6 +283,7 @@ impl SyncService {
                     task_handle,
                     task_event_handle,
                     peer_selector,
+                    progress_tracker,
                 })?;
                 SYNC_METRICS.sync_times.with_label_values(&["start"]).inc();
                 Ok(Some(fut.await?))
@@ -289,6 +367,42 @@ impl SyncService {
         }
     }
 
+    fn progress_report(&self) -> Option<SyncProgressReport> {
+        self.task_handle().and_then(|handle| {
+            handle.task_event_handle.total_report().map(|mut report| {
+                let target_number = handle.target.target_id.number();
+                let begin_number = handle.task_begin.as_ref().map(|begin| begin.number);
+                let total_blocks =
+                    begin_number.map(|begin_number| target_number.saturating_sub(begin_number));
+                if let Some(total_blocks) = total_blocks {
+                    report.fix_percent(total_blocks);
+                }
+                let tracker = &handle.progress_tracker;
+                let (blocks_per_second, txns_per_second) = tracker.throughput();
+                let eta_seconds = tracker
+                    .latest_number()
+                    .or(begin_number)
+                    .and_then(|current| tracker.eta_seconds(target_number.saturating_sub(current)));
+
+                SyncProgressReport {
+                    target_id: handle.target.target_id.id(),
+                    begin_number,
+                    target_number,
+                    target_difficulty: handle.target.block_info.total_difficulty,
+                    target_peers: handle.target.peers.clone(),
+                    current: report,
+                    stages: tracker
+                        .stage_reports(handle.task_event_handle.get_reports(), total_blocks),
+                    blocks_per_second,
+                    txns_per_second,
+                    eta_seconds,
+                    peer_contributions: tracker.peer_contributions(),
+                    stalled: tracker.is_stalled(),
+                }
+            })
+        })
+    }
+
     fn cancel_task(&mut self) {
         match std::mem::replace(&mut self.stage, SyncStage::Canceling) {
             SyncStage::Synchronizing(handle) => handle.task_handle.cancel(),
@@ -314,6 +428,9 @@ impl ActorService for SyncService {
         ctx.subscribe::<SystemStarted>();
         ctx.subscribe::<PeerEvent>();
         ctx.subscribe::<NewHeadBlock>();
+        ctx.run_interval(SYNC_PROGRESS_INTERVAL, |ctx| {
+            ctx.notify(SyncProgressTickEvent);
+        });
         Ok(())
     }
 
@@ -376,15 +493,17 @@ pub struct SyncBeginEvent {
     task_handle: TaskHandle,
     task_event_handle: Arc<TaskEventCounterHandle>,
     peer_selector: PeerSelector,
+    progress_tracker: Arc<SyncProgressTracker>,
 }
 
 impl EventHandler<Self, SyncBeginEvent> for SyncService {
     fn handle_event(&mut self, msg: SyncBeginEvent, ctx: &mut ServiceContext<Self>) {
-        let (target, task_handle, task_event_handle, peer_selector) = (
+        let (target, task_handle, task_event_handle, peer_selector, progress_tracker) = (
             msg.target,
             msg.task_handle,
             msg.task_event_handle,
             msg.peer_selector,
+            msg.progress_tracker,
         );
         let sync_task_handle = SyncTaskHandle {
             target: target.clone(),
@@ -392,6 +511,7 @@ impl EventHandler<Self, SyncBeginEvent> for SyncService {
             task_handle: task_handle.clone(),
             task_event_handle,
             peer_selector,
+            progress_tracker,
         };
         match std::mem::replace(
             &mut self.stage,
@@ -437,6 +557,27 @@ impl EventHandler<Self, SyncBeginEvent> for SyncService {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct SyncProgressTickEvent;
+
+impl EventHandler<Self, SyncProgressTickEvent> for SyncService {
+    fn handle_event(&mut self, _msg: SyncProgressTickEvent, ctx: &mut ServiceContext<Self>) {
+        if let Some(report) = self.progress_report() {
+            if report.stalled {
+                warn!(
+                    "[sync] Sync to target {} may be stalled, no block is fetched or executed in {:?}, eta: {:?}",
+                    report.target_number,
+                    self.task_handle()
+                        .map(|handle| handle.progress_tracker.stalled_for())
+                        .unwrap_or_default(),
+                    report.eta_seconds
+                );
+            }
+            ctx.broadcast(SyncProgressEvent(report));
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct CheckSyncEvent {
     /// check sync with special peers
@@ -512,7 +653,7 @@ impl EventHandler<Self, SyncDoneEvent> for SyncService {
 
 impl EventHandler<Self, NewHeadBlock> for SyncService {
     fn handle_event(&mut self, msg: NewHeadBlock, ctx: &mut ServiceContext<Self>) {
-        let NewHeadBlock(block) = msg;
+        let NewHeadBlock(block, _) = msg;
         if self.sync_status.update_chain_status(ChainStatus::new(
             block.header().clone(),
             block.block_info.clone(),
@@ -552,31 +693,7 @@ impl ServiceHandler<Self, SyncProgressRequest> for SyncService {
         _msg: SyncProgressRequest,
         _ctx: &mut ServiceContext<SyncService>,
     ) -> Option<SyncProgressReport> {
-        self.task_handle().and_then(|handle| {
-            handle.task_event_handle.total_report().map(|mut report| {
-                if let Some(begin) = handle.task_begin.as_ref() {
-                    report.fix_percent(
-                        handle
-                            .target
-                            .target_id
-                            .number()
-                            .saturating_sub(begin.number),
-                    );
-                }
-
-                SyncProgressReport {
-                    target_id: handle.target.target_id.id(),
-                    begin_number: handle
-                        .task_begin
-                        .as_ref()
-                        .map(|begin| -> u64 { begin.number }),
-                    target_number: handle.target.target_id.number(),
-                    target_difficulty: handle.target.block_info.total_difficulty,
-                    target_peers: handle.target.peers.clone(),
-                    current: report,
-                }
-            })
-        })
+        self.progress_report()
     }
 }
 
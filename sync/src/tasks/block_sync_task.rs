@@ -2,22 +2,28 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::sync_metrics::SYNC_METRICS;
-use crate::tasks::{BlockConnectedEvent, BlockConnectedEventHandle, BlockFetcher, BlockLocalStore};
+use crate::tasks::{
+    BlockConnectedEvent, BlockConnectedEventHandle, BlockFetcher, BlockLocalStore,
+    SyncProgressTracker,
+};
 use crate::verified_rpc_client::RpcVerifyError;
 use anyhow::{format_err, Result};
+use config::SyncConfig;
 use futures::future::BoxFuture;
 use futures::FutureExt;
 use logger::prelude::*;
-use network_api::PeerProvider;
+use network_api::{PeerProvider, PeerSelector};
 use starcoin_accumulator::{Accumulator, MerkleAccumulator};
 use starcoin_chain::{verifier::BasicVerifier, BlockChain};
 use starcoin_chain_api::{ChainReader, ChainWriter, ConnectBlockError, ExecutedBlock};
+use starcoin_crypto::HashValue;
 use starcoin_sync_api::SyncTarget;
 use starcoin_types::block::{Block, BlockIdAndNumber, BlockInfo, BlockNumber};
 use starcoin_types::peer_info::PeerId;
 use starcoin_vm_types::on_chain_config::GlobalTimeOnChain;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use stream_task::{CollectorState, TaskError, TaskResultCollector, TaskState};
 
 #[derive(Clone, Debug)]
@@ -44,6 +50,43 @@ impl Into<(Block, Option<BlockInfo>, Option<PeerId>)> for SyncBlockData {
     }
 }
 
+/// The score penalty of a peer which fails or times out when fetching blocks.
+const FETCH_BLOCKS_FAILED_PENALTY: u64 = 100;
+
+/// Options of the block fetch pipeline.
+#[derive(Clone, Copy, Debug)]
+pub struct BlockFetchOptions {
+    /// block count of every fetch request.
+    pub batch_size: u64,
+    /// max concurrent fetch requests.
+    pub max_parallel: usize,
+    /// fetch request timeout, a timed-out chunk is re-requested from another peer.
+    pub timeout: Duration,
+}
+
+impl BlockFetchOptions {
+    /// Concurrent fetch requests for the given peers, at most two in-flight requests per peer.
+    pub fn parallel(&self, peers: usize) -> usize {
+        std::cmp::min(self.max_parallel, peers.saturating_mul(2)).max(1)
+    }
+}
+
+impl Default for BlockFetchOptions {
+    fn default() -> Self {
+        Self::from(&SyncConfig::default())
+    }
+}
+
+impl From<&SyncConfig> for BlockFetchOptions {
+    fn from(config: &SyncConfig) -> Self {
+        Self {
+            batch_size: config.block_fetch_batch_size().max(1),
+            max_parallel: config.max_parallel_block_fetches().max(1) as usize,
+            timeout: Duration::from_millis(config.block_fetch_timeout()),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct BlockSyncTask {
     accumulator: Arc<MerkleAccumulator>,
@@ -53,6 +96,10 @@ pub struct BlockSyncTask {
     check_local_store: bool,
     local_store: Arc<dyn BlockLocalStore>,
     batch_size: u64,
+    // if peer_selector is set, every chunk select a peer by score, and re-request from other peers on timeout.
+    peer_selector: Option<PeerSelector>,
+    fetch_timeout: Duration,
+    progress_tracker: Option<Arc<SyncProgressTracker>>,
 }
 
 impl BlockSyncTask {
@@ -81,7 +128,92 @@ impl BlockSyncTask {
             check_local_store,
             local_store: Arc::new(local_store),
             batch_size,
+            peer_selector: None,
+            fetch_timeout: BlockFetchOptions::default().timeout,
+            progress_tracker: None,
+        }
+    }
+
+    /// Fetch every chunk from a peer selected by score, and re-request the chunk from other peers when the request is timeout or failed.
+    pub fn with_peer_selector(
+        mut self,
+        peer_selector: PeerSelector,
+        fetch_timeout: Duration,
+    ) -> Self {
+        self.peer_selector = Some(peer_selector);
+        self.fetch_timeout = fetch_timeout;
+        self
+    }
+
+    /// Report every fetched chunk to the `progress_tracker`.
+    pub fn with_progress_tracker(mut self, progress_tracker: Arc<SyncProgressTracker>) -> Self {
+        self.progress_tracker = Some(progress_tracker);
+        self
+    }
+
+    async fn fetch_blocks(
+        &self,
+        block_ids: Vec<HashValue>,
+    ) -> Result<Vec<(Block, Option<PeerId>)>> {
+        let peer_selector = match self.peer_selector.as_ref() {
+            Some(peer_selector) if !peer_selector.is_empty() => peer_selector,
+            _ => {
+                let start = Instant::now();
+                let blocks = self.fetcher.fetch_blocks(block_ids).await?;
+                if let Some(tracker) = self.progress_tracker.as_ref() {
+                    let peer_id = blocks.first().and_then(|(_, peer_id)| peer_id.clone());
+                    tracker.on_blocks_fetched(peer_id, blocks.len() as u64, start.elapsed());
+                }
+                return Ok(blocks);
+            }
+        };
+        let mut tried_peers = vec![];
+        let mut last_err = None;
+        while let Some(peer_id) = peer_selector.weighted_random_except(tried_peers.as_slice()) {
+            let start = Instant::now();
+            let fetch_fut = self
+                .fetcher
+                .fetch_blocks_from_peer(Some(peer_id.clone()), block_ids.clone());
+            let fetch_result = async_std::future::timeout(self.fetch_timeout, fetch_fut).await;
+            if let Some(tracker) = self.progress_tracker.as_ref() {
+                match &fetch_result {
+                    Ok(Ok(blocks)) => tracker.on_blocks_fetched(
+                        Some(peer_id.clone()),
+                        blocks.len() as u64,
+                        start.elapsed(),
+                    ),
+                    _ => tracker.on_fetch_failed(peer_id.clone()),
+                }
+            }
+            match fetch_result {
+                Ok(Ok(blocks)) => return Ok(blocks),
+                Ok(Err(err)) => {
+                    // the peer return an invalid response, break the task.
+                    if let Some(TaskError::BreakError(_)) = err.downcast_ref::<TaskError>() {
+                        return Err(err);
+                    }
+                    warn!(
+                        "[sync] Fetch {} blocks from peer {:?} failed: {:?}, try another peer.",
+                        block_ids.len(),
+                        peer_id,
+                        err
+                    );
+                    last_err = Some(err);
+                }
+                Err(_) => {
+                    warn!(
+                        "[sync] Fetch {} blocks from peer {:?} timeout after {:?}, try another peer.",
+                        block_ids.len(),
+                        peer_id,
+                        self.fetch_timeout
+                    );
+                    last_err = Some(format_err!("Fetch blocks from peer {:?} timeout", peer_id));
+                }
+            }
+            peer_selector.peer_penalty(&peer_id, FETCH_BLOCKS_FAILED_PENALTY);
+            tried_peers.push(peer_id);
         }
+        Err(last_err.unwrap_or_else(|| format_err!("No peers for fetch blocks.")))
     }
 }
 
@@ -121,8 +253,7 @@ impl TaskState for BlockSyncTask {
                 let mut result_map = if no_exist_block_ids.is_empty() {
                     result_map
                 } else {
-                    self.fetcher
-                        .fetch_blocks(no_exist_block_ids)
+                    self.fetch_blocks(no_exist_block_ids)
                         .await?
                         .into_iter()
                         .fold(result_map, |mut result_map, (block, peer_id)| {
@@ -142,7 +273,6 @@ impl TaskState for BlockSyncTask {
                 result
             } else {
                 Ok(self
-                    .fetcher
                     .fetch_blocks(block_ids)
                     .await?
                     .into_iter()
@@ -165,6 +295,9 @@ impl TaskState for BlockSyncTask {
                 check_local_store: self.check_local_store,
                 local_store: self.local_store.clone(),
                 batch_size: self.batch_size,
+                peer_selector: self.peer_selector.clone(),
+                fetch_timeout: self.fetch_timeout,
+                progress_tracker: self.progress_tracker.clone(),
             })
         }
     }
@@ -187,6 +320,7 @@ pub struct BlockCollector<N, H> {
     event_handle: H,
     peer_provider: N,
     skip_pow_verify: bool,
+    progress_tracker: Option<Arc<SyncProgressTracker>>,
 }
 
 impl<N, H> BlockCollector<N, H>
@@ -209,9 +343,16 @@ where
             event_handle,
             peer_provider,
             skip_pow_verify,
+            progress_tracker: None,
         }
     }
 
+    /// Report every executed block to the `progress_tracker`.
+    pub fn with_progress_tracker(mut self, progress_tracker: Arc<SyncProgressTracker>) -> Self {
+        self.progress_tracker = Some(progress_tracker);
+        self
+    }
+
     #[cfg(test)]
     pub fn apply_block_for_test(&mut self, block: Block) -> Result<()> {
         self.apply_block(block, None)
@@ -288,6 +429,8 @@ where
     fn collect(&mut self, item: SyncBlockData) -> Result<CollectorState> {
         let (block, block_info, peer_id) = item.into();
         let block_id = block.id();
+        let block_number = block.header().number();
+        let txns = block.transactions().len() as u64;
         let timestamp = block.header().timestamp();
         let block_info = match block_info {
             Some(block_info) => {
@@ -318,6 +461,9 @@ where
                 block_info
             }
         };
+        if let Some(tracker) = self.progress_tracker.as_ref() {
+            tracker.on_block_executed(block_number, txns);
+        }
 
         //verify target
         if block_info.block_accumulator_info.num_leaves
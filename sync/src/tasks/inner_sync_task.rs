@@ -1,8 +1,10 @@
 use crate::tasks::{
     AccumulatorCollector, BlockAccumulatorSyncTask, BlockCollector, BlockConnectedEventHandle,
-    BlockFetcher, BlockIdFetcher, BlockSyncTask, PeerOperator,
+    BlockFetchOptions, BlockFetcher, BlockIdFetcher, BlockSyncTask, PeerOperator,
+    SyncProgressTracker,
 };
 use anyhow::format_err;
+use executor::ParallelExecutor;
 use network_api::PeerProvider;
 use starcoin_accumulator::node::AccumulatorStoreType;
 use starcoin_chain::BlockChain;
@@ -31,6 +33,9 @@ where
     time_service: Arc<dyn TimeService>,
     peer_provider: N,
     custom_error_handle: Arc<dyn CustomErrorHandle>,
+    fetch_options: BlockFetchOptions,
+    progress_tracker: Arc<SyncProgressTracker>,
+    parallel_executor: ParallelExecutor,
 }
 
 impl<H, F, N> InnerSyncTask<H, F, N>
@@ -49,6 +54,9 @@ where
         time_service: Arc<dyn TimeService>,
         peer_provider: N,
         custom_error_handle: Arc<dyn CustomErrorHandle>,
+        fetch_options: BlockFetchOptions,
+        progress_tracker: Arc<SyncProgressTracker>,
+        parallel_executor: ParallelExecutor,
     ) -> Self {
         Self {
             ancestor,
@@ -60,6 +68,9 @@ where
             time_service,
             peer_provider,
             custom_error_handle,
+            fetch_options,
+            progress_tracker,
+            parallel_executor,
         }
     }
 
@@ -81,7 +92,8 @@ where
         delay_milliseconds_on_error: u64,
         skip_pow_verify_when_sync: bool,
     ) -> Result<(BlockChain, TaskHandle), TaskError> {
-        let buffer_size = self.target.peers.len();
+        // the target without peers is from a peer-independent block source, such as block archive.
+        let buffer_size = std::cmp::max(self.target.peers.len(), 1);
 
         let ancestor_block_info = self.ancestor_block_info().map_err(TaskError::BreakError)?;
         let accumulator_sync_task = BlockAccumulatorSyncTask::new(
@@ -123,10 +135,13 @@ where
                 self.fetcher.clone(),
                 check_local_store,
                 self.storage.clone(),
-                1,
-            );
-            let chain =
+                self.fetch_options.batch_size,
+            )
+            .with_peer_selector(self.fetcher.peer_selector(), self.fetch_options.timeout)
+            .with_progress_tracker(self.progress_tracker.clone());
+            let mut chain =
                 BlockChain::new(self.time_service.clone(), ancestor.id, self.storage.clone())?;
+            chain.set_parallel_executor(self.parallel_executor.clone());
             let block_collector = BlockCollector::new_with_handle(
                 current_block_info.clone(),
                 self.target.clone(),
@@ -134,10 +149,11 @@ where
                 self.block_event_handle.clone(),
                 self.peer_provider.clone(),
                 skip_pow_verify_when_sync,
-            );
+            )
+            .with_progress_tracker(self.progress_tracker.clone());
             Ok(TaskGenerator::new(
                 block_sync_task,
-                buffer_size,
+                self.fetch_options.parallel(buffer_size),
                 max_retry_times,
                 delay_milliseconds_on_error,
                 block_collector,
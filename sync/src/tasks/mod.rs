@@ -5,6 +5,7 @@ use crate::tasks::block_sync_task::SyncBlockData;
 use crate::tasks::inner_sync_task::InnerSyncTask;
 use crate::verified_rpc_client::{RpcVerifyError, VerifiedRpcClient};
 use anyhow::{format_err, Error, Result};
+use executor::ParallelExecutor;
 use futures::channel::mpsc::UnboundedSender;
 use futures::future::BoxFuture;
 use futures::{FutureExt, TryFutureExt};
@@ -281,6 +282,15 @@ pub trait BlockFetcher: Send + Sync {
         &self,
         block_ids: Vec<HashValue>,
     ) -> BoxFuture<Result<Vec<(Block, Option<PeerId>)>>>;
+
+    /// Fetch blocks from the given peer, if peer is None, the fetcher select a peer by itself.
+    fn fetch_blocks_from_peer(
+        &self,
+        _peer_id: Option<PeerId>,
+        block_ids: Vec<HashValue>,
+    ) -> BoxFuture<Result<Vec<(Block, Option<PeerId>)>>> {
+        self.fetch_blocks(block_ids)
+    }
 }
 
 impl<T> BlockFetcher for Arc<T>
@@ -293,6 +303,14 @@ where
     ) -> BoxFuture<'_, Result<Vec<(Block, Option<PeerId>)>>> {
         BlockFetcher::fetch_blocks(self.as_ref(), block_ids)
     }
+
+    fn fetch_blocks_from_peer(
+        &self,
+        peer_id: Option<PeerId>,
+        block_ids: Vec<HashValue>,
+    ) -> BoxFuture<'_, Result<Vec<(Block, Option<PeerId>)>>> {
+        BlockFetcher::fetch_blocks_from_peer(self.as_ref(), peer_id, block_ids)
+    }
 }
 
 impl BlockFetcher for VerifiedRpcClient {
@@ -300,7 +318,15 @@ impl BlockFetcher for VerifiedRpcClient {
         &self,
         block_ids: Vec<HashValue>,
     ) -> BoxFuture<'_, Result<Vec<(Block, Option<PeerId>)>>> {
-        self.get_blocks(block_ids.clone())
+        self.fetch_blocks_from_peer(None, block_ids)
+    }
+
+    fn fetch_blocks_from_peer(
+        &self,
+        peer_id: Option<PeerId>,
+        block_ids: Vec<HashValue>,
+    ) -> BoxFuture<'_, Result<Vec<(Block, Option<PeerId>)>>> {
+        self.get_blocks_from_peer(peer_id, block_ids.clone())
             .and_then(|blocks| async move {
                 let results: Result<Vec<(Block, Option<PeerId>)>> = block_ids
                     .iter()
@@ -506,14 +532,16 @@ mod find_ancestor_task;
 mod inner_sync_task;
 #[cfg(test)]
 pub(crate) mod mock;
+mod sync_progress;
 pub mod sync_score_metrics;
 #[cfg(test)]
 mod tests;
 
 use crate::tasks::sync_score_metrics::SYNC_SCORE_METRICS;
 pub use accumulator_sync_task::{AccumulatorCollector, BlockAccumulatorSyncTask};
-pub use block_sync_task::{BlockCollector, BlockSyncTask};
+pub use block_sync_task::{BlockCollector, BlockFetchOptions, BlockSyncTask};
 pub use find_ancestor_task::{AncestorCollector, FindAncestorTask};
+pub use sync_progress::SyncProgressTracker;
 
 pub fn full_sync_task<H, A, F, N>(
     current_block_id: HashValue,
@@ -526,6 +554,9 @@ pub fn full_sync_task<H, A, F, N>(
     ancestor_event_handle: A,
     peer_provider: N,
     max_retry_times: u64,
+    fetch_options: BlockFetchOptions,
+    progress_tracker: Arc<SyncProgressTracker>,
+    parallel_executor: ParallelExecutor,
 ) -> Result<(
     BoxFuture<'static, Result<BlockChain, TaskError>>,
     TaskHandle,
@@ -599,27 +630,34 @@ where
         let mut latest_block_chain;
 
         loop {
-            // for get new peers from network.
-            let all_peers = peer_provider
-                .peer_set()
-                .await
-                .map_err(TaskError::BreakError)?;
-            for peer in all_peers {
-                fetcher.peer_selector().add_or_update_peer(peer);
-            }
-            fetcher.peer_selector().retain_rpc_peers();
-
-            let sub_target = fetcher
-                .get_better_target(
-                    ancestor_block_info.total_difficulty,
-                    target.clone(),
-                    max_peers,
-                    latest_ancestor.number,
-                )
-                .await
-                .map_err(TaskError::BreakError)?;
-
-            fetcher.peer_selector().retain(sub_target.peers.as_slice());
+            // the target without peers is from a peer-independent block source, such as a
+            // block archive, sync to the target directly.
+            let sub_target = if target.peers.is_empty() {
+                target.clone()
+            } else {
+                // for get new peers from network.
+                let all_peers = peer_provider
+                    .peer_set()
+                    .await
+                    .map_err(TaskError::BreakError)?;
+                for peer in all_peers {
+                    fetcher.peer_selector().add_or_update_peer(peer);
+                }
+                fetcher.peer_selector().retain_rpc_peers();
+
+                let sub_target = fetcher
+                    .get_better_target(
+                        ancestor_block_info.total_difficulty,
+                        target.clone(),
+                        max_peers,
+                        latest_ancestor.number,
+                    )
+                    .await
+                    .map_err(TaskError::BreakError)?;
+
+                fetcher.peer_selector().retain(sub_target.peers.as_slice());
+                sub_target
+            };
 
             let inner = InnerSyncTask::new(
                 latest_ancestor,
@@ -631,6 +669,9 @@ where
                 time_service.clone(),
                 peer_provider.clone(),
                 ext_error_handle.clone(),
+                fetch_options,
+                progress_tracker.clone(),
+                parallel_executor.clone(),
             );
             let start_now = Instant::now();
             let (block_chain, _) = inner
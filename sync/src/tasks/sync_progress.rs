@@ -0,0 +1,280 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use parking_lot::Mutex;
+use starcoin_sync_api::{PeerContribution, SyncStageReport};
+use starcoin_types::block::BlockNumber;
+use starcoin_types::peer_info::PeerId;
+use std::collections::{HashMap, VecDeque};
+use std::convert::TryFrom;
+use std::time::{Duration, Instant};
+use stream_task::TaskProgressReport;
+
+/// The window of the moving average throughput.
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(60);
+/// The min interval between two throughput samples.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+/// Sync is treated as stalled if no block is fetched or executed in this duration.
+pub const STALL_THRESHOLD: Duration = Duration::from_secs(30);
+
+pub const STAGE_FIND_ANCESTOR: &str = "find_ancestor";
+pub const STAGE_ACCUMULATOR_SYNC: &str = "accumulator_sync";
+pub const STAGE_BLOCK_FETCH: &str = "block_fetch";
+pub const STAGE_EXECUTION: &str = "execution";
+
+#[derive(Clone, Copy, Debug)]
+struct Sample {
+    time: Instant,
+    executed_blocks: u64,
+    executed_txns: u64,
+}
+
+#[derive(Clone, Debug, Default)]
+struct PeerCounter {
+    blocks: u64,
+    requests: u64,
+    failed_requests: u64,
+    fetch_millis: u64,
+}
+
+#[derive(Debug)]
+struct ProgressState {
+    start_time: Instant,
+    fetched_blocks: u64,
+    executed_blocks: u64,
+    executed_txns: u64,
+    latest_number: Option<BlockNumber>,
+    peers: HashMap<PeerId, PeerCounter>,
+    samples: VecDeque<Sample>,
+    last_progress_time: Instant,
+}
+
+impl ProgressState {
+    fn new(now: Instant) -> Self {
+        let mut samples = VecDeque::new();
+        samples.push_back(Sample {
+            time: now,
+            executed_blocks: 0,
+            executed_txns: 0,
+        });
+        Self {
+            start_time: now,
+            fetched_blocks: 0,
+            executed_blocks: 0,
+            executed_txns: 0,
+            latest_number: None,
+            peers: HashMap::new(),
+            samples,
+            last_progress_time: now,
+        }
+    }
+
+    fn add_sample(&mut self, now: Instant) {
+        let need_sample = self
+            .samples
+            .back()
+            .map(|sample| now.saturating_duration_since(sample.time) >= SAMPLE_INTERVAL)
+            .unwrap_or(true);
+        if need_sample {
+            self.samples.push_back(Sample {
+                time: now,
+                executed_blocks: self.executed_blocks,
+                executed_txns: self.executed_txns,
+            });
+        }
+        // keep at least one sample out of the window as the base of the moving average.
+        while self.samples.len() > 1
+            && self
+                .samples
+                .get(1)
+                .map(|sample| now.saturating_duration_since(sample.time) >= THROUGHPUT_WINDOW)
+                .unwrap_or(false)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// (blocks_per_second, txns_per_second) in the moving average window.
+    fn throughput(&self, now: Instant) -> (f64, f64) {
+        let base = match self.samples.front() {
+            Some(base) => base,
+            None => return (0f64, 0f64),
+        };
+        let seconds = now.saturating_duration_since(base.time).as_secs_f64();
+        if seconds <= 0f64 {
+            return (0f64, 0f64);
+        }
+        (
+            self.executed_blocks.saturating_sub(base.executed_blocks) as f64 / seconds,
+            self.executed_txns.saturating_sub(base.executed_txns) as f64 / seconds,
+        )
+    }
+}
+
+/// Track the block fetch and execution progress of a sync task, for throughput, ETA, per-peer contribution and stall detection.
+#[derive(Debug)]
+pub struct SyncProgressTracker {
+    state: Mutex<ProgressState>,
+}
+
+impl Default for SyncProgressTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SyncProgressTracker {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(ProgressState::new(Instant::now())),
+        }
+    }
+
+    pub fn on_blocks_fetched(&self, peer_id: Option<PeerId>, blocks: u64, use_time: Duration) {
+        let mut state = self.state.lock();
+        state.fetched_blocks = state.fetched_blocks.saturating_add(blocks);
+        state.last_progress_time = Instant::now();
+        if let Some(peer_id) = peer_id {
+            let counter = state.peers.entry(peer_id).or_default();
+            counter.blocks = counter.blocks.saturating_add(blocks);
+            counter.requests = counter.requests.saturating_add(1);
+            counter.fetch_millis = counter
+                .fetch_millis
+                .saturating_add(use_time.as_millis() as u64);
+        }
+    }
+
+    pub fn on_fetch_failed(&self, peer_id: PeerId) {
+        let mut state = self.state.lock();
+        let counter = state.peers.entry(peer_id).or_default();
+        counter.requests = counter.requests.saturating_add(1);
+        counter.failed_requests = counter.failed_requests.saturating_add(1);
+    }
+
+    pub fn on_block_executed(&self, number: BlockNumber, txns: u64) {
+        let now = Instant::now();
+        let mut state = self.state.lock();
+        state.executed_blocks = state.executed_blocks.saturating_add(1);
+        state.executed_txns = state.executed_txns.saturating_add(txns);
+        state.latest_number = Some(number);
+        state.last_progress_time = now;
+        state.add_sample(now);
+    }
+
+    pub fn fetched_blocks(&self) -> u64 {
+        self.state.lock().fetched_blocks
+    }
+
+    pub fn executed_blocks(&self) -> u64 {
+        self.state.lock().executed_blocks
+    }
+
+    /// The latest executed block number.
+    pub fn latest_number(&self) -> Option<BlockNumber> {
+        self.state.lock().latest_number
+    }
+
+    /// Moving average of (blocks_per_second, txns_per_second).
+    pub fn throughput(&self) -> (f64, f64) {
+        self.state.lock().throughput(Instant::now())
+    }
+
+    /// Estimated seconds to execute the `remaining_blocks` by the moving average throughput.
+    pub fn eta_seconds(&self, remaining_blocks: u64) -> Option<u64> {
+        if remaining_blocks == 0 {
+            return Some(0);
+        }
+        let (blocks_per_second, _) = self.throughput();
+        if blocks_per_second > 0f64 {
+            Some((remaining_blocks as f64 / blocks_per_second).ceil() as u64)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_stalled(&self) -> bool {
+        self.stalled_for() >= STALL_THRESHOLD
+    }
+
+    /// The duration since the latest fetched or executed block.
+    pub fn stalled_for(&self) -> Duration {
+        Instant::now().saturating_duration_since(self.state.lock().last_progress_time)
+    }
+
+    /// Peer contributions, sorted by fetched blocks descending.
+    pub fn peer_contributions(&self) -> Vec<PeerContribution> {
+        let state = self.state.lock();
+        let mut contributions = state
+            .peers
+            .iter()
+            .map(|(peer_id, counter)| {
+                let succeed_requests = counter.requests.saturating_sub(counter.failed_requests);
+                PeerContribution {
+                    peer_id: peer_id.clone(),
+                    blocks: counter.blocks,
+                    requests: counter.requests,
+                    failed_requests: counter.failed_requests,
+                    avg_fetch_millis: counter
+                        .fetch_millis
+                        .checked_div(succeed_requests)
+                        .unwrap_or(0),
+                }
+            })
+            .collect::<Vec<_>>();
+        contributions.sort_by(|a, b| b.blocks.cmp(&a.blocks));
+        contributions
+    }
+
+    /// Build every stage's report, the find_ancestor and accumulator_sync stages come from the `task_reports`.
+    pub fn stage_reports(
+        &self,
+        task_reports: Vec<TaskProgressReport>,
+        total_blocks: Option<u64>,
+    ) -> Vec<SyncStageReport> {
+        let mut stages = vec![];
+        for (stage, task_name) in [
+            (STAGE_FIND_ANCESTOR, "FindAncestorTask"),
+            (STAGE_ACCUMULATOR_SYNC, "BlockAccumulatorSyncTask"),
+        ] {
+            let reports = task_reports
+                .iter()
+                .filter(|report| report.task_name.ends_with(task_name))
+                .cloned()
+                .collect::<Vec<_>>();
+            if let Ok(report) = TaskProgressReport::try_from(reports) {
+                stages.push(SyncStageReport {
+                    stage: stage.to_string(),
+                    processed_items: report.processed_items,
+                    total_items: report.total_items,
+                    use_seconds: report.use_seconds,
+                    items_per_second: per_second(report.processed_items, report.use_seconds),
+                });
+            }
+        }
+        let state = self.state.lock();
+        let use_seconds = Instant::now()
+            .saturating_duration_since(state.start_time)
+            .as_secs();
+        for (stage, processed_items) in [
+            (STAGE_BLOCK_FETCH, state.fetched_blocks),
+            (STAGE_EXECUTION, state.executed_blocks),
+        ] {
+            stages.push(SyncStageReport {
+                stage: stage.to_string(),
+                processed_items,
+                total_items: total_blocks,
+                use_seconds,
+                items_per_second: per_second(processed_items, use_seconds),
+            });
+        }
+        stages
+    }
+}
+
+fn per_second(items: u64, seconds: u64) -> f64 {
+    if seconds == 0 {
+        0f64
+    } else {
+        items as f64 / seconds as f64
+    }
+}
@@ -6,12 +6,14 @@ use crate::tasks::block_sync_task::SyncBlockData;
 use crate::tasks::mock::{ErrorStrategy, MockBlockIdFetcher, SyncNodeMocker};
 use crate::tasks::{
     full_sync_task, AccumulatorCollector, AncestorCollector, BlockAccumulatorSyncTask,
-    BlockCollector, BlockFetcher, BlockLocalStore, BlockSyncTask, FindAncestorTask, SyncFetcher,
+    BlockCollector, BlockFetchOptions, BlockFetcher, BlockLocalStore, BlockSyncTask,
+    FindAncestorTask, SyncFetcher, SyncProgressTracker,
 };
 use crate::verified_rpc_client::RpcVerifyError;
 use anyhow::Context;
 use anyhow::{format_err, Result};
 use config::{BuiltinNetworkID, ChainNetwork};
+use executor::ParallelExecutor;
 use futures::channel::mpsc::unbounded;
 use futures::future::BoxFuture;
 use futures::FutureExt;
@@ -36,6 +38,7 @@ use starcoin_types::{
 };
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 use stream_task::{
     DefaultCustomErrorHandle, Generator, TaskError, TaskEventCounterHandle, TaskGenerator,
 };
@@ -71,6 +74,9 @@ pub async fn test_full_sync_new_node() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOptions::default(),
+        Arc::new(SyncProgressTracker::new()),
+        ParallelExecutor::default(),
     )?;
     let join_handle = node2.process_block_connect_event(receiver_1).await;
     let branch = sync_task.await?;
@@ -100,6 +106,9 @@ pub async fn test_full_sync_new_node() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOptions::default(),
+        Arc::new(SyncProgressTracker::new()),
+        ParallelExecutor::default(),
     )?;
     let join_handle = node2.process_block_connect_event(receiver_1).await;
     let branch = sync_task.await?;
@@ -148,6 +157,9 @@ pub async fn test_sync_invalid_target() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOptions::default(),
+        Arc::new(SyncProgressTracker::new()),
+        ParallelExecutor::default(),
     )?;
     let _join_handle = node2.process_block_connect_event(receiver_1).await;
     let sync_result = sync_task.await;
@@ -229,6 +241,9 @@ pub async fn test_full_sync_fork() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOptions::default(),
+        Arc::new(SyncProgressTracker::new()),
+        ParallelExecutor::default(),
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let branch = sync_task.await?;
@@ -260,6 +275,9 @@ pub async fn test_full_sync_fork() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOptions::default(),
+        Arc::new(SyncProgressTracker::new()),
+        ParallelExecutor::default(),
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let branch = sync_task.await?;
@@ -307,6 +325,9 @@ pub async fn test_full_sync_fork_from_genesis() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOptions::default(),
+        Arc::new(SyncProgressTracker::new()),
+        ParallelExecutor::default(),
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let branch = sync_task.await?;
@@ -359,6 +380,9 @@ pub async fn test_full_sync_continue() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOptions::default(),
+        Arc::new(SyncProgressTracker::new()),
+        ParallelExecutor::default(),
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let branch = sync_task.await?;
@@ -392,6 +416,9 @@ pub async fn test_full_sync_continue() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOptions::default(),
+        Arc::new(SyncProgressTracker::new()),
+        ParallelExecutor::default(),
     )?;
 
     let join_handle = node2.process_block_connect_event(receiver).await;
@@ -442,6 +469,9 @@ pub async fn test_full_sync_cancel() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOptions::default(),
+        Arc::new(SyncProgressTracker::new()),
+        ParallelExecutor::default(),
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let sync_join_handle = tokio::task::spawn(sync_task);
@@ -853,6 +883,282 @@ async fn test_block_sync_with_local() -> Result<()> {
     Ok(())
 }
 
+/// A fetcher which every peer only serves the chunks assigned to it, and fails the others.
+struct AssignedPeerBlockFetcher {
+    inner: MockBlockFetcher,
+    peers: Vec<PeerId>,
+    batch_size: u64,
+    // (peer, first block number of the chunk) of every served request.
+    served: Mutex<Vec<(PeerId, u64)>>,
+    in_flight: Arc<std::sync::atomic::AtomicUsize>,
+    max_in_flight: Arc<std::sync::atomic::AtomicUsize>,
+}
+
+impl AssignedPeerBlockFetcher {
+    fn assigned_peer(&self, number: u64) -> PeerId {
+        let chunk = (number.saturating_sub(1) / self.batch_size) as usize;
+        self.peers[chunk % self.peers.len()].clone()
+    }
+}
+
+impl BlockFetcher for AssignedPeerBlockFetcher {
+    fn fetch_blocks(
+        &self,
+        block_ids: Vec<HashValue>,
+    ) -> BoxFuture<Result<Vec<(Block, Option<PeerId>)>>> {
+        self.inner.fetch_blocks(block_ids)
+    }
+
+    fn fetch_blocks_from_peer(
+        &self,
+        peer_id: Option<PeerId>,
+        block_ids: Vec<HashValue>,
+    ) -> BoxFuture<Result<Vec<(Block, Option<PeerId>)>>> {
+        use std::sync::atomic::Ordering;
+        let first_number = block_ids.first().and_then(|block_id| {
+            self.inner
+                .blocks
+                .lock()
+                .unwrap()
+                .get(block_id)
+                .map(|block| block.header().number())
+        });
+        let assigned = match (peer_id.as_ref(), first_number) {
+            (Some(peer_id), Some(number)) => &self.assigned_peer(number) == peer_id,
+            _ => false,
+        };
+        if !assigned {
+            return futures::future::ready(Err(format_err!("Chunk is not assigned to peer")))
+                .boxed();
+        }
+        let number = first_number.expect("checked by assigned");
+        let peer_id = peer_id.expect("checked by assigned");
+        self.served.lock().unwrap().push((peer_id.clone(), number));
+        let in_flight = self.in_flight.clone();
+        let max_in_flight = self.max_in_flight.clone();
+        let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        max_in_flight.fetch_max(current, Ordering::SeqCst);
+        let fut = self.inner.fetch_blocks(block_ids);
+        async move {
+            let result = fut.await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(result?
+                .into_iter()
+                .map(|(block, _)| (block, Some(peer_id.clone())))
+                .collect())
+        }
+        .boxed()
+    }
+}
+
+#[stest::test(timeout = 60)]
+async fn test_block_sync_distribute_chunks_across_peers() -> Result<()> {
+    let total_blocks = 31;
+    let batch_size = 5;
+    let (inner, accumulator) = build_block_fetcher(total_blocks);
+    let peers = vec![PeerInfo::random(), PeerInfo::random(), PeerInfo::random()];
+    let peer_ids: Vec<PeerId> = peers.iter().map(|peer| peer.peer_id()).collect();
+    let peer_selector = PeerSelector::new(peers, PeerStrategy::default());
+    let fetcher = Arc::new(AssignedPeerBlockFetcher {
+        inner,
+        peers: peer_ids.clone(),
+        batch_size,
+        served: Mutex::new(vec![]),
+        in_flight: Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+        max_in_flight: Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+    });
+    let ancestor = BlockIdAndNumber::new(accumulator.get_leaf(0)?.unwrap(), 0);
+    let options = BlockFetchOptions::default();
+    let block_sync_state = BlockSyncTask::new(
+        accumulator,
+        ancestor,
+        fetcher.clone(),
+        false,
+        MockLocalBlockStore::new(),
+        batch_size,
+    )
+    .with_peer_selector(peer_selector, options.timeout);
+    let result = TaskGenerator::new(
+        block_sync_state,
+        options.parallel(peer_ids.len()),
+        3,
+        1,
+        vec![],
+        Arc::new(TaskEventCounterHandle::new()),
+        Arc::new(DefaultCustomErrorHandle),
+    )
+    .generate()
+    .await?;
+    assert_eq!(result.len() as u64, total_blocks - 1);
+    // every block is served by the peer its chunk assigned to.
+    result.iter().for_each(|block_data| {
+        assert_eq!(
+            block_data.peer_id,
+            Some(fetcher.assigned_peer(block_data.block.header().number()))
+        );
+    });
+    // every chunk is served exactly once, and the chunks are spread across all peers.
+    let served = fetcher.served.lock().unwrap().clone();
+    let chunks = (total_blocks - 1) / batch_size;
+    assert_eq!(served.len() as u64, chunks);
+    let mut served_numbers: Vec<u64> = served.iter().map(|(_, number)| *number).collect();
+    served_numbers.sort_unstable();
+    served_numbers.dedup();
+    assert_eq!(served_numbers.len() as u64, chunks);
+    for peer_id in peer_ids.iter() {
+        let count = served.iter().filter(|(peer, _)| peer == peer_id).count() as u64;
+        assert_eq!(count, chunks / peer_ids.len() as u64);
+    }
+    assert!(
+        fetcher
+            .max_in_flight
+            .load(std::sync::atomic::Ordering::SeqCst)
+            > 1
+    );
+    Ok(())
+}
+
+async fn assigned_peers_block_sync_elapsed(
+    total_blocks: u64,
+    peer_count: usize,
+    buffer_size: usize,
+) -> Result<Duration> {
+    let batch_size = 5;
+    let (inner, accumulator) = build_block_fetcher(total_blocks);
+    let peers: Vec<PeerInfo> = (0..peer_count).map(|_| PeerInfo::random()).collect();
+    let peer_ids: Vec<PeerId> = peers.iter().map(|peer| peer.peer_id()).collect();
+    let peer_selector = PeerSelector::new(peers, PeerStrategy::default());
+    let fetcher = AssignedPeerBlockFetcher {
+        inner,
+        peers: peer_ids,
+        batch_size,
+        served: Mutex::new(vec![]),
+        in_flight: Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+        max_in_flight: Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+    };
+    let ancestor = BlockIdAndNumber::new(accumulator.get_leaf(0)?.unwrap(), 0);
+    let block_sync_state = BlockSyncTask::new(
+        accumulator,
+        ancestor,
+        fetcher,
+        false,
+        MockLocalBlockStore::new(),
+        batch_size,
+    )
+    .with_peer_selector(peer_selector, BlockFetchOptions::default().timeout);
+    let start = Instant::now();
+    let result = TaskGenerator::new(
+        block_sync_state,
+        buffer_size,
+        3,
+        1,
+        vec![],
+        Arc::new(TaskEventCounterHandle::new()),
+        Arc::new(DefaultCustomErrorHandle),
+    )
+    .generate()
+    .await?;
+    assert_eq!(result.len() as u64, total_blocks - 1);
+    Ok(start.elapsed())
+}
+
+#[stest::test(timeout = 60)]
+async fn test_block_sync_parallel_peers_speedup() -> Result<()> {
+    // every chunk request of the mock fetcher takes 100ms, 6 chunks in total.
+    let total_blocks = 31;
+    let sequential = assigned_peers_block_sync_elapsed(total_blocks, 1, 1).await?;
+    let options = BlockFetchOptions::default();
+    let parallel = assigned_peers_block_sync_elapsed(total_blocks, 3, options.parallel(3)).await?;
+    debug!(
+        "block sync elapsed, sequential: {:?}, parallel: {:?}",
+        sequential, parallel
+    );
+    assert!(sequential >= Duration::from_millis(600));
+    assert!(parallel * 2 < sequential);
+    Ok(())
+}
+
+struct MultiPeerBlockFetcher {
+    inner: MockBlockFetcher,
+    slow_peer: PeerId,
+}
+
+impl BlockFetcher for MultiPeerBlockFetcher {
+    fn fetch_blocks(
+        &self,
+        block_ids: Vec<HashValue>,
+    ) -> BoxFuture<Result<Vec<(Block, Option<PeerId>)>>> {
+        self.inner.fetch_blocks(block_ids)
+    }
+
+    fn fetch_blocks_from_peer(
+        &self,
+        peer_id: Option<PeerId>,
+        block_ids: Vec<HashValue>,
+    ) -> BoxFuture<Result<Vec<(Block, Option<PeerId>)>>> {
+        let slow = peer_id.as_ref() == Some(&self.slow_peer);
+        let fut = self.inner.fetch_blocks(block_ids);
+        async move {
+            if slow {
+                Delay::new(Duration::from_secs(60)).await;
+            }
+            Ok(fut
+                .await?
+                .into_iter()
+                .map(|(block, _)| (block, peer_id.clone()))
+                .collect())
+        }
+        .boxed()
+    }
+}
+
+#[stest::test(timeout = 60)]
+async fn test_block_sync_rerequest_timeout_chunk() -> Result<()> {
+    let total_blocks = 30;
+    let (inner, accumulator) = build_block_fetcher(total_blocks);
+    let peers = vec![PeerInfo::random(), PeerInfo::random()];
+    let slow_peer = peers[0].peer_id();
+    let fast_peer = peers[1].peer_id();
+    let peer_selector = PeerSelector::new(peers, PeerStrategy::default());
+    // the slow peer is almost always selected first, so every chunk times out on it once.
+    peer_selector.peer_score(&slow_peer, 1_000_000);
+    let fetcher = MultiPeerBlockFetcher {
+        inner,
+        slow_peer: slow_peer.clone(),
+    };
+    let ancestor = BlockIdAndNumber::new(accumulator.get_leaf(0)?.unwrap(), 0);
+    let block_sync_state = BlockSyncTask::new(
+        accumulator,
+        ancestor,
+        fetcher,
+        false,
+        MockLocalBlockStore::new(),
+        5,
+    )
+    .with_peer_selector(peer_selector.clone(), Duration::from_millis(500));
+    let result = TaskGenerator::new(
+        block_sync_state,
+        4,
+        3,
+        1,
+        vec![],
+        Arc::new(TaskEventCounterHandle::new()),
+        Arc::new(DefaultCustomErrorHandle),
+    )
+    .generate()
+    .await?;
+    assert_eq!(result.len() as u64, total_blocks - 1);
+    result.iter().for_each(|block_data| {
+        assert!(block_data.peer_id.is_some());
+        assert_ne!(block_data.peer_id, Some(slow_peer.clone()));
+    });
+    // the timed-out peer is penalized, and the fast peer keeps its score.
+    let scores: HashMap<PeerId, u64> = peer_selector.scores().into_iter().collect();
+    assert!(scores[&slow_peer] < 1_000_001);
+    assert_eq!(scores[&fast_peer], 1);
+    Ok(())
+}
+
 #[stest::test(timeout = 120)]
 async fn test_net_rpc_err() -> Result<()> {
     let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
@@ -883,6 +1189,9 @@ async fn test_net_rpc_err() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOptions::default(),
+        Arc::new(SyncProgressTracker::new()),
+        ParallelExecutor::default(),
     )?;
     let _join_handle = node2.process_block_connect_event(receiver).await;
     let sync_join_handle = tokio::task::spawn(sync_task);
@@ -958,3 +1267,72 @@ async fn test_sync_target() {
     assert_eq!(target.target_id.number(), low_chain_info.head().number());
     assert_eq!(target.target_id.id(), low_chain_info.head().id());
 }
+
+#[stest::test]
+pub async fn test_full_sync_progress_tracker() -> Result<()> {
+    let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut node1 = SyncNodeMocker::new(net1, 1, 50)?;
+    node1.produce_block(10)?;
+
+    let arc_node1 = Arc::new(node1);
+
+    let net2 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+
+    let node2 = SyncNodeMocker::new(net2.clone(), 1, 50)?;
+
+    let target = arc_node1.sync_target();
+
+    let current_block_header = node2.chain().current_header();
+
+    let storage = node2.chain().get_storage();
+    let (sender_1, receiver_1) = unbounded();
+    let (sender_2, _receiver_2) = unbounded();
+    let progress_tracker = Arc::new(SyncProgressTracker::new());
+    let (sync_task, _task_handle, task_event_counter) = full_sync_task(
+        current_block_header.id(),
+        target.clone(),
+        false,
+        net2.time_service(),
+        storage.clone(),
+        sender_1,
+        arc_node1.clone(),
+        sender_2,
+        DummyNetworkService::default(),
+        15,
+        BlockFetchOptions::default(),
+        progress_tracker.clone(),
+        ParallelExecutor::default(),
+    )?;
+    let join_handle = node2.process_block_connect_event(receiver_1).await;
+    let branch = sync_task.await?;
+    let _ = join_handle.await;
+    assert_eq!(branch.current_header().id(), target.target_id.id());
+
+    assert_eq!(progress_tracker.fetched_blocks(), 10);
+    assert_eq!(progress_tracker.executed_blocks(), 10);
+    assert_eq!(
+        progress_tracker.latest_number(),
+        Some(target.target_id.number())
+    );
+    assert_eq!(progress_tracker.eta_seconds(0), Some(0));
+    assert!(!progress_tracker.is_stalled());
+
+    let stages = progress_tracker.stage_reports(task_event_counter.get_reports(), Some(10));
+    let stage_names = stages
+        .iter()
+        .map(|stage| stage.stage.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        stage_names,
+        vec![
+            "find_ancestor",
+            "accumulator_sync",
+            "block_fetch",
+            "execution"
+        ]
+    );
+    let execution = stages.last().unwrap();
+    assert_eq!(execution.processed_items, 10);
+    assert_eq!(execution.total_items, Some(10));
+    Ok(())
+}
@@ -388,7 +388,18 @@ impl VerifiedRpcClient {
         &self,
         ids: Vec<HashValue>,
     ) -> Result<Vec<Option<(Block, Option<PeerId>)>>> {
-        let peer_id = self.select_a_peer()?;
+        self.get_blocks_from_peer(None, ids).await
+    }
+
+    pub async fn get_blocks_from_peer(
+        &self,
+        peer_id: Option<PeerId>,
+        ids: Vec<HashValue>,
+    ) -> Result<Vec<Option<(Block, Option<PeerId>)>>> {
+        let peer_id = match peer_id {
+            None => self.select_a_peer()?,
+            Some(p) => p,
+        };
         let timer = SYNC_SCORE_METRICS
             .peer_sync_per_time
             .with_label_values(&[&format!("peer-{:?}", peer_id)])
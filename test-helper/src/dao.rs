@@ -428,15 +428,15 @@ pub fn empty_txn_payload() -> TransactionPayload {
     TransactionPayload::ScriptFunction(build_empty_script())
 }
 
-pub fn dao_vote_test(
+/// Propose by the `vote_script` and vote for it until it is executable, return the last block timestamp.
+pub fn dao_vote_to_executable(
     alice: &Account,
     chain_state: &ChainStateDB,
     net: &ChainNetwork,
     vote_script: ScriptFunction,
-    action_type_tag: TypeTag,
-    execute_script: ScriptFunction,
+    action_type_tag: &TypeTag,
     proposal_id: u64,
-) -> Result<()> {
+) -> Result<u64> {
     let pre_mint_amount = net.genesis_config().pre_mine_amount;
     let one_day: u64 = 60 * 60 * 24 * 1000;
     // Block 1
@@ -489,7 +489,7 @@ pub fn dao_vote_test(
         net,
         chain_state,
         alice,
-        &action_type_tag,
+        action_type_tag,
         block_number,
         block_timestamp,
         proposal_id,
@@ -597,12 +597,32 @@ pub fn dao_vote_test(
             proposal_id,
         );
         assert_eq!(state, EXECUTABLE);
-        account_execute_should_success(
-            alice,
-            chain_state,
-            TransactionPayload::ScriptFunction(execute_script),
-        )?;
     }
+    Ok(block_timestamp)
+}
+
+pub fn dao_vote_test(
+    alice: &Account,
+    chain_state: &ChainStateDB,
+    net: &ChainNetwork,
+    vote_script: ScriptFunction,
+    action_type_tag: TypeTag,
+    execute_script: ScriptFunction,
+    proposal_id: u64,
+) -> Result<()> {
+    let block_timestamp = dao_vote_to_executable(
+        alice,
+        chain_state,
+        net,
+        vote_script,
+        &action_type_tag,
+        proposal_id,
+    )?;
+    account_execute_should_success(
+        alice,
+        chain_state,
+        TransactionPayload::ScriptFunction(execute_script),
+    )?;
 
     // block 7
     let block_number = current_block_number(chain_state) + 1;
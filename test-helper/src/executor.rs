@@ -23,7 +23,9 @@ use starcoin_types::{
 };
 use starcoin_vm_types::contract_event::ContractEvent;
 use starcoin_vm_types::move_resource::MoveResource;
+use starcoin_vm_types::on_chain_config::{OnChainConfig, Version};
 use starcoin_vm_types::vm_status::KeptVMStatus;
+use starcoin_vm_types::write_set::{WriteOp, WriteSetMut};
 use stdlib::restore_stdlib_in_dir;
 
 //TODO warp to A MockTxnExecutor
@@ -104,6 +106,17 @@ pub fn get_balance(address: AccountAddress, chain_state: &dyn ChainState) -> u12
         .unwrap_or_default()
 }
 
+/// Overwrite the on-chain stdlib version, to test the features gated by the version.
+pub fn set_version(chain_state: &ChainStateDB, major: u64) -> Result<()> {
+    chain_state.apply_write_set(
+        WriteSetMut::new(vec![(
+            Version::config_id().access_path(),
+            WriteOp::Value(bcs_ext::to_bytes(&Version { major })?),
+        )])
+        .freeze()?,
+    )
+}
+
 pub fn compile_modules_with_address(address: AccountAddress, code: &str) -> Vec<Module> {
     let temp_dir = temp_path();
     let stdlib_files =
@@ -23,9 +23,19 @@ pub async fn start_txpool_with_size(
 ) {
     let mut config = NodeConfig::random_for_test();
     config.tx_pool.set_max_count(pool_size);
-    let node_config = Arc::new(config);
-    // let node_config = Arc::new(NodeConfig::random_for_test());
+    start_txpool_with_config(config).await
+}
 
+pub async fn start_txpool_with_config(
+    config: NodeConfig,
+) -> (
+    TxPoolService,
+    Arc<Storage>,
+    Arc<NodeConfig>,
+    ServiceRef<TxPoolActorService>,
+    ServiceRef<RegistryService>,
+) {
+    let node_config = Arc::new(config);
     let (storage, _chain_info, _) =
         Genesis::init_storage_for_test(node_config.net()).expect("init storage by genesis fail.");
     let registry = RegistryService::launch();
@@ -7,12 +7,28 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use starcoin_crypto::hash::HashValue;
 use starcoin_types::{
-    account_address::AccountAddress, block::Block, transaction, transaction::SignedUserTransaction,
+    account_address::AccountAddress,
+    block::{Block, BlockNumber},
+    transaction,
+    transaction::SignedUserTransaction,
 };
 use std::fmt::Debug;
 use std::sync::Arc;
 pub type TxnStatusFullEvent = Arc<[(HashValue, transaction::TxStatus)]>;
 
+/// A status transition of a transaction, from pool admission to inclusion in a block.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TxnStatusEvent {
+    pub txn_hash: HashValue,
+    pub sender: AccountAddress,
+    pub status: transaction::TxStatus,
+    /// The block which includes or retracts the transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<HashValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<BlockNumber>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct TxPoolStatus {
     pub txn_count: usize,
@@ -23,12 +39,83 @@ pub struct TxPoolStatus {
     pub is_full: bool,
 }
 
+/// Gas price distribution of a set of transactions.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct GasPriceDistribution {
+    /// Count of the sampled transactions.
+    pub count: u64,
+    pub min: u64,
+    pub median: u64,
+    pub max: u64,
+}
+
+/// Suggested gas prices for several confidence levels, and the gas price distributions they are based on.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct GasPriceStats {
+    /// Gas price which is likely to be included in a few blocks.
+    pub slow: u64,
+    /// Gas price which is likely to be included in the next block.
+    pub standard: u64,
+    /// Gas price which is very likely to be included in the next block, even when blocks are full.
+    pub fast: u64,
+    /// The min gas price the txpool accepts.
+    pub min_gas_price: u64,
+    /// Count of the sampled recent blocks.
+    pub blocks: u64,
+    /// Gas prices of the transactions included in the sampled blocks.
+    pub block_txns: GasPriceDistribution,
+    /// Gas prices of the pending transactions in the pool.
+    pub pending_txns: GasPriceDistribution,
+}
+
+/// Readiness of a transaction in the pool.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TxnReadiness {
+    /// The transaction can be packaged into the next block.
+    Ready,
+    /// The transaction is waiting for the transactions with lower sequence number.
+    Future,
+    /// The transaction can never be packaged, and will be removed from the pool.
+    Stale,
+}
+
+/// A transaction in the pool, with its readiness.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TxnInspect {
+    pub txn_hash: HashValue,
+    pub sequence_number: u64,
+    pub gas_unit_price: u64,
+    pub expiration_timestamp_secs: u64,
+    pub readiness: TxnReadiness,
+    /// Why the transaction is not ready.
+    pub reason: Option<String>,
+}
+
+/// Transactions of a sender in the pool, split into ready and future ones.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SenderTxnsInspect {
+    pub sender: AccountAddress,
+    /// The sender's sequence number on chain.
+    pub sequence_number: u64,
+    pub ready: Vec<TxnInspect>,
+    /// Future and stale transactions.
+    pub future: Vec<TxnInspect>,
+}
+
 pub trait TxPoolSyncService: Clone + Send + Sync + Unpin {
     fn add_txns(
         &self,
         txns: Vec<SignedUserTransaction>,
     ) -> Vec<Result<(), transaction::TransactionError>>;
 
+    /// Add transactions which are submitted through this node, such as by local RPC.
+    /// Local transactions are prioritized, tracked, and journaled across node restarts.
+    fn add_local_txns(
+        &self,
+        txns: Vec<SignedUserTransaction>,
+    ) -> Vec<Result<(), transaction::TransactionError>>;
+
     /// Removes transaction from the pool.
     ///
     /// Attempts to "cancel" a transaction. If it was not propagated yet (or not accepted by other peers)
@@ -53,6 +140,9 @@ pub trait TxPoolSyncService: Clone + Send + Sync + Unpin {
 
     fn subscribe_pending_txn(&self) -> mpsc::UnboundedReceiver<Arc<[HashValue]>>;
 
+    /// subscribe the status transitions of txns, including the txns included or retracted by chain new blocks.
+    fn subscribe_txn_status(&self) -> mpsc::UnboundedReceiver<Arc<[TxnStatusEvent]>>;
+
     /// notify txpool about chain new blocks
     /// `enacted` is the blocks which enter the main chain.
     /// `retracted` is the blocks which belongs to previous main chain.
@@ -61,12 +151,25 @@ pub trait TxPoolSyncService: Clone + Send + Sync + Unpin {
     /// Tx Pool status
     fn status(&self) -> TxPoolStatus;
 
+    /// Suggested gas prices, by the recent blocks and the pending transactions.
+    fn gas_price_stats(&self) -> GasPriceStats;
+
     fn find_txn(&self, hash: &HashValue) -> Option<SignedUserTransaction>;
     fn txns_of_sender(
         &self,
         sender: &AccountAddress,
         max_len: Option<usize>,
     ) -> Vec<SignedUserTransaction>;
+
+    /// Inspect the readiness of the transactions of the `sender`, or of all senders if it's None.
+    fn inspect(&self, sender: Option<AccountAddress>) -> Vec<SenderTxnsInspect>;
+
+    /// Remove all the transactions of the `sender`, return the removed transactions' hash.
+    fn clear_sender(&self, sender: AccountAddress) -> Vec<HashValue>;
+
+    /// Get all the transactions in the pool, no matter ready or in future,
+    /// sorted by (sender, sequence_number), skip `offset` and return at most `max_len`.
+    fn all_txns(&self, offset: usize, max_len: usize) -> Vec<SignedUserTransaction>;
 }
 
 #[derive(Clone, Debug)]
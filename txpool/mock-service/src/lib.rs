@@ -4,7 +4,9 @@
 use anyhow::Result;
 use crypto::hash::HashValue;
 use futures_channel::mpsc;
-use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService};
+use starcoin_txpool_api::{
+    GasPriceStats, SenderTxnsInspect, TxPoolStatus, TxPoolSyncService, TxnStatusEvent,
+};
 use std::{
     iter::Iterator,
     sync::{Arc, Mutex},
@@ -42,6 +44,13 @@ impl TxPoolSyncService for MockTxPoolService {
         results
     }
 
+    fn add_local_txns(
+        &self,
+        txns: Vec<SignedUserTransaction>,
+    ) -> Vec<Result<(), transaction::TransactionError>> {
+        self.add_txns(txns)
+    }
+
     /// Removes transaction from the pool.
     ///
     /// Attempts to "cancel" a transaction. If it was not propagated yet (or not accepted by other peers)
@@ -82,6 +91,9 @@ impl TxPoolSyncService for MockTxPoolService {
     fn subscribe_pending_txn(&self) -> mpsc::UnboundedReceiver<Arc<[HashValue]>> {
         todo!()
     }
+    fn subscribe_txn_status(&self) -> mpsc::UnboundedReceiver<Arc<[TxnStatusEvent]>> {
+        todo!()
+    }
     fn chain_new_block(&self, _enacted: Vec<Block>, _retracted: Vec<Block>) -> Result<()> {
         Ok(())
     }
@@ -90,6 +102,16 @@ impl TxPoolSyncService for MockTxPoolService {
         unimplemented!()
     }
 
+    fn gas_price_stats(&self) -> GasPriceStats {
+        GasPriceStats {
+            slow: 1,
+            standard: 1,
+            fast: 1,
+            min_gas_price: 1,
+            ..Default::default()
+        }
+    }
+
     fn find_txn(&self, _hash: &HashValue) -> Option<SignedUserTransaction> {
         unimplemented!()
     }
@@ -101,6 +123,25 @@ impl TxPoolSyncService for MockTxPoolService {
     ) -> Vec<SignedUserTransaction> {
         todo!()
     }
+
+    fn inspect(&self, _sender: Option<AccountAddress>) -> Vec<SenderTxnsInspect> {
+        todo!()
+    }
+
+    fn clear_sender(&self, _sender: AccountAddress) -> Vec<HashValue> {
+        todo!()
+    }
+
+    fn all_txns(&self, offset: usize, max_len: usize) -> Vec<SignedUserTransaction> {
+        self.pool
+            .lock()
+            .unwrap()
+            .iter()
+            .skip(offset)
+            .take(max_len)
+            .cloned()
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,160 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Gas Price Oracle
+//!
+//! Suggest gas prices by the gas prices of the transactions included in the recent blocks,
+//! and the gas prices of the pending transactions in the pool.
+//!
+//! The slow/standard/fast suggestions are the 25th/50th/90th percentile of the recent blocks' gas prices.
+//! If the pending transactions can not be included in the next block, the pool is congested,
+//! and the standard/fast suggestions are raised to the 50th/90th percentile of the pending gas prices.
+//! All the suggestions are at least the txpool's min gas price.
+
+use anyhow::Result;
+use crypto::hash::HashValue;
+use parking_lot::RwLock;
+use starcoin_txpool_api::{GasPriceDistribution, GasPriceStats};
+use std::collections::VecDeque;
+use storage::{BlockStore, Store};
+use types::block::{Block, BlockNumber};
+
+const SLOW_PERCENTILE: usize = 25;
+const STANDARD_PERCENTILE: usize = 50;
+const FAST_PERCENTILE: usize = 90;
+
+#[derive(Debug)]
+struct BlockGasPrices {
+    id: HashValue,
+    number: BlockNumber,
+    gas_prices: Vec<u64>,
+}
+
+impl From<&Block> for BlockGasPrices {
+    fn from(block: &Block) -> Self {
+        Self {
+            id: block.id(),
+            number: block.header().number(),
+            gas_prices: block
+                .transactions()
+                .iter()
+                .map(|txn| txn.gas_unit_price())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GasPriceOracle {
+    max_blocks: usize,
+    min_gas_price: u64,
+    blocks: RwLock<VecDeque<BlockGasPrices>>,
+}
+
+impl GasPriceOracle {
+    /// Create an oracle which samples the latest `max_blocks` blocks.
+    pub fn new(max_blocks: usize, min_gas_price: u64) -> Self {
+        Self {
+            max_blocks,
+            min_gas_price,
+            blocks: RwLock::new(VecDeque::with_capacity(max_blocks)),
+        }
+    }
+
+    /// Load the recent blocks from `storage`, backward from the block `head`.
+    pub fn init(&self, storage: &dyn Store, head: HashValue) -> Result<()> {
+        let mut blocks = VecDeque::with_capacity(self.max_blocks);
+        let mut block_id = head;
+        while blocks.len() < self.max_blocks {
+            let block = match storage.get_block_by_hash(block_id)? {
+                Some(block) => block,
+                None => break,
+            };
+            let gas_prices = BlockGasPrices::from(&block);
+            let is_genesis = gas_prices.number == 0;
+            blocks.push_front(gas_prices);
+            if is_genesis {
+                break;
+            }
+            block_id = block.header().parent_hash();
+        }
+        *self.blocks.write() = blocks;
+        Ok(())
+    }
+
+    /// Sample the `enacted` blocks, and drop the `retracted` blocks.
+    pub fn on_chain_new_blocks(&self, enacted: &[Block], retracted: &[Block]) {
+        let mut blocks = self.blocks.write();
+        if !retracted.is_empty() {
+            blocks.retain(|block| !retracted.iter().any(|retracted| retracted.id() == block.id));
+        }
+        for block in enacted {
+            blocks.push_back(BlockGasPrices::from(block));
+        }
+        while blocks.len() > self.max_blocks {
+            blocks.pop_front();
+        }
+    }
+
+    /// Suggest gas prices with the gas prices of the pool's pending transactions.
+    pub fn stats(&self, mut pending_gas_prices: Vec<u64>) -> GasPriceStats {
+        let (block_count, mut block_gas_prices) = {
+            let blocks = self.blocks.read();
+            let gas_prices = blocks
+                .iter()
+                .flat_map(|block| block.gas_prices.iter().copied())
+                .collect::<Vec<_>>();
+            (blocks.len(), gas_prices)
+        };
+        block_gas_prices.sort_unstable();
+        pending_gas_prices.sort_unstable();
+
+        let (mut slow, mut standard, mut fast) = if !block_gas_prices.is_empty() {
+            (
+                percentile(&block_gas_prices, SLOW_PERCENTILE),
+                percentile(&block_gas_prices, STANDARD_PERCENTILE),
+                percentile(&block_gas_prices, FAST_PERCENTILE),
+            )
+        } else {
+            (self.min_gas_price, self.min_gas_price, self.min_gas_price)
+        };
+
+        let txns_per_block = block_gas_prices.len().checked_div(block_count).unwrap_or(0);
+        if txns_per_block > 0 && pending_gas_prices.len() > txns_per_block {
+            standard = standard.max(percentile(&pending_gas_prices, STANDARD_PERCENTILE));
+            fast = fast.max(percentile(&pending_gas_prices, FAST_PERCENTILE));
+        }
+
+        slow = slow.max(self.min_gas_price);
+        standard = standard.max(slow);
+        fast = fast.max(standard);
+
+        GasPriceStats {
+            slow,
+            standard,
+            fast,
+            min_gas_price: self.min_gas_price,
+            blocks: block_count as u64,
+            block_txns: distribution(&block_gas_prices),
+            pending_txns: distribution(&pending_gas_prices),
+        }
+    }
+}
+
+/// The `p`th percentile of the sorted and non empty `gas_prices`.
+fn percentile(gas_prices: &[u64], p: usize) -> u64 {
+    let index = (gas_prices.len() - 1) * p / 100;
+    gas_prices[index]
+}
+
+fn distribution(sorted_gas_prices: &[u64]) -> GasPriceDistribution {
+    if sorted_gas_prices.is_empty() {
+        return GasPriceDistribution::default();
+    }
+    GasPriceDistribution {
+        count: sorted_gas_prices.len() as u64,
+        min: sorted_gas_prices[0],
+        median: percentile(sorted_gas_prices, 50),
+        max: sorted_gas_prices[sorted_gas_prices.len() - 1],
+    }
+}
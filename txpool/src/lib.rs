@@ -30,6 +30,7 @@ use types::{
 };
 
 mod counters;
+mod gas_price_oracle;
 mod pool;
 mod pool_client;
 #[cfg(test)]
@@ -150,6 +151,14 @@ impl ActorService for TxPoolActorService {
             myself.try_propagate_txns(ctx)
         });
 
+        if self.inner.node_config.tx_pool.journal_path().is_some() {
+            let inner = self.inner.clone();
+            let interval = self.inner.node_config.tx_pool.journal_rotate_interval();
+            ctx.run_interval(Duration::from_secs(interval), move |_ctx| {
+                inner.rotate_journal()
+            });
+        }
+
         Ok(())
     }
 
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod client;
+pub(crate) mod journal;
 pub(crate) mod listener;
 pub(crate) mod local_transactions;
 pub(crate) mod queue;
@@ -10,9 +11,10 @@ pub(crate) mod replace;
 pub(crate) mod scoring;
 pub(crate) mod verifier;
 
-pub use client::{AccountSeqNumberClient, Client};
+pub use client::{AccountBalanceClient, AccountSeqNumberClient, Client};
 use crypto::hash::HashValue;
 pub use queue::{Status, TransactionQueue};
+pub use replace::ReplacePolicy;
 use std::ops::Deref;
 use transaction_pool as tx_pool;
 use types::{account_address::AccountAddress, transaction};
@@ -68,6 +70,10 @@ pub enum Priority {
     /// When block becomes non-canonical we re-import the transactions it contains
     /// to the queue and boost their priority.
     Retracted,
+    /// Transactions from the configured priority senders (high priority)
+    ///
+    /// Such transactions are exempt from the per sender limit, and can use the reserved pool capacity.
+    Allowlisted,
     /// Local transactions (high priority)
     ///
     /// Transactions either from a local account or
@@ -79,6 +85,11 @@ impl Priority {
     fn is_local(self) -> bool {
         matches!(self, Priority::Local)
     }
+
+    /// Local and priority senders' transactions.
+    fn is_prioritized(self) -> bool {
+        matches!(self, Priority::Local | Priority::Allowlisted)
+    }
 }
 /// Transaction to verify.
 #[derive(Clone)]
@@ -17,6 +17,12 @@ pub trait AccountSeqNumberClient: fmt::Debug + Clone + Any {
     fn account_seq_number(&self, address: &Address) -> SeqNumber;
 }
 
+/// State balance client
+pub trait AccountBalanceClient: fmt::Debug + Clone + Any {
+    /// Fetch the STC balance of given account, used to pay gas for sponsored transactions.
+    fn account_balance(&self, address: &Address) -> u128;
+}
+
 /// Verification client.
 pub trait Client: fmt::Debug {
     // /// Perform basic/cheap transaction verification.
@@ -0,0 +1,190 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Transaction Journal
+//!
+//! Persist the pending transactions to a local file, so that they can be replayed into the pool
+//! after the node restarts. Every imported transaction is appended to the journal, and the
+//! journal is regenerated from the pool's pending transactions periodically, to drop the
+//! transactions which are mined or removed.
+//!
+//! The journal file is a sequence of records, every record is a u32 little endian length prefix
+//! and the BCS bytes of a `JournalEntry`.
+
+use super::{ScoredTransaction, VerifiedTransaction as Transaction};
+use anyhow::{format_err, Result};
+use bcs_ext::BCSCodec;
+use serde_derive::{Deserialize, Serialize};
+use starcoin_config::TxPoolJournalScope;
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use transaction_pool as tx_pool;
+use tx_pool::VerifiedTransaction;
+use types::transaction::SignedUserTransaction;
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Is the transaction submitted through this node.
+    pub local: bool,
+    pub txn: SignedUserTransaction,
+}
+
+/// Appends the imported transactions to the journal file, disabled by default.
+#[derive(Default)]
+pub struct TransactionJournal {
+    scope: Option<TxPoolJournalScope>,
+    path: Option<PathBuf>,
+    writer: Option<BufWriter<File>>,
+}
+
+impl fmt::Debug for TransactionJournal {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("TransactionJournal")
+            .field("scope", &self.scope)
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl TransactionJournal {
+    /// Open the journal at `path` for appending, the `path`'s parent dir is created if not exists.
+    pub fn open(path: PathBuf, scope: TxPoolJournalScope) -> Result<Self> {
+        if scope == TxPoolJournalScope::Disable {
+            return Ok(Self::default());
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let writer = Self::open_writer(path.as_path())?;
+        Ok(Self {
+            scope: Some(scope),
+            path: Some(path),
+            writer: Some(writer),
+        })
+    }
+
+    fn open_writer(path: &Path) -> Result<BufWriter<File>> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(BufWriter::new(file))
+    }
+
+    pub fn scope(&self) -> Option<TxPoolJournalScope> {
+        self.scope
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.writer.is_some()
+    }
+
+    /// Should the transaction be journaled in current scope.
+    pub fn accept(&self, tx: &Transaction) -> bool {
+        match self.scope {
+            Some(TxPoolJournalScope::All) => true,
+            Some(TxPoolJournalScope::Local) => tx.priority().is_local(),
+            _ => false,
+        }
+    }
+
+    fn append(&mut self, entry: &JournalEntry) -> Result<()> {
+        if let Some(writer) = self.writer.as_mut() {
+            write_entry(writer, entry)?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Regenerate the journal with the given transactions, the previous journal is replaced atomically.
+    pub fn rotate<'a, I>(&mut self, txns: I) -> Result<usize>
+    where
+        I: IntoIterator<Item = &'a Arc<Transaction>>,
+    {
+        let path = match self.path.as_ref() {
+            Some(path) => path.clone(),
+            None => return Ok(0),
+        };
+        let tmp_path = path.with_extension("tmp");
+        let mut count = 0;
+        {
+            let mut writer = BufWriter::new(File::create(tmp_path.as_path())?);
+            for tx in txns.into_iter().filter(|tx| self.accept(tx)) {
+                write_entry(&mut writer, &JournalEntry::from(tx.as_ref()))?;
+                count += 1;
+            }
+            writer.flush()?;
+        }
+        // close the current writer before replace the file.
+        self.writer = None;
+        std::fs::rename(tmp_path.as_path(), path.as_path())?;
+        self.writer = Some(Self::open_writer(path.as_path())?);
+        Ok(count)
+    }
+}
+
+impl From<&Transaction> for JournalEntry {
+    fn from(tx: &Transaction) -> Self {
+        Self {
+            local: tx.priority().is_local(),
+            txn: tx.signed().clone(),
+        }
+    }
+}
+
+fn write_entry<W: Write>(writer: &mut W, entry: &JournalEntry) -> Result<()> {
+    let bytes = entry.encode()?;
+    let len = u32::try_from(bytes.len())?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+/// Load all entries of the journal at `path`, return empty if the journal not exists.
+/// A truncated tail record, which is caused by crash when writing, is ignored.
+pub fn load_journal(path: &Path) -> Result<Vec<JournalEntry>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.into()),
+    };
+    let mut reader = BufReader::new(file);
+    let mut entries = vec![];
+    loop {
+        let mut len_bytes = [0u8; 4];
+        match reader.read_exact(&mut len_bytes) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e.into()),
+        }
+        let len = u32::from_le_bytes(len_bytes) as usize;
+        let mut bytes = vec![0u8; len];
+        match reader.read_exact(&mut bytes) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => {
+                warn!(target: "txqueue", "Ignore truncated tail record of txpool journal {:?}", path);
+                break;
+            }
+            Err(e) => return Err(e.into()),
+        }
+        let entry = JournalEntry::decode(bytes.as_slice())
+            .map_err(|e| format_err!("Decode txpool journal {:?} record error: {:?}", path, e))?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+impl tx_pool::Listener<Transaction> for TransactionJournal {
+    fn added(&mut self, tx: &Arc<Transaction>, _old: Option<&Arc<Transaction>>) {
+        if !self.is_enabled() || !self.accept(tx) {
+            return;
+        }
+        if let Err(e) = self.append(&JournalEntry::from(tx.as_ref())) {
+            error!(target: "txqueue", "[{:?}] Append to txpool journal error: {:?}", tx.hash(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,103 @@
+use super::*;
+use crate::pool::Priority;
+use crypto::{ed25519, Uniform};
+use rand::SeedableRng;
+use std::io::Write;
+use tx_pool::Listener;
+use types::account_address::AccountAddress;
+use types::genesis_config::ChainId;
+use types::transaction::{self, helpers::get_current_timestamp, Script, TransactionPayload};
+
+fn new_tx(seq: u64, priority: Priority) -> Arc<Transaction> {
+    let raw = transaction::RawUserTransaction::new_with_default_gas_token(
+        AccountAddress::random(),
+        seq,
+        TransactionPayload::Script(Script::new(vec![1, 2, 3], vec![], vec![])),
+        100_000,
+        10,
+        get_current_timestamp() + 60,
+        ChainId::test(),
+    );
+    let mut rng = rand::rngs::StdRng::from_seed([0; 32]);
+    let private_key = ed25519::Ed25519PrivateKey::generate(&mut rng);
+    let public_key = (&private_key).into();
+
+    let signed = raw.sign(&private_key, public_key).unwrap().into_inner();
+    let mut tx = Transaction::from_pending_block_transaction(signed);
+    tx.priority = priority;
+    Arc::new(tx)
+}
+
+#[test]
+fn test_journal_local_scope() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("txpool").join("journal.bcs");
+    let mut journal = TransactionJournal::open(path.clone(), TxPoolJournalScope::Local).unwrap();
+
+    let local_tx = new_tx(0, Priority::Local);
+    let remote_tx = new_tx(1, Priority::Regular);
+    journal.added(&local_tx, None);
+    journal.added(&remote_tx, None);
+
+    let entries = load_journal(path.as_path()).unwrap();
+    assert_eq!(entries, vec![JournalEntry::from(local_tx.as_ref())]);
+    assert!(entries[0].local);
+}
+
+#[test]
+fn test_journal_all_scope_and_rotate() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("journal.bcs");
+    let mut journal = TransactionJournal::open(path.clone(), TxPoolJournalScope::All).unwrap();
+
+    let txns = vec![
+        new_tx(0, Priority::Local),
+        new_tx(1, Priority::Regular),
+        new_tx(2, Priority::Retracted),
+    ];
+    for tx in &txns {
+        journal.added(tx, None);
+    }
+    assert_eq!(load_journal(path.as_path()).unwrap().len(), 3);
+
+    // only the second txn is still in the pool.
+    assert_eq!(journal.rotate(txns.iter().skip(1).take(1)).unwrap(), 1);
+    let entries = load_journal(path.as_path()).unwrap();
+    assert_eq!(entries, vec![JournalEntry::from(txns[1].as_ref())]);
+
+    // append after rotate.
+    journal.added(&txns[2], None);
+    assert_eq!(load_journal(path.as_path()).unwrap().len(), 2);
+}
+
+#[test]
+fn test_load_journal_with_truncated_tail() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("journal.bcs");
+    assert!(load_journal(path.as_path()).unwrap().is_empty());
+
+    let mut journal = TransactionJournal::open(path.clone(), TxPoolJournalScope::All).unwrap();
+    let tx = new_tx(0, Priority::Local);
+    journal.added(&tx, None);
+
+    // simulate a crash while writing the next record.
+    let mut file = OpenOptions::new()
+        .append(true)
+        .open(path.as_path())
+        .unwrap();
+    file.write_all(&100u32.to_le_bytes()).unwrap();
+    file.write_all(&[1, 2, 3]).unwrap();
+
+    let entries = load_journal(path.as_path()).unwrap();
+    assert_eq!(entries, vec![JournalEntry::from(tx.as_ref())]);
+}
+
+#[test]
+fn test_disabled_journal() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("journal.bcs");
+    let mut journal = TransactionJournal::open(path.clone(), TxPoolJournalScope::Disable).unwrap();
+    assert!(!journal.is_enabled());
+    journal.added(&new_tx(0, Priority::Local), None);
+    assert!(!path.exists());
+}
@@ -3,10 +3,11 @@
 
 use std::{fmt, sync::Arc};
 
-use super::{TxStatus, VerifiedTransaction as Transaction};
+use super::{ScoredTransaction, TxStatus, VerifiedTransaction as Transaction};
 use crypto::hash::HashValue as H256;
 use futures_channel::mpsc;
 use starcoin_logger::prelude::*;
+use starcoin_txpool_api::TxnStatusEvent;
 use transaction_pool as tx_pool;
 use tx_pool::VerifiedTransaction;
 /// Transaction pool logger.
@@ -99,7 +100,7 @@ impl tx_pool::Listener<Transaction> for StatusLogger {
     fn added(&mut self, tx: &Arc<Transaction>, old: Option<&Arc<Transaction>>) {
         Self::log_status(tx, TxStatus::Added);
         if let Some(old) = old {
-            Self::log_status(old, TxStatus::Dropped);
+            Self::log_status(old, removed_status(tx, old));
         }
     }
 
@@ -128,12 +129,25 @@ impl tx_pool::Listener<Transaction> for StatusLogger {
     }
 }
 
+/// The status of the `old` transaction which is removed from the pool when the `new` transaction is added.
+///
+/// `old` is replaced if it has the same sequence number as `new`, otherwise it's pushed out because of the sender limit.
+fn removed_status(new: &Transaction, old: &Transaction) -> TxStatus {
+    if new.seq_number() == old.seq_number() {
+        TxStatus::Replaced
+    } else {
+        TxStatus::Dropped
+    }
+}
+
 /// Transactions pool notifier
 #[derive(Default)]
 pub struct TransactionsPoolNotifier {
     full_listeners: Vec<mpsc::UnboundedSender<Arc<[(H256, TxStatus)]>>>,
     pending_listeners: Vec<mpsc::UnboundedSender<Arc<[H256]>>>,
+    status_listeners: Vec<mpsc::UnboundedSender<Arc<[TxnStatusEvent]>>>,
     tx_statuses: Vec<(H256, TxStatus)>,
+    status_events: Vec<TxnStatusEvent>,
 }
 
 impl TransactionsPoolNotifier {
@@ -147,21 +161,56 @@ impl TransactionsPoolNotifier {
         self.pending_listeners.push(f);
     }
 
+    /// Add new status listener to receive the status transitions with the txn's sender.
+    pub fn add_status_listener(&mut self, f: mpsc::UnboundedSender<Arc<[TxnStatusEvent]>>) {
+        self.status_listeners.push(f);
+    }
+
+    /// Record the status transitions of the txns which are not caused by the pool,
+    /// such as the txns included or retracted by chain new blocks.
+    pub fn push_status_events(&mut self, events: impl IntoIterator<Item = TxnStatusEvent>) {
+        if !self.status_listeners.is_empty() {
+            self.status_events.extend(events);
+        }
+    }
+
+    fn push_status(&mut self, tx: &Transaction, status: TxStatus) {
+        self.tx_statuses.push((tx.hash, status));
+        if !self.status_listeners.is_empty() {
+            self.status_events.push(TxnStatusEvent {
+                txn_hash: tx.hash,
+                sender: tx.sender,
+                status,
+                block_hash: None,
+                block_number: None,
+            });
+        }
+    }
+
     /// Notify listeners about all currently transactions.
     pub fn notify(&mut self) {
+        if !self.status_events.is_empty() {
+            let to_status_send: Arc<[TxnStatusEvent]> =
+                std::mem::take(&mut self.status_events).into();
+            self.status_listeners
+                .retain(|listener| listener.unbounded_send(to_status_send.clone()).is_ok());
+        }
         if self.tx_statuses.is_empty() {
             return;
         }
 
+        // only the added transactions are new pending transactions.
         let to_pending_send: Arc<[H256]> = self
             .tx_statuses
-            .clone()
-            .into_iter()
-            .map(|(hash, _)| hash)
+            .iter()
+            .filter(|(_, status)| *status == TxStatus::Added)
+            .map(|(hash, _)| *hash)
             .collect::<Vec<_>>()
             .into();
-        self.pending_listeners
-            .retain(|listener| listener.unbounded_send(to_pending_send.clone()).is_ok());
+        if !to_pending_send.is_empty() {
+            self.pending_listeners
+                .retain(|listener| listener.unbounded_send(to_pending_send.clone()).is_ok());
+        }
 
         let to_full_send: Arc<[(H256, TxStatus)]> = std::mem::take(&mut self.tx_statuses).into();
         self.full_listeners
@@ -174,13 +223,17 @@ impl fmt::Debug for TransactionsPoolNotifier {
         fmt.debug_struct("TransactionsPoolNotifier")
             .field("full_listeners", &self.full_listeners.len())
             .field("pending_listeners", &self.pending_listeners.len())
+            .field("status_listeners", &self.status_listeners.len())
             .finish()
     }
 }
 
 impl tx_pool::Listener<Transaction> for TransactionsPoolNotifier {
-    fn added(&mut self, tx: &Arc<Transaction>, _old: Option<&Arc<Transaction>>) {
-        self.tx_statuses.push((tx.hash, TxStatus::Added));
+    fn added(&mut self, tx: &Arc<Transaction>, old: Option<&Arc<Transaction>>) {
+        self.push_status(tx, TxStatus::Added);
+        if let Some(old) = old {
+            self.push_status(old, removed_status(tx, old));
+        }
     }
 
     fn rejected<H: fmt::Debug + fmt::LowerHex>(
@@ -188,23 +241,23 @@ impl tx_pool::Listener<Transaction> for TransactionsPoolNotifier {
         tx: &Arc<Transaction>,
         _reason: &tx_pool::Error<H>,
     ) {
-        self.tx_statuses.push((tx.hash, TxStatus::Rejected));
+        self.push_status(tx, TxStatus::Rejected);
     }
 
     fn dropped(&mut self, tx: &Arc<Transaction>, _new: Option<&Transaction>) {
-        self.tx_statuses.push((tx.hash, TxStatus::Dropped));
+        self.push_status(tx, TxStatus::Dropped);
     }
 
     fn invalid(&mut self, tx: &Arc<Transaction>) {
-        self.tx_statuses.push((tx.hash, TxStatus::Invalid));
+        self.push_status(tx, TxStatus::Invalid);
     }
 
     fn canceled(&mut self, tx: &Arc<Transaction>) {
-        self.tx_statuses.push((tx.hash, TxStatus::Canceled));
+        self.push_status(tx, TxStatus::Canceled);
     }
 
     fn culled(&mut self, tx: &Arc<Transaction>) {
-        self.tx_statuses.push((tx.hash, TxStatus::Culled));
+        self.push_status(tx, TxStatus::Culled);
     }
 }
 
@@ -76,6 +76,22 @@ fn test_notify() {
     tx_listener.notify();
     let full_res = full_receiver.try_next().unwrap();
     assert_eq!(full_res, Some(vec![(*tx.hash(), TxStatus::Invalid)].into()));
+
+    // replaced by the transaction with the same sequence number
+    let new = new_tx();
+    tx_listener.added(&new, Some(&tx));
+    tx_listener.notify();
+    let full_res = full_receiver.try_next().unwrap();
+    assert_eq!(
+        full_res,
+        Some(
+            vec![
+                (*new.hash(), TxStatus::Added),
+                (*tx.hash(), TxStatus::Replaced)
+            ]
+            .into()
+        )
+    );
 }
 
 fn new_tx() -> Arc<Transaction> {
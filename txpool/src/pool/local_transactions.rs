@@ -110,6 +110,17 @@ impl LocalTransactionsList {
         self.pending > 0
     }
 
+    /// Returns the local transactions which are currently in the queue.
+    pub fn pending_transactions(&self) -> Vec<Arc<Transaction>> {
+        self.transactions
+            .values()
+            .filter_map(|status| match status {
+                Status::Pending(tx) => Some(tx.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
     fn clear_old(&mut self) {
         let number_of_old = self.transactions.len() - self.pending;
         if self.max_old >= number_of_old {
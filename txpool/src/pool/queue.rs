@@ -4,18 +4,21 @@
 //! Transaction Queue
 
 use super::{
-    client, listener, local_transactions::LocalTransactionsList, ready, replace, scoring, verifier,
-    PendingOrdering, PendingSettings, PrioritizationStrategy, SeqNumber, TxStatus,
+    client, journal::TransactionJournal, listener, local_transactions::LocalTransactionsList,
+    ready, replace, scoring, verifier, PendingOrdering, PendingSettings, PrioritizationStrategy,
+    SeqNumber, TxStatus,
 };
 use crate::pool::ready::Expiration;
-use crate::{pool, pool::PoolTransaction};
+use crate::{pool, pool::PoolTransaction, pool::ScoredTransaction};
+use anyhow::Result;
 use crypto::hash::HashValue;
 use futures_channel::mpsc;
 use parking_lot::RwLock;
-use starcoin_txpool_api::TxPoolStatus;
+use starcoin_config::TxPoolJournalScope;
+use starcoin_txpool_api::{TxPoolStatus, TxnStatusEvent};
 use std::{
     cmp,
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt,
     sync::{
         atomic::{self, AtomicUsize},
@@ -29,7 +32,10 @@ type Listener = (
     LocalTransactionsList,
     (
         listener::TransactionsPoolNotifier,
-        (listener::Logger, listener::StatusLogger),
+        (
+            (listener::Logger, listener::StatusLogger),
+            TransactionJournal,
+        ),
     ),
 );
 type Pool = tx_pool::Pool<pool::VerifiedTransaction, scoring::SeqNumberAndGasPrice, Listener>;
@@ -223,6 +229,7 @@ pub struct TransactionQueue {
     options: RwLock<verifier::Options>,
     cached_pending: RwLock<CachedPending>,
     recently_rejected: RecentlyRejected,
+    replacements: replace::ReplacementCounter,
 }
 
 impl TransactionQueue {
@@ -231,13 +238,14 @@ impl TransactionQueue {
         limits: tx_pool::Options,
         verification_options: verifier::Options,
         strategy: PrioritizationStrategy,
+        replace_policy: replace::ReplacePolicy,
     ) -> Self {
         let max_count = limits.max_count;
         TransactionQueue {
             insertion_id: Default::default(),
             pool: RwLock::new(tx_pool::Pool::new(
                 Default::default(),
-                scoring::SeqNumberAndGasPrice(strategy),
+                scoring::SeqNumberAndGasPrice(strategy, replace_policy),
                 limits,
             )),
             options: RwLock::new(verification_options),
@@ -246,6 +254,7 @@ impl TransactionQueue {
                 MIN_REJECTED_CACHE_SIZE,
                 max_count / 4,
             )),
+            replacements: Default::default(),
         }
     }
 
@@ -264,6 +273,33 @@ impl TransactionQueue {
         self.pool.write().listener_mut().0.set_in_chain_checker(f)
     }
 
+    /// Sets the journal, imported transactions are appended to the journal since now.
+    pub fn set_journal(&self, journal: TransactionJournal) {
+        (self.pool.write().listener_mut().1).1 .1 = journal;
+    }
+
+    /// Regenerate the journal from the transactions currently in the pool.
+    ///
+    /// Returns the count of journaled transactions.
+    pub fn rotate_journal(&self) -> Result<usize> {
+        let mut pool = self.pool.write();
+        let txns: Vec<_> = match pool.listener().1 .1 .1.scope() {
+            Some(TxPoolJournalScope::All) => pool.unordered_pending(Expiration::new(0)).collect(),
+            Some(TxPoolJournalScope::Local) => pool.listener().0.pending_transactions(),
+            _ => return Ok(0),
+        };
+        (pool.listener_mut().1).1 .1.rotate(txns.iter())
+    }
+
+    /// Whether the pool capacity for the regular txns is used up, the rest `reserved_count` is reserved for the prioritized txns.
+    fn is_regular_capacity_full(&self, reserved_count: usize) -> bool {
+        if reserved_count == 0 {
+            return false;
+        }
+        let pool = self.pool.read();
+        pool.light_status().transaction_count + reserved_count >= pool.options().max_count
+    }
+
     /// Import a set of transactions to the pool.
     ///
     /// Given blockchain and state access (Client)
@@ -295,6 +331,7 @@ impl TransactionQueue {
             }
         };
 
+        let verifier_options = options.clone();
         let verifier = verifier::Verifier::new(
             client.clone(),
             options,
@@ -302,8 +339,9 @@ impl TransactionQueue {
             transaction_to_replace,
         );
 
-        let replace =
-            replace::ReplaceByScoreAndReadiness::new(self.pool.read().scoring().clone(), client);
+        let scoring = self.pool.read().scoring().clone();
+        let max_replacements = scoring.replace_policy().max_replacements_per_sender;
+        let replace = replace::ReplaceByScoreAndReadiness::new(scoring, client);
 
         let mut results = Vec::new();
         for transaction in transactions.into_iter() {
@@ -318,6 +356,26 @@ impl TransactionQueue {
                 results.push(Err(err));
             }
 
+            let sender = transaction.signed().sender();
+            let is_replacement = self
+                .find_by_seq_number(&sender, transaction.signed().sequence_number())
+                .is_some();
+            if !is_replacement
+                && !verifier_options.is_prioritized(&transaction)
+                && self.is_regular_capacity_full(verifier_options.reserved_count)
+            {
+                debug!(target: "txqueue", "[{:?}] Rejected tx, the rest pool capacity is reserved for local and priority senders' txns", &hash);
+                results.push(Err(transaction::TransactionError::LimitReached));
+                continue;
+            }
+            if is_replacement && self.replacements.count(&sender) >= max_replacements {
+                debug!(target: "txqueue", "[{:?}] Rejected tx, sender {} replaced too many transactions", &hash, sender);
+                results.push(Err(transaction::TransactionError::TooManyReplacements {
+                    limit: max_replacements,
+                }));
+                continue;
+            }
+
             let imported = verifier
                 .verify_transaction(transaction)
                 .and_then(|verified| {
@@ -328,7 +386,12 @@ impl TransactionQueue {
                 });
 
             results.push(match imported {
-                Ok(_) => Ok(()),
+                Ok(_) => {
+                    if is_replacement {
+                        self.replacements.increase(sender);
+                    }
+                    Ok(())
+                }
                 Err(err) => {
                     self.recently_rejected.insert(hash, &err);
                     Err(err)
@@ -359,6 +422,45 @@ impl TransactionQueue {
             .collect()
     }
 
+    /// Find the transaction of `sender` with the sequence number `seq_number` in the pool.
+    pub fn find_by_seq_number(
+        &self,
+        sender: &Address,
+        seq_number: SeqNumber,
+    ) -> Option<Arc<pool::VerifiedTransaction>> {
+        // always ready
+        let ready = Expiration::new(0);
+        self.pool
+            .read()
+            .pending_from_sender(ready, sender)
+            .find(|tx| tx.seq_number() == seq_number)
+    }
+
+    /// Returns the senders which have transactions in the pool.
+    pub fn senders(&self) -> Vec<Address> {
+        self.pool.read().senders().cloned().collect()
+    }
+
+    /// Returns all the transactions in the pool, sorted by (sender, sequence number).
+    pub fn all_transactions(&self) -> Vec<Arc<pool::VerifiedTransaction>> {
+        let mut txns: Vec<_> = self
+            .pool
+            .read()
+            .unordered_pending(Expiration::new(0))
+            .collect();
+        txns.sort_by_key(|tx| (tx.sender, tx.seq_number()));
+        txns
+    }
+
+    /// Returns the gas prices of all the transactions in the pool.
+    pub fn gas_prices(&self) -> Vec<pool::GasPrice> {
+        self.pool
+            .read()
+            .unordered_pending(Expiration::new(0))
+            .map(|tx| tx.gas_price())
+            .collect()
+    }
+
     /// Returns current pending transactions ordered by priority.
     ///
     /// NOTE: This may return a cached version of pending transaction set.
@@ -370,7 +472,7 @@ impl TransactionQueue {
         settings: PendingSettings,
     ) -> Vec<Arc<pool::VerifiedTransaction>>
     where
-        C: client::AccountSeqNumberClient,
+        C: client::AccountSeqNumberClient + client::AccountBalanceClient,
     {
         let PendingSettings {
             block_number,
@@ -398,17 +500,24 @@ impl TransactionQueue {
         client: C,
         block_number: u64,
         current_timestamp: u64,
-    ) -> ((ready::Expiration, ready::Condition), ready::State<C>)
+    ) -> (
+        ((ready::Expiration, ready::Condition), ready::State<C>),
+        ready::FeePayerBalance<C>,
+    )
     where
-        C: client::AccountSeqNumberClient,
+        C: client::AccountSeqNumberClient + client::AccountBalanceClient,
     {
         let pending_readiness = ready::Condition::new(block_number, current_timestamp);
         // don't mark any transactions as stale at this point.
-        let state_readiness = ready::State::new(client, None);
+        let state_readiness = ready::State::new(client.clone(), None);
+        let balance_readiness = ready::FeePayerBalance::new(client);
 
         (
-            (ready::Expiration::new(current_timestamp), pending_readiness),
-            state_readiness,
+            (
+                (ready::Expiration::new(current_timestamp), pending_readiness),
+                state_readiness,
+            ),
+            balance_readiness,
         )
     }
 
@@ -441,6 +550,9 @@ impl TransactionQueue {
             let readiness = (ready::Expiration::new(now), state_readiness);
             removed += self.pool.write().cull(Some(chunk), readiness);
         }
+        // reset the replacement counts of the senders which have no transaction in the pool.
+        let senders: HashSet<_> = self.pool.read().senders().cloned().collect();
+        self.replacements.retain(|sender| senders.contains(sender));
         debug!(target: "txqueue", "Removed {} stalled transactions. {}", removed, self.status());
     }
 
@@ -501,6 +613,7 @@ impl TransactionQueue {
     /// Clear the entire pool.
     pub fn clear(&self) {
         self.pool.write().clear();
+        self.replacements.clear();
     }
 
     /// Penalize given senders.
@@ -512,7 +625,7 @@ impl TransactionQueue {
 
     pub(crate) fn inner_status<C>(&self, client: C, block_number: u64, current_timestamp: u64)
     where
-        C: client::AccountSeqNumberClient,
+        C: client::AccountSeqNumberClient + client::AccountBalanceClient,
     {
         let ready = Self::ready(client, block_number, current_timestamp);
         let status = self.pool.read().status(ready);
@@ -568,6 +681,21 @@ impl TransactionQueue {
         (self.pool.write().listener_mut().1).0.add_full_listener(f);
     }
 
+    /// Add a listener to be notified about the status transitions of all transactions
+    pub fn add_status_listener(&self, f: mpsc::UnboundedSender<Arc<[TxnStatusEvent]>>) {
+        (self.pool.write().listener_mut().1)
+            .0
+            .add_status_listener(f);
+    }
+
+    /// Notify the status listeners about the status transitions which are not caused by the pool.
+    pub fn notify_status_events(&self, events: Vec<TxnStatusEvent>) {
+        let mut pool = self.pool.write();
+        let notifier = &mut (pool.listener_mut().1).0;
+        notifier.push_status_events(events);
+        notifier.notify();
+    }
+
     /// Check if pending set is cached.
     #[cfg(test)]
     pub fn is_pending_cached(&self) -> bool {
@@ -30,7 +30,10 @@ use std::{cmp, collections::HashMap};
 use tx_pool::{self, VerifiedTransaction as PoolVerifiedTransaction};
 use types::{account_address::AccountAddress as Address, transaction};
 
-use super::{client::AccountSeqNumberClient, SeqNumber, VerifiedTransaction};
+use super::{
+    client::{AccountBalanceClient, AccountSeqNumberClient},
+    SeqNumber, VerifiedTransaction,
+};
 
 /// Checks readiness of transactions by comparing the nonce to state nonce.
 #[derive(Debug)]
@@ -89,6 +92,58 @@ impl<C: AccountSeqNumberClient> tx_pool::Ready<VerifiedTransaction> for State<C>
     }
 }
 
+/// Checks readiness of sponsored transactions by comparing the max gas fee to the fee payer balance.
+///
+/// The balance of every fee payer is loaded once and consumed by the ready transactions
+/// it pays gas for, both the sponsored ones and its own, so a fee payer can not sponsor more
+/// transactions than it can afford. Transactions without a fee payer are always ready.
+#[derive(Debug)]
+pub struct FeePayerBalance<C> {
+    balances: HashMap<Address, u128>,
+    spent: HashMap<Address, u128>,
+    state: C,
+}
+
+impl<C> FeePayerBalance<C> {
+    /// Create new FeePayerBalance checker, given client interface.
+    pub fn new(state: C) -> Self {
+        FeePayerBalance {
+            balances: Default::default(),
+            spent: Default::default(),
+            state,
+        }
+    }
+}
+
+impl<C: AccountBalanceClient> tx_pool::Ready<VerifiedTransaction> for FeePayerBalance<C> {
+    fn is_ready(&mut self, tx: &VerifiedTransaction) -> tx_pool::Readiness {
+        let txn = &tx.transaction.transaction;
+        let raw_txn = txn.raw_txn();
+        let max_gas_fee =
+            u128::from(raw_txn.gas_unit_price()) * u128::from(raw_txn.max_gas_amount());
+        let gas_payer = txn.gas_payer();
+        let spent = self.spent.entry(gas_payer).or_default();
+        if txn.fee_payer().is_none() {
+            // the gas of the sender's own transactions is checked by the prologue,
+            // it only reduces what is left for the transactions it sponsors.
+            *spent = spent.saturating_add(max_gas_fee);
+            return tx_pool::Readiness::Ready;
+        }
+        let state = &self.state;
+        let balance = *self
+            .balances
+            .entry(gas_payer)
+            .or_insert_with(|| state.account_balance(&gas_payer));
+        match spent.checked_add(max_gas_fee) {
+            Some(total) if total <= balance => {
+                *spent = total;
+                tx_pool::Readiness::Ready
+            }
+            _ => tx_pool::Readiness::Future,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct Expiration {
     now: u64,
@@ -177,3 +232,6 @@ impl<C: Fn(&Address) -> Option<SeqNumber>> tx_pool::Ready<VerifiedTransaction>
         }
     }
 }
+
+#[cfg(test)]
+mod tests;
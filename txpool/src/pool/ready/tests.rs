@@ -0,0 +1,105 @@
+use super::*;
+use crypto::keygen::KeyGen;
+use tx_pool::Ready;
+use types::genesis_config::ChainId;
+use types::transaction::authenticator::AccountPrivateKey;
+use types::transaction::{
+    helpers::get_current_timestamp, RawUserTransaction, RawUserTransactionWithFeePayer, Script,
+    SignedUserTransaction, TransactionPayload,
+};
+
+const MAX_GAS_AMOUNT: u64 = 100_000;
+const GAS_UNIT_PRICE: u64 = 10;
+const MAX_GAS_FEE: u128 = MAX_GAS_AMOUNT as u128 * GAS_UNIT_PRICE as u128;
+
+#[derive(Clone, Debug)]
+struct MockBalanceClient {
+    balances: HashMap<Address, u128>,
+}
+
+impl AccountBalanceClient for MockBalanceClient {
+    fn account_balance(&self, address: &Address) -> u128 {
+        self.balances.get(address).cloned().unwrap_or_default()
+    }
+}
+
+fn new_raw_txn(sender: Address) -> RawUserTransaction {
+    RawUserTransaction::new_with_default_gas_token(
+        sender,
+        0,
+        TransactionPayload::Script(Script::new(vec![1, 2, 3], vec![], vec![])),
+        MAX_GAS_AMOUNT,
+        GAS_UNIT_PRICE,
+        get_current_timestamp() + 60,
+        ChainId::test(),
+    )
+}
+
+fn new_txn(key: &AccountPrivateKey) -> VerifiedTransaction {
+    let raw_txn = new_raw_txn(key.public_key().derived_address());
+    let txn = SignedUserTransaction::new(raw_txn.clone(), key.sign(&raw_txn));
+    VerifiedTransaction::from_pending_block_transaction(txn)
+}
+
+fn new_sponsored_txn(
+    key: &AccountPrivateKey,
+    fee_payer_key: &AccountPrivateKey,
+) -> VerifiedTransaction {
+    let raw_txn = new_raw_txn(key.public_key().derived_address());
+    let fee_payer = fee_payer_key.public_key().derived_address();
+    let message = RawUserTransactionWithFeePayer::new(raw_txn.clone(), fee_payer);
+    let txn = SignedUserTransaction::with_fee_payer(
+        raw_txn,
+        key.sign(&message),
+        fee_payer,
+        fee_payer_key.sign(&message),
+    );
+    VerifiedTransaction::from_pending_block_transaction(txn)
+}
+
+fn new_key(key_gen: &mut KeyGen) -> AccountPrivateKey {
+    AccountPrivateKey::Single(key_gen.generate_keypair().0)
+}
+
+fn new_readiness(
+    fee_payer: &AccountPrivateKey,
+    balance: u128,
+) -> FeePayerBalance<MockBalanceClient> {
+    let mut balances = HashMap::new();
+    balances.insert(fee_payer.public_key().derived_address(), balance);
+    FeePayerBalance::new(MockBalanceClient { balances })
+}
+
+#[test]
+fn test_fee_payer_balance_limits_sponsored_txns() {
+    let mut key_gen = KeyGen::from_os_rng();
+    let fee_payer = new_key(&mut key_gen);
+    let mut readiness = new_readiness(&fee_payer, MAX_GAS_FEE * 2);
+
+    for _ in 0..2 {
+        let txn = new_sponsored_txn(&new_key(&mut key_gen), &fee_payer);
+        assert_eq!(readiness.is_ready(&txn), tx_pool::Readiness::Ready);
+    }
+    // the balance is used up by the previous sponsored txns.
+    let txn = new_sponsored_txn(&new_key(&mut key_gen), &fee_payer);
+    assert_eq!(readiness.is_ready(&txn), tx_pool::Readiness::Future);
+
+    // txns without a fee payer are not checked.
+    let txn = new_txn(&new_key(&mut key_gen));
+    assert_eq!(readiness.is_ready(&txn), tx_pool::Readiness::Ready);
+}
+
+#[test]
+fn test_fee_payer_balance_counts_own_txns() {
+    let mut key_gen = KeyGen::from_os_rng();
+    let fee_payer = new_key(&mut key_gen);
+    let mut readiness = new_readiness(&fee_payer, MAX_GAS_FEE * 2);
+
+    // the fee payer's own txn consumes the balance too.
+    let txn = new_txn(&fee_payer);
+    assert_eq!(readiness.is_ready(&txn), tx_pool::Readiness::Ready);
+    let txn = new_sponsored_txn(&new_key(&mut key_gen), &fee_payer);
+    assert_eq!(readiness.is_ready(&txn), tx_pool::Readiness::Ready);
+    let txn = new_sponsored_txn(&new_key(&mut key_gen), &fee_payer);
+    assert_eq!(readiness.is_ready(&txn), tx_pool::Readiness::Future);
+}
@@ -9,9 +9,17 @@
 //!
 //! Here we decide based on the sender, the nonce and gas price, and finally
 //! on the `Readiness` of the transactions when comparing them
+//!
+//! A pending transaction can be replaced by a new transaction with the same sender and
+//! sequence number, only if the new transaction follows the `ReplacePolicy`:
+//! - the new gas price is bumped at least `min_gas_price_bump_percent` percent.
+//! - a local transaction is only replaced by another local transaction, unless `replace_local_by_remote`.
+//! - the sender has not replaced `max_replacements_per_sender` transactions in the pool.
 
-use crate::pool::{client, ScoredTransaction};
+use crate::pool::{client, GasPrice, ScoredTransaction};
+use parking_lot::RwLock;
 use std::cmp;
+use std::collections::HashMap;
 use tx_pool::{
     self,
     scoring::{Choice, Scoring},
@@ -19,6 +27,78 @@ use tx_pool::{
 };
 use types::account_address::AccountAddress as Address;
 
+/// Policy to replace a pending transaction with the same sender and sequence number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplacePolicy {
+    /// The new gas price must be at least `old_gas_price * (100 + min_gas_price_bump_percent) / 100`.
+    pub min_gas_price_bump_percent: u64,
+    /// Can a local transaction be replaced by a non-local transaction.
+    pub replace_local_by_remote: bool,
+    /// Max replacements of a sender, the count is reset after all the sender's transactions leave the pool.
+    pub max_replacements_per_sender: u64,
+}
+
+impl Default for ReplacePolicy {
+    fn default() -> Self {
+        ReplacePolicy {
+            min_gas_price_bump_percent: 0,
+            replace_local_by_remote: false,
+            max_replacements_per_sender: 16,
+        }
+    }
+}
+
+impl ReplacePolicy {
+    /// Calculate minimal gas price requirement to replace a transaction with `old_gp`.
+    pub fn bump_gas_price(&self, old_gp: GasPrice) -> GasPrice {
+        let bump = old_gp
+            .saturating_mul(self.min_gas_price_bump_percent)
+            .saturating_add(99)
+            / 100;
+        old_gp.saturating_add(bump)
+    }
+
+    /// Decide whether the `new` transaction can replace the `old` one with the same sender and sequence number.
+    pub fn choose<T: ScoredTransaction>(&self, old: &T, new: &T) -> Choice {
+        if old.priority().is_local() && !new.priority().is_local() && !self.replace_local_by_remote
+        {
+            return Choice::RejectNew;
+        }
+        if new.gas_price() < self.bump_gas_price(old.gas_price()) {
+            Choice::RejectNew
+        } else {
+            Choice::ReplaceOld
+        }
+    }
+}
+
+/// Count the replacements of every sender in the pool.
+#[derive(Debug, Default)]
+pub struct ReplacementCounter {
+    counts: RwLock<HashMap<Address, u64>>,
+}
+
+impl ReplacementCounter {
+    pub fn count(&self, sender: &Address) -> u64 {
+        self.counts.read().get(sender).copied().unwrap_or(0)
+    }
+
+    pub fn increase(&self, sender: Address) {
+        let mut counts = self.counts.write();
+        let count = counts.entry(sender).or_insert(0);
+        *count = count.saturating_add(1);
+    }
+
+    /// Only keep the counts of the senders which `f` returns true.
+    pub fn retain<F: Fn(&Address) -> bool>(&self, f: F) {
+        self.counts.write().retain(|sender, _| f(sender))
+    }
+
+    pub fn clear(&self) {
+        self.counts.write().clear()
+    }
+}
+
 /// Choose whether to replace based on the sender, the score and finally the
 /// `Readiness` of the transactions being compared.
 #[derive(Debug)]
@@ -3,50 +3,43 @@
 
 use std::cmp;
 
-use super::{GasPrice, PrioritizationStrategy, ScoredTransaction, VerifiedTransaction};
+use super::{
+    replace::ReplacePolicy, PoolTransaction, PrioritizationStrategy, Priority, ScoredTransaction,
+    VerifiedTransaction,
+};
 use tx_pool::{self, scoring};
-/// Transaction with the same (sender, nonce) can be replaced only if
-/// `new_gas_price >= old_gas_price + old_gas_price >> SHIFT`
-const GAS_PRICE_BUMP_SHIFT: usize = 3; // 2 = 25%, 3 = 12.5%, 4 = 6.25%
-
-/// TODO: re-consider this, after we settle down the unit of gas price.
-/// Calculate minimal gas price requirement.
-#[inline]
-fn bump_gas_price(old_gp: GasPrice) -> GasPrice {
-    old_gp
-    // old_gp.saturating_add(old_gp >> GAS_PRICE_BUMP_SHIFT as u64)
-}
 
 /// Simple, gas-price based scoring for transactions.
 ///
 /// NOTE: Currently penalization does not apply to new transactions that enter the pool.
 /// We might want to store penalization status in some persistent state.
 #[derive(Debug, Clone)]
-pub struct SeqNumberAndGasPrice(pub PrioritizationStrategy);
+pub struct SeqNumberAndGasPrice(pub PrioritizationStrategy, pub ReplacePolicy);
 
 impl SeqNumberAndGasPrice {
+    /// The policy to replace a transaction with the same sender and sequence number.
+    pub fn replace_policy(&self) -> &ReplacePolicy {
+        &self.1
+    }
+
     /// Decide if the transaction should even be considered into the pool (if the pool is full).
     ///
     /// Used by Verifier to quickly reject transactions that don't have any chance to get into the pool later on,
     /// and save time on more expensive checks like sender recovery, etc.
     ///
-    /// NOTE The method is never called for zero-gas-price transactions or local transactions
+    /// NOTE The method is never called for zero-gas-price transactions, local transactions or priority senders' transactions
     /// (such transactions are always considered to the pool and potentially rejected later on)
-    pub fn should_reject_early(&self, _old: &VerifiedTransaction) -> bool {
-        todo!()
+    pub fn should_reject_early(&self, old: &VerifiedTransaction, new: &PoolTransaction) -> bool {
+        if old.priority().is_prioritized() {
+            return true;
+        }
+        let new_priority = if new.is_retracted() {
+            Priority::Retracted
+        } else {
+            Priority::Regular
+        };
+        (new_priority, new.gas_price()) <= (old.priority(), old.gas_price())
     }
-
-    //    pub fn should_reject_early(
-    //        &self,
-    //        old: &VerifiedTransaction,
-    //        new: &verifier::Transaction,
-    //    ) -> bool {
-    //        if old.priority().is_local() {
-    //            return true;
-    //        }
-    //
-    //        &old.transaction.gas_price > new.gas_price()
-    //    }
 }
 
 impl<P> tx_pool::Scoring<P> for SeqNumberAndGasPrice
@@ -65,15 +58,7 @@ where
             return scoring::Choice::InsertNew;
         }
 
-        let old_gp = old.gas_price();
-        let new_gp = new.gas_price();
-
-        let min_required_gp = bump_gas_price(old_gp);
-
-        match min_required_gp.cmp(&new_gp) {
-            cmp::Ordering::Greater => scoring::Choice::RejectNew,
-            _ => scoring::Choice::ReplaceOld,
-        }
+        self.replace_policy().choose(old, new)
     }
 
     fn update_scores(
@@ -94,17 +79,18 @@ where
                 scores[i] = txs[i].transaction.gas_price();
                 let boost = match txs[i].priority() {
                     super::Priority::Local => 15,
+                    super::Priority::Allowlisted => 12,
                     super::Priority::Retracted => 10,
                     super::Priority::Regular => 0,
                 };
                 scores[i] <<= boost;
             }
             // We are only sending an event in case of penalization.
-            // So just lower the priority of all non-local transactions.
+            // So just lower the priority of all non-prioritized transactions.
             Change::Event(_) => {
                 for (score, tx) in scores.iter_mut().zip(txs) {
-                    // Never penalize local transactions.
-                    if !tx.priority().is_local() {
+                    // Never penalize local and priority senders' transactions.
+                    if !tx.priority().is_prioritized() {
                         *score >>= 3;
                     }
                 }
@@ -112,8 +98,7 @@ where
         }
     }
 
-    fn should_ignore_sender_limit(&self, _new: &P) -> bool {
-        false
-        // new.priority().is_local()
+    fn should_ignore_sender_limit(&self, new: &P) -> bool {
+        new.priority().is_prioritized()
     }
 }
@@ -12,8 +12,9 @@ use crate::pool::{
     client::Client, scoring, PoolTransaction, Priority, UnverifiedUserTransaction,
     VerifiedTransaction,
 };
+use std::collections::HashSet;
 use std::sync::{atomic::AtomicUsize, Arc};
-use types::transaction;
+use types::{account_address::AccountAddress, transaction};
 
 /// Verification options.
 #[derive(Debug, Clone, PartialEq)]
@@ -22,6 +23,17 @@ pub struct Options {
     pub no_early_reject: bool,
     /// reject txn whose gas_price is lower than this.
     pub min_gas_price: u64,
+    /// txns of these senders are prioritized, and never rejected early.
+    pub priority_senders: HashSet<AccountAddress>,
+    /// pool capacity reserved for the local and priority senders' txns.
+    pub reserved_count: usize,
+}
+
+impl Options {
+    /// Whether the txn is prioritized as a local txn.
+    pub fn is_prioritized(&self, tx: &PoolTransaction) -> bool {
+        tx.is_local() || self.priority_senders.contains(&tx.signed().sender())
+    }
 }
 
 #[cfg(test)]
@@ -30,6 +42,8 @@ impl Default for Options {
         Options {
             no_early_reject: false,
             min_gas_price: 0,
+            priority_senders: HashSet::new(),
+            reserved_count: 0,
         }
     }
 }
@@ -79,7 +93,29 @@ impl<C: Client> tx_pool::Verifier<PoolTransaction>
             });
         }
         let hash = tx.hash();
+        if let Some((ref scoring, ref worst)) = self.transaction_to_replace {
+            if !self.options.is_prioritized(&tx)
+                && tx.gas_price() > 0
+                && scoring.should_reject_early(worst, &tx)
+            {
+                debug!(
+                    target: "txqueue",
+                    "[{:?}] Rejected tx early, cause it doesn't have any chance to get to the pool: (gas price: {} < {})",
+                    hash,
+                    tx.gas_price(),
+                    worst.signed().gas_unit_price(),
+                );
+                return Err(transaction::TransactionError::TooCheapToReplace {
+                    prev: Some(worst.signed().gas_unit_price()),
+                    new: Some(tx.gas_price()),
+                });
+            }
+        }
         let is_local_txn = tx.is_local();
+        let is_priority_sender = self
+            .options
+            .priority_senders
+            .contains(&tx.signed().sender());
         let is_retracted = tx.is_retracted();
         let verified_txn = match tx {
             PoolTransaction::Unverified(unverified) | PoolTransaction::Retracted(unverified) => {
@@ -107,10 +143,11 @@ impl<C: Client> tx_pool::Verifier<PoolTransaction>
         };
 
         let sender = verified_txn.sender();
-        let priority = match (is_local_txn, is_retracted) {
-            (true, _) => Priority::Local,
-            (false, true) => Priority::Retracted,
-            (false, false) => Priority::Regular,
+        let priority = match (is_local_txn, is_priority_sender, is_retracted) {
+            (true, _, _) => Priority::Local,
+            (false, true, _) => Priority::Allowlisted,
+            (false, false, true) => Priority::Retracted,
+            (false, false, false) => Priority::Regular,
         };
         Ok(VerifiedTransaction {
             transaction: verified_txn,
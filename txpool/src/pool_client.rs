@@ -1,4 +1,4 @@
-use crate::pool::{AccountSeqNumberClient, UnverifiedUserTransaction};
+use crate::pool::{AccountBalanceClient, AccountSeqNumberClient, UnverifiedUserTransaction};
 use anyhow::Result;
 use parking_lot::RwLock;
 use starcoin_state_api::AccountStateReader;
@@ -141,6 +141,22 @@ impl crate::pool::AccountSeqNumberClient for PoolClient {
     }
 }
 
+impl AccountBalanceClient for PoolClient {
+    fn account_balance(&self, address: &AccountAddress) -> u128 {
+        let account_state_reader = AccountStateReader::new(self.nonce_client.statedb.as_ref());
+        match account_state_reader.get_balance(address) {
+            Err(e) => {
+                error!(
+                    "Get account {} balance from statedb error: {:?}, return 0 as balance",
+                    address, e
+                );
+                0
+            }
+            Ok(balance) => balance.unwrap_or_default(),
+        }
+    }
+}
+
 impl crate::pool::Client for PoolClient {
     fn verify_transaction(
         &self,
@@ -1,14 +1,16 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::pool::AccountSeqNumberClient;
+use crate::gas_price_oracle::GasPriceOracle;
+use crate::pool::{AccountSeqNumberClient, ReplacePolicy};
+use crate::TxPoolService;
 use crate::TxStatus;
 use anyhow::Result;
 use crypto::keygen::KeyGen;
 use network_api::messages::{PeerTransactionsMessage, TransactionsMessage};
 use network_api::PeerId;
 use parking_lot::RwLock;
-use starcoin_config::NodeConfig;
+use starcoin_config::{NodeConfig, TxPoolJournalScope};
 use starcoin_executor::{
     create_signed_txn_with_association_account, encode_transfer_script_function,
     DEFAULT_EXPIRATION_TIME, DEFAULT_MAX_GAS_AMOUNT,
@@ -20,11 +22,12 @@ use starcoin_txpool_api::{TxPoolSyncService, TxnStatusFullEvent};
 use std::time::Duration;
 use std::{collections::HashMap, sync::Arc};
 use stest::actix_export::time::delay_for;
-use storage::BlockStore;
+use storage::{BlockStore, Storage};
 use types::{
     account_address::{self, AccountAddress},
     account_config,
-    transaction::{SignedUserTransaction, Transaction, TransactionPayload},
+    block::{Block, BlockBody, BlockHeader},
+    transaction::{SignedUserTransaction, Transaction, TransactionError, TransactionPayload},
     U256,
 };
 
@@ -131,36 +134,36 @@ async fn test_pool_pending() -> Result<()> {
     Ok(())
 }
 
-#[stest::test]
-async fn test_rollback() -> Result<()> {
-    let (pool, storage, config, _, _) = test_helper::start_txpool().await;
-    let start_timestamp = 0;
-    let retracted_txn = {
-        let (_private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
-        let account_address = account_address::from_public_key(&public_key);
-        let txn = starcoin_executor::build_transfer_from_association(
-            account_address,
-            0,
-            10000,
-            start_timestamp + DEFAULT_EXPIRATION_TIME,
-            config.net(),
-        );
-        txn.as_signed_user_txn()?.clone()
-    };
-    let _ = pool.add_txns(vec![retracted_txn.clone()]);
+/// A retracted txn which is added to the pool, an enacted txn, and the blocks packing them.
+struct RollbackFixture {
+    retracted_txn: SignedUserTransaction,
+    enacted_txn: SignedUserTransaction,
+    retracted_block: Block,
+    enacted_block: Block,
+}
 
-    let enacted_txn = {
+/// Prepare the blocks of a rollback on the current main block, and flush the state of the enacted block.
+fn prepare_rollback(
+    pool: &TxPoolService,
+    storage: Arc<Storage>,
+    config: &NodeConfig,
+    start_timestamp: u64,
+) -> Result<RollbackFixture> {
+    let new_txn = |amount: u128| {
         let (_private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
         let account_address = account_address::from_public_key(&public_key);
         let txn = starcoin_executor::build_transfer_from_association(
             account_address,
             0,
-            20000,
+            amount,
             start_timestamp + DEFAULT_EXPIRATION_TIME,
             config.net(),
         );
-        txn.as_signed_user_txn()?.clone()
+        txn.as_signed_user_txn().map(|txn| txn.clone())
     };
+    let retracted_txn = new_txn(10000)?;
+    let enacted_txn = new_txn(20000)?;
+    let _ = pool.add_txns(vec![retracted_txn.clone()]);
 
     let pack_txn_to_block = |txn: SignedUserTransaction| {
         let (_private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
@@ -188,8 +191,8 @@ async fn test_rollback() -> Result<()> {
         Ok::<_, anyhow::Error>(block)
     };
 
-    let retracted_block = pack_txn_to_block(retracted_txn)?;
-    let enacted_block = pack_txn_to_block(enacted_txn)?;
+    let retracted_block = pack_txn_to_block(retracted_txn.clone())?;
+    let enacted_block = pack_txn_to_block(enacted_txn.clone())?;
 
     // flush the state, to make txpool happy
     {
@@ -214,13 +217,47 @@ async fn test_rollback() -> Result<()> {
         assert_eq!(root, enacted_block.header().state_root());
         chain_state.flush()?;
     }
-    pool.chain_new_block(vec![enacted_block], vec![retracted_block])
+    Ok(RollbackFixture {
+        retracted_txn,
+        enacted_txn,
+        retracted_block,
+        enacted_block,
+    })
+}
+
+#[stest::test]
+async fn test_rollback() -> Result<()> {
+    let (pool, storage, config, _, _) = test_helper::start_txpool().await;
+    let start_timestamp = 0;
+    let fixture = prepare_rollback(&pool, storage, &config, start_timestamp)?;
+    pool.chain_new_block(vec![fixture.enacted_block], vec![fixture.retracted_block])
         .unwrap();
     let txns = pool.get_pending_txns(Some(100), Some(start_timestamp + 60 * 10));
     assert_eq!(txns.len(), 0);
     Ok(())
 }
 
+#[stest::test]
+async fn test_txn_status_on_new_block() -> Result<()> {
+    let (pool, storage, config, _, _) = test_helper::start_txpool().await;
+    let fixture = prepare_rollback(&pool, storage, &config, 0)?;
+    let enacted_block_id = fixture.enacted_block.id();
+    let mut status_receiver = pool.subscribe_txn_status();
+    pool.chain_new_block(vec![fixture.enacted_block], vec![fixture.retracted_block])?;
+
+    let mut events = vec![];
+    while let Ok(Some(event)) = status_receiver.try_next() {
+        events.extend(event.iter().cloned());
+    }
+    assert!(events
+        .iter()
+        .any(|e| e.txn_hash == fixture.retracted_txn.id() && e.status == TxStatus::Retracted));
+    assert!(events.iter().any(|e| e.txn_hash == fixture.enacted_txn.id()
+        && e.status == TxStatus::Included
+        && e.block_hash == Some(enacted_block_id)));
+    Ok(())
+}
+
 #[stest::test(timeout = 480)]
 async fn test_txpool_actor_service() {
     let (_txpool_service, _storage, config, tx_pool_actor, _registry) =
@@ -246,15 +283,227 @@ async fn test_txpool_actor_service() {
 }
 
 fn generate_txn(config: Arc<NodeConfig>, seq: u64) -> SignedUserTransaction {
+    generate_txn_with_gas_price(config, seq, 1)
+}
+
+fn generate_txn_with_gas_price(
+    config: Arc<NodeConfig>,
+    seq: u64,
+    gas_price: u64,
+) -> SignedUserTransaction {
     let (_private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
     let account_address = account_address::from_public_key(&public_key);
     let txn = create_signed_txn_with_association_account(
         TransactionPayload::ScriptFunction(encode_transfer_script_function(account_address, 10000)),
         seq,
         DEFAULT_MAX_GAS_AMOUNT,
-        1,
+        gas_price,
         2,
         config.net(),
     );
     txn
 }
+
+#[stest::test]
+async fn test_txpool_journal_replay() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    config.tx_pool.set_journal_scope(TxPoolJournalScope::Local);
+    let (txpool_service, storage, config, _, _) =
+        test_helper::start_txpool_with_config(config).await;
+    let local_txn = generate_txn(config.clone(), 0);
+    let remote_txn = generate_txn(config.clone(), 1);
+    txpool_service
+        .add_local_txns(vec![local_txn.clone()])
+        .pop()
+        .unwrap()?;
+    txpool_service
+        .add_txns(vec![remote_txn.clone()])
+        .pop()
+        .unwrap()?;
+
+    // restart the pool with the same data dir, only the local txn is replayed.
+    let startup_info = storage.get_startup_info()?.unwrap();
+    let header = storage
+        .get_block_header_by_hash(startup_info.main)?
+        .unwrap();
+    let restarted_pool = TxPoolService::new(config.clone(), storage.clone(), header);
+    assert!(restarted_pool.find_txn(&local_txn.id()).is_some());
+    assert!(restarted_pool.find_txn(&remote_txn.id()).is_none());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_txn_replace() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    config.tx_pool.set_replace_gas_price_bump_percent(10);
+    let (txpool_service, _storage, config, _, _) =
+        test_helper::start_txpool_with_config(config).await;
+    let mut txn_status_receiver = txpool_service.subscribe_txns();
+    let old_txn = generate_txn_with_gas_price(config.clone(), 0, 10);
+    txpool_service
+        .add_txns(vec![old_txn.clone()])
+        .pop()
+        .unwrap()?;
+
+    // the gas price is not bumped enough.
+    let cheap_txn = generate_txn_with_gas_price(config.clone(), 0, 10);
+    let result = txpool_service.add_txns(vec![cheap_txn]).pop().unwrap();
+    assert!(matches!(
+        result,
+        Err(TransactionError::TooCheapToReplace { .. })
+    ));
+
+    let new_txn = generate_txn_with_gas_price(config.clone(), 0, 11);
+    txpool_service
+        .add_txns(vec![new_txn.clone()])
+        .pop()
+        .unwrap()?;
+    assert!(txpool_service.find_txn(&old_txn.id()).is_none());
+    assert!(txpool_service.find_txn(&new_txn.id()).is_some());
+
+    let mut statuses = vec![];
+    while let Ok(Some(event)) = txn_status_receiver.try_next() {
+        statuses.extend(event.iter().cloned());
+    }
+    assert!(statuses.contains(&(old_txn.id(), TxStatus::Replaced)));
+    assert!(statuses.contains(&(new_txn.id(), TxStatus::Added)));
+
+    // a local txn can not be replaced by a remote txn.
+    let local_txn = generate_txn_with_gas_price(config.clone(), 1, 10);
+    txpool_service
+        .add_local_txns(vec![local_txn.clone()])
+        .pop()
+        .unwrap()?;
+    let remote_txn = generate_txn_with_gas_price(config, 1, 100);
+    assert!(txpool_service
+        .add_txns(vec![remote_txn])
+        .pop()
+        .unwrap()
+        .is_err());
+    assert!(txpool_service.find_txn(&local_txn.id()).is_some());
+    Ok(())
+}
+
+#[test]
+fn test_replace_policy_bump_gas_price() {
+    // no bump is required by default, a txn with the same gas price can replace the old one.
+    let policy = ReplacePolicy::default();
+    assert_eq!(policy.bump_gas_price(10), 10);
+    let policy = ReplacePolicy {
+        min_gas_price_bump_percent: 10,
+        ..Default::default()
+    };
+    assert_eq!(policy.bump_gas_price(1), 2);
+    assert_eq!(policy.bump_gas_price(10), 11);
+    assert_eq!(policy.bump_gas_price(100), 110);
+    assert_eq!(policy.bump_gas_price(u64::MAX), u64::MAX);
+}
+
+#[test]
+fn test_gas_price_oracle() {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let new_block = |gas_prices: Vec<u64>| {
+        let txns = gas_prices
+            .into_iter()
+            .map(|gas_price| generate_txn_with_gas_price(config.clone(), 0, gas_price))
+            .collect();
+        Block::new(BlockHeader::random(), BlockBody::new(txns, None))
+    };
+    let oracle = GasPriceOracle::new(2, 1);
+    let stats = oracle.stats(vec![]);
+    assert_eq!((stats.slow, stats.standard, stats.fast), (1, 1, 1));
+    assert_eq!(stats.blocks, 0);
+
+    let block = new_block((1..=10).collect());
+    oracle.on_chain_new_blocks(&[block.clone()], &[]);
+    let stats = oracle.stats(vec![]);
+    assert_eq!((stats.slow, stats.standard, stats.fast), (3, 5, 9));
+    assert_eq!(stats.block_txns.count, 10);
+
+    // the pool is congested, more pending txns than a block can include.
+    let stats = oracle.stats(vec![50; 20]);
+    assert_eq!((stats.slow, stats.standard, stats.fast), (3, 50, 50));
+    assert_eq!(stats.pending_txns.count, 20);
+
+    oracle.on_chain_new_blocks(&[], &[block]);
+    let stats = oracle.stats(vec![]);
+    assert_eq!((stats.slow, stats.standard, stats.fast), (1, 1, 1));
+
+    oracle.on_chain_new_blocks(
+        &[new_block(vec![1]), new_block(vec![2]), new_block(vec![3])],
+        &[],
+    );
+    let stats = oracle.stats(vec![]);
+    assert_eq!(stats.blocks, 2);
+    assert_eq!(stats.block_txns.min, 2);
+}
+
+#[stest::test]
+async fn test_gas_price_stats_respect_min_gas_price() -> Result<()> {
+    let (txpool_service, _storage, config, _, _) = test_helper::start_txpool().await;
+    let stats = txpool_service.gas_price_stats();
+    assert_eq!(stats.min_gas_price, config.tx_pool.min_gas_price());
+    assert!(stats.slow >= stats.min_gas_price);
+    assert!(stats.standard >= stats.slow);
+    assert!(stats.fast >= stats.standard);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_priority_sender_lane() -> Result<()> {
+    let (_txpool_service, storage, config, _, _) = test_helper::start_txpool().await;
+    let startup_info = storage.get_startup_info()?.unwrap();
+    let header = storage
+        .get_block_header_by_hash(startup_info.main)?
+        .unwrap();
+    let new_pool = |priority_senders: Vec<AccountAddress>| {
+        let mut config = (*config).clone();
+        config.tx_pool.set_max_count(10);
+        config.tx_pool.set_max_per_sender(2);
+        config.tx_pool.set_local_reserved_percent(50);
+        config.tx_pool.set_priority_senders(priority_senders);
+        TxPoolService::new(Arc::new(config), storage.clone(), header.clone())
+    };
+    let txns: Vec<_> = (0..6)
+        .map(|seq| generate_txn(config.clone(), seq))
+        .collect();
+
+    // regular txns are limited by the per sender limit and the reserved capacity.
+    let pool = new_pool(vec![]);
+    let results = pool.add_txns(txns.clone());
+    assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 2);
+
+    // the priority sender's txns are exempt from both.
+    let pool = new_pool(vec![account_config::association_address()]);
+    for result in pool.add_txns(txns.clone()) {
+        result?;
+    }
+    let pending = pool.get_pending_txns(None, None);
+    assert_eq!(pending.len(), txns.len());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_local_reserved_capacity() -> Result<()> {
+    let (_txpool_service, storage, config, _, _) = test_helper::start_txpool().await;
+    let startup_info = storage.get_startup_info()?.unwrap();
+    let header = storage
+        .get_block_header_by_hash(startup_info.main)?
+        .unwrap();
+    let mut pool_config = (*config).clone();
+    pool_config.tx_pool.set_max_count(10);
+    pool_config.tx_pool.set_local_reserved_percent(50);
+    let pool = TxPoolService::new(Arc::new(pool_config), storage, header);
+
+    let txns: Vec<_> = (0..6)
+        .map(|seq| generate_txn(config.clone(), seq))
+        .collect();
+    for result in pool.add_txns(txns[..5].to_vec()) {
+        result?;
+    }
+    // the rest capacity is reserved for local txns.
+    let result = pool.add_txns(vec![txns[5].clone()]).pop().unwrap();
+    assert!(matches!(result, Err(TransactionError::LimitReached)));
+    pool.add_local_txns(vec![txns[5].clone()]).pop().unwrap()?;
+    Ok(())
+}
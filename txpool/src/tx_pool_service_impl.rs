@@ -3,7 +3,9 @@
 
 use crate::{
     counters::TXPOOL_SERVICE_HISTOGRAM,
+    gas_price_oracle::GasPriceOracle,
     pool,
+    pool::journal::{load_journal, TransactionJournal},
     pool::{
         PendingOrdering, PendingSettings, PoolTransaction, PrioritizationStrategy, Status,
         TxStatus, UnverifiedUserTransaction, VerifiedTransaction,
@@ -11,14 +13,17 @@ use crate::{
     pool_client::{NonceCache, PoolClient},
 };
 
-use crate::pool::{Client, TransactionQueue};
+use crate::pool::{AccountSeqNumberClient, Client, TransactionQueue};
 use anyhow::Result;
 use crypto::hash::HashValue;
 use futures_channel::mpsc;
 use parking_lot::RwLock;
 use starcoin_config::NodeConfig;
 use starcoin_statedb::ChainStateDB;
-use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService};
+use starcoin_txpool_api::{
+    GasPriceStats, SenderTxnsInspect, TxPoolStatus, TxPoolSyncService, TxnInspect, TxnReadiness,
+    TxnStatusEvent,
+};
 use std::sync::Arc;
 use storage::Store;
 use types::{
@@ -42,6 +47,9 @@ impl TxPoolService {
         let verifier_options = pool::VerifierOptions {
             no_early_reject: false,
             min_gas_price: node_config.tx_pool.min_gas_price(),
+            priority_senders: pool_config.priority_senders().into_iter().collect(),
+            reserved_count: (pool_config.max_count() * pool_config.local_reserved_percent() / 100)
+                as usize,
         };
         let queue = TxnQueue::new(
             tx_pool::Options {
@@ -51,15 +59,31 @@ impl TxPoolService {
             },
             verifier_options,
             PrioritizationStrategy::GasPriceOnly,
+            pool::ReplacePolicy {
+                min_gas_price_bump_percent: pool_config.replace_gas_price_bump_percent(),
+                replace_local_by_remote: pool_config.replace_local_by_remote(),
+                max_replacements_per_sender: pool_config.max_replacements_per_sender(),
+            },
         );
         let queue = Arc::new(queue);
+        let gas_price_oracle = GasPriceOracle::new(
+            pool_config.gas_price_sample_blocks() as usize,
+            pool_config.min_gas_price(),
+        );
+        if let Err(e) = gas_price_oracle.init(storage.as_ref(), chain_header.id()) {
+            error!("txpool: fail to init gas price oracle, err: {:?}", e);
+        }
         let inner = Inner {
             node_config,
             queue,
+            gas_price_oracle: Arc::new(gas_price_oracle),
             storage,
             chain_header: Arc::new(RwLock::new(chain_header)),
             sequence_number_cache: NonceCache::new(128),
         };
+        if let Err(e) = inner.init_journal() {
+            error!("txpool: fail to init journal, err: {:?}", e);
+        }
 
         Self { inner }
     }
@@ -98,6 +122,16 @@ impl TxPoolSyncService for TxPoolService {
         self.inner.import_txns(txns)
     }
 
+    fn add_local_txns(
+        &self,
+        txns: Vec<SignedUserTransaction>,
+    ) -> Vec<Result<(), transaction::TransactionError>> {
+        let _timer = TXPOOL_SERVICE_HISTOGRAM
+            .with_label_values(&["add_local_txns"])
+            .start_timer();
+        self.inner.import_local_txns(txns)
+    }
+
     fn remove_txn(&self, txn_hash: HashValue, is_invalid: bool) -> Option<SignedUserTransaction> {
         let _timer = TXPOOL_SERVICE_HISTOGRAM
             .with_label_values(&["remove_txn"])
@@ -148,6 +182,13 @@ impl TxPoolSyncService for TxPoolService {
         self.inner.subscribe_pending_txns()
     }
 
+    fn subscribe_txn_status(&self) -> mpsc::UnboundedReceiver<Arc<[TxnStatusEvent]>> {
+        let _timer = TXPOOL_SERVICE_HISTOGRAM
+            .with_label_values(&["subscribe_txn_status"])
+            .start_timer();
+        self.inner.subscribe_txn_status()
+    }
+
     /// rollback
     fn chain_new_block(&self, enacted: Vec<Block>, retracted: Vec<Block>) -> Result<()> {
         let _timer = TXPOOL_SERVICE_HISTOGRAM
@@ -161,6 +202,13 @@ impl TxPoolSyncService for TxPoolService {
         self.inner.queue.status().into()
     }
 
+    fn gas_price_stats(&self) -> GasPriceStats {
+        let _timer = TXPOOL_SERVICE_HISTOGRAM
+            .with_label_values(&["gas_price_stats"])
+            .start_timer();
+        self.inner.gas_price_stats()
+    }
+
     fn find_txn(&self, hash: &HashValue) -> Option<SignedUserTransaction> {
         self.inner
             .queue
@@ -179,6 +227,34 @@ impl TxPoolSyncService for TxPoolService {
             .map(|t| t.signed().clone())
             .collect()
     }
+
+    fn inspect(&self, sender: Option<AccountAddress>) -> Vec<SenderTxnsInspect> {
+        let _timer = TXPOOL_SERVICE_HISTOGRAM
+            .with_label_values(&["inspect"])
+            .start_timer();
+        self.inner.inspect(sender)
+    }
+
+    fn clear_sender(&self, sender: AccountAddress) -> Vec<HashValue> {
+        let _timer = TXPOOL_SERVICE_HISTOGRAM
+            .with_label_values(&["clear_sender"])
+            .start_timer();
+        self.inner.clear_sender(sender)
+    }
+
+    fn all_txns(&self, offset: usize, max_len: usize) -> Vec<SignedUserTransaction> {
+        let _timer = TXPOOL_SERVICE_HISTOGRAM
+            .with_label_values(&["all_txns"])
+            .start_timer();
+        self.inner
+            .queue
+            .all_transactions()
+            .into_iter()
+            .skip(offset)
+            .take(max_len)
+            .map(|t| t.signed().clone())
+            .collect()
+    }
 }
 
 pub(crate) type TxnQueue = TransactionQueue;
@@ -186,6 +262,7 @@ pub(crate) type TxnQueue = TransactionQueue;
 pub(crate) struct Inner {
     pub(crate) node_config: Arc<NodeConfig>,
     queue: Arc<TxnQueue>,
+    gas_price_oracle: Arc<GasPriceOracle>,
     chain_header: Arc<RwLock<BlockHeader>>,
     storage: Arc<dyn Store>,
     sequence_number_cache: NonceCache,
@@ -241,6 +318,60 @@ impl Inner {
             .map(|t| PoolTransaction::Unverified(UnverifiedUserTransaction::from(t)));
         self.queue.import(self.get_pool_client(), txns)
     }
+    pub(crate) fn import_local_txns(
+        &self,
+        txns: Vec<transaction::SignedUserTransaction>,
+    ) -> Vec<Result<(), transaction::TransactionError>> {
+        let txns = txns
+            .into_iter()
+            .map(|t| PoolTransaction::Local(transaction::PendingTransaction::from(t)));
+        self.queue.import(self.get_pool_client(), txns)
+    }
+
+    /// Replay the journaled transactions into the pool, then start journal the new imported transactions.
+    pub(crate) fn init_journal(&self) -> Result<()> {
+        let pool_config = &self.node_config.tx_pool;
+        let path = match pool_config.journal_path() {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        let entries = load_journal(path.as_path())?;
+        let total = entries.len();
+        let txns = entries.into_iter().map(|entry| {
+            if entry.local {
+                PoolTransaction::Local(transaction::PendingTransaction::from(entry.txn))
+            } else {
+                PoolTransaction::Unverified(UnverifiedUserTransaction::from(entry.txn))
+            }
+        });
+        let imported = self
+            .queue
+            .import(self.get_pool_client(), txns)
+            .into_iter()
+            .filter(|result| result.is_ok())
+            .count();
+        info!(
+            "txpool: replay {}/{} transactions from journal {:?}",
+            imported, total, path
+        );
+        self.queue
+            .set_journal(TransactionJournal::open(path, pool_config.journal_scope())?);
+        // drop the stale transactions from the journal.
+        self.queue.rotate_journal()?;
+        Ok(())
+    }
+
+    pub(crate) fn rotate_journal(&self) {
+        match self.queue.rotate_journal() {
+            Ok(count) => debug!("txpool: rotate journal with {} transactions", count),
+            Err(e) => error!("txpool: fail to rotate journal, err: {:?}", e),
+        }
+    }
+
+    pub(crate) fn gas_price_stats(&self) -> GasPriceStats {
+        self.gas_price_oracle.stats(self.queue.gas_prices())
+    }
+
     pub(crate) fn remove_txn(
         &self,
         txn_hash: HashValue,
@@ -251,6 +382,96 @@ impl Inner {
             .pop()
             .expect("remove should return one result per hash")
     }
+    /// Classify every transaction of the sender, by the sender's sequence number on chain and the transaction's expiration.
+    pub(crate) fn inspect(&self, sender: Option<AccountAddress>) -> Vec<SenderTxnsInspect> {
+        let senders = match sender {
+            Some(sender) => vec![sender],
+            None => self.queue.senders(),
+        };
+        let client = self.get_pool_client();
+        let now = self.node_config.net().time_service().now_secs();
+        senders
+            .into_iter()
+            .filter_map(|sender| {
+                let txns = self.queue.txns_of_sender(&sender, usize::max_value());
+                if txns.is_empty() {
+                    return None;
+                }
+                let sequence_number = client.account_seq_number(&sender);
+                let mut next_sequence_number = sequence_number;
+                let mut ready = vec![];
+                let mut future = vec![];
+                for txn in txns {
+                    let signed = txn.signed();
+                    let seq = signed.sequence_number();
+                    let (readiness, reason) = if seq < next_sequence_number {
+                        (
+                            TxnReadiness::Stale,
+                            Some(format!(
+                                "sequence number {} is lower than expected {}",
+                                seq, next_sequence_number
+                            )),
+                        )
+                    } else if signed.expiration_timestamp_secs() <= now {
+                        (
+                            TxnReadiness::Stale,
+                            Some(format!(
+                                "expired at {}, now is {}",
+                                signed.expiration_timestamp_secs(),
+                                now
+                            )),
+                        )
+                    } else if seq == next_sequence_number {
+                        next_sequence_number = next_sequence_number.saturating_add(1);
+                        (TxnReadiness::Ready, None)
+                    } else {
+                        (
+                            TxnReadiness::Future,
+                            Some(format!(
+                                "sequence number gap, expect {}, got {}",
+                                next_sequence_number, seq
+                            )),
+                        )
+                    };
+                    let inspect = TxnInspect {
+                        txn_hash: signed.id(),
+                        sequence_number: seq,
+                        gas_unit_price: signed.gas_unit_price(),
+                        expiration_timestamp_secs: signed.expiration_timestamp_secs(),
+                        readiness,
+                        reason,
+                    };
+                    if readiness == TxnReadiness::Ready {
+                        ready.push(inspect);
+                    } else {
+                        future.push(inspect);
+                    }
+                }
+                Some(SenderTxnsInspect {
+                    sender,
+                    sequence_number,
+                    ready,
+                    future,
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) fn clear_sender(&self, sender: AccountAddress) -> Vec<HashValue> {
+        let hashes = self
+            .queue
+            .txns_of_sender(&sender, usize::max_value())
+            .into_iter()
+            .map(|txn| txn.signed().id())
+            .collect::<Vec<_>>();
+        self.queue
+            .remove(hashes.iter(), false)
+            .into_iter()
+            .flatten()
+            .map(|txn| txn.signed().id())
+            .collect()
+    }
+
     pub(crate) fn get_pending(
         &self,
         max_len: u64,
@@ -284,6 +505,11 @@ impl Inner {
         self.queue.add_pending_listener(tx);
         rx
     }
+    pub(crate) fn subscribe_txn_status(&self) -> mpsc::UnboundedReceiver<Arc<[TxnStatusEvent]>> {
+        let (tx, rx) = mpsc::unbounded();
+        self.queue.add_status_listener(tx);
+        rx
+    }
 
     pub(crate) fn chain_new_block(&self, enacted: Vec<Block>, retracted: Vec<Block>) {
         debug!(
@@ -302,6 +528,20 @@ impl Inner {
         if let Some(block) = enacted.last() {
             self.notify_new_chain_header(block.header().clone());
         }
+        self.gas_price_oracle
+            .on_chain_new_blocks(enacted.as_slice(), retracted.as_slice());
+
+        // notify the txns retracted and included by the blocks, before the pool re-imports or culls them.
+        let status_events = retracted
+            .iter()
+            .flat_map(|block| block_txn_status_events(block, TxStatus::Retracted))
+            .chain(
+                enacted
+                    .iter()
+                    .flat_map(|block| block_txn_status_events(block, TxStatus::Included)),
+            )
+            .collect();
+        self.queue.notify_status_events(status_events);
 
         // remove outdated txns.
         self.cull();
@@ -330,3 +570,18 @@ impl Inner {
         )
     }
 }
+
+fn block_txn_status_events(
+    block: &Block,
+    status: TxStatus,
+) -> impl Iterator<Item = TxnStatusEvent> + '_ {
+    let block_hash = block.id();
+    let block_number = block.header().number();
+    block.transactions().iter().map(move |txn| TxnStatusEvent {
+        txn_hash: txn.id(),
+        sender: txn.sender(),
+        status,
+        block_hash: Some(block_hash),
+        block_number: Some(block_number),
+    })
+}
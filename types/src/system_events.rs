@@ -1,21 +1,44 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::block::{Block, BlockHeaderExtra, ExecutedBlock};
+use crate::block::{Block, BlockHeader, BlockHeaderExtra, BlockIdAndNumber, ExecutedBlock};
 use crate::sync_status::SyncStatus;
+use crate::write_set::WriteSet;
 use crate::U256;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use starcoin_crypto::HashValue;
 use starcoin_vm_types::genesis_config::ConsensusStrategy;
 use std::sync::Arc;
+/// The second field is the write sets of every block enacted by the new head, in ascending order
+/// of block number, the last one is the head block. The write sets are not persisted, they are
+/// `None` if they can not be collected when the head is selected.
 #[derive(Clone, Debug)]
-pub struct NewHeadBlock(pub Arc<ExecutedBlock>);
+pub struct NewHeadBlock(pub Arc<ExecutedBlock>, pub Option<Arc<[BlockWriteSets]>>);
+
+/// The write sets of the block's txns (include the block metadata txn), in the order of the
+/// block's txn infos.
+#[derive(Clone, Debug)]
+pub struct BlockWriteSets {
+    pub header: BlockHeader,
+    pub write_sets: Arc<[WriteSet]>,
+}
 
 /// may be uncle block
 #[derive(Clone, Debug)]
 pub struct NewBranch(pub Arc<ExecutedBlock>);
 
+/// Fire this event when the main chain switches to another branch,
+/// before the `NewHeadBlock` of the new head.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChainReorg {
+    pub common_ancestor: BlockIdAndNumber,
+    /// blocks removed from the main chain, in ascending order of block number.
+    pub retracted: Vec<BlockIdAndNumber>,
+    /// blocks added to the main chain, in ascending order of block number.
+    pub enacted: Vec<BlockIdAndNumber>,
+}
+
 #[derive(Clone, Debug)]
 pub struct MinedBlock(pub Arc<Block>);
 
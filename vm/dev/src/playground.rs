@@ -1,7 +1,8 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use crate::state_overlay::OverlayStateView;
+use anyhow::{format_err, Result};
 use starcoin_abi_resolver::ABIResolver;
 use starcoin_abi_types::TypeInstantiation;
 use starcoin_crypto::HashValue;
@@ -9,13 +10,17 @@ use starcoin_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue, MoveValu
 use starcoin_state_api::StateNodeStore;
 use starcoin_statedb::ChainStateDB;
 use starcoin_vm_runtime::starcoin_vm::StarcoinVM;
+use starcoin_vm_runtime::tracer::{CallTrace, ExecutionTracer};
+use starcoin_vm_types::block_metadata::BlockMetadata;
 use starcoin_vm_types::identifier::{IdentStr, Identifier};
 use starcoin_vm_types::language_storage::{ModuleId, StructTag, TypeTag};
 use starcoin_vm_types::state_view::StateView;
-use starcoin_vm_types::transaction::{DryRunTransaction, TransactionOutput};
+use starcoin_vm_types::transaction::{
+    DryRunTransaction, RawUserTransaction, Transaction, TransactionOutput, TransactionStatus,
+};
 use starcoin_vm_types::transaction_argument::convert_txn_args;
 use starcoin_vm_types::transaction_argument::TransactionArgument;
-use starcoin_vm_types::vm_status::VMStatus;
+use starcoin_vm_types::vm_status::{KeptVMStatus, StatusCode, VMStatus};
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -83,6 +88,161 @@ pub fn dry_run(
     vm.dry_run_transaction(state_view, txn)
 }
 
+/// Dry run the txn with tracing, return the calls into Move besides the dry run result.
+pub fn dry_run_with_trace(
+    state_view: &dyn StateView,
+    txn: DryRunTransaction,
+) -> Result<(VMStatus, TransactionOutput, Vec<CallTrace>)> {
+    let tracer = ExecutionTracer::new();
+    let mut vm = StarcoinVM::new();
+    vm.set_tracer(Some(tracer.clone()));
+    let (vm_status, output) = vm.dry_run_transaction(state_view, txn)?;
+    Ok((vm_status, output, tracer.take()))
+}
+
+/// Execute the block txns, and trace the last one of them.
+/// The txns before the last one are executed to get the state which the last one is executed on.
+pub fn trace_block_transaction(
+    state_view: &dyn StateView,
+    mut txns: Vec<Transaction>,
+) -> Result<(VMStatus, TransactionOutput, Vec<CallTrace>)> {
+    let txn = txns
+        .pop()
+        .ok_or_else(|| format_err!("No transaction to trace"))?;
+    let mut vm = StarcoinVM::new();
+    let mut state = OverlayStateView::new(state_view);
+    for (_, output) in vm.execute_block_transactions(state_view, txns, None)? {
+        state.apply_write_set(output.write_set());
+    }
+    let tracer = ExecutionTracer::new();
+    vm.set_tracer(Some(tracer.clone()));
+    let (vm_status, output) = vm
+        .execute_block_transactions(&state, vec![txn], None)?
+        .pop()
+        .ok_or_else(|| format_err!("The traced transaction is not executed"))?;
+    Ok((vm_status, output, tracer.take()))
+}
+
+pub fn simulate_bundle(
+    state_view: &dyn StateView,
+    block_metadata: Option<BlockMetadata>,
+    txns: Vec<DryRunTransaction>,
+) -> Result<Vec<(VMStatus, TransactionOutput)>> {
+    let mut vm = StarcoinVM::new();
+    vm.simulate_transactions(state_view, block_metadata, txns)
+}
+
+/// Safety margin of the suggested max gas amount, in percent of the estimated one.
+pub const GAS_ESTIMATE_MARGIN_PERCENT: u64 = 10;
+
+/// Result of `estimate_gas`.
+#[derive(Clone, Debug)]
+pub struct GasEstimate {
+    /// Status of the txn executed with enough max gas amount.
+    pub vm_status: VMStatus,
+    /// Gas used by the txn executed with enough max gas amount.
+    pub gas_used: u64,
+    /// The minimal max gas amount to execute the txn successfully, `None` if the txn fails.
+    pub min_max_gas_amount: Option<u64>,
+    /// The `min_max_gas_amount` with a safety margin, not more than the max gas amount of the chain.
+    pub suggested_max_gas_amount: Option<u64>,
+}
+
+/// Dry run the txn to get the gas used, and binary search the minimal max gas amount
+/// between the gas used and the max gas amount of the txn.
+/// If the max gas amount of the txn is not enough, the search goes up to
+/// the max gas amount of the chain.
+pub fn estimate_gas(state_view: &dyn StateView, txn: DryRunTransaction) -> Result<GasEstimate> {
+    let mut vm = StarcoinVM::new();
+    vm.load_configs(state_view)?;
+    let chain_max_gas_amount = vm
+        .get_gas_schedule()
+        .map_err(|e| format_err!("Load gas schedule fail: {:?}", e))?
+        .gas_constants
+        .maximum_number_of_gas_units
+        .get();
+    let mut dry_run_with = |max_gas_amount: u64| -> Result<(VMStatus, TransactionOutput)> {
+        let raw_txn = &txn.raw_txn;
+        vm.dry_run_transaction(
+            state_view,
+            DryRunTransaction {
+                raw_txn: RawUserTransaction::new(
+                    raw_txn.sender(),
+                    raw_txn.sequence_number(),
+                    raw_txn.payload().clone(),
+                    max_gas_amount,
+                    raw_txn.gas_unit_price(),
+                    raw_txn.expiration_timestamp_secs(),
+                    raw_txn.chain_id(),
+                    raw_txn.gas_token_code(),
+                ),
+                public_key: txn.public_key.clone(),
+            },
+        )
+    };
+    let need_more_gas = |output: &TransactionOutput| {
+        matches!(
+            output.status(),
+            TransactionStatus::Keep(KeptVMStatus::OutOfGas)
+                | TransactionStatus::Discard(
+                    StatusCode::MAX_GAS_UNITS_BELOW_MIN_TRANSACTION_GAS_UNITS
+                )
+        )
+    };
+
+    // the minimal max gas amount is in range [low, high].
+    let (mut low, mut high) = (0, txn.raw_txn.max_gas_amount().min(chain_max_gas_amount));
+    let (mut vm_status, mut output) = dry_run_with(high)?;
+    if need_more_gas(&output) && high < chain_max_gas_amount {
+        low = high + 1;
+        high = chain_max_gas_amount;
+        let (status, out) = dry_run_with(high)?;
+        vm_status = status;
+        output = out;
+    }
+    let gas_used = output.gas_used();
+    if !matches!(vm_status, VMStatus::Executed) {
+        return Ok(GasEstimate {
+            vm_status,
+            gas_used,
+            min_max_gas_amount: None,
+            suggested_max_gas_amount: None,
+        });
+    }
+    let mut executed = |max_gas_amount: u64| -> Result<bool> {
+        Ok(matches!(
+            dry_run_with(max_gas_amount)?.0,
+            VMStatus::Executed
+        ))
+    };
+    // the gas used is enough in most cases.
+    low = low.max(gas_used);
+    if low < high {
+        if executed(low)? {
+            high = low;
+        } else {
+            low += 1;
+        }
+    }
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if executed(mid)? {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    Ok(GasEstimate {
+        vm_status,
+        gas_used,
+        min_max_gas_amount: Some(high),
+        suggested_max_gas_amount: Some(
+            high.saturating_add(high.saturating_mul(GAS_ESTIMATE_MARGIN_PERCENT) / 100)
+                .min(chain_max_gas_amount),
+        ),
+    })
+}
+
 pub fn call_contract(
     state_view: &dyn StateView,
     module_id: ModuleId,
@@ -0,0 +1,111 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_config::BalanceResource;
+use starcoin_vm_types::file_format::CompiledModule;
+use starcoin_vm_types::language_storage::StructTag;
+use starcoin_vm_types::state_view::StateView;
+use starcoin_vm_types::write_set::{WriteOp, WriteSet};
+use std::collections::HashMap;
+
+/// A `StateView` which overrides some states of the base state view,
+/// used to simulate txns on a modified state without touching the real one.
+pub struct OverlayStateView<'a> {
+    base: &'a dyn StateView,
+    /// `None` means the state is deleted.
+    overrides: HashMap<AccessPath, Option<Vec<u8>>>,
+}
+
+impl<'a> OverlayStateView<'a> {
+    pub fn new(base: &'a dyn StateView) -> Self {
+        Self {
+            base,
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Override the state at the access path, delete it if the `value` is `None`.
+    pub fn set(&mut self, access_path: AccessPath, value: Option<Vec<u8>>) {
+        self.overrides.insert(access_path, value);
+    }
+
+    /// Override the bcs encoded resource of the account, delete it if the `value` is `None`.
+    pub fn set_resource(
+        &mut self,
+        address: AccountAddress,
+        struct_tag: StructTag,
+        value: Option<Vec<u8>>,
+    ) {
+        self.set(AccessPath::resource_access_path(address, struct_tag), value)
+    }
+
+    /// Publish or replace the module, the module id is read from the bytecode.
+    pub fn set_module(&mut self, code: Vec<u8>) -> Result<()> {
+        let module = CompiledModule::deserialize(code.as_slice())?;
+        self.set(AccessPath::from(&module.self_id()), Some(code));
+        Ok(())
+    }
+
+    /// Override the balance of the token, which is a struct tag such as `0x1::STC::STC`.
+    pub fn set_balance(
+        &mut self,
+        address: AccountAddress,
+        token: StructTag,
+        amount: u128,
+    ) -> Result<()> {
+        let value = bcs_ext::to_bytes(&BalanceResource::new(amount))?;
+        self.set_resource(
+            address,
+            BalanceResource::struct_tag_for_token(token),
+            Some(value),
+        );
+        Ok(())
+    }
+
+    /// Apply the write set of a txn output, so following txns can see the changes.
+    pub fn apply_write_set(&mut self, write_set: &WriteSet) {
+        for (access_path, write_op) in write_set.iter() {
+            let value = match write_op {
+                WriteOp::Value(value) => Some(value.clone()),
+                WriteOp::Deletion => None,
+            };
+            self.set(access_path.clone(), value);
+        }
+    }
+}
+
+impl<'a> StateView for OverlayStateView<'a> {
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        match self.overrides.get(access_path) {
+            Some(value) => Ok(value.clone()),
+            None => self.base.get(access_path),
+        }
+    }
+
+    /// The states which are not overridden are read from the base state view in one batch.
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        let missed_access_paths: Vec<AccessPath> = access_paths
+            .iter()
+            .filter(|access_path| !self.overrides.contains_key(access_path))
+            .cloned()
+            .collect();
+        let mut missed_values = self
+            .base
+            .multi_get(missed_access_paths.as_slice())?
+            .into_iter();
+        Ok(access_paths
+            .iter()
+            .map(|access_path| match self.overrides.get(access_path) {
+                Some(value) => value.clone(),
+                None => missed_values.next().unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.base.is_genesis()
+    }
+}
@@ -0,0 +1,28 @@
+// Test for secp256k1 signature recovery and ripemd160
+
+script {
+use 0x1::Hash;
+use 0x1::Option;
+use 0x1::Secp256k1;
+use 0x1::Vector;
+
+fun main() {
+    // the ecrecover precompile test vector of go-ethereum
+    let hash = x"38d18acb67d25c8bb9942764b62f18e17054f66a817bd4295423adf9ed98873e";
+    let signature = x"38d18acb67d25c8bb9942764b62f18e17054f66a817bd4295423adf9ed98873e789d1dd423d25f0772d2748d60f7e4b81bb14d086eba8e8e8efb6dcff8a4ae021b";
+    let address = Secp256k1::recover_eth_address(copy hash, copy signature);
+    assert(Option::is_some(&address), 9001);
+    assert(Option::destroy_some(address) == x"ceaccac640adf55b2028469bd36ba501f28b699d", 9002);
+
+    let public_key = Option::destroy_some(Secp256k1::recover(copy hash, copy signature));
+    // drop the recovery id
+    let _ = Vector::pop_back(&mut signature);
+    assert(Secp256k1::verify(copy signature, public_key, copy hash), 9003);
+
+    let short_signature = x"0100";
+    assert(Option::is_none(&Secp256k1::recover(copy hash, short_signature)), 9004);
+
+    assert(Hash::ripemd160(b"") == x"9c1185a5c5e9fc54612808977ee8f548b2258d31", 9005);
+    assert(Hash::ripemd160(b"abc") == x"8eb208f7e05d987a9b044a8e98c6b087f15a0bfc", 9006);
+}
+}
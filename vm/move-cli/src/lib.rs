@@ -456,8 +456,11 @@ impl StateView for OnDiskStateView {
     }
 
     /// Gets states for a list of access paths.
-    fn multi_get(&self, _access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
-        unreachable!()
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        access_paths
+            .iter()
+            .map(|access_path| self.get(access_path))
+            .collect()
     }
 
     /// VM needs this method to know whether the current state view is for genesis state creation.
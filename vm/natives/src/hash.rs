@@ -1,3 +1,4 @@
+use crate::native_gas as starcoin_native_gas;
 use move_binary_format::errors::PartialVMResult;
 use move_vm_runtime::native_functions::NativeContext;
 use move_vm_types::{
@@ -7,7 +8,9 @@ use move_vm_types::{
     pop_arg,
     values::Value,
 };
+use ripemd160::{Digest, Ripemd160};
 use smallvec::smallvec;
+use starcoin_vm_types::gas_schedule::StarcoinNativeCostIndex;
 use std::collections::VecDeque;
 use tiny_keccak::Hasher;
 
@@ -36,3 +39,19 @@ pub fn native_keccak_256(
 
     Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(output)]))
 }
+
+pub fn native_ripemd160(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let hash_arg = pop_arg!(arguments, Vec<u8>);
+
+    let cost = starcoin_native_gas(context, StarcoinNativeCostIndex::RIPEMD160, hash_arg.len())?;
+    let output = Ripemd160::digest(hash_arg.as_slice()).to_vec();
+
+    Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(output)]))
+}
@@ -1,7 +1,30 @@
+use move_binary_format::errors::{PartialVMError, PartialVMResult};
+use move_core_types::vm_status::StatusCode;
+use move_vm_runtime::native_functions::NativeContext;
+use starcoin_vm_types::gas_schedule::{
+    starcoin_native_gas, GasCarrier, InternalGasUnits, StarcoinNativeCostIndex,
+};
+
 pub mod bcs;
 pub mod debug;
 pub mod hash;
+pub mod secp256k1;
 pub mod token;
 // the following two modules are copied from diem-framework. As we don't want to add deps on diem.
 pub mod account;
 pub mod signature;
+
+/// The cost of the native only defined by Starcoin,
+/// the native is not enabled if the on chain native table does not include it.
+pub(crate) fn native_gas(
+    context: &NativeContext,
+    key: StarcoinNativeCostIndex,
+    size: usize,
+) -> PartialVMResult<InternalGasUnits<GasCarrier>> {
+    starcoin_native_gas(context.cost_table(), key, size).ok_or_else(|| {
+        PartialVMError::new(StatusCode::FUNCTION_RESOLUTION_FAILURE).with_message(format!(
+            "Native function of {:?} is not enabled by the native cost table.",
+            key
+        ))
+    })
+}
@@ -0,0 +1,170 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::native_gas;
+use libsecp256k1::{Message, PublicKey, RecoveryId, Signature};
+use move_binary_format::errors::PartialVMResult;
+use move_vm_runtime::native_functions::NativeContext;
+use move_vm_types::{
+    loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
+};
+use smallvec::smallvec;
+use starcoin_vm_types::gas_schedule::StarcoinNativeCostIndex;
+use std::collections::VecDeque;
+
+const HASH_LENGTH: usize = 32;
+const SIGNATURE_LENGTH: usize = 64;
+const RECOVERABLE_SIGNATURE_LENGTH: usize = 65;
+
+/// Rust implementation of Move's `native fun native_recover(hash: vector<u8>, signature: vector<u8>): vector<u8>;`
+/// The signature is `r || s || v`, v is the recovery id in 0, 1, 27 or 28 as Ethereum.
+/// Return the 64 bytes uncompressed public key without the 0x04 prefix, or an empty vector if the recovery fails.
+pub fn native_secp256k1_recover(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let signature = pop_arg!(arguments, Vec<u8>);
+    let hash = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(context, StarcoinNativeCostIndex::SECP256K1_RECOVER, 1)?;
+
+    let public_key = recover(&hash, &signature)
+        .map(|public_key| public_key.serialize()[1..].to_vec())
+        .unwrap_or_default();
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(public_key)],
+    ))
+}
+
+/// Rust implementation of Move's `native fun native_verify(signature: vector<u8>, public_key: vector<u8>, hash: vector<u8>): bool;`
+/// The signature is `r || s` and must be in the lower-S form, the public key can be
+/// compressed (33 bytes), uncompressed (65 bytes) or uncompressed without the prefix (64 bytes).
+pub fn native_secp256k1_verify(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let hash = pop_arg!(arguments, Vec<u8>);
+    let public_key = pop_arg!(arguments, Vec<u8>);
+    let signature = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(context, StarcoinNativeCostIndex::SECP256K1_VERIFY, 1)?;
+
+    let verify_result = verify(&signature, &public_key, &hash);
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(verify_result)],
+    ))
+}
+
+fn recover(hash: &[u8], signature: &[u8]) -> Option<PublicKey> {
+    if hash.len() != HASH_LENGTH || signature.len() != RECOVERABLE_SIGNATURE_LENGTH {
+        return None;
+    }
+    let message = Message::parse_slice(hash).ok()?;
+    let sig = Signature::parse_standard_slice(&signature[..SIGNATURE_LENGTH]).ok()?;
+    let v = signature[SIGNATURE_LENGTH];
+    let recovery_id = RecoveryId::parse(if v >= 27 { v - 27 } else { v }).ok()?;
+    libsecp256k1::recover(&message, &sig, &recovery_id).ok()
+}
+
+fn verify(signature: &[u8], public_key: &[u8], hash: &[u8]) -> bool {
+    if hash.len() != HASH_LENGTH || signature.len() != SIGNATURE_LENGTH {
+        return false;
+    }
+    let message = match Message::parse_slice(hash) {
+        Ok(message) => message,
+        Err(_) => return false,
+    };
+    let sig = match Signature::parse_standard_slice(signature) {
+        Ok(sig) => sig,
+        Err(_) => return false,
+    };
+    // reject the malleable signature.
+    if sig.s.is_high() {
+        return false;
+    }
+    let pk = match PublicKey::parse_slice(public_key, None) {
+        Ok(pk) => pk,
+        Err(_) => return false,
+    };
+    libsecp256k1::verify(&message, &sig, &pk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libsecp256k1::SecretKey;
+    use tiny_keccak::Hasher;
+
+    fn decode_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn sign(secret: &SecretKey, hash: &[u8; 32]) -> (Vec<u8>, PublicKey) {
+        let (sig, recovery_id) = libsecp256k1::sign(&Message::parse(hash), secret);
+        let mut signature = sig.serialize().to_vec();
+        signature.push(recovery_id.serialize() + 27);
+        (signature, PublicKey::from_secret_key(secret))
+    }
+
+    #[test]
+    fn test_recover_and_verify() {
+        let secret = SecretKey::parse(&[7u8; 32]).unwrap();
+        let hash = [1u8; 32];
+        let (signature, public_key) = sign(&secret, &hash);
+
+        assert_eq!(Some(public_key), recover(&hash, &signature));
+        assert!(verify(
+            &signature[..SIGNATURE_LENGTH],
+            &public_key.serialize_compressed(),
+            &hash
+        ));
+        assert!(verify(
+            &signature[..SIGNATURE_LENGTH],
+            &public_key.serialize()[1..],
+            &hash
+        ));
+
+        let other_hash = [2u8; 32];
+        assert_ne!(Some(public_key), recover(&other_hash, &signature));
+        assert!(!verify(
+            &signature[..SIGNATURE_LENGTH],
+            &public_key.serialize(),
+            &other_hash
+        ));
+        // invalid length or recovery id
+        assert!(recover(&hash[..31], &signature).is_none());
+        let mut invalid_signature = signature.clone();
+        invalid_signature[SIGNATURE_LENGTH] = 30;
+        assert!(recover(&hash, &invalid_signature).is_none());
+        assert!(!verify(&signature, &public_key.serialize(), &hash));
+    }
+
+    #[test]
+    fn test_recover_eth_address() {
+        // the ecrecover precompile test vector of go-ethereum
+        let hash = decode_hex("38d18acb67d25c8bb9942764b62f18e17054f66a817bd4295423adf9ed98873e");
+        let signature = decode_hex("38d18acb67d25c8bb9942764b62f18e17054f66a817bd4295423adf9ed98873e789d1dd423d25f0772d2748d60f7e4b81bb14d086eba8e8e8efb6dcff8a4ae021b");
+        let public_key = recover(&hash, &signature).unwrap();
+        let mut public_key_hash = [0u8; 32];
+        let mut keccak = tiny_keccak::Keccak::v256();
+        keccak.update(&public_key.serialize()[1..]);
+        keccak.finalize(&mut public_key_hash);
+        assert_eq!(
+            decode_hex("ceaccac640adf55b2028469bd36ba501f28b699d"),
+            public_key_hash[12..].to_vec()
+        );
+    }
+}
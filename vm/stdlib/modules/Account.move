@@ -980,6 +980,138 @@ module Account {
         aborts_if txn_gas_price * (txn_max_gas_units - gas_units_remaining) > 0 &&
                 global<TransactionFee::TransactionFee<TokenType>>(CoreAddresses::SPEC_GENESIS_ADDRESS()).fee.value + txn_gas_price * (txn_max_gas_units - gas_units_remaining) > max_u128();
     }
+
+    /// Check the hash of the transaction's public key matches the auth key of the account at `addr`,
+    /// or the derived address if the account's auth key is empty.
+    fun check_authentication_key(addr: address, txn_authentication_key_preimage: vector<u8>) acquires Account {
+        let account = borrow_global<Account>(addr);
+        if (is_dummy_auth_key(account)) {
+            assert(
+                Authenticator::derived_address(Hash::sha3_256(txn_authentication_key_preimage)) == addr,
+                Errors::invalid_argument(EPROLOGUE_INVALID_ACCOUNT_AUTH_KEY)
+            );
+        } else {
+            assert(
+                Hash::sha3_256(txn_authentication_key_preimage) == *&account.authentication_key,
+                Errors::invalid_argument(EPROLOGUE_INVALID_ACCOUNT_AUTH_KEY)
+            );
+        };
+    }
+
+    spec check_authentication_key {
+        pragma verify = false;
+    }
+
+    /// Set the auth key of the account if it is empty, the account sends or pays for a transaction first.
+    fun set_authentication_key_if_dummy(account: &mut Account, txn_authentication_key_preimage: vector<u8>) {
+        if (is_dummy_auth_key(account) && !Vector::is_empty(&txn_authentication_key_preimage)) {
+            account.authentication_key = Hash::sha3_256(txn_authentication_key_preimage);
+        };
+    }
+
+    spec set_authentication_key_if_dummy {
+        pragma verify = false;
+    }
+
+    /// The prologue of a sponsored transaction, the gas is paid by the fee payer instead of the sender.
+    /// It verifies:
+    /// - The sender's and the fee payer's auth keys match the transaction's public keys
+    /// - That the fee payer has enough balance to pay for all of the gas
+    /// - That the sequence number matches the sender's sequence key
+    public fun txn_prologue_with_fee_payer<TokenType: store>(
+        account: &signer,
+        txn_sender: address,
+        txn_sequence_number: u64,
+        txn_authentication_key_preimage: vector<u8>,
+        txn_fee_payer: address,
+        txn_fee_payer_authentication_key_preimage: vector<u8>,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+    ) acquires Account, Balance {
+        CoreAddresses::assert_genesis_address(account);
+
+        // Verify that the transaction sender's and fee payer's accounts exist
+        assert(exists_at(txn_sender), Errors::requires_address(EPROLOGUE_ACCOUNT_DOES_NOT_EXIST));
+        assert(exists_at(txn_fee_payer), Errors::requires_address(EPROLOGUE_ACCOUNT_DOES_NOT_EXIST));
+
+        check_authentication_key(txn_sender, txn_authentication_key_preimage);
+        check_authentication_key(txn_fee_payer, txn_fee_payer_authentication_key_preimage);
+
+        // Check that the fee payer has enough balance for all of the gas
+        assert(
+            (txn_gas_price as u128) * (txn_max_gas_units as u128) <= MAX_U64,
+            Errors::invalid_argument(EPROLOGUE_CANT_PAY_GAS_DEPOSIT),
+        );
+        let max_transaction_fee = txn_gas_price * txn_max_gas_units;
+        if (max_transaction_fee > 0) {
+            assert(
+                STC::is_stc<TokenType>(),
+                Errors::invalid_argument(EBAD_TRANSACTION_FEE_TOKEN)
+            );
+
+            let balance_amount = balance<TokenType>(txn_fee_payer);
+            assert(balance_amount >= (max_transaction_fee as u128), Errors::invalid_argument(EPROLOGUE_CANT_PAY_GAS_DEPOSIT));
+        };
+
+        // Check that the transaction sequence number matches the sequence number of the sender
+        assert(
+            (txn_sequence_number as u128) < MAX_U64,
+            Errors::limit_exceeded(EPROLOGUE_SEQUENCE_NUMBER_TOO_BIG)
+        );
+        let sender_account = borrow_global<Account>(txn_sender);
+        assert(txn_sequence_number >= sender_account.sequence_number, Errors::invalid_argument(EPROLOGUE_SEQUENCE_NUMBER_TOO_OLD));
+        assert(txn_sequence_number == sender_account.sequence_number, Errors::invalid_argument(EPROLOGUE_SEQUENCE_NUMBER_TOO_NEW));
+    }
+
+    spec txn_prologue_with_fee_payer {
+        pragma verify = false;
+    }
+
+    /// The epilogue of a sponsored transaction.
+    /// It collects gas from the fee payer and bumps the sequence number of the sender
+    public fun txn_epilogue_with_fee_payer<TokenType: store>(
+        account: &signer,
+        txn_sender: address,
+        txn_sequence_number: u64,
+        txn_authentication_key_preimage: vector<u8>,
+        txn_fee_payer: address,
+        txn_fee_payer_authentication_key_preimage: vector<u8>,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        gas_units_remaining: u64,
+    ) acquires Account, Balance {
+        CoreAddresses::assert_genesis_address(account);
+
+        // Charge for gas
+        let fee_payer_balance = borrow_global_mut<Balance<TokenType>>(txn_fee_payer);
+        let transaction_fee_amount =(txn_gas_price * (txn_max_gas_units - gas_units_remaining) as u128);
+        assert(
+            balance_for(fee_payer_balance) >= transaction_fee_amount,
+            Errors::limit_exceeded(EINSUFFICIENT_BALANCE)
+        );
+
+        // Bump the sequence number of the sender
+        {
+            let sender_account = borrow_global_mut<Account>(txn_sender);
+            sender_account.sequence_number = txn_sequence_number + 1;
+            set_authentication_key_if_dummy(sender_account, txn_authentication_key_preimage);
+        };
+        {
+            let fee_payer_account = borrow_global_mut<Account>(txn_fee_payer);
+            set_authentication_key_if_dummy(fee_payer_account, txn_fee_payer_authentication_key_preimage);
+        };
+        if (transaction_fee_amount > 0) {
+            let transaction_fee = withdraw_from_balance(
+                    fee_payer_balance,
+                    transaction_fee_amount
+            );
+            TransactionFee::pay_fee(transaction_fee);
+        };
+    }
+
+    spec txn_epilogue_with_fee_payer {
+        pragma verify = false;
+    }
 }
 
 }
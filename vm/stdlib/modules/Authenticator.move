@@ -18,6 +18,7 @@ module Authenticator {
 
     const ED25519_SCHEME_ID: u8 = 0;
     const MULTI_ED25519_SCHEME_ID: u8 = 1;
+    const SECP256K1_ECDSA_SCHEME_ID: u8 = 2;
 
     /// A multi-ed25519 public key
     struct MultiEd25519PublicKey has copy, drop, store {
@@ -85,6 +86,17 @@ module Authenticator {
     /// does not matter for the verification of callers.
     spec fun spec_ed25519_authentication_key(public_key: vector<u8>): vector<u8>;
 
+    /// Compute an authentication key for the 64 bytes uncompressed secp256k1 public key `public_key`
+    public fun secp256k1_authentication_key(public_key: vector<u8>): vector<u8> {
+        Vector::push_back(&mut public_key, SECP256K1_ECDSA_SCHEME_ID);
+        Hash::sha3_256(public_key)
+    }
+
+    spec secp256k1_authentication_key {
+        pragma opaque = true;
+        aborts_if false;
+    }
+
     /// convert authentication key to address
     public fun derived_address(authentication_key: vector<u8>): address {
         assert(Vector::length(&authentication_key) == AUTHENTICATION_KEY_LENGTH, Errors::invalid_argument(EWRONG_AUTHENTICATION_KEY_LENGTH));
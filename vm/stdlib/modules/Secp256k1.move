@@ -0,0 +1,56 @@
+address 0x1 {
+
+/// Contains functions for [secp256k1](https://en.bitcoin.it/wiki/Secp256k1) ECDSA signatures,
+/// which are used to verify the signatures of Ethereum and Bitcoin.
+module Secp256k1 {
+    use 0x1::Hash;
+    use 0x1::Option::{Self, Option};
+    use 0x1::Vector;
+
+    spec module {
+        pragma verify = false;
+    }
+
+    /// Recover the 64 bytes uncompressed public key (without the 0x04 prefix) from the 32 bytes `hash`
+    /// and the 65 bytes `signature` in the form `r || s || v`, `v` is 0, 1, 27 or 28.
+    /// Return an empty vector if the recovery fails.
+    native fun native_recover(hash: vector<u8>, signature: vector<u8>): vector<u8>;
+
+    /// Verify the 64 bytes `signature` in the form `r || s` of the 32 bytes `hash`,
+    /// the `public_key` is compressed (33 bytes) or uncompressed (64 or 65 bytes).
+    native fun native_verify(signature: vector<u8>, public_key: vector<u8>, hash: vector<u8>): bool;
+
+    /// Recover the uncompressed public key (without the 0x04 prefix) from the signature of `hash`.
+    public fun recover(hash: vector<u8>, signature: vector<u8>): Option<vector<u8>> {
+        let public_key = native_recover(hash, signature);
+        if (Vector::is_empty(&public_key)) {
+            Option::none()
+        } else {
+            Option::some(public_key)
+        }
+    }
+
+    /// Recover the 20 bytes Ethereum address from the signature of `hash`,
+    /// the address is the last 20 bytes of the keccak-256 hash of the public key.
+    public fun recover_eth_address(hash: vector<u8>, signature: vector<u8>): Option<vector<u8>> {
+        let public_key = recover(hash, signature);
+        if (Option::is_none(&public_key)) {
+            return Option::none()
+        };
+        let public_key_hash = Hash::keccak_256(Option::destroy_some(public_key));
+        let address = Vector::empty<u8>();
+        let i = 12;
+        while (i < 32) {
+            Vector::push_back(&mut address, *Vector::borrow(&public_key_hash, i));
+            i = i + 1;
+        };
+        Option::some(address)
+    }
+
+    /// Verify the signature of `hash` with the `public_key`, the signature must be in the lower-S form.
+    public fun verify(signature: vector<u8>, public_key: vector<u8>, hash: vector<u8>): bool {
+        native_verify(signature, public_key, hash)
+    }
+}
+
+}
@@ -70,33 +70,14 @@ module TransactionManager {
             txn_gas_price,
             txn_max_gas_units,
         );
-        assert(
-            TransactionTimeout::is_valid_transaction_timestamp(txn_expiration_time),
-            Errors::invalid_argument(EPROLOGUE_TRANSACTION_EXPIRED),
+        txn_payload_prologue(
+            &account,
+            txn_sender,
+            txn_expiration_time,
+            txn_payload_type,
+            txn_script_or_package_hash,
+            txn_package_address,
         );
-        if (txn_payload_type == TXN_PAYLOAD_TYPE_PACKAGE) {
-            // stdlib upgrade is not affected by PublishOption
-            if (txn_package_address != CoreAddresses::GENESIS_ADDRESS()) {
-                assert(
-                    TransactionPublishOption::is_module_allowed(Signer::address_of(&account)),
-                    Errors::invalid_argument(EPROLOGUE_MODULE_NOT_ALLOWED),
-                );
-            };
-            PackageTxnManager::package_txn_prologue_v2(
-                &account,
-                txn_sender,
-                txn_package_address,
-                txn_script_or_package_hash,
-            );
-        } else if (txn_payload_type == TXN_PAYLOAD_TYPE_SCRIPT) {
-            assert(
-                TransactionPublishOption::is_script_allowed(
-                    Signer::address_of(&account),
-                ),
-                Errors::invalid_argument(EPROLOGUE_SCRIPT_NOT_ALLOWED),
-            );
-        };
-        // do nothing for TXN_PAYLOAD_TYPE_SCRIPT_FUNCTION
     }
 
     spec prologue {
@@ -124,6 +105,44 @@ module TransactionManager {
         include PackageTxnManager::CheckPackageTxnAbortsIfWithType{is_package: (txn_payload_type == TXN_PAYLOAD_TYPE_PACKAGE), sender:txn_sender, package_address: txn_package_address, package_hash: txn_script_or_package_hash};
     }
 
+    /// Check the expiration time and the payload of the transaction.
+    fun txn_payload_prologue(
+        account: &signer,
+        txn_sender: address,
+        txn_expiration_time: u64,
+        txn_payload_type: u8,
+        txn_script_or_package_hash: vector<u8>,
+        txn_package_address: address,
+    ) {
+        assert(
+            TransactionTimeout::is_valid_transaction_timestamp(txn_expiration_time),
+            Errors::invalid_argument(EPROLOGUE_TRANSACTION_EXPIRED),
+        );
+        if (txn_payload_type == TXN_PAYLOAD_TYPE_PACKAGE) {
+            // stdlib upgrade is not affected by PublishOption
+            if (txn_package_address != CoreAddresses::GENESIS_ADDRESS()) {
+                assert(
+                    TransactionPublishOption::is_module_allowed(Signer::address_of(account)),
+                    Errors::invalid_argument(EPROLOGUE_MODULE_NOT_ALLOWED),
+                );
+            };
+            PackageTxnManager::package_txn_prologue_v2(
+                account,
+                txn_sender,
+                txn_package_address,
+                txn_script_or_package_hash,
+            );
+        } else if (txn_payload_type == TXN_PAYLOAD_TYPE_SCRIPT) {
+            assert(
+                TransactionPublishOption::is_script_allowed(
+                    Signer::address_of(account),
+                ),
+                Errors::invalid_argument(EPROLOGUE_SCRIPT_NOT_ALLOWED),
+            );
+        };
+        // do nothing for TXN_PAYLOAD_TYPE_SCRIPT_FUNCTION
+    }
+
     /// The epilogue is invoked at the end of transactions.
     /// It collects gas and bumps the sequence number
     public fun epilogue<TokenType: store>(
@@ -194,6 +213,91 @@ module TransactionManager {
         };
     }
 
+    /// The prologue of a sponsored transaction, the gas is paid by `txn_fee_payer`.
+    /// It verifies the same as `prologue`, but the fee payer must have enough balance to pay for all of the gas.
+    public fun prologue_with_fee_payer<TokenType: store>(
+        account: signer,
+        txn_sender: address,
+        txn_sequence_number: u64,
+        txn_authentication_key_preimage: vector<u8>,
+        txn_fee_payer: address,
+        txn_fee_payer_authentication_key_preimage: vector<u8>,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        txn_expiration_time: u64,
+        chain_id: u8,
+        txn_payload_type: u8,
+        txn_script_or_package_hash: vector<u8>,
+        txn_package_address: address,
+    ) {
+        // Can only be invoked by genesis account
+        assert(
+            Signer::address_of(&account) == CoreAddresses::GENESIS_ADDRESS(),
+            Errors::requires_address(EPROLOGUE_ACCOUNT_DOES_NOT_EXIST),
+        );
+        // Check that the chain ID stored on-chain matches the chain ID
+        // specified by the transaction
+        assert(ChainId::get() == chain_id, Errors::invalid_argument(EPROLOGUE_BAD_CHAIN_ID));
+        Account::txn_prologue_with_fee_payer<TokenType>(
+            &account,
+            txn_sender,
+            txn_sequence_number,
+            txn_authentication_key_preimage,
+            txn_fee_payer,
+            txn_fee_payer_authentication_key_preimage,
+            txn_gas_price,
+            txn_max_gas_units,
+        );
+        txn_payload_prologue(
+            &account,
+            txn_sender,
+            txn_expiration_time,
+            txn_payload_type,
+            txn_script_or_package_hash,
+            txn_package_address,
+        );
+    }
+
+    /// The epilogue of a sponsored transaction.
+    /// It collects gas from the fee payer and bumps the sequence number of the sender
+    public fun epilogue_with_fee_payer<TokenType: store>(
+        account: signer,
+        txn_sender: address,
+        txn_sequence_number: u64,
+        txn_authentication_key_preimage: vector<u8>,
+        txn_fee_payer: address,
+        txn_fee_payer_authentication_key_preimage: vector<u8>,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        gas_units_remaining: u64,
+        txn_payload_type: u8,
+        _txn_script_or_package_hash: vector<u8>,
+        txn_package_address: address,
+        // txn execute success or fail.
+        success: bool,
+    ) {
+        CoreAddresses::assert_genesis_address(&account);
+        Account::txn_epilogue_with_fee_payer<TokenType>(
+            &account,
+            txn_sender,
+            txn_sequence_number,
+            txn_authentication_key_preimage,
+            txn_fee_payer,
+            txn_fee_payer_authentication_key_preimage,
+            txn_gas_price,
+            txn_max_gas_units,
+            gas_units_remaining,
+        );
+        if (txn_payload_type == TXN_PAYLOAD_TYPE_PACKAGE) {
+            PackageTxnManager::package_txn_epilogue(
+                &account,
+                txn_sender,
+                txn_package_address,
+                success,
+            );
+        }
+    }
+
     /// Set the metadata for the current block and distribute transaction fees and block rewards.
     /// The runtime always runs this before executing the transactions in a block.
     public fun block_prologue(
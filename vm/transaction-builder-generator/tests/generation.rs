@@ -287,3 +287,26 @@ fn test_that_java_code_compiles_and_demo_runs() {
     assert!(output.status.success());
     assert_eq!(std::str::from_utf8(&output.stdout).unwrap(), OUTPUT);
 }
+
+#[test]
+fn test_that_generated_types_include_secp256k1_authenticator() {
+    let registry = get_starcoin_registry();
+    let config = serdegen::CodeGeneratorConfig::new("starcoin_types".to_string())
+        .with_encodings(vec![serdegen::Encoding::Bcs]);
+
+    let mut python_source = Vec::new();
+    serdegen::python3::CodeGenerator::new(&config)
+        .output(&mut python_source, &registry)
+        .unwrap();
+    let python_source = String::from_utf8(python_source).unwrap();
+    assert!(python_source.contains("class TransactionAuthenticator__Secp256k1Ecdsa("));
+    assert!(python_source.contains("class Secp256k1PublicKey:"));
+
+    let mut rust_source = Vec::new();
+    serdegen::rust::CodeGenerator::new(&config)
+        .output(&mut rust_source, &registry)
+        .unwrap();
+    let rust_source = String::from_utf8(rust_source).unwrap();
+    assert!(rust_source.contains("Secp256k1Ecdsa {"));
+    assert!(rust_source.contains("pub struct Secp256k1Signature("));
+}
@@ -40,3 +40,7 @@ pub static BLOCK_PROLOGUE_NAME: Lazy<Identifier> =
 pub static EPILOGUE_NAME: Lazy<Identifier> = Lazy::new(|| Identifier::new("epilogue").unwrap());
 pub static EPILOGUE_V2_NAME: Lazy<Identifier> =
     Lazy::new(|| Identifier::new("epilogue_v2").unwrap());
+pub static PROLOGUE_WITH_FEE_PAYER_NAME: Lazy<Identifier> =
+    Lazy::new(|| Identifier::new("prologue_with_fee_payer").unwrap());
+pub static EPILOGUE_WITH_FEE_PAYER_NAME: Lazy<Identifier> =
+    Lazy::new(|| Identifier::new("epilogue_with_fee_payer").unwrap());
@@ -0,0 +1,40 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::gas_schedule::{
+    AbstractMemorySize, CostTable, GasAlgebra, GasCarrier, InternalGasUnits,
+};
+use vm::file_format::NUMBER_OF_NATIVE_FUNCTIONS;
+
+/// The cost index of the natives only defined by Starcoin.
+/// Their costs are appended to the native table after the natives of `NativeCostIndex`,
+/// so a native is only available after the on chain native table is upgraded to include it.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[repr(u8)]
+pub enum StarcoinNativeCostIndex {
+    SECP256K1_RECOVER = 0,
+    SECP256K1_VERIFY = 1,
+    RIPEMD160 = 2,
+}
+
+/// The number of `StarcoinNativeCostIndex`.
+pub const NUMBER_OF_STARCOIN_NATIVE_FUNCTIONS: usize = 3;
+
+impl StarcoinNativeCostIndex {
+    /// The index of the native in the native table.
+    pub fn table_index(self) -> usize {
+        NUMBER_OF_NATIVE_FUNCTIONS + self as usize
+    }
+}
+
+/// Like `native_gas`, but return None if the native table does not include the native.
+pub fn starcoin_native_gas(
+    table: &CostTable,
+    key: StarcoinNativeCostIndex,
+    size: usize,
+) -> Option<InternalGasUnits<GasCarrier>> {
+    let gas_amt = table.native_table.get(key.table_index())?;
+    let memory_size = AbstractMemorySize::new(std::cmp::max(1, size) as GasCarrier);
+    Some(gas_amt.total().mul(memory_size))
+}
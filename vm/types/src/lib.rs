@@ -2,11 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 //TODO FIXME for fuzzing Arbitrary;
 #![allow(clippy::unit_arg)]
+mod gas_schedule_ext;
 mod language_storage_ext;
 
 pub mod account_address;
 
 pub mod gas_schedule {
+    pub use crate::gas_schedule_ext::{
+        starcoin_native_gas, StarcoinNativeCostIndex, NUMBER_OF_STARCOIN_NATIVE_FUNCTIONS,
+    };
     pub use move_core_types::gas_schedule::*;
     pub use move_vm_types::gas_schedule::*;
 }
@@ -3,7 +3,10 @@
 
 //! This file contains the starting gas schedule published at genesis.
 
-use crate::gas_schedule::{CostTable, GasConstants, GasCost};
+use crate::gas_schedule::{
+    CostTable, GasConstants, GasCost, StarcoinNativeCostIndex as S,
+    NUMBER_OF_STARCOIN_NATIVE_FUNCTIONS,
+};
 use move_vm_types::gas_schedule::NativeCostIndex as N;
 use vm::{
     file_format::{
@@ -175,6 +178,32 @@ pub fn initial_native_table() -> Vec<GasCost> {
     native_table
 }
 
+/// The costs of the natives only defined by Starcoin, they follow the costs of `NativeCostIndex`.
+pub fn starcoin_native_table() -> Vec<GasCost> {
+    let mut raw_native_table = vec![
+        (S::SECP256K1_RECOVER, GasCost::new(6720, 1)),
+        (S::SECP256K1_VERIFY, GasCost::new(3900, 1)),
+        (S::RIPEMD160, GasCost::new(20, 1)),
+    ];
+    raw_native_table.sort_by_key(|cost| cost.0 as u64);
+    raw_native_table
+        .into_iter()
+        .map(|(_, cost)| cost)
+        .collect::<Vec<_>>()
+}
+
+/// The native table with all the natives of Starcoin, the native table of a chain
+/// should be upgraded to it to enable the natives of `StarcoinNativeCostIndex`.
+pub fn latest_native_table() -> Vec<GasCost> {
+    let mut native_table = initial_native_table();
+    native_table.extend(starcoin_native_table());
+    debug_assert!(
+        native_table.len() == NUMBER_OF_NATIVE_FUNCTIONS + NUMBER_OF_STARCOIN_NATIVE_FUNCTIONS,
+        "all native functions must be in the cost table"
+    );
+    native_table
+}
+
 pub fn v1_native_table() -> Vec<GasCost> {
     let mut raw_native_table = vec![
         (N::SHA2_256, GasCost::new(21, 1)),
@@ -26,7 +26,8 @@ pub use self::{
     consensus_config::{consensus_config_type_tag, ConsensusConfig, CONSENSUS_CONFIG_IDENTIFIER},
     dao_config::DaoConfig,
     genesis_gas_schedule::{
-        init_cost_table, initial_instruction_table, initial_native_table, v1_native_table,
+        init_cost_table, initial_instruction_table, initial_native_table, latest_native_table,
+        starcoin_native_table, v1_native_table,
     },
     move_lang_version::MoveLanguageVersion,
     version::{version_config_type_tag, Version, VERSION_CONFIG_IDENTIFIER},
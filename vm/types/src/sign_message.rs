@@ -104,6 +104,10 @@ impl SignedMessage {
     /// Checks that the signature of given message. Returns `Ok()` if the signature is valid.
     /// Note: this method do not check the relation of account and public key.
     pub fn check_signature(&self) -> Result<()> {
+        ensure!(
+            self.authenticator.fee_payer_address().is_none(),
+            "FeePayer authenticator can not sign message"
+        );
         self.authenticator.verify(&self.message)
     }
 
@@ -13,6 +13,10 @@ use starcoin_crypto::ed25519::{
     Ed25519PrivateKey, ED25519_PRIVATE_KEY_LENGTH, ED25519_PUBLIC_KEY_LENGTH,
 };
 use starcoin_crypto::multi_ed25519::multi_shard::MultiEd25519KeyShard;
+use starcoin_crypto::secp256k1::{
+    Secp256k1PrivateKey, Secp256k1PublicKey, Secp256k1Signature, SECP256K1_PRIVATE_KEY_LENGTH,
+    SECP256K1_PUBLIC_KEY_LENGTH,
+};
 use starcoin_crypto::{
     derive::{DeserializeKey, SerializeKey},
     ed25519::{Ed25519PublicKey, Ed25519Signature},
@@ -42,6 +46,7 @@ use std::{convert::TryFrom, fmt, str::FromStr};
 pub enum Scheme {
     Ed25519 = 0,
     MultiEd25519 = 1,
+    Secp256k1Ecdsa = 2,
     // ... add more schemes here
 }
 
@@ -50,6 +55,7 @@ impl fmt::Display for Scheme {
         let display = match self {
             Scheme::Ed25519 => "Ed25519",
             Scheme::MultiEd25519 => "MultiEd25519",
+            Scheme::Secp256k1Ecdsa => "Secp256k1Ecdsa",
         };
         write!(f, "Scheme::{}", display)
     }
@@ -72,15 +78,32 @@ pub enum TransactionAuthenticator {
         #[schemars(with = "String")]
         signature: MultiEd25519Signature,
     },
+    /// Single secp256k1 ECDSA signature
+    Secp256k1Ecdsa {
+        #[schemars(with = "String")]
+        public_key: Secp256k1PublicKey,
+        #[schemars(with = "String")]
+        signature: Secp256k1Signature,
+    },
+    /// Sponsored transaction, the sender and the fee payer both sign the
+    /// `RawUserTransactionWithFeePayer`, and the gas is charged to the fee payer.
+    FeePayer {
+        sender: Box<TransactionAuthenticator>,
+        #[schemars(with = "String")]
+        fee_payer_address: AccountAddress,
+        fee_payer: Box<TransactionAuthenticator>,
+    },
     // ... add more schemes here
 }
 
 impl TransactionAuthenticator {
-    /// Unique identifier for the signature scheme
+    /// Unique identifier for the signature scheme, it is the sender's scheme for a sponsored transaction.
     pub fn scheme(&self) -> Scheme {
         match self {
             Self::Ed25519 { .. } => Scheme::Ed25519,
             Self::MultiEd25519 { .. } => Scheme::MultiEd25519,
+            Self::Secp256k1Ecdsa { .. } => Scheme::Secp256k1Ecdsa,
+            Self::FeePayer { sender, .. } => sender.scheme(),
         }
     }
 
@@ -103,6 +126,53 @@ impl TransactionAuthenticator {
         }
     }
 
+    /// Create a single-signature secp256k1 ECDSA authenticator
+    pub fn secp256k1_ecdsa(public_key: Secp256k1PublicKey, signature: Secp256k1Signature) -> Self {
+        Self::Secp256k1Ecdsa {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Create a sponsored transaction authenticator, the gas is paid by `fee_payer_address`.
+    pub fn with_fee_payer(
+        sender: TransactionAuthenticator,
+        fee_payer_address: AccountAddress,
+        fee_payer: TransactionAuthenticator,
+    ) -> Self {
+        Self::FeePayer {
+            sender: Box::new(sender),
+            fee_payer_address,
+            fee_payer: Box::new(fee_payer),
+        }
+    }
+
+    /// Return the address of the fee payer if it is a sponsored transaction authenticator.
+    pub fn fee_payer_address(&self) -> Option<AccountAddress> {
+        match self {
+            Self::FeePayer {
+                fee_payer_address, ..
+            } => Some(*fee_payer_address),
+            _ => None,
+        }
+    }
+
+    /// Return the authenticator of the fee payer if it is a sponsored transaction authenticator.
+    pub fn fee_payer_authenticator(&self) -> Option<&TransactionAuthenticator> {
+        match self {
+            Self::FeePayer { fee_payer, .. } => Some(fee_payer.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// Return the authenticator of the sender, it is `self` if not a sponsored transaction authenticator.
+    pub fn sender_authenticator(&self) -> &TransactionAuthenticator {
+        match self {
+            Self::FeePayer { sender, .. } => sender.as_ref(),
+            _ => self,
+        }
+    }
+
     /// Return Ok if the authenticator's public key matches its signature, Err otherwise
     pub fn verify<T: Serialize + CryptoHash>(&self, message: &T) -> Result<()> {
         match self {
@@ -114,6 +184,20 @@ impl TransactionAuthenticator {
                 public_key,
                 signature,
             } => signature.verify(message, public_key),
+            Self::Secp256k1Ecdsa {
+                public_key,
+                signature,
+            } => signature.verify(message, public_key),
+            Self::FeePayer {
+                sender, fee_payer, ..
+            } => {
+                ensure!(
+                    sender.fee_payer_address().is_none() && fee_payer.fee_payer_address().is_none(),
+                    "FeePayer authenticator can not be nested"
+                );
+                sender.verify(message)?;
+                fee_payer.verify(message)
+            }
         }
     }
 
@@ -122,6 +206,8 @@ impl TransactionAuthenticator {
         match self {
             Self::Ed25519 { public_key, .. } => public_key.to_bytes().to_vec(),
             Self::MultiEd25519 { public_key, .. } => public_key.to_bytes().to_vec(),
+            Self::Secp256k1Ecdsa { public_key, .. } => public_key.to_bytes(),
+            Self::FeePayer { sender, .. } => sender.public_key_bytes(),
         }
     }
 
@@ -129,6 +215,10 @@ impl TransactionAuthenticator {
         match self {
             Self::Ed25519 { public_key, .. } => AccountPublicKey::Single(public_key.clone()),
             Self::MultiEd25519 { public_key, .. } => AccountPublicKey::Multi(public_key.clone()),
+            Self::Secp256k1Ecdsa { public_key, .. } => {
+                AccountPublicKey::Secp256k1(public_key.clone())
+            }
+            Self::FeePayer { sender, .. } => sender.public_key(),
         }
     }
 
@@ -137,6 +227,8 @@ impl TransactionAuthenticator {
         match self {
             Self::Ed25519 { signature, .. } => signature.to_bytes().to_vec(),
             Self::MultiEd25519 { signature, .. } => signature.to_bytes().to_vec(),
+            Self::Secp256k1Ecdsa { signature, .. } => signature.to_bytes(),
+            Self::FeePayer { sender, .. } => sender.signature_bytes(),
         }
     }
 
@@ -206,6 +298,11 @@ impl AuthenticationKey {
         Self::from_preimage(&AuthenticationKeyPreimage::multi_ed25519(public_key))
     }
 
+    /// Create an authentication key from a Secp256k1 public key
+    pub fn secp256k1_ecdsa(public_key: &Secp256k1PublicKey) -> Self {
+        Self::from_preimage(&AuthenticationKeyPreimage::secp256k1_ecdsa(public_key))
+    }
+
     /// Return an address derived from the last `AccountAddress::LENGTH` bytes of this
     /// authentication key.
     pub fn derived_address(&self) -> AccountAddress {
@@ -266,6 +363,11 @@ impl AuthenticationKeyPreimage {
         Self::new(public_key.to_bytes(), Scheme::MultiEd25519)
     }
 
+    /// Construct a preimage from a Secp256k1 public key
+    pub fn secp256k1_ecdsa(public_key: &Secp256k1PublicKey) -> AuthenticationKeyPreimage {
+        Self::new(public_key.to_bytes(), Scheme::Secp256k1Ecdsa)
+    }
+
     /// Construct a vector from this authentication key
     pub fn into_vec(self) -> Vec<u8> {
         self.0
@@ -342,12 +444,15 @@ pub enum AccountPublicKey {
     Single(Ed25519PublicKey),
     #[schemars(with = "String")]
     Multi(MultiEd25519PublicKey),
+    #[schemars(with = "String")]
+    Secp256k1(Secp256k1PublicKey),
 }
 
 #[derive(Eq, PartialEq, Debug, DeserializeKey, SerializeKey)]
 pub enum AccountPrivateKey {
     Single(Ed25519PrivateKey),
     Multi(MultiEd25519KeyShard),
+    Secp256k1(Secp256k1PrivateKey),
 }
 
 impl ValidCryptoMaterial for AccountPublicKey {
@@ -355,6 +460,7 @@ impl ValidCryptoMaterial for AccountPublicKey {
         match self {
             Self::Single(key) => key.to_bytes().to_vec(),
             Self::Multi(key) => key.to_bytes(),
+            Self::Secp256k1(key) => key.to_bytes(),
         }
     }
 }
@@ -371,6 +477,10 @@ impl AccountPublicKey {
         )?))
     }
 
+    pub fn secp256k1(public_key: Secp256k1PublicKey) -> Self {
+        AccountPublicKey::Secp256k1(public_key)
+    }
+
     pub fn derived_address(&self) -> AccountAddress {
         self.authentication_key().derived_address()
     }
@@ -379,6 +489,7 @@ impl AccountPublicKey {
         match self {
             Self::Single(p) => AuthenticationKeyPreimage::ed25519(p),
             Self::Multi(p) => AuthenticationKeyPreimage::multi_ed25519(p),
+            Self::Secp256k1(p) => AuthenticationKeyPreimage::secp256k1_ecdsa(p),
         }
     }
 
@@ -392,6 +503,7 @@ impl AccountPublicKey {
         match self {
             Self::Single(public_key) => public_key.to_bytes().to_vec(),
             Self::Multi(public_key) => public_key.to_bytes().to_vec(),
+            Self::Secp256k1(public_key) => public_key.to_bytes(),
         }
     }
 
@@ -400,6 +512,7 @@ impl AccountPublicKey {
         match self {
             Self::Single { .. } => Scheme::Ed25519,
             Self::Multi { .. } => Scheme::MultiEd25519,
+            Self::Secp256k1 { .. } => Scheme::Secp256k1Ecdsa,
         }
     }
 
@@ -416,14 +529,24 @@ impl AccountPublicKey {
             _ => None,
         }
     }
+
+    pub fn as_secp256k1(&self) -> Option<Secp256k1PublicKey> {
+        match self {
+            Self::Secp256k1(key) => Some(key.clone()),
+            _ => None,
+        }
+    }
 }
 
 impl TryFrom<&[u8]> for AccountPublicKey {
     type Error = CryptoMaterialError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        // the length of MultiEd25519PublicKey is `32 * n + 1`, so it can not be confused with the others.
         if value.len() == ED25519_PUBLIC_KEY_LENGTH {
             Ed25519PublicKey::try_from(value).map(Self::Single)
+        } else if value.len() == SECP256K1_PUBLIC_KEY_LENGTH {
+            Secp256k1PublicKey::try_from(value).map(Self::Secp256k1)
         } else {
             MultiEd25519PublicKey::try_from(value).map(Self::Multi)
         }
@@ -444,11 +567,24 @@ impl Into<AccountPublicKey> for MultiEd25519PublicKey {
     }
 }
 
+#[allow(clippy::from_over_into)]
+impl Into<AccountPublicKey> for Secp256k1PublicKey {
+    fn into(self) -> AccountPublicKey {
+        AccountPublicKey::Secp256k1(self)
+    }
+}
+
 impl ValidCryptoMaterial for AccountPrivateKey {
+    /// The Secp256k1 key is prefixed with its scheme id, as it has the same length as the Ed25519 key.
     fn to_bytes(&self) -> Vec<u8> {
         match self {
             Self::Single(key) => key.to_bytes().to_vec(),
             Self::Multi(key) => key.to_bytes(),
+            Self::Secp256k1(key) => {
+                let mut bytes = vec![Scheme::Secp256k1Ecdsa as u8];
+                bytes.extend(key.to_bytes());
+                bytes
+            }
         }
     }
 }
@@ -458,6 +594,7 @@ impl AccountPrivateKey {
         match self {
             Self::Single(key) => AccountPublicKey::Single(key.public_key()),
             Self::Multi(key) => AccountPublicKey::Multi(key.public_key()),
+            Self::Secp256k1(key) => AccountPublicKey::Secp256k1(key.public_key()),
         }
     }
 
@@ -469,6 +606,9 @@ impl AccountPrivateKey {
             Self::Multi(key) => {
                 TransactionAuthenticator::multi_ed25519(key.public_key(), key.sign(message).into())
             }
+            Self::Secp256k1(key) => {
+                TransactionAuthenticator::secp256k1_ecdsa(key.public_key(), key.sign(message))
+            }
         }
     }
 
@@ -491,12 +631,24 @@ impl Into<AccountPrivateKey> for MultiEd25519KeyShard {
     }
 }
 
+#[allow(clippy::from_over_into)]
+impl Into<AccountPrivateKey> for Secp256k1PrivateKey {
+    fn into(self) -> AccountPrivateKey {
+        AccountPrivateKey::Secp256k1(self)
+    }
+}
+
 impl TryFrom<&[u8]> for AccountPrivateKey {
     type Error = CryptoMaterialError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        // the length of MultiEd25519KeyShard is `3 + 32 * n`, so it can not be confused with the others.
         if value.len() == ED25519_PRIVATE_KEY_LENGTH {
             Ed25519PrivateKey::try_from(value).map(Self::Single)
+        } else if value.len() == SECP256K1_PRIVATE_KEY_LENGTH + 1
+            && value[0] == Scheme::Secp256k1Ecdsa as u8
+        {
+            Secp256k1PrivateKey::try_from(&value[1..]).map(Self::Secp256k1)
         } else {
             MultiEd25519KeyShard::try_from(value).map(Self::Multi)
         }
@@ -505,9 +657,14 @@ impl TryFrom<&[u8]> for AccountPrivateKey {
 
 #[cfg(test)]
 mod tests {
-    use crate::transaction::authenticator::{AccountPublicKey, AuthenticationKey};
+    use crate::transaction::authenticator::{
+        AccountPrivateKey, AccountPublicKey, AuthenticationKey, Scheme,
+    };
     use starcoin_crypto::keygen::KeyGen;
     use starcoin_crypto::multi_ed25519::MultiEd25519PublicKey;
+    use starcoin_crypto::test_utils::TestDiemCrypto;
+    use starcoin_crypto::ValidCryptoMaterial;
+    use std::convert::TryFrom;
     use std::str::FromStr;
 
     #[test]
@@ -530,4 +687,38 @@ mod tests {
         let auth_key2 = AuthenticationKey::multi_ed25519(&multi_pubkey);
         assert_eq!(auth_key, auth_key2);
     }
+
+    #[test]
+    fn test_secp256k1_account_key() {
+        let mut key_gen = KeyGen::from_os_rng();
+        let (private_key, public_key) = key_gen.generate_secp256k1_keypair();
+        let account_private_key = AccountPrivateKey::Secp256k1(private_key);
+        let account_public_key = AccountPublicKey::secp256k1(public_key.clone());
+        assert_eq!(account_public_key, account_private_key.public_key());
+        assert_eq!(
+            account_public_key.authentication_key(),
+            AuthenticationKey::secp256k1_ecdsa(&public_key)
+        );
+
+        // the keys can be distinguished from the Ed25519 keys by bytes.
+        let private_key_bytes = account_private_key.to_bytes();
+        assert_eq!(Scheme::Secp256k1Ecdsa as u8, private_key_bytes[0]);
+        assert_eq!(
+            account_private_key,
+            AccountPrivateKey::try_from(private_key_bytes.as_slice()).unwrap()
+        );
+        assert_eq!(
+            account_public_key,
+            AccountPublicKey::try_from(account_public_key.to_bytes().as_slice()).unwrap()
+        );
+
+        let message = TestDiemCrypto("Test Message".to_string());
+        let authenticator = account_private_key.sign(&message);
+        assert!(matches!(authenticator.scheme(), Scheme::Secp256k1Ecdsa));
+        assert_eq!(account_public_key, authenticator.public_key());
+        authenticator.verify(&message).unwrap();
+        assert!(authenticator
+            .verify(&TestDiemCrypto("Other Message".to_string()))
+            .is_err());
+    }
 }
@@ -28,6 +28,11 @@ pub enum Error {
         /// new transaction's gas price
         new: Option<GasPrice>,
     },
+    /// Transaction was not imported because the sender has replaced too many transactions in the pool.
+    TooManyReplacements {
+        /// Max replacements per sender
+        limit: u64,
+    },
     /// Transaction's gas is below currently set minimal gas requirement.
     InsufficientGas {
         /// Minimal expected gas
@@ -78,6 +83,9 @@ impl fmt::Display for Error {
                 "Gas price too low to replace, previous tx gas: {:?}, new tx gas: {:?}",
                 prev, new
             ),
+            TooManyReplacements { limit } => {
+                format!("Too many replacements from the sender, limit: {}", limit)
+            }
             LimitReached => "Transaction limit reached".into(),
             InsufficientGasPrice { minimal, got } => {
                 format!("Insufficient gas price. Min={}, Given={}", minimal, got)
@@ -15,7 +15,7 @@ use crate::{
     vm_status::{StatusCode, VMStatus},
     write_set::WriteSet,
 };
-use anyhow::{format_err, Error, Result};
+use anyhow::{ensure, format_err, Error, Result};
 use bcs_ext::Sample;
 use serde::{Deserialize, Deserializer, Serialize};
 use starcoin_accumulator::inmemory::InMemoryAccumulator;
@@ -366,6 +366,32 @@ impl Sample for RawUserTransaction {
     }
 }
 
+/// The message signed by both the sender and the fee payer of a sponsored transaction,
+/// it binds the signatures to the fee payer, so they can not be used as a normal transaction.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize, CryptoHasher, CryptoHash)]
+pub struct RawUserTransactionWithFeePayer {
+    raw_txn: RawUserTransaction,
+    fee_payer: AccountAddress,
+}
+
+impl RawUserTransactionWithFeePayer {
+    pub fn new(raw_txn: RawUserTransaction, fee_payer: AccountAddress) -> Self {
+        Self { raw_txn, fee_payer }
+    }
+
+    pub fn raw_txn(&self) -> &RawUserTransaction {
+        &self.raw_txn
+    }
+
+    pub fn fee_payer(&self) -> AccountAddress {
+        self.fee_payer
+    }
+
+    pub fn into_raw_transaction(self) -> RawUserTransaction {
+        self.raw_txn
+    }
+}
+
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum TransactionPayload {
     /// A transaction that executes code.
@@ -511,10 +537,33 @@ impl SignedUserTransaction {
         Self::new(raw_txn, authenticator)
     }
 
+    /// Create a sponsored transaction, `sender` and `fee_payer` are the authenticators
+    /// of the `RawUserTransactionWithFeePayer`.
+    pub fn with_fee_payer(
+        raw_txn: RawUserTransaction,
+        sender: TransactionAuthenticator,
+        fee_payer_address: AccountAddress,
+        fee_payer: TransactionAuthenticator,
+    ) -> SignedUserTransaction {
+        let authenticator =
+            TransactionAuthenticator::with_fee_payer(sender, fee_payer_address, fee_payer);
+        Self::new(raw_txn, authenticator)
+    }
+
     pub fn authenticator(&self) -> TransactionAuthenticator {
         self.authenticator.clone()
     }
 
+    /// Return the fee payer if it is a sponsored transaction.
+    pub fn fee_payer(&self) -> Option<AccountAddress> {
+        self.authenticator.fee_payer_address()
+    }
+
+    /// Return the account pays the gas, it is the fee payer for a sponsored transaction, otherwise the sender.
+    pub fn gas_payer(&self) -> AccountAddress {
+        self.fee_payer().unwrap_or_else(|| self.sender())
+    }
+
     pub fn raw_txn(&self) -> &RawUserTransaction {
         &self.raw_txn
     }
@@ -562,7 +611,20 @@ impl SignedUserTransaction {
     /// Checks that the signature of given transaction. Returns `Ok(SignatureCheckedTransaction)` if
     /// the signature is valid.
     pub fn check_signature(self) -> Result<SignatureCheckedTransaction> {
-        self.authenticator.verify(&self.raw_txn)?;
+        match self.fee_payer() {
+            Some(fee_payer) => {
+                ensure!(
+                    fee_payer != self.sender(),
+                    "The fee payer of a sponsored transaction should not be the sender"
+                );
+                self.authenticator
+                    .verify(&RawUserTransactionWithFeePayer::new(
+                        self.raw_txn.clone(),
+                        fee_payer,
+                    ))?;
+            }
+            None => self.authenticator.verify(&self.raw_txn)?,
+        }
         Ok(SignatureCheckedTransaction(self))
     }
 
@@ -876,6 +938,12 @@ pub enum TxStatus {
     Canceled,
     /// Culled transaction
     Culled,
+    /// Replaced by a transaction with the same sender and sequence number but higher gas price
+    Replaced,
+    /// Included in a block of the main chain
+    Included,
+    /// The block which includes the transaction is removed from the main chain by a re-org
+    Retracted,
 }
 
 impl std::fmt::Display for TxStatus {
@@ -887,6 +955,9 @@ impl std::fmt::Display for TxStatus {
             TxStatus::Invalid => "invalid",
             TxStatus::Canceled => "canceled",
             TxStatus::Culled => "culled",
+            TxStatus::Replaced => "replaced",
+            TxStatus::Included => "included",
+            TxStatus::Retracted => "retracted",
         };
         write!(f, "{}", s)
     }
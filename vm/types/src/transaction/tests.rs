@@ -24,3 +24,62 @@ fn test_transaction_argument_to_json() {
     let script3 = serde_json::from_value(json_value).expect("json from_value should success.");
     assert_eq!(script, script3);
 }
+
+#[test]
+fn test_fee_payer_transaction_signature() {
+    use crate::transaction::authenticator::AccountPrivateKey;
+    use crate::transaction::{
+        RawUserTransaction, RawUserTransactionWithFeePayer, SignedUserTransaction,
+    };
+    use starcoin_crypto::keygen::KeyGen;
+
+    let mut key_gen = KeyGen::from_os_rng();
+    let sender_key = AccountPrivateKey::Single(key_gen.generate_keypair().0);
+    let fee_payer_key = AccountPrivateKey::Single(key_gen.generate_keypair().0);
+    let fee_payer = fee_payer_key.public_key().derived_address();
+    let raw_txn = RawUserTransaction::mock_by_sender(sender_key.public_key().derived_address());
+
+    let message = RawUserTransactionWithFeePayer::new(raw_txn.clone(), fee_payer);
+    let txn = SignedUserTransaction::with_fee_payer(
+        raw_txn.clone(),
+        sender_key.sign(&message),
+        fee_payer,
+        fee_payer_key.sign(&message),
+    );
+    assert_eq!(Some(fee_payer), txn.fee_payer());
+    assert_eq!(fee_payer, txn.gas_payer());
+    assert_eq!(
+        sender_key.public_key().authentication_key(),
+        txn.authenticator().authentication_key()
+    );
+    assert!(txn.check_signature().is_ok());
+
+    // the signatures are bound to the fee payer.
+    let other_fee_payer = AccountAddress::random();
+    let txn = SignedUserTransaction::with_fee_payer(
+        raw_txn.clone(),
+        sender_key.sign(&message),
+        other_fee_payer,
+        fee_payer_key.sign(&message),
+    );
+    assert!(txn.check_signature().is_err());
+
+    // the signatures of the raw txn can not be used as a sponsored transaction.
+    let txn = SignedUserTransaction::with_fee_payer(
+        raw_txn.clone(),
+        sender_key.sign(&raw_txn),
+        fee_payer,
+        fee_payer_key.sign(&raw_txn),
+    );
+    assert!(txn.check_signature().is_err());
+
+    // the sender can not be the fee payer.
+    let message = RawUserTransactionWithFeePayer::new(raw_txn.clone(), raw_txn.sender());
+    let txn = SignedUserTransaction::with_fee_payer(
+        raw_txn.clone(),
+        sender_key.sign(&message),
+        raw_txn.sender(),
+        sender_key.sign(&message),
+    );
+    assert!(txn.check_signature().is_err());
+}
@@ -46,14 +46,24 @@ pub struct TransactionMetadata {
     pub expiration_timestamp_secs: u64,
     pub chain_id: ChainId,
     pub payload: TransactionPayloadMetadata,
+    /// The fee payer of a sponsored transaction.
+    pub fee_payer: Option<AccountAddress>,
+    pub fee_payer_authentication_key_preimage: Vec<u8>,
 }
 
 impl TransactionMetadata {
     pub fn new(txn: &SignedUserTransaction) -> Result<Self, VMStatus> {
-        Self::from_raw_txn_and_preimage(
+        let authenticator = txn.authenticator();
+        let mut metadata = Self::from_raw_txn_and_preimage(
             txn.raw_txn(),
-            txn.authenticator().authentication_key_preimage(),
-        )
+            authenticator.authentication_key_preimage(),
+        )?;
+        if let Some(fee_payer) = authenticator.fee_payer_authenticator() {
+            metadata.fee_payer = authenticator.fee_payer_address();
+            metadata.fee_payer_authentication_key_preimage =
+                fee_payer.authentication_key_preimage().into_vec();
+        }
+        Ok(metadata)
     }
 
     pub fn from_raw_txn_and_preimage(
@@ -81,6 +91,8 @@ impl TransactionMetadata {
                 ),
                 TransactionPayload::ScriptFunction(_) => TransactionPayloadMetadata::ScriptFunction,
             },
+            fee_payer: None,
+            fee_payer_authentication_key_preimage: vec![],
         })
     }
     pub fn max_gas_amount(&self) -> GasUnits<GasCarrier> {
@@ -103,6 +115,19 @@ impl TransactionMetadata {
         &self.authentication_key_preimage
     }
 
+    pub fn fee_payer(&self) -> Option<AccountAddress> {
+        self.fee_payer
+    }
+
+    pub fn fee_payer_authentication_key_preimage(&self) -> &[u8] {
+        &self.fee_payer_authentication_key_preimage
+    }
+
+    /// The account pays the gas, it is the fee payer for a sponsored transaction, otherwise the sender.
+    pub fn gas_payer(&self) -> AccountAddress {
+        self.fee_payer.unwrap_or(self.sender)
+    }
+
     pub fn sequence_number(&self) -> u64 {
         self.sequence_number
     }
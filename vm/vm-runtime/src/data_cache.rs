@@ -15,6 +15,7 @@ use starcoin_vm_types::{
     vm_status::StatusCode,
     write_set::{WriteOp, WriteSet},
 };
+use std::cell::RefCell;
 use std::collections::btree_map::BTreeMap;
 
 /// A local cache for a given a `StateView`. The cache is private to the Diem layer
@@ -33,6 +34,8 @@ use std::collections::btree_map::BTreeMap;
 pub struct StateViewCache<'a> {
     data_view: &'a dyn StateView,
     data_map: BTreeMap<AccessPath, Option<Vec<u8>>>,
+    // The values of the `StateView` read by `prefetch`, shadowed by `data_map`.
+    prefetched: RefCell<BTreeMap<AccessPath, Option<Vec<u8>>>>,
 }
 
 impl<'a> StateViewCache<'a> {
@@ -42,6 +45,7 @@ impl<'a> StateViewCache<'a> {
         StateViewCache {
             data_view,
             data_map: BTreeMap::new(),
+            prefetched: RefCell::new(BTreeMap::new()),
         }
     }
 
@@ -63,11 +67,49 @@ impl<'a> StateViewCache<'a> {
     }
 }
 
+impl<'a> StateViewCache<'a> {
+    /// The changes made by the pushed write sets.
+    pub(crate) fn changes(&self) -> &BTreeMap<AccessPath, Option<Vec<u8>>> {
+        &self.data_map
+    }
+
+    /// Read the values of the `access_paths` missed in the cache from the `StateView` in one batch,
+    /// and keep them for the following reads.
+    pub(crate) fn prefetch(&self, access_paths: &[AccessPath]) -> anyhow::Result<()> {
+        let missed_access_paths: Vec<AccessPath> = {
+            let prefetched = self.prefetched.borrow();
+            access_paths
+                .iter()
+                .filter(|access_path| {
+                    !self.data_map.contains_key(access_path)
+                        && !prefetched.contains_key(access_path)
+                })
+                .cloned()
+                .collect()
+        };
+        if missed_access_paths.is_empty() {
+            return Ok(());
+        }
+        let values = self.data_view.multi_get(missed_access_paths.as_slice())?;
+        self.prefetched
+            .borrow_mut()
+            .extend(missed_access_paths.into_iter().zip(values));
+        Ok(())
+    }
+
+    fn get_cached(&self, access_path: &AccessPath) -> Option<Option<Vec<u8>>> {
+        match self.data_map.get(access_path) {
+            Some(opt_data) => Some(opt_data.clone()),
+            None => self.prefetched.borrow().get(access_path).cloned(),
+        }
+    }
+}
+
 impl<'block> StateView for StateViewCache<'block> {
     // Get some data either through the cache or the `StateView` on a cache miss.
     fn get(&self, access_path: &AccessPath) -> anyhow::Result<Option<Vec<u8>>> {
-        match self.data_map.get(access_path) {
-            Some(opt_data) => Ok(opt_data.clone()),
+        match self.get_cached(access_path) {
+            Some(opt_data) => Ok(opt_data),
             None => match self.data_view.get(access_path) {
                 Ok(remote_data) => Ok(remote_data),
                 // TODO: should we forward some error info?
@@ -79,8 +121,39 @@ impl<'block> StateView for StateViewCache<'block> {
         }
     }
 
-    fn multi_get(&self, _access_paths: &[AccessPath]) -> anyhow::Result<Vec<Option<Vec<u8>>>> {
-        unimplemented!()
+    // Get the data through the cache, and the missed data through the `StateView` in one batch.
+    fn multi_get(&self, access_paths: &[AccessPath]) -> anyhow::Result<Vec<Option<Vec<u8>>>> {
+        let mut values = Vec::with_capacity(access_paths.len());
+        let mut missed_indexes = vec![];
+        let mut missed_access_paths = vec![];
+        for (index, access_path) in access_paths.iter().enumerate() {
+            match self.get_cached(access_path) {
+                Some(opt_data) => values.push(opt_data),
+                None => {
+                    values.push(None);
+                    missed_indexes.push(index);
+                    missed_access_paths.push(access_path.clone());
+                }
+            }
+        }
+        if missed_access_paths.is_empty() {
+            return Ok(values);
+        }
+        match self.data_view.multi_get(missed_access_paths.as_slice()) {
+            Ok(remote_values) => {
+                for (index, remote_data) in missed_indexes.into_iter().zip(remote_values) {
+                    values[index] = remote_data;
+                }
+                Ok(values)
+            }
+            Err(e) => {
+                error!(
+                    "[VM] Error getting data from storage for {:?}",
+                    missed_access_paths
+                );
+                Err(e)
+            }
+        }
     }
 
     fn is_genesis(&self) -> bool {
@@ -5,7 +5,9 @@ extern crate prometheus;
 pub mod data_cache;
 pub mod metrics;
 pub mod natives;
+pub mod parallel_executor;
 pub mod starcoin_vm;
+pub mod tracer;
 pub use move_vm_runtime::move_vm;
 mod access_path_cache;
 mod errors;
@@ -27,3 +27,12 @@ pub static BLOCK_UNCLES: Lazy<Histogram> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static PARALLEL_EXECUTION_TXNS: Lazy<IntCounterVec> = Lazy::new(|| {
+    let opts = Opts::new(
+        "vm_parallel_execution_txns",
+        "Counters of txns executed by parallel executor",
+    )
+    .namespace("starcoin");
+    register_int_counter_vec!(opts, &["result"]).unwrap()
+});
@@ -17,6 +17,11 @@ pub fn starcoin_natives() -> NativeFunctionTable {
             "keccak_256",
             starcoin_natives::hash::native_keccak_256,
         ),
+        (
+            "Hash",
+            "ripemd160",
+            starcoin_natives::hash::native_ripemd160,
+        ),
         ("BCS", "to_bytes", bcs::native_to_bytes),
         (
             "BCS",
@@ -33,6 +38,16 @@ pub fn starcoin_natives() -> NativeFunctionTable {
             "ed25519_verify",
             signature::native_ed25519_signature_verification,
         ),
+        (
+            "Secp256k1",
+            "native_recover",
+            starcoin_natives::secp256k1::native_secp256k1_recover,
+        ),
+        (
+            "Secp256k1",
+            "native_verify",
+            starcoin_natives::secp256k1::native_secp256k1_verify,
+        ),
         ("Vector", "length", vector::native_length),
         ("Vector", "empty", vector::native_empty),
         ("Vector", "borrow", vector::native_borrow),
@@ -0,0 +1,330 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Optimistic parallel execution of the user txns in a block.
+//!
+//! The txns are executed speculatively by the threads of a `ParallelExecutor` on the state
+//! before them, and every state read is recorded. Then the outputs are committed in order,
+//! a txn whose reads overlap the writes of the txns committed before it is re-executed
+//! on the latest state. So the outputs are the same as sequential execution.
+//!
+//! Every txn deposits its fee to the global `TransactionFee` resource in the epilogue, so the
+//! resource is not checked for conflicts. The fee deposited by a speculation is added to the
+//! latest value of the resource when it is committed instead.
+
+use crate::data_cache::StateViewCache;
+use crate::metrics::PARALLEL_EXECUTION_TXNS;
+use crate::starcoin_vm::{CommitStatus, StarcoinVM};
+use anyhow::Result;
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use starcoin_types::transaction::{SignedUserTransaction, TransactionOutput, TransactionStatus};
+use starcoin_vm_types::access_path::{AccessPath, DataPath};
+use starcoin_vm_types::account_config::genesis_address;
+use starcoin_vm_types::state_view::StateView;
+use starcoin_vm_types::vm_status::VMStatus;
+use starcoin_vm_types::write_set::{WriteOp, WriteSetMut};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+const TRANSACTION_FEE_MODULE_NAME: &str = "TransactionFee";
+const TRANSACTION_FEE_STRUCT_NAME: &str = "TransactionFee";
+
+/// The threads to execute the user txns of blocks in parallel, clones share the threads.
+/// Txns are executed sequentially by the default one.
+#[derive(Clone, Debug, Default)]
+pub struct ParallelExecutor {
+    pool: Option<Arc<ThreadPool>>,
+}
+
+impl ParallelExecutor {
+    /// Create `concurrency_level` threads, no thread is created if it is not more than 1.
+    pub fn new(concurrency_level: usize) -> Result<Self> {
+        if concurrency_level <= 1 {
+            return Ok(Self::default());
+        }
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(concurrency_level)
+            .thread_name(|index| format!("parallel-executor-{}", index))
+            .build()?;
+        Ok(Self {
+            pool: Some(Arc::new(pool)),
+        })
+    }
+
+    pub fn concurrency_level(&self) -> usize {
+        self.pool
+            .as_ref()
+            .map(|pool| pool.current_num_threads())
+            .unwrap_or(1)
+    }
+
+    pub fn is_parallel(&self) -> bool {
+        self.pool.is_some()
+    }
+
+    pub(crate) fn pool(&self) -> Option<Arc<ThreadPool>> {
+        self.pool.clone()
+    }
+}
+
+/// Execute the user txns on the `state_view` changed by the `data_cache`,
+/// return false if the block gas is used up.
+pub(crate) fn execute_user_transactions(
+    vm: &mut StarcoinVM,
+    pool: &ThreadPool,
+    state_view: &(dyn StateView + Sync),
+    data_cache: &mut StateViewCache<'_>,
+    txns: Vec<SignedUserTransaction>,
+    gas_left: &mut u64,
+    result: &mut Vec<(VMStatus, TransactionOutput)>,
+) -> Result<bool> {
+    let mut start = 0;
+    'speculate: while start < txns.len() {
+        let speculations = speculate(vm, pool, state_view, data_cache, &txns[start..]);
+        let mut written = HashSet::new();
+        for (index, speculation) in (start..).zip(speculations) {
+            let txn = &txns[index];
+            let Speculation {
+                read_set,
+                fee_reads,
+                status,
+                output,
+            } = speculation;
+            let valid_output = if read_set.is_disjoint(&written) {
+                aggregate_transaction_fee(data_cache, &fee_reads, output)?
+            } else {
+                None
+            };
+            let (status, output) = match valid_output {
+                Some(output) => {
+                    PARALLEL_EXECUTION_TXNS.with_label_values(&["valid"]).inc();
+                    (status, output)
+                }
+                None => {
+                    PARALLEL_EXECUTION_TXNS
+                        .with_label_values(&["re_executed"])
+                        .inc();
+                    vm.execute_user_transaction(txn.clone(), data_cache)
+                }
+            };
+            if let TransactionStatus::Keep(_) = output.status() {
+                written.extend(
+                    output
+                        .write_set()
+                        .iter()
+                        .map(|(access_path, _)| access_path)
+                        .filter(|access_path| !is_transaction_fee(access_path))
+                        .cloned(),
+                );
+            }
+            match vm.commit_user_transaction(
+                data_cache,
+                txn.gas_unit_price(),
+                status,
+                output,
+                gas_left,
+                result,
+            )? {
+                CommitStatus::GasUsedUp => return Ok(false),
+                // The following txns are executed with stale vm configs.
+                CommitStatus::Committed { reconfigured: true } => {
+                    start = index + 1;
+                    continue 'speculate;
+                }
+                CommitStatus::Committed {
+                    reconfigured: false,
+                } => {}
+            }
+        }
+        break;
+    }
+    Ok(true)
+}
+
+struct Speculation {
+    read_set: HashSet<AccessPath>,
+    /// The values of the `TransactionFee` resources read by the txn, they are not in the `read_set`.
+    fee_reads: HashMap<AccessPath, Option<Vec<u8>>>,
+    status: VMStatus,
+    output: TransactionOutput,
+}
+
+/// Whether the access path is a `0x1::TransactionFee::TransactionFee<TokenType>` resource.
+fn is_transaction_fee(access_path: &AccessPath) -> bool {
+    access_path.address == genesis_address()
+        && matches!(&access_path.path, DataPath::Resource(struct_tag)
+            if struct_tag.address == genesis_address()
+                && struct_tag.module.as_str() == TRANSACTION_FEE_MODULE_NAME
+                && struct_tag.name.as_str() == TRANSACTION_FEE_STRUCT_NAME)
+}
+
+/// The `TransactionFee` resource only has the `u128` value of the fee token.
+fn decode_fee(value: Option<&Vec<u8>>) -> Option<u128> {
+    value.and_then(|value| bcs_ext::from_bytes::<u128>(value.as_slice()).ok())
+}
+
+/// Rewrite the `TransactionFee` resources written by the speculative `output`, the fee deposited
+/// by the txn is added to the latest value in the `data_cache`.
+/// Return `None` if the txn does not only deposit to the resources, it needs to be re-executed.
+fn aggregate_transaction_fee(
+    data_cache: &StateViewCache<'_>,
+    fee_reads: &HashMap<AccessPath, Option<Vec<u8>>>,
+    output: TransactionOutput,
+) -> Result<Option<TransactionOutput>> {
+    // the resource read but not written by the txn must be unchanged.
+    for (access_path, read_value) in fee_reads {
+        let written = output
+            .write_set()
+            .iter()
+            .any(|(written_path, _)| written_path == access_path);
+        if !written && &data_cache.get(access_path)? != read_value {
+            return Ok(None);
+        }
+    }
+    if !output
+        .write_set()
+        .iter()
+        .any(|(access_path, _)| is_transaction_fee(access_path))
+    {
+        return Ok(Some(output));
+    }
+    let (write_set, events, gas_used, status) = output.into_inner();
+    let mut write_ops = vec![];
+    for (access_path, write_op) in write_set {
+        if !is_transaction_fee(&access_path) {
+            write_ops.push((access_path, write_op));
+            continue;
+        }
+        let speculated_fee = fee_reads
+            .get(&access_path)
+            .and_then(|read_value| decode_fee(read_value.as_ref()));
+        let written_fee = match &write_op {
+            WriteOp::Value(value) => decode_fee(Some(value)),
+            WriteOp::Deletion => None,
+        };
+        let latest_fee = decode_fee(data_cache.get(&access_path)?.as_ref());
+        let fee = match (speculated_fee, written_fee, latest_fee) {
+            (Some(speculated_fee), Some(written_fee), Some(latest_fee)) => written_fee
+                .checked_sub(speculated_fee)
+                .and_then(|deposit| latest_fee.checked_add(deposit)),
+            _ => None,
+        };
+        match fee {
+            Some(fee) => write_ops.push((access_path, WriteOp::Value(bcs_ext::to_bytes(&fee)?))),
+            None => return Ok(None),
+        }
+    }
+    let write_set = WriteSetMut::new(write_ops).freeze()?;
+    Ok(Some(TransactionOutput::new(
+        write_set, events, gas_used, status,
+    )))
+}
+
+/// Execute the txns in parallel on the state changed by the `data_cache`, every txn ignores the others.
+fn speculate(
+    vm: &StarcoinVM,
+    pool: &ThreadPool,
+    state_view: &(dyn StateView + Sync),
+    data_cache: &StateViewCache<'_>,
+    txns: &[SignedUserTransaction],
+) -> Vec<Speculation> {
+    let changes = data_cache.changes();
+    pool.install(|| {
+        txns.par_iter()
+            .map_init(
+                || vm.clone(),
+                |vm, txn| {
+                    let speculative_view = SpeculativeStateView::new(state_view, changes);
+                    let (status, output) = {
+                        let mut data_cache = StateViewCache::new(&speculative_view);
+                        vm.execute_user_transaction(txn.clone(), &mut data_cache)
+                    };
+                    let (read_set, fee_reads) = speculative_view.into_reads();
+                    Speculation {
+                        read_set,
+                        fee_reads,
+                        status,
+                        output,
+                    }
+                },
+            )
+            .collect()
+    })
+}
+
+/// A `StateView` of the base state with the changes of the committed txns, which records the reads.
+struct SpeculativeStateView<'a> {
+    base: &'a (dyn StateView + Sync),
+    changes: &'a BTreeMap<AccessPath, Option<Vec<u8>>>,
+    read_set: RefCell<HashSet<AccessPath>>,
+    fee_reads: RefCell<HashMap<AccessPath, Option<Vec<u8>>>>,
+}
+
+impl<'a> SpeculativeStateView<'a> {
+    fn new(
+        base: &'a (dyn StateView + Sync),
+        changes: &'a BTreeMap<AccessPath, Option<Vec<u8>>>,
+    ) -> Self {
+        Self {
+            base,
+            changes,
+            read_set: RefCell::new(HashSet::new()),
+            fee_reads: RefCell::new(HashMap::new()),
+        }
+    }
+
+    fn into_reads(self) -> (HashSet<AccessPath>, HashMap<AccessPath, Option<Vec<u8>>>) {
+        (self.read_set.into_inner(), self.fee_reads.into_inner())
+    }
+
+    fn record_read(&self, access_path: &AccessPath, value: &Option<Vec<u8>>) {
+        if is_transaction_fee(access_path) {
+            self.fee_reads
+                .borrow_mut()
+                .entry(access_path.clone())
+                .or_insert_with(|| value.clone());
+        } else {
+            self.read_set.borrow_mut().insert(access_path.clone());
+        }
+    }
+}
+
+impl<'a> StateView for SpeculativeStateView<'a> {
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        let value = match self.changes.get(access_path) {
+            Some(value) => value.clone(),
+            None => self.base.get(access_path)?,
+        };
+        self.record_read(access_path, &value);
+        Ok(value)
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        let missed_access_paths: Vec<AccessPath> = access_paths
+            .iter()
+            .filter(|access_path| !self.changes.contains_key(access_path))
+            .cloned()
+            .collect();
+        let mut missed_values = self
+            .base
+            .multi_get(missed_access_paths.as_slice())?
+            .into_iter();
+        Ok(access_paths
+            .iter()
+            .map(|access_path| {
+                let value = match self.changes.get(access_path) {
+                    Some(value) => value.clone(),
+                    None => missed_values.next().unwrap_or_default(),
+                };
+                self.record_read(access_path, &value);
+                value
+            })
+            .collect())
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.base.is_genesis()
+    }
+}
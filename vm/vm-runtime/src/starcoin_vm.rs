@@ -7,6 +7,8 @@ use crate::errors::{
     convert_normal_success_epilogue_error, convert_prologue_runtime_error, error_split,
 };
 use crate::metrics::{BLOCK_UNCLES, TXN_EXECUTION_GAS_USAGE};
+use crate::parallel_executor::{self, ParallelExecutor};
+use crate::tracer::{CallKind, CallTrace, ExecutionTracer};
 use anyhow::{format_err, Error, Result};
 use crypto::HashValue;
 use move_vm_runtime::data_cache::MoveStorage;
@@ -29,19 +31,23 @@ use starcoin_types::{
     write_set::WriteSet,
 };
 use starcoin_vm_types::access::{ModuleAccess, ScriptAccess};
+use starcoin_vm_types::access_path::AccessPath;
 use starcoin_vm_types::account_address::AccountAddress;
 use starcoin_vm_types::account_config::upgrade::UpgradeEvent;
 use starcoin_vm_types::account_config::{
-    genesis_address, ModuleUpgradeStrategy, TwoPhaseUpgradeV2Resource, EPILOGUE_NAME,
-    EPILOGUE_V2_NAME, PROLOGUE_NAME,
+    genesis_address, AccountResource, BalanceResource, ModuleUpgradeStrategy,
+    TwoPhaseUpgradeV2Resource, EPILOGUE_NAME, EPILOGUE_V2_NAME, EPILOGUE_WITH_FEE_PAYER_NAME,
+    PROLOGUE_NAME, PROLOGUE_WITH_FEE_PAYER_NAME,
 };
 use starcoin_vm_types::contract_event::ContractEvent;
 use starcoin_vm_types::file_format::{CompiledModule, CompiledScript};
 use starcoin_vm_types::gas_schedule::{zero_cost_schedule, GasStatus};
 use starcoin_vm_types::genesis_config::StdlibVersion;
 use starcoin_vm_types::identifier::IdentStr;
-use starcoin_vm_types::language_storage::ModuleId;
+use starcoin_vm_types::language_storage::{ModuleId, StructTag};
+use starcoin_vm_types::move_resource::MoveResource;
 use starcoin_vm_types::on_chain_config::MoveLanguageVersion;
+use starcoin_vm_types::transaction::authenticator::Scheme;
 use starcoin_vm_types::transaction::{DryRunTransaction, Package, TransactionPayloadType};
 use starcoin_vm_types::transaction_metadata::TransactionPayloadMetadata;
 use starcoin_vm_types::value::{serialize_values, MoveValue};
@@ -62,6 +68,12 @@ use starcoin_vm_types::{
 use std::convert::{TryFrom, TryInto};
 use std::sync::Arc;
 
+/// The `Secp256k1Ecdsa` authenticator is enabled from this stdlib version.
+const SECP256K1_AUTHENTICATOR_STDLIB_VERSION: StdlibVersion = StdlibVersion::Version(8);
+/// The sponsored transaction is enabled from this stdlib version, which provides the
+/// `TransactionManager::prologue_with_fee_payer` and `epilogue_with_fee_payer`.
+const FEE_PAYER_TXN_STDLIB_VERSION: StdlibVersion = StdlibVersion::Version(8);
+
 #[derive(Clone)]
 #[allow(clippy::upper_case_acronyms)]
 /// Wrapper of MoveVM
@@ -70,6 +82,8 @@ pub struct StarcoinVM {
     vm_config: Option<VMConfig>,
     version: Option<Version>,
     move_version: Option<MoveLanguageVersion>,
+    tracer: Option<ExecutionTracer>,
+    parallel_executor: ParallelExecutor,
 }
 
 impl Default for StarcoinVM {
@@ -87,9 +101,60 @@ impl StarcoinVM {
             vm_config: None,
             version: None,
             move_version: None,
+            tracer: None,
+            parallel_executor: ParallelExecutor::default(),
+        }
+    }
+
+    /// Create a vm which executes the user txns of blocks with the threads of `parallel_executor`,
+    /// see `execute_block_transactions_in_parallel`.
+    pub fn new_with_parallel_executor(parallel_executor: ParallelExecutor) -> Self {
+        Self {
+            parallel_executor,
+            ..Self::new()
         }
     }
 
+    /// Record the calls into Move with the tracer, disable tracing if `tracer` is `None`.
+    pub fn set_tracer(&mut self, tracer: Option<ExecutionTracer>) {
+        self.tracer = tracer;
+    }
+
+    /// Create a `CallTrace` if tracing is enabled.
+    fn new_call_trace(
+        &self,
+        kind: CallKind,
+        module: Option<&ModuleId>,
+        function: Option<&IdentStr>,
+        type_args: &[TypeTag],
+        args: &[Vec<u8>],
+    ) -> Option<CallTrace> {
+        self.tracer
+            .as_ref()
+            .map(|_| CallTrace::new(kind, module, function, type_args, args))
+    }
+
+    /// Run the call, and record the gas used and status to the `trace`.
+    fn trace_call<T>(
+        &self,
+        trace: Option<CallTrace>,
+        gas_status: &mut GasStatus,
+        call: impl FnOnce(&mut GasStatus) -> Result<T, VMStatus>,
+    ) -> Result<T, VMStatus> {
+        let (mut trace, tracer) = match (trace, self.tracer.as_ref()) {
+            (Some(trace), Some(tracer)) => (trace, tracer),
+            _ => return call(gas_status),
+        };
+        let remaining_gas = gas_status.remaining_gas().get();
+        let result = call(gas_status);
+        trace.gas_used = remaining_gas.saturating_sub(gas_status.remaining_gas().get());
+        if let Err(vm_status) = &result {
+            trace.status = vm_status.clone();
+        }
+        tracer.record(trace);
+        result
+    }
+
     pub fn load_configs(&mut self, state: &dyn StateView) -> Result<(), Error> {
         if state.is_genesis() {
             self.vm_config = Some(VMConfig {
@@ -146,6 +211,26 @@ impl StarcoinVM {
         Ok(())
     }
 
+    fn check_authenticator_scheme(&self, txn: &SignedUserTransaction) -> Result<(), VMStatus> {
+        let authenticator = txn.authenticator();
+        let mut required_version = None;
+        if authenticator.fee_payer_address().is_some() {
+            required_version = Some(FEE_PAYER_TXN_STDLIB_VERSION);
+        }
+        let is_secp256k1 = std::iter::once(&authenticator)
+            .chain(authenticator.fee_payer_authenticator())
+            .any(|auth| matches!(auth.scheme(), Scheme::Secp256k1Ecdsa));
+        if is_secp256k1 {
+            required_version = required_version.max(Some(SECP256K1_AUTHENTICATOR_STDLIB_VERSION));
+        }
+        if let Some(required_version) = required_version {
+            if self.get_version()?.into_stdlib_version() < required_version {
+                return Err(VMStatus::Error(StatusCode::FEATURE_UNDER_GATING));
+            }
+        }
+        Ok(())
+    }
+
     fn check_gas(&self, txn_data: &TransactionMetadata) -> Result<(), VMStatus> {
         let gas_constants = &self.get_gas_schedule()?.gas_constants;
         let raw_bytes_len = txn_data.transaction_size;
@@ -315,6 +400,9 @@ impl StarcoinVM {
             warn!("Load config error at verify_transaction: {}", err);
             return Some(VMStatus::Error(StatusCode::VM_STARTUP_FAILURE));
         }
+        if let Err(err) = self.check_authenticator_scheme(&signature_verified_txn) {
+            return Some(err);
+        }
         match self.verify_transaction_impl(&signature_verified_txn, &data_cache) {
             Ok(_) => None,
             Err(err) => {
@@ -434,17 +522,26 @@ impl StarcoinVM {
                     init_script.function(),
                     sender
                 );
-                session
-                    .as_mut()
-                    .execute_script_function(
-                        init_script.module(),
-                        init_script.function(),
-                        init_script.ty_args().to_vec(),
-                        init_script.args().to_vec(),
-                        vec![sender],
-                        cost_strategy,
-                    )
-                    .map_err(|e| e.into_vm_status())?;
+                let trace = self.new_call_trace(
+                    CallKind::InitScript,
+                    Some(init_script.module()),
+                    Some(init_script.function()),
+                    init_script.ty_args(),
+                    init_script.args(),
+                );
+                self.trace_call(trace, cost_strategy, |cost_strategy| {
+                    session
+                        .as_mut()
+                        .execute_script_function(
+                            init_script.module(),
+                            init_script.function(),
+                            init_script.ty_args().to_vec(),
+                            init_script.args().to_vec(),
+                            vec![sender],
+                            cost_strategy,
+                        )
+                        .map_err(|e| e.into_vm_status())
+                })?;
             }
             charge_global_write_gas_usage(cost_strategy, &session, &txn_data.sender())?;
 
@@ -490,29 +587,52 @@ impl StarcoinVM {
                         self.check_move_version(s.version() as u64)?;
                     };
 
-                    session.as_mut().execute_script(
-                        script.code().to_vec(),
-                        script.ty_args().to_vec(),
-                        script.args().to_vec(),
-                        vec![txn_data.sender()],
-                        cost_strategy,
-                    )
+                    let trace = self.new_call_trace(
+                        CallKind::Script,
+                        None,
+                        None,
+                        script.ty_args(),
+                        script.args(),
+                    );
+                    self.trace_call(trace, cost_strategy, |cost_strategy| {
+                        session
+                            .as_mut()
+                            .execute_script(
+                                script.code().to_vec(),
+                                script.ty_args().to_vec(),
+                                script.args().to_vec(),
+                                vec![txn_data.sender()],
+                                cost_strategy,
+                            )
+                            .map_err(|e| e.into_vm_status())
+                    })
                 }
                 TransactionPayload::ScriptFunction(script_function) => {
-                    session.as_mut().execute_script_function(
-                        script_function.module(),
-                        script_function.function(),
-                        script_function.ty_args().to_vec(),
-                        script_function.args().to_vec(),
-                        vec![txn_data.sender()],
-                        cost_strategy,
-                    )
+                    let trace = self.new_call_trace(
+                        CallKind::ScriptFunction,
+                        Some(script_function.module()),
+                        Some(script_function.function()),
+                        script_function.ty_args(),
+                        script_function.args(),
+                    );
+                    self.trace_call(trace, cost_strategy, |cost_strategy| {
+                        session
+                            .as_mut()
+                            .execute_script_function(
+                                script_function.module(),
+                                script_function.function(),
+                                script_function.ty_args().to_vec(),
+                                script_function.args().to_vec(),
+                                vec![txn_data.sender()],
+                                cost_strategy,
+                            )
+                            .map_err(|e| e.into_vm_status())
+                    })
                 }
                 TransactionPayload::Package(_) => {
                     return Err(VMStatus::Error(StatusCode::UNREACHABLE));
                 }
-            }
-            .map_err(|e| e.into_vm_status())?;
+            }?;
 
             charge_global_write_gas_usage(cost_strategy, &session, &txn_data.sender())?;
 
@@ -562,12 +682,30 @@ impl StarcoinVM {
         };
 
         // Run prologue by genesis account
-        session
-            .as_mut()
-            .execute_function(
-                &account_config::TRANSACTION_MANAGER_MODULE,
+        let type_args = vec![gas_token_ty];
+        // The sponsored transaction charges the gas to the fee payer.
+        let (function_name, args) = if let Some(fee_payer) = txn_data.fee_payer() {
+            (
+                &PROLOGUE_WITH_FEE_PAYER_NAME,
+                serialize_values(&vec![
+                    MoveValue::Signer(genesis_address),
+                    MoveValue::Address(txn_data.sender),
+                    MoveValue::U64(txn_sequence_number),
+                    MoveValue::vector_u8(authentication_key_preimage),
+                    MoveValue::Address(fee_payer),
+                    MoveValue::vector_u8(txn_data.fee_payer_authentication_key_preimage().to_vec()),
+                    MoveValue::U64(txn_gas_price),
+                    MoveValue::U64(txn_max_gas_amount),
+                    MoveValue::U64(txn_expiration_time),
+                    MoveValue::U8(chain_id),
+                    MoveValue::U8(payload_type.into()),
+                    MoveValue::vector_u8(script_or_package_hash.to_vec()),
+                    MoveValue::Address(package_address),
+                ]),
+            )
+        } else {
+            (
                 &PROLOGUE_NAME,
-                vec![gas_token_ty],
                 serialize_values(&vec![
                     MoveValue::Signer(genesis_address),
                     MoveValue::Address(txn_data.sender),
@@ -581,10 +719,28 @@ impl StarcoinVM {
                     MoveValue::vector_u8(script_or_package_hash.to_vec()),
                     MoveValue::Address(package_address),
                 ]),
-                gas_status,
             )
-            .map(|_return_vals| ())
-            .or_else(convert_prologue_runtime_error)
+        };
+        let trace = self.new_call_trace(
+            CallKind::Prologue,
+            Some(&*account_config::TRANSACTION_MANAGER_MODULE),
+            Some(function_name.as_ident_str()),
+            &type_args,
+            &args,
+        );
+        self.trace_call(trace, gas_status, |gas_status| {
+            session
+                .as_mut()
+                .execute_function(
+                    &account_config::TRANSACTION_MANAGER_MODULE,
+                    function_name,
+                    type_args,
+                    args,
+                    gas_status,
+                )
+                .map(|_return_vals| ())
+                .or_else(convert_prologue_runtime_error)
+        })
     }
 
     /// Run the epilogue of a transaction by calling into `EPILOGUE_NAME` function stored
@@ -624,7 +780,27 @@ impl StarcoinVM {
         let stdlib_version = self.get_version()?.into_stdlib_version();
         // Run epilogue by genesis account, second arg is txn sender.
         // From stdlib v5, the epilogue function add `txn_authentication_key_preimage` argument, change to epilogue_v2
-        let (function_name, args) = if stdlib_version > StdlibVersion::Version(4) {
+        // The sponsored transaction charges the gas to the fee payer.
+        let (function_name, args) = if let Some(fee_payer) = txn_data.fee_payer() {
+            (
+                &EPILOGUE_WITH_FEE_PAYER_NAME,
+                serialize_values(&vec![
+                    MoveValue::Signer(genesis_address),
+                    MoveValue::Address(txn_data.sender),
+                    MoveValue::U64(txn_sequence_number),
+                    MoveValue::vector_u8(txn_authentication_key_preimage),
+                    MoveValue::Address(fee_payer),
+                    MoveValue::vector_u8(txn_data.fee_payer_authentication_key_preimage().to_vec()),
+                    MoveValue::U64(txn_gas_price),
+                    MoveValue::U64(txn_max_gas_amount),
+                    MoveValue::U64(gas_remaining),
+                    MoveValue::U8(payload_type.into()),
+                    MoveValue::vector_u8(script_or_package_hash.to_vec()),
+                    MoveValue::Address(package_address),
+                    MoveValue::Bool(success),
+                ]),
+            )
+        } else if stdlib_version > StdlibVersion::Version(4) {
             (
                 &EPILOGUE_V2_NAME,
                 serialize_values(&vec![
@@ -658,17 +834,27 @@ impl StarcoinVM {
                 ]),
             )
         };
-        session
-            .as_mut()
-            .execute_function(
-                &account_config::TRANSACTION_MANAGER_MODULE,
-                function_name,
-                vec![gas_token_ty],
-                args,
-                gas_status,
-            )
-            .map(|_return_vals| ())
-            .or_else(convert_normal_success_epilogue_error)
+        let type_args = vec![gas_token_ty];
+        let trace = self.new_call_trace(
+            CallKind::Epilogue,
+            Some(&*account_config::TRANSACTION_MANAGER_MODULE),
+            Some(function_name.as_ident_str()),
+            &type_args,
+            &args,
+        );
+        self.trace_call(trace, gas_status, |gas_status| {
+            session
+                .as_mut()
+                .execute_function(
+                    &account_config::TRANSACTION_MANAGER_MODULE,
+                    function_name,
+                    type_args,
+                    args,
+                    gas_status,
+                )
+                .map(|_return_vals| ())
+                .or_else(convert_normal_success_epilogue_error)
+        })
     }
 
     fn process_block_metadata(
@@ -711,17 +897,26 @@ impl StarcoinVM {
             MoveValue::U64(parent_gas_used),
         ]);
         let mut session: SessionAdapter<_> = self.move_vm.new_session(remote_cache).into();
-        session
-            .as_mut()
-            .execute_function(
-                &account_config::TRANSACTION_MANAGER_MODULE,
-                &account_config::BLOCK_PROLOGUE_NAME,
-                vec![],
-                args,
-                &mut gas_status,
-            )
-            .map(|_return_vals| ())
-            .or_else(convert_prologue_runtime_error)?;
+        let trace = self.new_call_trace(
+            CallKind::BlockPrologue,
+            Some(&*account_config::TRANSACTION_MANAGER_MODULE),
+            Some(account_config::BLOCK_PROLOGUE_NAME.as_ident_str()),
+            &[],
+            &args,
+        );
+        self.trace_call(trace, &mut gas_status, |gas_status| {
+            session
+                .as_mut()
+                .execute_function(
+                    &account_config::TRANSACTION_MANAGER_MODULE,
+                    &account_config::BLOCK_PROLOGUE_NAME,
+                    vec![],
+                    args,
+                    gas_status,
+                )
+                .map(|_return_vals| ())
+                .or_else(convert_prologue_runtime_error)
+        })?;
         BLOCK_UNCLES.observe(uncles as f64);
         get_transaction_output(
             &mut (),
@@ -732,7 +927,7 @@ impl StarcoinVM {
         )
     }
 
-    fn execute_user_transaction(
+    pub(crate) fn execute_user_transaction(
         &mut self,
         txn: SignedUserTransaction,
         remote_cache: &mut StateViewCache<'_>,
@@ -754,6 +949,7 @@ impl StarcoinVM {
                 return discard_error_vm_status(e);
             }
         };
+        prefetch_transaction_states(remote_cache, &txn_data);
 
         let mut gas_status = {
             let mut gas_status = GasStatus::new(gas_schedule, txn_data.max_gas_amount());
@@ -762,7 +958,7 @@ impl StarcoinVM {
         };
         // check signature
         let signature_checked_txn = match txn.check_signature() {
-            Ok(t) => Ok(t),
+            Ok(t) => self.check_authenticator_scheme(&t).map(|_| t),
             Err(_) => Err(VMStatus::Error(StatusCode::INVALID_SIGNATURE)),
         };
 
@@ -826,14 +1022,52 @@ impl StarcoinVM {
         let remote_cache = StateViewCache::new(state_view);
         //TODO load config by config change event.
         self.load_configs(&remote_cache)?;
+        Ok(self.dry_run_transaction_with_cache(&remote_cache, txn))
+    }
+
+    /// Dry run the txns in order, each txn is executed on the state changed by the previous txns.
+    /// If `block_metadata` is present, the txns are executed after the block prologue.
+    pub fn simulate_transactions(
+        &mut self,
+        state_view: &dyn StateView,
+        block_metadata: Option<BlockMetadata>,
+        txns: Vec<DryRunTransaction>,
+    ) -> Result<Vec<(VMStatus, TransactionOutput)>> {
+        let mut data_cache = StateViewCache::new(state_view);
+        //TODO load config by config change event.
+        self.load_configs(&data_cache)?;
+        if let Some(block_metadata) = block_metadata {
+            let output = self
+                .process_block_metadata(&mut data_cache, block_metadata)
+                .map_err(|vm_status| {
+                    format_err!("Simulate block prologue failed: {:?}", vm_status)
+                })?;
+            data_cache.push_write_set(output.write_set());
+        }
+        let mut result = vec![];
+        for txn in txns {
+            let (status, output) = self.dry_run_transaction_with_cache(&data_cache, txn);
+            if let TransactionStatus::Keep(_) = output.status() {
+                data_cache.push_write_set(output.write_set())
+            }
+            self.check_reconfigure(&data_cache, &output)?;
+            result.push((status, output));
+        }
+        Ok(result)
+    }
 
+    fn dry_run_transaction_with_cache(
+        &mut self,
+        remote_cache: &StateViewCache<'_>,
+        txn: DryRunTransaction,
+    ) -> (VMStatus, TransactionOutput) {
         let gas_schedule = match self.get_gas_schedule() {
             Ok(gas_schedule) => gas_schedule,
             Err(e) => {
                 if remote_cache.is_genesis() {
                     &INITIAL_GAS_SCHEDULE
                 } else {
-                    return Ok(discard_error_vm_status(e));
+                    return discard_error_vm_status(e);
                 }
             }
         };
@@ -842,8 +1076,9 @@ impl StarcoinVM {
             txn.public_key.authentication_key_preimage(),
         ) {
             Ok(txn_data) => txn_data,
-            Err(e) => return Ok(discard_error_vm_status(e)),
+            Err(e) => return discard_error_vm_status(e),
         };
+        prefetch_transaction_states(remote_cache, &txn_data);
         let mut gas_status = {
             let mut gas_status = GasStatus::new(gas_schedule, txn_data.max_gas_amount());
             gas_status.set_metering(false);
@@ -853,17 +1088,17 @@ impl StarcoinVM {
             payload @ TransactionPayload::Script(_)
             | payload @ TransactionPayload::ScriptFunction(_) => self
                 .execute_script_or_script_function(
-                    &remote_cache,
+                    remote_cache,
                     gas_schedule,
                     &mut gas_status,
                     &txn_data,
                     payload,
                 ),
             TransactionPayload::Package(p) => {
-                self.execute_package(&remote_cache, gas_schedule, &mut gas_status, &txn_data, p)
+                self.execute_package(remote_cache, gas_schedule, &mut gas_status, &txn_data, p)
             }
         };
-        Ok(match result {
+        match result {
             Ok(status_and_output) => status_and_output,
             Err(err) => {
                 let txn_status = TransactionStatus::from(err.clone());
@@ -875,18 +1110,20 @@ impl StarcoinVM {
                         gas_schedule,
                         gas_status.remaining_gas(),
                         &txn_data,
-                        &remote_cache,
+                        remote_cache,
                     )
                 }
             }
-        })
+        }
     }
 
+    /// Reload the vm configs if the output contains reconfigure event, return whether reloaded.
     fn check_reconfigure(
         &mut self,
         state_view: &dyn StateView,
         output: &TransactionOutput,
-    ) -> Result<(), Error> {
+    ) -> Result<bool, Error> {
+        let mut reconfigured = false;
         for event in output.events() {
             if event.key().get_creator_address() == genesis_address()
                 && (event.is::<UpgradeEvent>()
@@ -895,9 +1132,76 @@ impl StarcoinVM {
             {
                 info!("Load vm configs trigger by reconfigure event. ");
                 self.load_configs(state_view)?;
+                reconfigured = true;
             }
         }
-        Ok(())
+        Ok(reconfigured)
+    }
+
+    /// Execute the user txns one by one, return false if the block gas is used up.
+    fn execute_user_transactions(
+        &mut self,
+        data_cache: &mut StateViewCache<'_>,
+        txns: Vec<SignedUserTransaction>,
+        gas_left: &mut u64,
+        result: &mut Vec<(VMStatus, TransactionOutput)>,
+    ) -> Result<bool> {
+        for transaction in txns {
+            let gas_unit_price = transaction.gas_unit_price();
+            let (status, output) = self.execute_user_transaction(transaction, data_cache);
+            if let CommitStatus::GasUsedUp = self.commit_user_transaction(
+                data_cache,
+                gas_unit_price,
+                status,
+                output,
+                gas_left,
+                result,
+            )? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Charge the block gas and apply the output of a user txn to the `data_cache`.
+    pub(crate) fn commit_user_transaction(
+        &mut self,
+        data_cache: &mut StateViewCache<'_>,
+        gas_unit_price: u64,
+        status: VMStatus,
+        output: TransactionOutput,
+        gas_left: &mut u64,
+        result: &mut Vec<(VMStatus, TransactionOutput)>,
+    ) -> Result<CommitStatus> {
+        // only need to check for user transactions.
+        match gas_left.checked_sub(output.gas_used()) {
+            Some(l) => *gas_left = l,
+            None => return Ok(CommitStatus::GasUsedUp),
+        }
+
+        if let TransactionStatus::Keep(_) = output.status() {
+            if gas_unit_price > 0 {
+                debug_assert_ne!(
+                    output.gas_used(),
+                    0,
+                    "Keep transaction gas used must not be zero"
+                );
+            }
+            data_cache.push_write_set(output.write_set())
+        }
+        let reconfigured = self.check_reconfigure(data_cache, &output)?;
+        result.push((status, output));
+        Ok(CommitStatus::Committed { reconfigured })
+    }
+
+    fn is_parallel_execution_enabled(&self, txns: &[SignedUserTransaction]) -> bool {
+        // Traces must be recorded in order, and the module cache of the vm may be stale if
+        // some txns publish modules, so execute them sequentially.
+        self.tracer.is_none()
+            && txns.len() > 1
+            && !txns
+                .iter()
+                .any(|txn| matches!(txn.payload(), TransactionPayload::Package(_)))
     }
 
     /// Execute a block transactions with gas_limit,
@@ -907,6 +1211,33 @@ impl StarcoinVM {
         state_view: &dyn StateView,
         transactions: Vec<Transaction>,
         block_gas_limit: Option<u64>,
+    ) -> Result<Vec<(VMStatus, TransactionOutput)>> {
+        self.do_execute_block_transactions(state_view, None, transactions, block_gas_limit)
+    }
+
+    /// Execute a block transactions like `execute_block_transactions`, but the user txns are
+    /// executed in parallel by the parallel executor of the vm, which reads the `state_view`
+    /// from multiple threads. The outputs are the same as `execute_block_transactions`.
+    pub fn execute_block_transactions_in_parallel(
+        &mut self,
+        state_view: &(dyn StateView + Sync),
+        transactions: Vec<Transaction>,
+        block_gas_limit: Option<u64>,
+    ) -> Result<Vec<(VMStatus, TransactionOutput)>> {
+        self.do_execute_block_transactions(
+            state_view,
+            Some(state_view),
+            transactions,
+            block_gas_limit,
+        )
+    }
+
+    fn do_execute_block_transactions(
+        &mut self,
+        state_view: &dyn StateView,
+        parallel_state_view: Option<&(dyn StateView + Sync)>,
+        transactions: Vec<Transaction>,
+        block_gas_limit: Option<u64>,
     ) -> Result<Vec<(VMStatus, TransactionOutput)>> {
         let mut data_cache = StateViewCache::new(state_view);
         let mut result = vec![];
@@ -919,28 +1250,29 @@ impl StarcoinVM {
         'outer: for block in blocks {
             match block {
                 TransactionBlock::UserTransaction(txns) => {
-                    for transaction in txns {
-                        let gas_unit_price = transaction.gas_unit_price();
-                        let (status, output) =
-                            self.execute_user_transaction(transaction, &mut data_cache);
-                        // only need to check for user transactions.
-                        match gas_left.checked_sub(output.gas_used()) {
-                            Some(l) => gas_left = l,
-                            None => break 'outer,
-                        }
-
-                        if let TransactionStatus::Keep(_) = output.status() {
-                            if gas_unit_price > 0 {
-                                debug_assert_ne!(
-                                    output.gas_used(),
-                                    0,
-                                    "Keep transaction gas used must not be zero"
-                                );
-                            }
-                            data_cache.push_write_set(output.write_set())
+                    let completed = match (parallel_state_view, self.parallel_executor.pool()) {
+                        (Some(parallel_state_view), Some(pool))
+                            if self.is_parallel_execution_enabled(&txns) =>
+                        {
+                            parallel_executor::execute_user_transactions(
+                                self,
+                                &pool,
+                                parallel_state_view,
+                                &mut data_cache,
+                                txns,
+                                &mut gas_left,
+                                &mut result,
+                            )?
                         }
-                        self.check_reconfigure(&data_cache, &output)?;
-                        result.push((status, output));
+                        _ => self.execute_user_transactions(
+                            &mut data_cache,
+                            txns,
+                            &mut gas_left,
+                            &mut result,
+                        )?,
+                    };
+                    if !completed {
+                        break 'outer;
                     }
                 }
                 TransactionBlock::BlockPrologue(block_metadata) => {
@@ -1071,6 +1403,15 @@ impl StarcoinVM {
     }
 }
 
+pub(crate) enum CommitStatus {
+    Committed {
+        /// The vm configs are reloaded, txns executed before are stale.
+        reconfigured: bool,
+    },
+    /// The block gas is used up, the txn and the following txns are not committed.
+    GasUsedUp,
+}
+
 #[allow(clippy::large_enum_variant)]
 pub enum TransactionBlock {
     UserTransaction(Vec<SignedUserTransaction>),
@@ -1100,6 +1441,34 @@ pub fn chunk_block_transactions(txns: Vec<Transaction>) -> Vec<TransactionBlock>
     blocks
 }
 
+/// Read the states of the sender and the fee payer which the prologue and epilogue use in one batch,
+/// so the following reads of the Move VM hit the `remote_cache`.
+fn prefetch_transaction_states(remote_cache: &StateViewCache<'_>, txn_data: &TransactionMetadata) {
+    let mut access_paths = vec![AccessPath::resource_access_path(
+        txn_data.sender,
+        AccountResource::struct_tag(),
+    )];
+    if let Some(fee_payer) = txn_data.fee_payer() {
+        access_paths.push(AccessPath::resource_access_path(
+            fee_payer,
+            AccountResource::struct_tag(),
+        ));
+    }
+    if let Ok(gas_token) = StructTag::try_from(txn_data.gas_token_code()) {
+        access_paths.push(AccessPath::resource_access_path(
+            txn_data.gas_payer(),
+            BalanceResource::struct_tag_for_token(gas_token),
+        ));
+    }
+    // The prologue reports the error if the states can not be read.
+    if let Err(e) = remote_cache.prefetch(&access_paths) {
+        debug!(
+            "Prefetch states of txn sender {} failed: {}",
+            txn_data.sender, e
+        );
+    }
+}
+
 pub(crate) fn charge_global_write_gas_usage<R: MoveStorage>(
     cost_strategy: &mut GasStatus,
     session: &SessionAdapter<R>,
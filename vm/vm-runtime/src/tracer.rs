@@ -0,0 +1,87 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Record the calls which the VM makes into Move when executing txns.
+//! The calls inside Move are run by the Move interpreter and are not visible here,
+//! so every trace is a top level call, such as the txn prologue, the script function and the epilogue.
+//! Nested frames, return values and the events of each frame are not recorded,
+//! they need hooks in the Move interpreter which it does not provide.
+
+use starcoin_vm_types::identifier::{IdentStr, Identifier};
+use starcoin_vm_types::language_storage::{ModuleId, TypeTag};
+use starcoin_vm_types::vm_status::VMStatus;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CallKind {
+    BlockPrologue,
+    Prologue,
+    Script,
+    ScriptFunction,
+    InitScript,
+    Epilogue,
+}
+
+#[derive(Clone, Debug)]
+pub struct CallTrace {
+    pub kind: CallKind,
+    /// `None` for script.
+    pub module: Option<ModuleId>,
+    /// `None` for script.
+    pub function: Option<Identifier>,
+    pub type_args: Vec<TypeTag>,
+    /// Bcs encoded arguments, the signers of script and script function are not included.
+    pub args: Vec<Vec<u8>>,
+    /// Gas charged during the call, the prologue and epilogue are not metered.
+    pub gas_used: u64,
+    pub status: VMStatus,
+}
+
+impl CallTrace {
+    pub fn new(
+        kind: CallKind,
+        module: Option<&ModuleId>,
+        function: Option<&IdentStr>,
+        type_args: &[TypeTag],
+        args: &[Vec<u8>],
+    ) -> Self {
+        Self {
+            kind,
+            module: module.cloned(),
+            function: function.map(|f| f.to_owned()),
+            type_args: type_args.to_vec(),
+            args: args.to_vec(),
+            gas_used: 0,
+            status: VMStatus::Executed,
+        }
+    }
+}
+
+/// Collect `CallTrace`s in execution order, clones of the tracer share the traces.
+#[derive(Clone, Debug, Default)]
+pub struct ExecutionTracer {
+    calls: Arc<Mutex<Vec<CallTrace>>>,
+}
+
+impl ExecutionTracer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn record(&self, call: CallTrace) {
+        self.calls
+            .lock()
+            .expect("tracer lock should not be poisoned")
+            .push(call);
+    }
+
+    /// Take the recorded calls, and clear the tracer.
+    pub fn take(&self) -> Vec<CallTrace> {
+        std::mem::take(
+            &mut *self
+                .calls
+                .lock()
+                .expect("tracer lock should not be poisoned"),
+        )
+    }
+}